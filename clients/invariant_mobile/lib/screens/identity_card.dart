@@ -6,6 +6,7 @@ import 'package:google_fonts/google_fonts.dart';
 import 'package:provider/provider.dart';
 import '../api_client.dart';
 import '../utils/time_helper.dart';
+import '../utils/signing_payload.dart';
 import '../widgets/genesis_ring.dart'; 
 import '../theme_manager.dart';
 import '../services/update_service.dart';
@@ -74,7 +75,7 @@ class _IdentityCardState extends State<IdentityCard> {
       }
 
       String timestamp = TimeHelper.canonicalUtcTimestamp();
-      final payload = "${widget.identityId}|$nonce|$timestamp";
+      final payload = SigningPayload.heartbeat(widget.identityId, nonce, timestamp);
       
       final signature = await platform.invokeMethod('signHeartbeat', {'payload': payload});
       final sigBytes = (signature as List<Object?>).map((e) => e as int).toList();
@@ -0,0 +1,33 @@
+// clients/invariant_mobile/lib/utils/signing_payload.dart
+/// Mirrors `invariant_shared::signing` on the node.
+/// Any change here MUST match the Rust payload builders byte-for-byte
+/// (checked against `crates/invariant_shared/tests/vectors/signing_payloads.json`).
+class SigningPayload {
+  static const String domainTag = "INVARIANT";
+  static const int protocolVersion = 1;
+
+  /// `INVARIANT|v1|heartbeat|<identity_id>|<nonce_hex>|<timestamp>`
+  static String heartbeat(String identityId, String nonceHex, String timestamp) {
+    return _encode("heartbeat", [identityId, nonceHex, timestamp]);
+  }
+
+  /// `INVARIANT|v1|action|<identity_id>|<nonce_hex>|<payload_hash_hex>`
+  static String action(String identityId, String nonceHex, String payloadHashHex) {
+    return _encode("action", [identityId, nonceHex, payloadHashHex]);
+  }
+
+  /// `INVARIANT|v1|reattestation|<identity_id>|<nonce_hex>`
+  static String reattestation(String identityId, String nonceHex) {
+    return _encode("reattestation", [identityId, nonceHex]);
+  }
+
+  /// `INVARIANT|v1|key_rotation|<identity_id>|<new_public_key_hex>|<nonce_hex>`
+  /// Signed by the OLD key.
+  static String keyRotation(String identityId, String newPublicKeyHex, String nonceHex) {
+    return _encode("key_rotation", [identityId, newPublicKeyHex, nonceHex]);
+  }
+
+  static String _encode(String purpose, List<String> fields) {
+    return [domainTag, "v$protocolVersion", purpose, ...fields].join("|");
+  }
+}
@@ -0,0 +1,31 @@
+// clients/invariant_mobile/test/signing_payload_test.dart
+// The node checks the same vectors in `crates/invariant_shared/tests/signing_tests.rs`.
+
+import 'dart:convert';
+import 'dart:io';
+
+import 'package:flutter_test/flutter_test.dart';
+import 'package:invariant_mobile/utils/signing_payload.dart';
+
+void main() {
+  final vectors = jsonDecode(
+    File('../../crates/invariant_shared/tests/vectors/signing_payloads.json').readAsStringSync(),
+  ) as List<dynamic>;
+
+  test('payloads match the node byte-for-byte', () {
+    expect(vectors, hasLength(4));
+
+    for (final vector in vectors.cast<Map<String, dynamic>>()) {
+      final String id = vector['identity_id'];
+      final String nonce = vector['nonce'];
+      final String payload = switch (vector['purpose'] as String) {
+        'heartbeat' => SigningPayload.heartbeat(id, nonce, vector['timestamp']),
+        'action' => SigningPayload.action(id, nonce, vector['payload_hash']),
+        'reattestation' => SigningPayload.reattestation(id, nonce),
+        'key_rotation' => SigningPayload.keyRotation(id, vector['new_public_key'], nonce),
+        final other => throw ArgumentError('unknown purpose $other'),
+      };
+      expect(utf8.encode(payload), utf8.encode(vector['payload']), reason: vector['purpose']);
+    }
+  });
+}
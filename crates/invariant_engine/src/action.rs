@@ -0,0 +1,102 @@
+// crates/invariant_engine/src/action.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use chrono::Duration;
+use rand::RngCore;
+use uuid::Uuid;
+use invariant_shared::{ActionChallenge, ActionChallengeRequest, ActionVerdict, ActionVerifyRequest, IdentityStatus, ReceiptVerdict, Identity};
+use invariant_shared::signing;
+use crate::core::InvariantEngine;
+use crate::crypto;
+use crate::ports::{IdentityStorage, NonceStorage, PairwiseStorage};
+use crate::error::EngineError;
+
+/// Actions are identified by a SHA-256 digest of the partner's canonical description.
+pub const ACTION_PAYLOAD_HASH_LEN: usize = 32;
+const ACTION_NONCE_LEN: usize = 32;
+
+fn validate_payload_hash(payload_hash: &[u8]) -> Result<(), EngineError> {
+    if payload_hash.len() != ACTION_PAYLOAD_HASH_LEN {
+        return Err(EngineError::InvalidAction(format!("payload_hash must be {} bytes (SHA-256)", ACTION_PAYLOAD_HASH_LEN)));
+    }
+    Ok(())
+}
+
+impl<S: IdentityStorage, N: NonceStorage> InvariantEngine<S, N> {
+    /// Verifies a hardware approval of one action and burns its nonce.
+    /// Ok(false) means the signature did not verify; a reused nonce is `ReplayDetected`.
+    pub async fn validate_action_signature(
+        &self,
+        identity_id: Uuid,
+        payload_hash: &[u8],
+        nonce: &[u8],
+        signature: &[u8]
+    ) -> Result<bool, EngineError> {
+        let identity = self.load_identity(&identity_id).await?;
+
+        if identity.status == IdentityStatus::Revoked {
+            return Err(EngineError::IdentityRevoked(identity.id));
+        }
+
+        // Nonce Finality (Anti-Replay): one challenge, one approval.
+        if !self.nonce_storage.consume_nonce(nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+
+        let signed_data = signing::action_payload(&identity_id, nonce, payload_hash);
+        Ok(crypto::verify_signature(&identity.public_key, &signed_data, signature).is_ok())
+    }
+}
+
+/// ✍️ ACTION SIGNING
+/// Partner-gated actions (withdrawals, votes) approved by a fresh hardware signature.
+/// The node issues the challenge; binding it to the subject and payload is the caller's
+/// job (the server keeps that binding next to the challenge until it is used).
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + PairwiseStorage,
+    N: NonceStorage,
+{
+    pub async fn issue_action_challenge(&self, request: &ActionChallengeRequest) -> Result<ActionChallenge, EngineError> {
+        validate_payload_hash(&request.payload_hash)?;
+        let identity = self.resolve_pairwise(&request.partner_id, &request.subject_id).await?;
+        check_can_approve(&identity)?;
+
+        let mut nonce = vec![0u8; ACTION_NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let ttl = Duration::seconds(self.config.params.nonce_ttl_seconds as i64);
+        Ok(ActionChallenge { nonce, expires_at: self.now() + ttl })
+    }
+
+    /// The verdict is unsigned here; the node signs it with its receipt key.
+    pub async fn verify_action(&self, request: &ActionVerifyRequest) -> Result<ActionVerdict, EngineError> {
+        validate_payload_hash(&request.payload_hash)?;
+        let identity = self.resolve_pairwise(&request.partner_id, &request.subject_id).await?;
+        check_can_approve(&identity)?;
+
+        let valid = self.validate_action_signature(identity.id, &request.payload_hash, &request.nonce, &request.signature).await?;
+        Ok(ActionVerdict {
+            partner_id: request.partner_id.clone(),
+            subject_id: request.subject_id,
+            payload_hash: request.payload_hash.clone(),
+            nonce: request.nonce.clone(),
+            verdict: if valid { ReceiptVerdict::Accepted } else { ReceiptVerdict::Rejected },
+            issued_at: self.now(),
+        })
+    }
+}
+
+/// High-value actions need current hardware trust, not just a valid key.
+fn check_can_approve(identity: &Identity) -> Result<(), EngineError> {
+    match identity.status {
+        IdentityStatus::Revoked => Err(EngineError::IdentityRevoked(identity.id)),
+        // A dormant identity only wakes with a fresh attestation, like a stale one.
+        IdentityStatus::Stale | IdentityStatus::Dormant => Err(EngineError::AttestationRequired),
+        IdentityStatus::Active => Ok(()),
+    }
+}
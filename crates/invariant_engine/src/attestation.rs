@@ -13,6 +13,8 @@ use der_parser::ber::BerObjectContent;
 use p256::pkcs8::DecodePublicKey;
 use p256::ecdsa::VerifyingKey;
 use std::str;
+use invariant_shared::SecurityLevel;
+use sha2::{Digest, Sha256};
 
 /// OID for Android Key Attestation Extension (1.3.6.1.4.1.11129.2.1.17)
 const ANDROID_ATTESTATION_OID: &str = "1.3.6.1.4.1.11129.2.1.17";
@@ -20,6 +22,7 @@ const ANDROID_ATTESTATION_OID: &str = "1.3.6.1.4.1.11129.2.1.17";
 // --- TAG CONSTANTS ---
 const KM_TAG_NO_AUTH_REQUIRED: u32 = 503;
 const KM_TAG_ROOT_OF_TRUST: u32 = 704;
+const KM_TAG_OS_PATCHLEVEL: u32 = 706;
 const KM_TAG_ATTESTATION_ID_BRAND: u32 = 710;
 const KM_TAG_ATTESTATION_ID_DEVICE: u32 = 711;
 const KM_TAG_ATTESTATION_ID_PRODUCT: u32 = 712;
@@ -65,8 +68,15 @@ pub struct AttestationMetadata {
     pub device: Option<String>,
     pub product: Option<String>,
     pub trust_tier: String, 
+    pub security_level: Option<SecurityLevel>,
+    /// Android security patch level (YYYYMM), if the device reports it.
+    pub os_patch_level: Option<u32>,
     pub is_user_presence_required: bool,
     pub is_boot_locked: bool,
+    /// Verified boot key from the root of trust (OEM key on a locked bootloader).
+    pub verified_boot_key: Option<Vec<u8>>,
+    /// SHA-256 (hex) of the certificate that signed the attested key.
+    pub intermediate_fingerprint: Option<String>,
 }
 
 pub fn validate_attestation_chain(
@@ -96,7 +106,8 @@ pub fn validate_attestation_chain(
         .find(|ext| format!("{}", ext.oid) == oid_str)
         .ok_or(EngineError::InvalidAttestation("Missing Android Attestation Extension".into()))?;
 
-    let metadata = verify_extension_and_extract(extension.value, expected_challenge)?;
+    let mut metadata = verify_extension_and_extract(extension.value, expected_challenge)?;
+    metadata.intermediate_fingerprint = Some(hex::encode(Sha256::digest(&chain[1])));
 
     // 5. Verify Signatures up the Chain
     for i in 0..chain.len() - 1 {
@@ -141,9 +152,9 @@ pub fn verify_extension_and_extract(
     // A. Verify Security Level (Index 1)
     let att_sec_level = items[1].as_u32().map_err(|_| EngineError::InvalidAttestation("Invalid SecurityLevel".into()))?;
     
-    let tier_name = match att_sec_level {
-        1 => "TEE (TrustZone)",
-        2 => "StrongBox (SE)",
+    let (tier_name, security_level) = match att_sec_level {
+        1 => ("TEE (TrustZone)", SecurityLevel::Tee),
+        2 => ("StrongBox (SE)", SecurityLevel::StrongBox),
         _ => return Err(EngineError::InvalidAttestation("REJECTED: Software-backed key.".into()))
     };
 
@@ -166,11 +177,13 @@ pub fn verify_extension_and_extract(
     let mut has_root_of_trust = false;
     let mut is_boot_locked = false;
     let mut is_verified_boot = false;
+    let mut verified_boot_key: Option<Vec<u8>> = None;
     let mut no_auth_required = false;
     
     let mut brand: Option<String> = None;
     let mut device: Option<String> = None;
     let mut product: Option<String> = None;
+    let mut os_patch_level: Option<u32> = None;
 
     for item in tee_enforced_list {
         let tag = item.header.tag().0;
@@ -183,6 +196,7 @@ pub fn verify_extension_and_extract(
                 if let Ok((_, rot_seq_obj)) = parse_der_sequence(content_bytes) {
                     if let Ok(seq) = rot_seq_obj.as_sequence() {
                         if seq.len() >= 3 {
+                            if let Ok(key) = seq[0].as_slice() { verified_boot_key = Some(key.to_vec()); }
                             if let Ok(locked) = seq[1].as_bool() { is_boot_locked = locked; }
                             if let Ok(state) = seq[2].as_u32() { 
                                 if state == 0 { is_verified_boot = true; } 
@@ -194,6 +208,7 @@ pub fn verify_extension_and_extract(
         }
         
         if tag == KM_TAG_NO_AUTH_REQUIRED { no_auth_required = true; }
+        if tag == KM_TAG_OS_PATCHLEVEL { os_patch_level = extract_u32(item); }
 
         // Metadata Extraction
         if tag == KM_TAG_ATTESTATION_ID_BRAND { brand = extract_string(item); }
@@ -216,8 +231,12 @@ pub fn verify_extension_and_extract(
         device,
         product,
         trust_tier: tier_name.to_string(),
+        security_level: Some(security_level),
+        os_patch_level,
         is_user_presence_required: !no_auth_required,
         is_boot_locked,
+        verified_boot_key,
+        intermediate_fingerprint: None,
     };
     
     Ok(metadata)
@@ -266,6 +285,15 @@ fn extract_string(item: &DerObject) -> Option<String> {
     }
 }
 
+fn extract_u32(item: &DerObject) -> Option<u32> {
+    match &item.content {
+        BerObjectContent::Integer(_) => item.as_u32().ok(),
+        BerObjectContent::Unknown(any) => parse_der_integer(any.data).ok().and_then(|(_, inner)| inner.as_u32().ok()),
+        BerObjectContent::Tagged(_class, _tag, inner) => extract_u32(inner),
+        _ => None
+    }
+}
+
 fn verify_google_root(root: &X509Certificate) -> Result<(), EngineError> {
     let actual_spki = root.tbs_certificate.subject_pki.raw;
     let pem = GOOGLE_HARDWARE_ROOT_PEM.trim();
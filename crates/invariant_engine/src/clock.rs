@@ -0,0 +1,47 @@
+// crates/invariant_engine/src/clock.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use std::sync::{Arc, RwLock};
+use chrono::{DateTime, Duration, Utc};
+use crate::ports::Clock;
+
+/// Wall-clock time. The default for every engine.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> { Utc::now() }
+}
+
+/// ⏩ Manually driven time for tests and simulations.
+/// Clones share the same instant, so a test can keep a handle and move the
+/// engine's clock forward across streaks, trust decay and reaping.
+#[derive(Debug, Clone)]
+pub struct AdjustableClock {
+    now: Arc<RwLock<DateTime<Utc>>>,
+}
+
+impl AdjustableClock {
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self { now: Arc::new(RwLock::new(start)) }
+    }
+
+    pub fn set(&self, instant: DateTime<Utc>) {
+        *self.now.write().unwrap_or_else(|e| e.into_inner()) = instant;
+    }
+
+    pub fn advance(&self, by: Duration) {
+        *self.now.write().unwrap_or_else(|e| e.into_inner()) += by;
+    }
+}
+
+impl Clock for AdjustableClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.read().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -6,46 +6,122 @@
  * found in the LICENSE.md file in the root directory of this source tree.
  */
 
-use invariant_shared::{Heartbeat, IdentityStatus, GenesisRequest, ReAttestationRequest, Identity, Network};
-use crate::ports::{IdentityStorage, NonceStorage};
+use invariant_shared::{Heartbeat, DeviceStatus, IdentityStatus, GenesisRequest, ReAttestationRequest, KeyRotationRequest, Identity, Network, LogEventKind, TransitionReason, DomainEventKind};
+use invariant_shared::signing;
+use invariant_shared::transparency::MerkleTree;
+use crate::ports::{AttestationRefresh, Clock, EventSink, IdentityStorage, KeyRotation, NonceStorage, TransparencyStorage, LifecycleStorage, StreakStorage, SybilStorage, EligibilityStorage, DeviceStorage, UpgradeStorage, ReactivationStorage};
 use crate::error::EngineError;
 use crate::crypto;        
 use crate::attestation; 
-use chrono::{Utc, Duration};
+use crate::lifecycle;
+use crate::devices;
+use crate::params::ProtocolParameters;
+use crate::clock::SystemClock;
+use crate::events::{self, NoopEventSink};
+use crate::trust::TrustScorer;
+use crate::eligibility::EligibilityPolicy;
+use crate::sybil::AttestationFingerprint;
+use crate::transparency::log_record;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::AtomicU64;
 use uuid::Uuid;
 
-const MAX_TIMESTAMP_DRIFT_SECONDS: i64 = 120; // 2 Minutes
-const ATTESTATION_TTL_DAYS: i64 = 7; // 🛡️ TRUST DECAY: Re-prove hardware every 7 days
-
 #[derive(Debug, Clone)]
 pub struct EngineConfig {
     pub network: Network,
     pub genesis_version: u16,
+    pub params: ProtocolParameters,
 }
 
 pub struct InvariantEngine<S: IdentityStorage, N: NonceStorage> {
-    storage: S,
-    nonce_storage: N, // 🛡️ NEW
-    config: EngineConfig, 
+    pub(crate) storage: S,
+    pub(crate) nonce_storage: N, // 🛡️ NEW
+    pub(crate) config: EngineConfig, 
+    pub(crate) clock: Arc<dyn Clock>,
+    pub(crate) trust: TrustScorer,
+    pub(crate) events: Arc<dyn EventSink>,
+    pub(crate) eligibility: EligibilityPolicy,
+    /// Subtree hashes of the transparency log, filled lazily (see `transparency.rs`).
+    pub(crate) log_tree: RwLock<MerkleTree>,
+    /// Events the sink refused (see `emit`).
+    pub(crate) dropped_events: AtomicU64,
 }
 
 impl<S: IdentityStorage, N: NonceStorage> InvariantEngine<S, N> {
     pub fn new(storage: S, nonce_storage: N, config: EngineConfig) -> Self { 
-        Self { storage, nonce_storage, config } 
+        Self { storage, nonce_storage, config, clock: Arc::new(SystemClock), trust: TrustScorer::default(), events: Arc::new(NoopEventSink), eligibility: EligibilityPolicy::default(), log_tree: RwLock::new(MerkleTree::new()), dropped_events: AtomicU64::new(0) } 
+    }
+
+    /// Replaces the system clock (tests, simulations).
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Replaces the default trust weights and the (empty) device catalog.
+    pub fn with_trust_scorer(mut self, scorer: TrustScorer) -> Self {
+        self.trust = scorer;
+        self
+    }
+
+    /// Replaces the default genesis eligibility thresholds.
+    pub fn with_eligibility_policy(mut self, policy: EligibilityPolicy) -> Self {
+        self.eligibility = policy;
+        self
+    }
+
+    /// Publishes domain events (genesis, heartbeats, trust decay) to `sink`.
+    pub fn with_event_sink(mut self, sink: impl EventSink + 'static) -> Self {
+        self.events = Arc::new(sink);
+        self
     }
+
+    pub fn now(&self) -> DateTime<Utc> { self.clock.now() }
     
     pub fn get_storage(&self) -> &S { &self.storage }
 
+    pub fn params(&self) -> &ProtocolParameters { &self.config.params }
+
     pub async fn check_identity(&self, id: Uuid) -> Result<bool, EngineError> {
         let identity = self.storage.get_identity(&id).await?;
         Ok(identity.is_some())
     }
 
+    /// Loads an identity for an operation on this node. Identities minted on another
+    /// network are refused: a testnet identity reaches mainnet only by migrating.
+    pub(crate) async fn load_identity(&self, id: &Uuid) -> Result<Identity, EngineError> {
+        let identity = self.storage.get_identity(id).await?
+            .ok_or(EngineError::IdentityNotFound(*id))?;
+        self.check_network(&identity)?;
+        Ok(identity)
+    }
+
+    pub(crate) fn check_network(&self, identity: &Identity) -> Result<(), EngineError> {
+        if identity.network != self.config.network {
+            return Err(EngineError::NetworkMismatch {
+                expected: self.config.network.clone(),
+                found: identity.network.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Lifecycle operations. Every identity-changing event is appended to the transparency log,
+/// and every status change goes through the state machine (`lifecycle`).
+impl<S: IdentityStorage + TransparencyStorage + LifecycleStorage + StreakStorage + SybilStorage + EligibilityStorage + DeviceStorage + UpgradeStorage + ReactivationStorage, N: NonceStorage> InvariantEngine<S, N> {
+
     pub async fn process_genesis(&self, request: GenesisRequest) -> Result<Identity, EngineError> {
         // 1. Cheap Check
         if let Some(existing) = self.storage.get_identity_by_public_key(&request.public_key).await? {
+            self.check_network(&existing)?;
             return Ok(existing); 
         }
+        // A key retired by rotation, recovery or device removal never anchors a new identity
+        if self.storage.is_key_retired(&request.public_key).await? {
+            return Err(EngineError::AlreadyExists);
+        }
 
         // 2. Expensive Check (Hardware Attestation)
         let metadata = attestation::validate_attestation_chain(
@@ -53,11 +129,14 @@ impl<S: IdentityStorage, N: NonceStorage> InvariantEngine<S, N> {
             &request.public_key,
             Some(&request.nonce)
         )?;
+        let fingerprint = AttestationFingerprint::of(&metadata);
 
-        // 3. Construct Identity
-        let now = Utc::now();
+        // 3. Construct Identity (its key is the primary device)
+        let now = self.now();
+        let id = Uuid::new_v4();
+        let device = devices::attested_device(id, request.public_key.clone(), None, &metadata, now);
         let identity = Identity {
-            id: Uuid::new_v4(),
+            id,
             public_key: request.public_key,
             continuity_score: 0,
             streak: 0,
@@ -72,49 +151,89 @@ impl<S: IdentityStorage, N: NonceStorage> InvariantEngine<S, N> {
             hardware_brand: metadata.brand,
             hardware_device: metadata.device,
             hardware_product: metadata.product,
+            security_level: metadata.security_level,
+            os_patch_level: metadata.os_patch_level,
             
             genesis_version: self.config.genesis_version,
             network: self.config.network.clone(),
         };
 
         // 4. Persistence
-        self.storage.save_identity(&identity).await?;
+        let log = log_record(LogEventKind::Genesis, identity.id, &identity.public_key);
+        self.storage.record_genesis(&identity, &device, &fingerprint, &log).await?;
+        self.evaluate_eligibility(&identity).await?;
+        self.emit(identity.id, DomainEventKind::IdentityMinted {
+            network: identity.network.clone(),
+            genesis_version: identity.genesis_version,
+        }).await;
         
         Ok(identity)
     }
 
     /// The "Secure Tap" Verification Processor
     pub async fn process_heartbeat(&self, heartbeat: Heartbeat) -> Result<u64, EngineError> {
-        let identity = self.storage
-            .get_identity(&heartbeat.identity_id)
-            .await?
-            .ok_or(EngineError::IdentityNotFound(heartbeat.identity_id))?;
+        let identity_id = heartbeat.identity_id;
+        let result = self.accept_heartbeat(heartbeat).await;
+        match &result {
+            Ok(score) => self.emit(identity_id, DomainEventKind::HeartbeatAccepted { continuity_score: *score }).await,
+            Err(e) => if let Some(reason) = events::heartbeat_rejection(e) {
+                self.emit(identity_id, DomainEventKind::HeartbeatRejected { reason }).await;
+            },
+        }
+        result
+    }
+
+    async fn accept_heartbeat(&self, heartbeat: Heartbeat) -> Result<u64, EngineError> {
+        let mut identity = self.load_identity(&heartbeat.identity_id).await?;
 
         if identity.status == IdentityStatus::Revoked {
-             return Err(EngineError::Storage("Identity is Revoked".into()));
+             return Err(EngineError::IdentityRevoked(identity.id));
         }
 
         // 🛡️ 1. NONCE FINALITY (Anti-Replay)
         // We enforce single-use nonces ATOMICALLY via the nonce_storage (Redis).
         // This prevents race conditions where DB might not have synced yet.
-        // TTL = 300s (5 mins) covers the challenge validity window.
-        if !self.nonce_storage.consume_nonce(&heartbeat.nonce, 300).await? {
+        // TTL covers the challenge validity window.
+        if !self.nonce_storage.consume_nonce(&heartbeat.nonce, self.config.params.nonce_ttl_seconds).await? {
             return Err(EngineError::ReplayDetected);
         }
 
+        let payload = signing::heartbeat_payload(
+            &heartbeat.identity_id, 
+            &heartbeat.nonce,
+            &heartbeat.timestamp
+        );
+        let devices = self.storage.get_devices(&identity.id).await?;
+
         // 🛡️ 2. TRUST DECAY CHECK (Anti-Rooting Persistence)
-        // If the last hardware proof is too old, we require a refresh.
-        // This prevents a compromised device from mining indefinitely.
-        let days_since_attest = Utc::now()
-            .signed_duration_since(identity.last_attestation)
-            .num_days();
+        // If the last hardware proof is too old, we require a refresh, unless the heartbeat
+        // carries one (verified in step 6, after the cheap checks).
+        // This prevents a compromised device from mining indefinitely. A dormant identity
+        // likewise only wakes with a fresh proof (`reactivation`).
+        let attestation_ttl = Duration::days(self.config.params.attestation_ttl_days);
+        let carries_attestation = heartbeat.attestation_chain.is_some();
+        let since_attest = self.now().signed_duration_since(identity.last_attestation);
 
-        if days_since_attest > ATTESTATION_TTL_DAYS {
+        if !carries_attestation && (since_attest > attestation_ttl || matches!(identity.status, IdentityStatus::Stale | IdentityStatus::Dormant)) {
+            // Only a heartbeat the holder actually signed may record the decay.
+            if identity.status == IdentityStatus::Active {
+                devices::signing_device(&identity, &devices, &payload, &heartbeat.device_signature)?;
+                self.apply_transition(&mut identity, TransitionReason::AttestationExpired, None).await?;
+                self.emit(identity.id, DomainEventKind::TrustDecayed).await;
+            }
             return Err(EngineError::AttestationRequired);
         }
 
+        // A retired genesis version counts again only once it re-attests (here: step 6).
+        if !carries_attestation && self.requires_genesis_upgrade(&identity) {
+            return Err(EngineError::GenesisUpgradeRequired {
+                version: identity.genesis_version,
+                minimum: self.config.params.min_genesis_version,
+            });
+        }
+
         // 3. RATE LIMIT CHECK (Cheap Rejection)
-        let min_interval = Duration::minutes(1380); // 23 Hours
+        let min_interval = self.config.params.heartbeat_interval();
         
         if identity.continuity_score > 0 {
             let time_since_last = heartbeat.timestamp.signed_duration_since(identity.last_heartbeat);
@@ -122,91 +241,221 @@ impl<S: IdentityStorage, N: NonceStorage> InvariantEngine<S, N> {
                  return Err(EngineError::RateLimitExceeded);
             }
         }
+        self.check_cooling_off(&identity).await?;
 
-        // 4. NONCE-BOUND CRYPTO CHECK
-        let payload_str = format!("{}|{}|{}", 
-            heartbeat.identity_id, 
-            hex::encode(&heartbeat.nonce),
-            heartbeat.timestamp.to_rfc3339()
-        );
-        
-        crypto::verify_signature(
-            &identity.public_key,
-            payload_str.as_bytes(),
+        // 4. NONCE-BOUND CRYPTO CHECK (any active device of the identity)
+        let device = devices::signing_device(
+            &identity,
+            &devices,
+            &payload,
             &heartbeat.device_signature
         )?;
 
+        // Each key decays on its own: a fresh tablet does not vouch for a phone left in a drawer.
+        if !carries_attestation && device.is_some_and(|d| self.now().signed_duration_since(d.last_attestation) > attestation_ttl) {
+            return Err(EngineError::AttestationRequired);
+        }
+
         // 5. TIMESTAMP SANITY CHECK
-        let now = Utc::now();
+        let now = self.now();
         let sig_age = now.signed_duration_since(heartbeat.timestamp);
         
-        if sig_age.num_seconds() > MAX_TIMESTAMP_DRIFT_SECONDS {
-             return Err(EngineError::StaleHeartbeat(format!("Timestamp too old (>{}s)", MAX_TIMESTAMP_DRIFT_SECONDS)));
+        let max_drift = self.config.params.max_timestamp_drift_seconds;
+        let max_skew = self.config.params.max_future_skew_seconds;
+        if sig_age.num_seconds() > max_drift {
+             return Err(EngineError::StaleHeartbeat(format!("Timestamp too old (>{}s)", max_drift)));
         }
-        if sig_age.num_seconds() < -30 {
-            return Err(EngineError::StaleHeartbeat("Timestamp in the future (>30s)".into()));
+        if sig_age.num_seconds() < -max_skew {
+            return Err(EngineError::StaleHeartbeat(format!("Timestamp in the future (>{}s)", max_skew)));
         }
 
-        // 6. Update Score
-        let new_score = self.storage.log_heartbeat(&identity, &heartbeat).await?;
+        // 6. PIGGY-BACKED RE-ATTESTATION (Expensive)
+        // The chain must attest the key that signed this heartbeat, over this nonce.
+        let new_score = if let Some(chain) = &heartbeat.attestation_chain {
+            let signing_key = device.map_or(&identity.public_key, |d| &d.public_key);
+            let is_primary = *signing_key == identity.public_key;
+            let metadata = attestation::validate_attestation_chain(chain, signing_key, Some(&heartbeat.nonce))?;
+            let reactivation = self.plan_reactivation(&identity, TransitionReason::Reattestation).await?;
+            let transition = self.transition_for(&identity, TransitionReason::Reattestation, Some(heartbeat.identity_id))?;
+            // Waking a dormant identity starts its cooling-off: the proof counts, the heartbeat does not.
+            let cooling_off = reactivation.as_ref().and_then(|(r, _)| r.cooling_off_until);
+
+            // Trust timer, score and status move together (Stale/Dormant -> Active)
+            identity.last_attestation = now;
+            if is_primary {
+                identity.security_level = metadata.security_level;
+                identity.os_patch_level = metadata.os_patch_level;
+            }
+            let device = device.cloned().map(|mut d| {
+                d.last_attestation = now;
+                d.security_level = metadata.security_level;
+                d.os_patch_level = metadata.os_patch_level;
+                d
+            });
+            let new_score = self.storage.record_attestation_refresh(&AttestationRefresh {
+                identity: &identity,
+                device: device.as_ref(),
+                heartbeat: cooling_off.is_none().then_some(&heartbeat),
+                at: now,
+                reactivation: reactivation.clone(),
+                transition: transition.clone(),
+                fingerprint: is_primary.then(|| AttestationFingerprint::of(&metadata)),
+                log: log_record(LogEventKind::Reattestation, identity.id, &identity.public_key),
+            }).await?;
+            if let Some(transition) = transition {
+                identity.status = transition.to;
+            }
+            if let Some((reactivation, _)) = &reactivation {
+                self.reactivated(&mut identity, reactivation).await;
+            }
+            self.emit(identity.id, DomainEventKind::Reattested).await;
+            self.upgrade_genesis(&mut identity).await?;
+            if let Some(until) = cooling_off {
+                return Err(EngineError::CoolingOff(until));
+            }
+            new_score
+        } else {
+            // 7. Update Score
+            let new_score = self.storage.log_heartbeat(&identity, &heartbeat, now).await?;
+            if let Some(device) = device {
+                self.storage.record_device_heartbeat(&device.id, now).await?;
+            }
+            new_score
+        };
+        self.record_streak_day(heartbeat.identity_id, heartbeat.timestamp).await?;
+        identity.continuity_score = new_score;
+        self.evaluate_eligibility(&identity).await?;
         Ok(new_score)
     }
 
     /// 🛡️ NEW: Trust Refresh Handler
     /// Upgrades a 'Stale' identity back to 'Active' by verifying fresh hardware proofs.
+    /// Any active device may re-attest; it refreshes that device and the identity's timer.
     pub async fn process_reattestation(&self, request: ReAttestationRequest) -> Result<(), EngineError> {
         // 1. Verify Binding (Identity must exist)
-        let mut identity = self.storage.get_identity(&request.id).await?
-            .ok_or(EngineError::IdentityNotFound(request.id))?;
+        let mut identity = self.load_identity(&request.id).await?;
 
-        // 2. Verify Key Continuity (Must match registered key)
-        if identity.public_key != request.public_key {
+        // 2. Verify Key Continuity (Must be an active device of the identity)
+        let is_primary = identity.public_key == request.public_key;
+        let device = self.storage.get_devices(&identity.id).await?.into_iter()
+            .find(|d| d.status == DeviceStatus::Active && d.public_key == request.public_key);
+        if !is_primary && device.is_none() {
             return Err(EngineError::InvalidAttestation("Public Key mismatch during re-attestation".into()));
         }
+        let transition = self.transition_for(&identity, TransitionReason::Reattestation, Some(request.id))?;
 
         // 3. Verify Hardware Attestation (Expensive)
         // This fails if bootloader was unlocked or OS downgraded since Genesis.
-        attestation::validate_attestation_chain(
+        let metadata = attestation::validate_attestation_chain(
             &request.attestation_chain, 
             &request.public_key,
             Some(&request.nonce)
         )?;
 
-        // 4. Refresh Trust Timer
-        identity.last_attestation = Utc::now();
-        if identity.status == IdentityStatus::Stale {
-            identity.status = IdentityStatus::Active;
+        // 4. Refresh Trust Timer (and the patch level, which moves with OS updates)
+        let now = self.now();
+        identity.last_attestation = now;
+        if is_primary {
+            identity.security_level = metadata.security_level;
+            identity.os_patch_level = metadata.os_patch_level;
         }
         
-        // 5. Persistence
-        self.storage.save_identity(&identity).await?;
+        // 5. Persistence (Stale/Dormant -> Active together with the new timer)
+        let reactivation = self.plan_reactivation(&identity, TransitionReason::Reattestation).await?;
+        let device = device.map(|mut d| {
+            d.last_attestation = now;
+            d.security_level = metadata.security_level;
+            d.os_patch_level = metadata.os_patch_level;
+            d
+        });
+        identity.continuity_score = self.storage.record_attestation_refresh(&AttestationRefresh {
+            identity: &identity,
+            device: device.as_ref(),
+            heartbeat: None,
+            at: now,
+            reactivation: reactivation.clone(),
+            transition: transition.clone(),
+            fingerprint: is_primary.then(|| AttestationFingerprint::of(&metadata)),
+            log: log_record(LogEventKind::Reattestation, identity.id, &identity.public_key),
+        }).await?;
+        if let Some(transition) = transition {
+            identity.status = transition.to;
+        }
+        if let Some((reactivation, _)) = &reactivation {
+            self.reactivated(&mut identity, reactivation).await;
+        }
+        self.emit(identity.id, DomainEventKind::Reattested).await;
+        self.upgrade_genesis(&mut identity).await?;
         
         Ok(())
     }
 
-    pub async fn validate_action_signature(
-        &self, 
-        identity_id: Uuid, 
-        payload_hash: &[u8],
-        nonce: &[u8],
-        signature: &[u8]
-    ) -> Result<bool, EngineError> {
-        let identity = self.storage
-            .get_identity(&identity_id)
-            .await?
-            .ok_or(EngineError::IdentityNotFound(identity_id))?;
-
-        let mut signed_data = Vec::new();
-        signed_data.extend_from_slice(nonce);
-        signed_data.extend_from_slice(payload_hash);
-
-        match crypto::verify_signature(
-            &identity.public_key, 
-            &signed_data, 
-            signature
-        ) {
-            Ok(_) => Ok(true),
-            Err(_) => Ok(false),
+    /// 🔑 Hardware Key Rotation
+    /// Moves an identity (and its continuity) onto a new device key.
+    /// Requires BOTH a statement signed by the old key and a fresh attestation of the new key.
+    pub async fn process_key_rotation(&self, request: KeyRotationRequest) -> Result<Identity, EngineError> {
+        let mut identity = self.load_identity(&request.id).await?;
+
+        if identity.status == IdentityStatus::Revoked {
+            return Err(EngineError::IdentityRevoked(identity.id));
+        }
+        lifecycle::check_transition(&identity, TransitionReason::KeyRotation)?;
+
+        // 1. Nonce Finality (Anti-Replay)
+        if !self.nonce_storage.consume_nonce(&request.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+
+        // 2. Old Key Authorization (Cheap)
+        let payload = signing::key_rotation_payload(&request.id, &request.new_public_key, &request.nonce);
+        crypto::verify_signature(
+            &identity.public_key,
+            &payload,
+            &request.rotation_signature
+        )?;
+
+        // 3. Sybil Guard: the new key must not already anchor an identity
+        if self.storage.get_identity_by_public_key(&request.new_public_key).await?.is_some()
+            || self.storage.is_key_retired(&request.new_public_key).await?
+        {
+            return Err(EngineError::AlreadyExists);
         }
+
+        // 4. New Key Hardware Attestation (Expensive)
+        let metadata = attestation::validate_attestation_chain(
+            &request.attestation_chain,
+            &request.new_public_key,
+            Some(&request.nonce)
+        )?;
+
+        // 5. Swap Key, Refresh Trust Timer
+        let fingerprint = AttestationFingerprint::of(&metadata);
+        let previous_public_key = std::mem::replace(&mut identity.public_key, request.new_public_key);
+        identity.hardware_brand = metadata.brand;
+        identity.hardware_device = metadata.device;
+        identity.hardware_product = metadata.product;
+        identity.security_level = metadata.security_level;
+        identity.os_patch_level = metadata.os_patch_level;
+        identity.last_attestation = self.now();
+
+        // 6. Persistence (swap, key history, fingerprint and Stale/Dormant -> Active in one transaction)
+        let reactivation = self.plan_reactivation(&identity, TransitionReason::KeyRotation).await?;
+        let transition = self.transition_for(&identity, TransitionReason::KeyRotation, Some(request.id))?;
+        self.storage.rotate_public_key(&KeyRotation {
+            identity: &identity,
+            previous_public_key: &previous_public_key,
+            fingerprint,
+            reactivation: reactivation.clone(),
+            transition: transition.clone(),
+            log: log_record(LogEventKind::KeyRotation, identity.id, &identity.public_key),
+        }).await?;
+        if let Some(transition) = transition {
+            identity.status = transition.to;
+        }
+        if let Some((reactivation, _)) = &reactivation {
+            self.reactivated(&mut identity, reactivation).await;
+        }
+
+        Ok(identity)
     }
-}
\ No newline at end of file
+}
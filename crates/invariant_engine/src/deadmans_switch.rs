@@ -0,0 +1,184 @@
+// crates/invariant_engine/src/deadmans_switch.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use invariant_shared::{DeadMansSwitch, SwitchStatus, SwitchArmRequest, SwitchDisarmRequest, IdentityStatus};
+use invariant_shared::signing;
+use crate::core::InvariantEngine;
+use crate::ports::{IdentityStorage, NonceStorage, SwitchStorage};
+use crate::error::EngineError;
+use crate::crypto;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+pub const MIN_SWITCH_THRESHOLD_DAYS: u32 = 1;
+pub const MAX_SWITCH_THRESHOLD_DAYS: u32 = 3650;
+
+/// A push the node owes after a sweep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwitchAlert {
+    /// The identity whose switch moved.
+    pub holder_id: Uuid,
+    /// The holder (warning) or the beneficiary (trigger).
+    pub recipient_id: Uuid,
+    pub push_token: Option<String>,
+}
+
+/// Outcome of one `sweep_switches` run.
+#[derive(Debug, Default)]
+pub struct SwitchSweep {
+    /// Switches that entered their grace period; the holder is warned.
+    pub warned: Vec<SwitchAlert>,
+    /// Switches that fired; the beneficiary is notified.
+    pub triggered: Vec<SwitchAlert>,
+    /// Pending switches cancelled by a heartbeat.
+    pub cancelled: usize,
+}
+
+/// The switch's next state at `now`, or None if it stays put.
+///
+/// Armed → Pending once the holder has been silent for `threshold_days`.
+/// Pending → Armed if a heartbeat arrived since (cancellation).
+/// Pending → Triggered once `grace` has elapsed with the holder still silent.
+pub fn next_switch_state(
+    switch: &DeadMansSwitch,
+    last_heartbeat: DateTime<Utc>,
+    now: DateTime<Utc>,
+    grace: Duration,
+) -> Option<DeadMansSwitch> {
+    let overdue = now - last_heartbeat >= Duration::days(switch.threshold_days as i64);
+    let next = match switch.status {
+        SwitchStatus::Armed if overdue => DeadMansSwitch {
+            status: SwitchStatus::Pending,
+            pending_since: Some(now),
+            ..switch.clone()
+        },
+        SwitchStatus::Pending if !overdue => DeadMansSwitch {
+            status: SwitchStatus::Armed,
+            pending_since: None,
+            ..switch.clone()
+        },
+        SwitchStatus::Pending if switch.pending_since.is_none_or(|since| now - since >= grace) => DeadMansSwitch {
+            status: SwitchStatus::Triggered,
+            triggered_at: Some(now),
+            ..switch.clone()
+        },
+        _ => return None,
+    };
+    Some(next)
+}
+
+/// 🪦 DEAD MAN'S SWITCH
+/// The holder names a beneficiary identity and a silence threshold. When no heartbeat
+/// arrives for that long, the holder is warned and, after the grace period, the
+/// beneficiary is notified. A heartbeat or a disarm during the grace period cancels it.
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + SwitchStorage,
+    N: NonceStorage,
+{
+    pub async fn arm_switch(&self, request: SwitchArmRequest) -> Result<DeadMansSwitch, EngineError> {
+        let identity = self.load_identity(&request.id).await?;
+
+        if identity.status == IdentityStatus::Revoked {
+            return Err(EngineError::IdentityRevoked(identity.id));
+        }
+        if !(MIN_SWITCH_THRESHOLD_DAYS..=MAX_SWITCH_THRESHOLD_DAYS).contains(&request.threshold_days) {
+            return Err(EngineError::InvalidSwitch(format!(
+                "Threshold must be {}..={} days", MIN_SWITCH_THRESHOLD_DAYS, MAX_SWITCH_THRESHOLD_DAYS
+            )));
+        }
+        if request.beneficiary_id == request.id {
+            return Err(EngineError::InvalidSwitch("An identity cannot be its own beneficiary".into()));
+        }
+
+        // Nonce Finality + Owner Authorization
+        if !self.nonce_storage.consume_nonce(&request.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+        let payload = signing::switch_arm_payload(&request.id, &request.beneficiary_id, request.threshold_days, &request.nonce);
+        crypto::verify_signature(&identity.public_key, &payload, &request.signature)?;
+
+        let beneficiary = self.load_identity(&request.beneficiary_id).await?;
+        if beneficiary.status == IdentityStatus::Revoked {
+            return Err(EngineError::InvalidSwitch(format!("Beneficiary {} is revoked", beneficiary.id)));
+        }
+
+        let switch = DeadMansSwitch {
+            identity_id: request.id,
+            status: SwitchStatus::Armed,
+            threshold_days: request.threshold_days,
+            beneficiary_id: Some(request.beneficiary_id),
+            pending_since: None,
+            triggered_at: None,
+        };
+        self.storage.save_switch(&switch).await?;
+        Ok(switch)
+    }
+
+    pub async fn disarm_switch(&self, request: SwitchDisarmRequest) -> Result<DeadMansSwitch, EngineError> {
+        let identity = self.load_identity(&request.id).await?;
+
+        if !self.nonce_storage.consume_nonce(&request.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+        let payload = signing::switch_disarm_payload(&request.id, &request.nonce);
+        crypto::verify_signature(&identity.public_key, &payload, &request.signature)?;
+
+        let current = self.get_switch(request.id).await?;
+        let switch = DeadMansSwitch {
+            status: SwitchStatus::Inactive,
+            pending_since: None,
+            ..current
+        };
+        self.storage.save_switch(&switch).await?;
+        Ok(switch)
+    }
+
+    pub async fn get_switch(&self, identity_id: Uuid) -> Result<DeadMansSwitch, EngineError> {
+        self.storage.get_switch(&identity_id).await?
+            .ok_or(EngineError::IdentityNotFound(identity_id))
+    }
+
+    /// Advances every due switch by one step. Run periodically by the node.
+    pub async fn sweep_switches(&self) -> Result<SwitchSweep, EngineError> {
+        let now = self.now();
+        let grace = Duration::hours(self.config.params.switch_grace_hours);
+        let mut sweep = SwitchSweep::default();
+
+        for switch in self.storage.get_due_switches(now).await? {
+            let Some(holder) = self.storage.get_identity(&switch.identity_id).await? else { continue };
+            if holder.status == IdentityStatus::Revoked {
+                continue;
+            }
+            let Some(next) = next_switch_state(&switch, holder.last_heartbeat, now, grace) else { continue };
+
+            // Lost a race with a disarm or a concurrent sweep: leave it to them.
+            if !self.storage.transition_switch(&next, switch.status).await? {
+                continue;
+            }
+
+            match next.status {
+                SwitchStatus::Pending => sweep.warned.push(SwitchAlert {
+                    holder_id: holder.id,
+                    recipient_id: holder.id,
+                    push_token: holder.fcm_token.clone(),
+                }),
+                SwitchStatus::Triggered => {
+                    let Some(beneficiary_id) = next.beneficiary_id else { continue };
+                    let push_token = self.storage.get_identity(&beneficiary_id).await?
+                        .and_then(|b| b.fcm_token);
+                    sweep.triggered.push(SwitchAlert { holder_id: holder.id, recipient_id: beneficiary_id, push_token });
+                }
+                SwitchStatus::Armed => sweep.cancelled += 1,
+                SwitchStatus::Inactive => (),
+            }
+        }
+
+        Ok(sweep)
+    }
+}
@@ -0,0 +1,189 @@
+// crates/invariant_engine/src/devices.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use invariant_shared::{Device, DeviceStatus, DeviceEnrollmentRequest, DeviceRevocationRequest, Identity, IdentityStatus, LogEventKind};
+use invariant_shared::signing;
+use crate::attestation::{self, AttestationMetadata};
+use crate::core::InvariantEngine;
+use crate::ports::{IdentityStorage, NonceStorage, DeviceStorage, TransparencyStorage};
+use crate::error::EngineError;
+use crate::crypto;
+use crate::transparency::log_record;
+
+const MAX_LABEL_CHARS: usize = 64;
+
+/// A device row for a freshly attested key.
+pub(crate) fn attested_device(
+    identity_id: Uuid,
+    public_key: Vec<u8>,
+    label: Option<String>,
+    metadata: &AttestationMetadata,
+    now: DateTime<Utc>,
+) -> Device {
+    Device {
+        id: Uuid::new_v4(),
+        identity_id,
+        public_key,
+        label,
+        status: DeviceStatus::Active,
+        primary: false,
+        hardware_brand: metadata.brand.clone(),
+        hardware_device: metadata.device.clone(),
+        hardware_product: metadata.product.clone(),
+        security_level: metadata.security_level,
+        os_patch_level: metadata.os_patch_level,
+        added_at: now,
+        last_attestation: now,
+        last_heartbeat: None,
+        revoked_at: None,
+    }
+}
+
+/// The active device whose key produced `signature`. The primary key is tried first and
+/// always signs for the identity; `None` means it signed but has no device row.
+pub(crate) fn signing_device<'a>(
+    identity: &Identity,
+    devices: &'a [Device],
+    payload: &[u8],
+    signature: &[u8],
+) -> Result<Option<&'a Device>, EngineError> {
+    if crypto::verify_signature(&identity.public_key, payload, signature).is_ok() {
+        return Ok(devices.iter().find(|d| d.public_key == identity.public_key));
+    }
+    devices.iter()
+        .filter(|d| d.status == DeviceStatus::Active && d.public_key != identity.public_key)
+        .find(|d| crypto::verify_signature(&d.public_key, payload, signature).is_ok())
+        .map(Some)
+        .ok_or(EngineError::InvalidSignature)
+}
+
+/// 📱 MULTI-DEVICE
+/// An identity holds up to `max_devices_per_identity` attested keys. Any active device
+/// heartbeats for the identity and can enroll or retire another; the primary key
+/// (`Identity.public_key`) still signs everything else (guardians, switch, revocation).
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + DeviceStorage + TransparencyStorage,
+    N: NonceStorage,
+{
+    /// Adds a device key. Requires an authorization by an active device AND a fresh
+    /// attestation of the new key.
+    pub async fn enroll_device(&self, request: DeviceEnrollmentRequest) -> Result<Device, EngineError> {
+        let identity = self.load_identity(&request.identity_id).await?;
+
+        if identity.status != IdentityStatus::Active {
+            return Err(EngineError::InvalidDevice(format!("{:?} identities cannot enroll devices", identity.status)));
+        }
+        if request.label.as_ref().is_some_and(|l| l.chars().count() > MAX_LABEL_CHARS) {
+            return Err(EngineError::InvalidDevice(format!("Label exceeds {} characters", MAX_LABEL_CHARS)));
+        }
+
+        // 1. Nonce Finality (Anti-Replay)
+        if !self.nonce_storage.consume_nonce(&request.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+
+        // 2. Authorization by an active device (Cheap)
+        let devices = self.storage.get_devices(&identity.id).await?;
+        let payload = signing::device_enrollment_payload(&identity.id, &request.public_key, &request.nonce);
+        signing_device(&identity, &devices, &payload, &request.authorization_signature)?;
+
+        // 3. Device Limit (checked again atomically on insert)
+        let max_active = self.config.params.max_devices_per_identity as u32;
+        if devices.iter().filter(|d| d.status == DeviceStatus::Active).count() as u32 >= max_active {
+            return Err(EngineError::DeviceLimitReached(max_active));
+        }
+
+        // 4. Sybil Guard: the key must not already anchor an identity
+        if self.storage.get_identity_by_public_key(&request.public_key).await?.is_some()
+            || self.storage.is_key_retired(&request.public_key).await?
+        {
+            return Err(EngineError::AlreadyExists);
+        }
+
+        // 5. New Key Hardware Attestation (Expensive)
+        let metadata = attestation::validate_attestation_chain(
+            &request.attestation_chain,
+            &request.public_key,
+            Some(&request.nonce)
+        )?;
+
+        // 6. Persistence
+        let device = attested_device(identity.id, request.public_key, request.label, &metadata, self.now());
+        let log = log_record(LogEventKind::DeviceEnrolled, identity.id, &device.public_key);
+        if !self.storage.add_device(&device, max_active, &log).await? {
+            return Err(EngineError::DeviceLimitReached(max_active));
+        }
+
+        Ok(device)
+    }
+
+    /// Retires a device key. Any active device can retire a secondary one, but only the
+    /// primary key can retire itself (a lost primary goes through recovery). Retiring the
+    /// primary promotes the most recently attested remaining device; the last device cannot
+    /// be retired (revoke the identity instead). Retired keys are archived for good.
+    pub async fn revoke_device(&self, request: DeviceRevocationRequest) -> Result<Device, EngineError> {
+        let identity = self.load_identity(&request.identity_id).await?;
+
+        if identity.status == IdentityStatus::Revoked {
+            return Err(EngineError::IdentityRevoked(identity.id));
+        }
+
+        // 1. Nonce Finality (Anti-Replay)
+        if !self.nonce_storage.consume_nonce(&request.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+
+        // 2. Authorization by an active device
+        let devices = self.storage.get_devices(&identity.id).await?;
+        let payload = signing::device_revocation_payload(&identity.id, &request.device_id, &request.nonce);
+        signing_device(&identity, &devices, &payload, &request.signature)?;
+
+        let mut device = devices.iter()
+            .find(|d| d.id == request.device_id && d.status == DeviceStatus::Active)
+            .cloned()
+            .ok_or_else(|| EngineError::InvalidDevice("Unknown or already revoked device".into()))?;
+
+        // 3. Primary Succession (a secondary device cannot depose the primary)
+        let promote = if device.public_key == identity.public_key {
+            crypto::verify_signature(&identity.public_key, &payload, &request.signature)?;
+            let successor = devices.iter()
+                .filter(|d| d.status == DeviceStatus::Active && d.id != device.id)
+                .max_by_key(|d| d.last_attestation)
+                .ok_or_else(|| EngineError::InvalidDevice("Cannot remove the only device; revoke the identity instead".into()))?;
+            Some(successor)
+        } else {
+            None
+        };
+
+        // 4. Persistence
+        device.status = DeviceStatus::Revoked;
+        device.revoked_at = Some(self.now());
+        let mut log = vec![log_record(LogEventKind::DeviceRevoked, identity.id, &device.public_key)];
+        if let Some(successor) = promote {
+            log.push(log_record(LogEventKind::KeyRotation, identity.id, &successor.public_key));
+        }
+        self.storage.revoke_device(&device, promote, &log).await?;
+
+        Ok(device)
+    }
+
+    /// Every device of the identity, revoked ones included, oldest first.
+    pub async fn list_devices(&self, identity_id: Uuid) -> Result<Vec<Device>, EngineError> {
+        let identity = self.storage.get_identity(&identity_id).await?
+            .ok_or(EngineError::IdentityNotFound(identity_id))?;
+
+        let mut devices = self.storage.get_devices(&identity_id).await?;
+        for device in &mut devices {
+            device.primary = device.public_key == identity.public_key;
+        }
+        Ok(devices)
+    }
+}
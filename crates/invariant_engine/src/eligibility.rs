@@ -0,0 +1,166 @@
+// crates/invariant_engine/src/eligibility.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use invariant_shared::{Identity, IdentityStatus, SecurityLevel, SybilConfidence, EligibilityRule, RuleOutcome, EligibilityReport};
+use crate::core::InvariantEngine;
+use crate::ports::{IdentityStorage, NonceStorage, StreakStorage, SybilStorage, EligibilityStorage};
+use crate::error::EngineError;
+
+/// Thresholds of genesis eligibility. A zero threshold, `false` or `null` disables a rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EligibilityPolicy {
+    pub min_streak_days: u64,
+    pub min_continuity_score: u64,
+    pub require_strongbox: bool,
+    /// Membership of a Sybil cluster at or above this confidence disqualifies.
+    pub sybil_disqualify_at: Option<SybilConfidence>,
+    pub min_account_age_days: i64,
+}
+
+impl Default for EligibilityPolicy {
+    fn default() -> Self {
+        Self {
+            min_streak_days: 7,
+            min_continuity_score: 30,
+            require_strongbox: true,
+            sybil_disqualify_at: Some(SybilConfidence::Low),
+            min_account_age_days: 30,
+        }
+    }
+}
+
+impl EligibilityPolicy {
+    /// Parses a policy file. Omitted rules keep their default threshold.
+    pub fn from_json(json: &str) -> Result<Self, EngineError> {
+        let policy: Self = serde_json::from_str(json).map_err(|e| EngineError::InvalidPolicy(e.to_string()))?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    pub fn validate(&self) -> Result<(), EngineError> {
+        if self.min_account_age_days < 0 {
+            return Err(EngineError::InvalidPolicy("min_account_age_days must not be negative".into()));
+        }
+        Ok(())
+    }
+
+    /// Applies every enabled rule. `streak` is the current day streak and `sybil_flag`
+    /// the strongest cluster the identity belongs to.
+    pub fn evaluate(&self, identity: &Identity, streak: u64, sybil_flag: Option<SybilConfidence>, now: DateTime<Utc>) -> EligibilityReport {
+        let mut rules = vec![outcome(
+            EligibilityRule::Active,
+            identity.status == IdentityStatus::Active,
+            format!("status {:?}", identity.status).to_lowercase(),
+        )];
+
+        if self.min_streak_days > 0 {
+            rules.push(outcome(
+                EligibilityRule::MinStreak,
+                streak >= self.min_streak_days,
+                format!("streak {} of {} days", streak, self.min_streak_days),
+            ));
+        }
+        if self.min_continuity_score > 0 {
+            rules.push(outcome(
+                EligibilityRule::MinContinuityScore,
+                identity.continuity_score >= self.min_continuity_score,
+                format!("{} of {} verified heartbeats", identity.continuity_score, self.min_continuity_score),
+            ));
+        }
+        if self.require_strongbox {
+            let strongbox = identity.security_level == Some(SecurityLevel::StrongBox);
+            rules.push(outcome(
+                EligibilityRule::StrongBox,
+                strongbox,
+                if strongbox { "StrongBox-backed key".into() } else { "key is not StrongBox-backed".into() },
+            ));
+        }
+        if let Some(threshold) = self.sybil_disqualify_at {
+            let flagged = sybil_flag.filter(|c| *c >= threshold);
+            rules.push(outcome(
+                EligibilityRule::NoSybilFlag,
+                flagged.is_none(),
+                match flagged {
+                    Some(confidence) => format!("in a {} confidence Sybil cluster", confidence.tag()),
+                    None => "not in a Sybil cluster".into(),
+                },
+            ));
+        }
+        if self.min_account_age_days > 0 {
+            let age = (now - identity.created_at).num_days();
+            rules.push(outcome(
+                EligibilityRule::MinAccountAge,
+                now - identity.created_at >= Duration::days(self.min_account_age_days),
+                format!("account {} of {} days old", age.max(0), self.min_account_age_days),
+            ));
+        }
+
+        EligibilityReport { eligible: rules.iter().all(|r| r.passed), rules, evaluated_at: now }
+    }
+}
+
+fn outcome(rule: EligibilityRule, passed: bool, detail: String) -> RuleOutcome {
+    RuleOutcome { rule, passed, detail }
+}
+
+/// Result of one periodic re-evaluation batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EligibilitySweep {
+    pub evaluated: u32,
+    pub eligible: u32,
+}
+
+/// 🌱 GENESIS ELIGIBILITY
+/// Recomputed on genesis, on every counted heartbeat and by a periodic job (account age
+/// and Sybil flags change without the holder doing anything). The last report is stored.
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + StreakStorage + SybilStorage + EligibilityStorage,
+    N: NonceStorage,
+{
+    /// Evaluates the policy against the identity's current state and stores the report.
+    pub async fn evaluate_eligibility(&self, identity: &Identity) -> Result<EligibilityReport, EngineError> {
+        let streak = self.storage.get_streak(&identity.id).await?.map_or(0, |s| s.streak);
+        let sybil_flag = self.sybil_flag(&identity.id).await?;
+        let report = self.eligibility.evaluate(identity, streak, sybil_flag, self.now());
+        self.storage.save_eligibility(&identity.id, &report).await?;
+        Ok(report)
+    }
+
+    /// The stored report, re-evaluated if there is none or if it predates a status change.
+    pub async fn genesis_eligibility(&self, identity: &Identity) -> Result<EligibilityReport, EngineError> {
+        match self.storage.get_eligibility(&identity.id).await? {
+            Some(report) if !report.eligible || identity.status == IdentityStatus::Active => Ok(report),
+            _ => self.evaluate_eligibility(identity).await,
+        }
+    }
+
+    /// Re-evaluates up to `limit` identities whose report is missing, older than
+    /// `eligibility_refresh_hours`, or still eligible after a status change.
+    pub async fn refresh_eligibility(&self, limit: u32) -> Result<EligibilitySweep, EngineError> {
+        let before = self.now() - Duration::hours(self.config.params.eligibility_refresh_hours);
+        let mut sweep = EligibilitySweep::default();
+
+        for id in self.storage.get_eligibility_due(before, limit).await? {
+            let Some(identity) = self.storage.get_identity(&id).await? else { continue };
+            let report = self.evaluate_eligibility(&identity).await?;
+            sweep.evaluated += 1;
+            sweep.eligible += report.eligible as u32;
+        }
+        Ok(sweep)
+    }
+
+    pub async fn eligibility_for(&self, identity_id: Uuid) -> Result<EligibilityReport, EngineError> {
+        let identity = self.load_identity(&identity_id).await?;
+        self.genesis_eligibility(&identity).await
+    }
+}
@@ -6,12 +6,17 @@
  
 use thiserror::Error;
 use uuid::Uuid;
+use invariant_shared::Network;
+use chrono::{DateTime, Utc};
 
 #[derive(Error, Debug)]
 pub enum EngineError {
     #[error("Identity {0} not found")]
     IdentityNotFound(Uuid),
 
+    #[error("Identity {0} is revoked")]
+    IdentityRevoked(Uuid),
+
     #[error("Identity already exists")]
     AlreadyExists,
 
@@ -24,7 +29,7 @@ pub enum EngineError {
     #[error("Verification rejected: Timestamp {0} is too old")]
     StaleHeartbeat(String),
 
-    #[error("Rate Limit: Verification is allowed once every 24 hours.")]
+    #[error("Rate Limit: Too soon since the last accepted request.")]
     RateLimitExceeded, 
 
     #[error("Storage failure: {0}")]
@@ -36,4 +41,55 @@ pub enum EngineError {
 
     #[error("Trust Decay: Hardware attestation is stale. Please re-attest.")]
     AttestationRequired,
+
+    #[error("Recovery {0} not found")]
+    RecoveryNotFound(Uuid),
+
+    #[error("Recovery rejected: {0}")]
+    RecoveryRejected(String),
+
+    #[error("Invalid partner: {0}")]
+    InvalidPartner(String),
+
+    #[error("Token request rejected: {0}")]
+    InvalidTokenRequest(String),
+
+    #[error("Invalid log range: {0}")]
+    InvalidLogRange(String),
+
+    #[error("Illegal status transition: {0}")]
+    IllegalTransition(String),
+
+    #[error("Revocation rejected: {0}")]
+    InvalidRevocation(String),
+
+    #[error("Dead man's switch rejected: {0}")]
+    InvalidSwitch(String),
+
+    #[error("Unknown time zone: {0}")]
+    InvalidTimezone(String),
+
+    #[error("Action rejected: {0}")]
+    InvalidAction(String),
+
+    #[error("Device rejected: {0}")]
+    InvalidDevice(String),
+
+    #[error("Device limit reached: at most {0} active devices per identity")]
+    DeviceLimitReached(u32),
+
+    #[error("Invalid protocol policy: {0}")]
+    InvalidPolicy(String),
+
+    #[error("Network mismatch: this node serves {expected}, the identity belongs to {found}")]
+    NetworkMismatch { expected: Network, found: Network },
+
+    #[error("Migration rejected: {0}")]
+    InvalidMigration(String),
+
+    #[error("Genesis version {version} is retired (minimum {minimum}). Please re-attest to upgrade.")]
+    GenesisUpgradeRequired { version: u16, minimum: u16 },
+
+    #[error("Reactivation cooling-off: heartbeats count again from {0}")]
+    CoolingOff(DateTime<Utc>),
 }
\ No newline at end of file
@@ -0,0 +1,96 @@
+// crates/invariant_engine/src/events.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::Ordering;
+use async_trait::async_trait;
+use invariant_shared::{DomainEvent, DomainEventKind, HeartbeatRejection};
+use uuid::Uuid;
+use crate::core::InvariantEngine;
+use crate::error::EngineError;
+use crate::ports::{EventSink, IdentityStorage, NonceStorage};
+
+/// Drops every event. The default for every engine.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopEventSink;
+
+#[async_trait]
+impl EventSink for NoopEventSink {
+    async fn publish(&self, _event: &DomainEvent) -> Result<(), EngineError> { Ok(()) }
+}
+
+/// Refuses every event, like a sink whose backend is down. For tests of the drop path.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FailingEventSink;
+
+#[async_trait]
+impl EventSink for FailingEventSink {
+    async fn publish(&self, _event: &DomainEvent) -> Result<(), EngineError> {
+        Err(EngineError::Storage("Event sink unavailable".into()))
+    }
+}
+
+/// 📼 Keeps events in memory for tests and simulations.
+/// Clones share the same buffer, so a test can keep a handle to what the engine emitted.
+#[derive(Debug, Clone, Default)]
+pub struct RecordingEventSink {
+    events: Arc<Mutex<Vec<DomainEvent>>>,
+}
+
+impl RecordingEventSink {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn events(&self) -> Vec<DomainEvent> {
+        self.events.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn kinds(&self) -> Vec<DomainEventKind> {
+        self.events().into_iter().map(|e| e.kind).collect()
+    }
+}
+
+#[async_trait]
+impl EventSink for RecordingEventSink {
+    async fn publish(&self, event: &DomainEvent) -> Result<(), EngineError> {
+        self.events.lock().unwrap_or_else(|e| e.into_inner()).push(event.clone());
+        Ok(())
+    }
+}
+
+/// The public reason behind a rejected heartbeat. `None` for failures that are not a
+/// verdict on the heartbeat itself (unknown identity, storage errors).
+pub fn heartbeat_rejection(error: &EngineError) -> Option<HeartbeatRejection> {
+    match error {
+        EngineError::ReplayDetected => Some(HeartbeatRejection::Replay),
+        EngineError::InvalidSignature => Some(HeartbeatRejection::InvalidSignature),
+        EngineError::StaleHeartbeat(_) => Some(HeartbeatRejection::StaleTimestamp),
+        EngineError::RateLimitExceeded => Some(HeartbeatRejection::RateLimited),
+        EngineError::AttestationRequired => Some(HeartbeatRejection::AttestationRequired),
+        EngineError::InvalidAttestation(_) => Some(HeartbeatRejection::InvalidAttestation),
+        EngineError::GenesisUpgradeRequired { .. } => Some(HeartbeatRejection::UpgradeRequired),
+        EngineError::CoolingOff(_) => Some(HeartbeatRejection::CoolingOff),
+        _ => None,
+    }
+}
+
+impl<S: IdentityStorage, N: NonceStorage> InvariantEngine<S, N> {
+    /// Publishes an event stamped with the engine clock. A sink failure never fails the
+    /// operation (see `EventSink`): the change has already been committed, so the event is
+    /// dropped and counted in `dropped_events`.
+    pub(crate) async fn emit(&self, identity_id: Uuid, kind: DomainEventKind) {
+        let event = DomainEvent { id: Uuid::new_v4(), identity_id, occurred_at: self.now(), kind };
+        if self.events.publish(&event).await.is_err() {
+            self.dropped_events.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Events the sink refused since the engine started.
+    pub fn dropped_events(&self) -> u64 {
+        self.dropped_events.load(Ordering::Relaxed)
+    }
+}
@@ -22,9 +22,69 @@ pub mod crypto;
 /// Hardware Attestation Validation Logic.
 pub mod attestation;
 
+/// Guardian-based Social Recovery for lost devices.
+pub mod recovery;
+
+/// Partner-scoped pseudonymous identifiers (unlinkability).
+pub mod pairwise;
+
+/// Anonymous Privacy Pass tokens (RFC 9578, Blind RSA).
+pub mod privacy_pass;
+
+/// Injectable time source (system and adjustable clocks).
+pub mod clock;
+
+/// Identity status state machine and its audit trail.
+pub mod lifecycle;
+
+/// Per-network timing rules (policy file).
+pub mod params;
+
+/// Identity revocation (operator and self-service) and the partner feed.
+pub mod revocation;
+
+/// Dead man's switch: beneficiary notification after prolonged silence.
+pub mod deadmans_switch;
+
+/// Day-based streaks with a grace window and earned freezes.
+pub mod streak;
+
+/// Composite, explainable trust score.
+pub mod trust;
+
+/// Sybil cluster detection over shared device, network and timing signals.
+pub mod sybil;
+
+/// Partner action challenges and hardware-signed approvals.
+pub mod action;
+
+/// Rule-driven genesis eligibility.
+pub mod eligibility;
+
+/// Multiple attested device keys per identity.
+pub mod devices;
+
+/// Network isolation and testnet-to-mainnet migration.
+pub mod migration;
+
+/// Genesis version upgrades of re-attested identities.
+pub mod upgrade;
+
+/// Reactivation of dormant identities: score decay, streak rules and cooling-off.
+pub mod reactivation;
+
+/// Domain event stream (`EventSink`) and the built-in sinks.
+pub mod events;
+
+/// Append-only Merkle log of identity lifecycle events.
+pub mod transparency;
+
 // Re-exports
 pub use core::InvariantEngine;
+pub use params::ProtocolParameters;
+pub use trust::{TrustScorer, TrustWeights, DeviceCatalog};
+pub use eligibility::EligibilityPolicy;
 pub use error::EngineError;
-pub use ports::IdentityStorage;
+pub use ports::{AttestationRefresh, Clock, EventSink, IdentityStorage, KeyRotation, MigrationHandover, RecoveryStorage, RecoveryCompletion, PairwiseStorage, PrivacyPassStorage, TransparencyStorage, LogRecord, LifecycleStorage, RevocationStorage, SwitchStorage, StreakStorage, TrustStorage, SybilStorage, EligibilityStorage, DeviceStorage, MigrationStorage, UpgradeStorage, ReactivationStorage};
 pub use crypto::verify_signature;
 pub use attestation::validate_attestation_chain;
\ No newline at end of file
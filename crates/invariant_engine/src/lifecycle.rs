@@ -0,0 +1,103 @@
+// crates/invariant_engine/src/lifecycle.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use invariant_shared::{Identity, IdentityStatus, IdentityTransition, TransitionReason};
+use crate::core::InvariantEngine;
+use crate::ports::{IdentityStorage, LifecycleStorage, NonceStorage};
+use crate::error::EngineError;
+use uuid::Uuid;
+
+/// 🚦 IDENTITY STATE MACHINE
+/// The status an identity moves to when `reason` fires in state `from`, or `None`
+/// if the trigger is illegal there. Returning `from` itself means "legal, no change".
+///
+/// | from            | trigger                                  | to      |
+/// |-----------------|------------------------------------------|---------|
+/// | Active          | AttestationExpired                       | Stale   |
+/// | Stale / Dormant | Reattestation, KeyRotation, Recovery     | Active  |
+/// | Active / Stale  | Inactivity (reaper)                      | Dormant |
+/// | any but Revoked | Revocation                               | Revoked |
+///
+/// Revoked is terminal. A heartbeat never changes the status: waking a dormant identity
+/// takes a fresh attestation (`reactivation`).
+pub fn next_status(from: &IdentityStatus, reason: TransitionReason) -> Option<IdentityStatus> {
+    use IdentityStatus::*;
+    match (from, reason) {
+        (Revoked, _) => None,
+        (Active, TransitionReason::Heartbeat) => Some(Active),
+        (Active | Stale, TransitionReason::AttestationExpired) => Some(Stale),
+        (_, TransitionReason::Reattestation | TransitionReason::KeyRotation | TransitionReason::Recovery) => Some(Active),
+        (Active | Stale, TransitionReason::Inactivity) => Some(Dormant),
+        (_, TransitionReason::Revocation) => Some(Revoked),
+        _ => None,
+    }
+}
+
+/// Rejects `reason` up front, before any side effect of the operation it belongs to.
+pub(crate) fn check_transition(identity: &Identity, reason: TransitionReason) -> Result<IdentityStatus, EngineError> {
+    next_status(&identity.status, reason).ok_or_else(|| EngineError::IllegalTransition(
+        format!("{:?} cannot take '{}'", identity.status, reason.tag())
+    ))
+}
+
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + LifecycleStorage,
+    N: NonceStorage,
+{
+    /// The only way an identity's status changes. Writes the audit row together with
+    /// the new status, guarded on the status we read (a concurrent change fails).
+    pub(crate) async fn apply_transition(
+        &self,
+        identity: &mut Identity,
+        reason: TransitionReason,
+        actor_id: Option<Uuid>,
+    ) -> Result<(), EngineError> {
+        let Some(transition) = self.transition_for(identity, reason, actor_id)? else {
+            return Ok(());
+        };
+        if !self.storage.record_transition(&transition).await? {
+            return Err(EngineError::Storage("Concurrent status change".into()));
+        }
+
+        identity.status = transition.to;
+        Ok(())
+    }
+
+    /// The transition `reason` causes, or `None` when the status stays. For storage calls
+    /// that record it together with other writes; `apply_transition` otherwise.
+    pub(crate) fn transition_for(
+        &self,
+        identity: &Identity,
+        reason: TransitionReason,
+        actor_id: Option<Uuid>,
+    ) -> Result<Option<IdentityTransition>, EngineError> {
+        let to = check_transition(identity, reason)?;
+        Ok((to != identity.status).then(|| IdentityTransition {
+            identity_id: identity.id,
+            from: identity.status.clone(),
+            to,
+            reason,
+            actor_id,
+            created_at: self.now(),
+        }))
+    }
+
+    /// Moves identities silent for the reaper window (as of the engine clock) to Dormant.
+    /// Returns how many were reaped.
+    pub async fn reap_inactive(&self) -> Result<u64, EngineError> {
+        self.storage.run_reaper(self.now()).await
+    }
+
+    /// Status history of an identity, oldest first.
+    pub async fn transition_history(&self, identity_id: Uuid) -> Result<Vec<IdentityTransition>, EngineError> {
+        self.storage.get_identity(&identity_id).await?
+            .ok_or(EngineError::IdentityNotFound(identity_id))?;
+        self.storage.get_transitions(&identity_id).await
+    }
+}
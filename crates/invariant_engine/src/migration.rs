@@ -0,0 +1,148 @@
+// crates/invariant_engine/src/migration.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use uuid::Uuid;
+use invariant_shared::{Identity, IdentityMigration, IdentityStatus, LogEventKind, MigrationRequest, Network, StreakState, TransitionReason, DomainEventKind};
+use invariant_shared::signing;
+use crate::core::InvariantEngine;
+use crate::ports::{IdentityStorage, MigrationHandover, NonceStorage, TransparencyStorage, LifecycleStorage, StreakStorage, SybilStorage, EligibilityStorage, DeviceStorage, MigrationStorage};
+use crate::error::EngineError;
+use crate::sybil::AttestationFingerprint;
+use crate::transparency::log_record;
+use crate::{attestation, crypto, devices, lifecycle};
+
+/// 🌐 TESTNET → MAINNET MIGRATION
+/// Every operation runs against identities of the node's own network (`load_identity`).
+/// The one crossing is this flow, served by mainnet nodes: the testnet identity hands
+/// over to a fresh, attested mainnet key, the mainnet identity is minted under mainnet
+/// policy with the continuity that policy lets it keep, and the testnet identity is
+/// revoked (`migrated`). Both records stay linked.
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + TransparencyStorage + LifecycleStorage + StreakStorage
+        + SybilStorage + EligibilityStorage + DeviceStorage + MigrationStorage,
+    N: NonceStorage,
+{
+    pub async fn process_migration(&self, request: MigrationRequest) -> Result<Identity, EngineError> {
+        if self.config.network != Network::Mainnet {
+            return Err(EngineError::InvalidMigration(format!("This node serves {}; migrations target mainnet", self.config.network)));
+        }
+
+        let testnet = self.storage.get_identity(&request.testnet_id).await?
+            .ok_or(EngineError::IdentityNotFound(request.testnet_id))?;
+
+        if testnet.network != Network::Testnet {
+            return Err(EngineError::InvalidMigration(format!("Only testnet identities migrate ({} is {})", testnet.id, testnet.network)));
+        }
+        if self.storage.get_migration(&testnet.id).await?.is_some() {
+            return Err(EngineError::InvalidMigration("Identity already migrated".into()));
+        }
+        lifecycle::check_transition(&testnet, TransitionReason::Revocation)?;
+
+        // 1. Nonce Finality (Anti-Replay)
+        if !self.nonce_storage.consume_nonce(&request.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+
+        // 2. Testnet Key Authorization (Cheap)
+        let payload = signing::migration_payload(&testnet.id, &request.new_public_key, &request.nonce);
+        crypto::verify_signature(&testnet.public_key, &payload, &request.signature)?;
+
+        // 3. Sybil Guard: the new key must not already anchor an identity
+        if self.storage.get_identity_by_public_key(&request.new_public_key).await?.is_some()
+            || self.storage.is_key_retired(&request.new_public_key).await?
+        {
+            return Err(EngineError::AlreadyExists);
+        }
+
+        // 4. New Key Hardware Attestation (Expensive)
+        let metadata = attestation::validate_attestation_chain(
+            &request.attestation_chain,
+            &request.new_public_key,
+            Some(&request.nonce)
+        )?;
+
+        // 5. Continuity under mainnet policy
+        let testnet_streak = self.storage.get_streak(&testnet.id).await?.unwrap_or_default();
+        let streak = if self.config.params.migration_carry_streak {
+            testnet_streak
+        } else {
+            StreakState { timezone: testnet_streak.timezone, ..StreakState::default() }
+        };
+        let carried_score = testnet.continuity_score
+            .saturating_mul(self.config.params.migration_score_carry_percent as u64) / 100;
+
+        // 6. Mint the mainnet identity (its key is the primary device) and link both records
+        let now = self.now();
+        let id = Uuid::new_v4();
+        let device = devices::attested_device(id, request.new_public_key.clone(), None, &metadata, now);
+        let fingerprint = AttestationFingerprint::of(&metadata);
+        let identity = Identity {
+            id,
+            public_key: request.new_public_key,
+            continuity_score: carried_score,
+            streak: streak.streak,
+            is_genesis_eligible: false,
+            username: None,
+            fcm_token: testnet.fcm_token.clone(),
+            created_at: now,
+            last_heartbeat: now,
+            last_attestation: now,
+            status: IdentityStatus::Active,
+
+            hardware_brand: metadata.brand,
+            hardware_device: metadata.device,
+            hardware_product: metadata.product,
+            security_level: metadata.security_level,
+            os_patch_level: metadata.os_patch_level,
+
+            genesis_version: self.config.genesis_version,
+            network: Network::Mainnet,
+        };
+        let migration = IdentityMigration {
+            testnet_id: testnet.id,
+            mainnet_id: identity.id,
+            carried_score,
+            carried_streak: streak.streak,
+            migrated_at: now,
+        };
+
+        // 7. Retire the testnet identity in the same transaction, then publish
+        let revocation = self.transition_for(&testnet, TransitionReason::Revocation, Some(testnet.id))?
+            .ok_or(EngineError::IdentityRevoked(testnet.id))?;
+        let migrated = self.storage.record_migration(&MigrationHandover {
+            identity: &identity,
+            device: &device,
+            streak: &streak,
+            migration: &migration,
+            fingerprint,
+            revocation,
+            log: [
+                log_record(LogEventKind::Migration, identity.id, &identity.public_key),
+                log_record(LogEventKind::Revocation, testnet.id, &testnet.public_key),
+            ],
+        }).await?;
+        if !migrated {
+            return Err(EngineError::InvalidMigration("Identity already migrated".into()));
+        }
+        self.evaluate_eligibility(&identity).await?;
+        self.emit(identity.id, DomainEventKind::IdentityMigrated {
+            testnet_id: testnet.id,
+            continuity_score: carried_score,
+        }).await;
+
+        Ok(identity)
+    }
+
+    /// The migration an identity took part in, as the testnet or the mainnet side.
+    pub async fn migration_for(&self, identity_id: Uuid) -> Result<Option<IdentityMigration>, EngineError> {
+        self.storage.get_identity(&identity_id).await?
+            .ok_or(EngineError::IdentityNotFound(identity_id))?;
+        self.storage.get_migration(&identity_id).await
+    }
+}
@@ -0,0 +1,97 @@
+// crates/invariant_engine/src/pairwise.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1) 
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use invariant_shared::{Identity, PairwiseLinkRequest, ProofTokenRequest};
+use invariant_shared::signing::{self, DOMAIN_TAG, PROTOCOL_VERSION};
+use crate::core::InvariantEngine;
+use crate::ports::{IdentityStorage, NonceStorage, PairwiseStorage};
+use crate::error::EngineError;
+use crate::crypto;
+use ring::hmac;
+use uuid::{Builder, Uuid};
+
+pub const MAX_PARTNER_ID_LEN: usize = 256;
+
+/// Keyed, one-way derivation of the identifier a partner knows an identity by.
+/// Without `secret`, two partners cannot tell whether their subjects are the same person.
+pub fn derive_pairwise_id(secret: &[u8], partner_id: &str, identity_id: &Uuid) -> Uuid {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+    let message = format!("{}|v{}|pairwise|{}|{}", DOMAIN_TAG, PROTOCOL_VERSION, partner_id, identity_id);
+    let tag = hmac::sign(&key, message.as_bytes());
+
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&tag.as_ref()[..16]);
+    // Shaped as a v4 UUID so it is indistinguishable from any other random identifier.
+    Builder::from_random_bytes(bytes).into_uuid()
+}
+
+/// 🕶️ PAIRWISE IDENTIFIERS
+/// Partners never see the global identity UUID. The first time an identity is
+/// presented to a partner, a pairwise ID is derived and recorded; from then on
+/// the stored link is authoritative, so rotating the secret never re-keys
+/// existing partner relationships.
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + PairwiseStorage,
+    N: NonceStorage,
+{
+    /// The holder's pairwise ID at `request.partner_id`, linked on first use.
+    /// Signed by the holder, so nobody else can learn (or mint) the identity's partner handles.
+    pub async fn pairwise_id_for(&self, secret: &[u8], identity_id: &Uuid, request: &PairwiseLinkRequest) -> Result<Uuid, EngineError> {
+        let partner_id = request.partner_id.as_str();
+        validate_partner_id(partner_id)?;
+
+        let identity = self.load_identity(identity_id).await?;
+
+        if !self.nonce_storage.consume_nonce(&request.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+        let payload = signing::pairwise_link_payload(identity_id, partner_id, &request.nonce);
+        crypto::verify_signature(&identity.public_key, &payload, &request.signature)?;
+
+        if let Some(existing) = self.storage.get_pairwise_id(partner_id, identity_id).await? {
+            return Ok(existing);
+        }
+
+        let pairwise_id = derive_pairwise_id(secret, partner_id, identity_id);
+        self.storage.link_pairwise(partner_id, &pairwise_id, identity_id).await?;
+        Ok(pairwise_id)
+    }
+
+    /// Partner-side lookup. IDs issued to another partner resolve to nothing.
+    pub async fn resolve_pairwise(&self, partner_id: &str, pairwise_id: &Uuid) -> Result<Identity, EngineError> {
+        validate_partner_id(partner_id)?;
+
+        let identity_id = self.storage.resolve_pairwise(partner_id, pairwise_id).await?
+            .ok_or(EngineError::IdentityNotFound(*pairwise_id))?;
+
+        self.storage.get_identity(&identity_id).await?
+            .ok_or(EngineError::IdentityNotFound(*pairwise_id))
+    }
+
+    /// Resolves the subject of a proof token request and checks that its holder signed for
+    /// this partner. Tokens are only minted on the holder's say-so.
+    pub async fn authorize_proof_token(&self, partner_id: &str, pairwise_id: &Uuid, request: &ProofTokenRequest) -> Result<Identity, EngineError> {
+        let identity = self.resolve_pairwise(partner_id, pairwise_id).await?;
+
+        if !self.nonce_storage.consume_nonce(&request.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+        let payload = signing::proof_token_payload(&identity.id, partner_id, &request.nonce);
+        crypto::verify_signature(&identity.public_key, &payload, &request.signature)?;
+
+        Ok(identity)
+    }
+}
+
+pub(crate) fn validate_partner_id(partner_id: &str) -> Result<(), EngineError> {
+    if partner_id.trim().is_empty() || partner_id.len() > MAX_PARTNER_ID_LEN {
+        return Err(EngineError::InvalidPartner(format!("Partner ID must be 1..={} bytes", MAX_PARTNER_ID_LEN)));
+    }
+    Ok(())
+}
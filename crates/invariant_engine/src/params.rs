@@ -0,0 +1,205 @@
+// crates/invariant_engine/src/params.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, Utc};
+use invariant_shared::Network;
+use serde::{Deserialize, Serialize};
+use crate::error::EngineError;
+
+/// ⏱️ PROTOCOL PARAMETERS
+/// Every timing rule of the protocol, in one place. The engine, the HTTP layer and
+/// the SQL queries all read from here, so a network's cadence is changed in the
+/// policy file and nowhere else.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProtocolParameters {
+    /// Minimum gap between two counted heartbeats.
+    pub heartbeat_interval_minutes: i64,
+    /// How long after local midnight a heartbeat may still count for a missed previous day.
+    pub streak_grace_minutes: i64,
+    /// A streak freeze is earned every this many consecutive days.
+    pub streak_freeze_interval_days: i64,
+    /// Cap on banked streak freezes.
+    pub max_streak_freezes: i64,
+    /// Lifetime of a server challenge (and of its consumed-nonce record).
+    pub nonce_ttl_seconds: u64,
+    /// Oldest acceptable TrustedTime timestamp on a signed heartbeat.
+    pub max_timestamp_drift_seconds: i64,
+    /// How far in the future a signed timestamp may be (device clock skew).
+    pub max_future_skew_seconds: i64,
+    /// Trust decay: hardware must be re-attested after this many days.
+    pub attestation_ttl_days: i64,
+    /// Active identities silent for this long are moved to `dormant`.
+    pub reaper_window_days: i64,
+    /// Silence after which a wake-up push is sent.
+    pub wake_up_after_minutes: i64,
+    /// Minimum gap between two Privacy Pass batches.
+    pub token_issuance_interval_minutes: i64,
+    /// Cancellation window between a recovery reaching its threshold and the key swap.
+    pub recovery_delay_hours: i64,
+    /// A recovery that has not reached its threshold this long after initiation expires.
+    pub recovery_expiry_hours: i64,
+    /// Look-back window for the trust score's velocity signal.
+    pub velocity_window_days: i64,
+    /// A Sybil signal value shared by more identities than this is treated as a population trait.
+    pub sybil_max_group_size: i64,
+    /// Grace period between a dead man's switch missing its threshold and firing.
+    pub switch_grace_hours: i64,
+    /// Genesis eligibility reports older than this are re-evaluated by the background job.
+    pub eligibility_refresh_hours: i64,
+    /// Active device keys an identity may hold at once, the primary included.
+    pub max_devices_per_identity: i64,
+    /// Share of a testnet continuity score kept when the identity migrates here (0 resets it).
+    pub migration_score_carry_percent: i64,
+    /// Whether a migrating identity keeps its testnet streak (and banked freezes).
+    pub migration_carry_streak: bool,
+    /// Identities minted under an older genesis version must re-attest (and are upgraded
+    /// to the node's version) before their heartbeats count.
+    pub min_genesis_version: u16,
+    /// Share of the continuity score lost per full week of dormancy, up to all of it.
+    pub dormancy_score_decay_percent_per_week: i64,
+    /// Whether the streak survives dormancy (the reaper leaves it and reactivation resumes it).
+    pub dormancy_keeps_streak: bool,
+    /// After a reactivation, no further heartbeat is counted for this long (0 = none).
+    pub reactivation_cooldown_hours: i64,
+}
+
+impl Default for ProtocolParameters {
+    /// Mainnet cadence.
+    fn default() -> Self {
+        Self {
+            heartbeat_interval_minutes: 1380, // 23 Hours
+            streak_grace_minutes: 180,
+            streak_freeze_interval_days: 7,
+            max_streak_freezes: 2,
+            nonce_ttl_seconds: 300,
+            max_timestamp_drift_seconds: 120,
+            max_future_skew_seconds: 30,
+            attestation_ttl_days: 7,
+            reaper_window_days: 30,
+            wake_up_after_minutes: 24 * 60,
+            token_issuance_interval_minutes: 1380,
+            recovery_delay_hours: 72,
+            recovery_expiry_hours: 7 * 24,
+            velocity_window_days: 30,
+            sybil_max_group_size: 50,
+            switch_grace_hours: 72,
+            eligibility_refresh_hours: 24,
+            max_devices_per_identity: 3,
+            migration_score_carry_percent: 0,
+            migration_carry_streak: false,
+            min_genesis_version: 1,
+            dormancy_score_decay_percent_per_week: 0,
+            dormancy_keeps_streak: false,
+            reactivation_cooldown_hours: 0,
+        }
+    }
+}
+
+impl ProtocolParameters {
+    /// Built-in parameters for a network. Testnet and Dev run an hourly cadence so
+    /// client releases can be exercised end to end in a single afternoon.
+    pub fn for_network(network: &Network) -> Self {
+        match network {
+            Network::Mainnet => Self::default(),
+            Network::Testnet | Network::Dev => Self {
+                heartbeat_interval_minutes: 60,
+                wake_up_after_minutes: 90,
+                token_issuance_interval_minutes: 60,
+                reaper_window_days: 7,
+                recovery_delay_hours: 1,
+                recovery_expiry_hours: 24,
+                switch_grace_hours: 1,
+                eligibility_refresh_hours: 1,
+                ..Self::default()
+            },
+        }
+    }
+
+    /// Parses a policy file and returns the parameters for `network`.
+    ///
+    /// The file is a JSON object keyed by network name. Each section only needs the
+    /// fields it overrides; a missing section means the built-in parameters:
+    ///
+    /// `{ "testnet": { "heartbeat_interval_minutes": 30 }, "mainnet": {} }`
+    pub fn from_policy(json: &str, network: &Network) -> Result<Self, EngineError> {
+        let mut sections: HashMap<String, serde_json::Value> = serde_json::from_str(json)
+            .map_err(|e| EngineError::InvalidPolicy(e.to_string()))?;
+
+        let params = match sections.remove(&network.to_string()) {
+            Some(serde_json::Value::Object(overrides)) => {
+                let mut merged = serde_json::to_value(Self::for_network(network))
+                    .map_err(|e| EngineError::InvalidPolicy(e.to_string()))?;
+                if let serde_json::Value::Object(base) = &mut merged {
+                    base.extend(overrides);
+                }
+                serde_json::from_value(merged)
+                    .map_err(|e| EngineError::InvalidPolicy(format!("{}: {}", network, e)))?
+            }
+            Some(_) => return Err(EngineError::InvalidPolicy(format!("{}: section must be an object", network))),
+            None => Self::for_network(network),
+        };
+
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Rejects parameter sets that would disable a security check.
+    pub fn validate(&self) -> Result<(), EngineError> {
+        let positive = [
+            ("heartbeat_interval_minutes", self.heartbeat_interval_minutes),
+            ("streak_freeze_interval_days", self.streak_freeze_interval_days),
+            ("nonce_ttl_seconds", self.nonce_ttl_seconds as i64),
+            ("max_timestamp_drift_seconds", self.max_timestamp_drift_seconds),
+            ("attestation_ttl_days", self.attestation_ttl_days),
+            ("reaper_window_days", self.reaper_window_days),
+            ("wake_up_after_minutes", self.wake_up_after_minutes),
+            ("token_issuance_interval_minutes", self.token_issuance_interval_minutes),
+            ("recovery_delay_hours", self.recovery_delay_hours),
+            ("recovery_expiry_hours", self.recovery_expiry_hours),
+            ("velocity_window_days", self.velocity_window_days),
+            ("sybil_max_group_size", self.sybil_max_group_size),
+            ("switch_grace_hours", self.switch_grace_hours),
+            ("eligibility_refresh_hours", self.eligibility_refresh_hours),
+            ("max_devices_per_identity", self.max_devices_per_identity),
+            ("min_genesis_version", self.min_genesis_version as i64),
+        ];
+        if let Some((name, _)) = positive.iter().find(|(_, v)| *v <= 0) {
+            return Err(EngineError::InvalidPolicy(format!("{} must be positive", name)));
+        }
+        if self.max_future_skew_seconds < 0 {
+            return Err(EngineError::InvalidPolicy("max_future_skew_seconds must not be negative".into()));
+        }
+        if !(0..24 * 60).contains(&self.streak_grace_minutes) {
+            return Err(EngineError::InvalidPolicy("streak_grace_minutes must be within 0..1440".into()));
+        }
+        if self.max_streak_freezes < 0 {
+            return Err(EngineError::InvalidPolicy("max_streak_freezes must not be negative".into()));
+        }
+        if !(0..=100).contains(&self.migration_score_carry_percent) {
+            return Err(EngineError::InvalidPolicy("migration_score_carry_percent must be within 0..=100".into()));
+        }
+        if !(0..=100).contains(&self.dormancy_score_decay_percent_per_week) {
+            return Err(EngineError::InvalidPolicy("dormancy_score_decay_percent_per_week must be within 0..=100".into()));
+        }
+        if self.reactivation_cooldown_hours < 0 {
+            return Err(EngineError::InvalidPolicy("reactivation_cooldown_hours must not be negative".into()));
+        }
+        Ok(())
+    }
+
+    pub fn heartbeat_interval(&self) -> Duration { Duration::minutes(self.heartbeat_interval_minutes) }
+
+    pub fn reactivation_cooldown(&self) -> Duration { Duration::hours(self.reactivation_cooldown_hours) }
+
+    /// Earliest time the next heartbeat will be counted.
+    pub fn next_heartbeat_at(&self, last_heartbeat: DateTime<Utc>) -> DateTime<Utc> {
+        last_heartbeat + self.heartbeat_interval()
+    }
+}
@@ -8,22 +8,54 @@
 
 use async_trait::async_trait;
 use uuid::Uuid;
-use invariant_shared::{Identity, Heartbeat};
+use chrono::{DateTime, Duration, Utc};
+use invariant_shared::{Identity, IdentityTransition, Heartbeat, Recovery, RecoveryEvent, LogEntry, LogEventKind, Revocation, RevocationReason, RevocationNotice, DeadMansSwitch, SwitchStatus, StreakState, SybilCluster, SybilConfidence, DomainEvent, EligibilityReport, Device, IdentityMigration, GenesisUpgrade, GenesisVersionStats, Reactivation};
 use crate::error::EngineError;
+use crate::trust::RecentActivity;
+use crate::sybil::{AttestationFingerprint, IdentityFingerprint};
 
 #[async_trait]
 pub trait IdentityStorage: Send + Sync {
     async fn get_identity(&self, id: &Uuid) -> Result<Option<Identity>, EngineError>;
+    /// Matches the primary key or any active device key of the identity.
     async fn get_identity_by_public_key(&self, public_key: &[u8]) -> Result<Option<Identity>, EngineError>;
     async fn save_identity(&self, identity: &Identity) -> Result<(), EngineError>;
-    async fn log_heartbeat(&self, identity: &Identity, heartbeat: &Heartbeat) -> Result<u64, EngineError>;
+    /// Mints a new identity with its primary device and attestation fingerprint, and
+    /// appends its genesis entry to the transparency log, in one transaction.
+    async fn record_genesis(&self, identity: &Identity, device: &Device, fingerprint: &AttestationFingerprint, log: &LogRecord) -> Result<(), EngineError>;
+    /// Counts the heartbeat and moves the identity's `last_heartbeat` to `at` (the engine clock).
+    async fn log_heartbeat(&self, identity: &Identity, heartbeat: &Heartbeat, at: DateTime<Utc>) -> Result<u64, EngineError>;
+
+    /// Swaps the identity onto the new key in one transaction, see `KeyRotation`.
+    /// Fails (and stores nothing) if the key or the status changed concurrently.
+    async fn rotate_public_key(&self, write: &KeyRotation<'_>) -> Result<(), EngineError>;
+    /// Whether the key has been archived in the key history. A retired key never anchors anything again.
+    async fn is_key_retired(&self, public_key: &[u8]) -> Result<bool, EngineError>;
     
-    async fn run_reaper(&self) -> Result<u64, EngineError>;
+    /// Bulk `Inactivity` transition (Active/Stale → Dormant) of identities silent for the
+    /// reaper window as of `now`. Writes one transition row per identity, stamped `now`.
+    async fn run_reaper(&self, now: DateTime<Utc>) -> Result<u64, EngineError>;
     async fn set_username(&self, id: &Uuid, username: &str) -> Result<bool, EngineError>;
     async fn get_leaderboard(&self, limit: i64) -> Result<Vec<Identity>, EngineError>;
 
     async fn update_fcm_token(&self, id: &Uuid, token: &str) -> Result<(), EngineError>;
-    async fn get_late_fcm_tokens(&self, minutes_since_heartbeat: i64) -> Result<Vec<String>, EngineError>;
+    async fn get_late_fcm_tokens(&self, minutes_since_heartbeat: i64, now: DateTime<Utc>) -> Result<Vec<String>, EngineError>;
+}
+
+/// Source of "now" for every engine decision (timestamps, windows, trust decay).
+/// Injected so tests and simulations can move time deterministically.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Receives domain events after the change they describe has been stored.
+/// Delivery is best-effort (at most once): events are published outside the storage
+/// transaction, and a failing sink never undoes a committed change. The engine counts
+/// refused events (`InvariantEngine::dropped_events`); implementations log the cause.
+/// Consumers that must not miss a change read the transitions or the transparency log.
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    async fn publish(&self, event: &DomainEvent) -> Result<(), EngineError>;
 }
 
 /// 🛡️ NEW: Interface for Atomic Nonce Management (Redis SETNX)
@@ -34,4 +66,279 @@ pub trait NonceStorage: Send + Sync {
     /// - Ok(true): Nonce was fresh and is now consumed.
     /// - Ok(false): Nonce was ALREADY used (Replay Attack).
     async fn consume_nonce(&self, nonce: &[u8], ttl_seconds: u64) -> Result<bool, EngineError>;
-}
\ No newline at end of file
+}
+
+/// Guardian sets and recovery attempts (Social Recovery).
+/// Every state change is mirrored into an append-only audit trail via `log_recovery_event`.
+#[async_trait]
+pub trait RecoveryStorage: Send + Sync {
+    /// Replaces the identity's guardian set.
+    async fn set_guardians(&self, identity_id: &Uuid, guardian_ids: &[Uuid], threshold: u8) -> Result<(), EngineError>;
+    async fn get_guardians(&self, identity_id: &Uuid) -> Result<Option<(Vec<Uuid>, u8)>, EngineError>;
+
+    /// Stores a new recovery. The identity's Pending recoveries already expired at
+    /// `recovery.initiated_at` are closed as `Expired` first, so they never hold the open slot.
+    async fn create_recovery(&self, recovery: &Recovery) -> Result<(), EngineError>;
+    async fn get_recovery(&self, recovery_id: &Uuid) -> Result<Option<Recovery>, EngineError>;
+    /// The open recovery for an identity, if any: Approved, or Pending and not expired at `now`.
+    async fn get_open_recovery(&self, identity_id: &Uuid, now: DateTime<Utc>) -> Result<Option<Recovery>, EngineError>;
+
+    /// Idempotently records a guardian's approval. Returns the total approval count.
+    /// A guardian votes once: after a rejection, their approval is ignored (and vice versa).
+    async fn add_recovery_approval(&self, recovery_id: &Uuid, guardian_id: &Uuid) -> Result<u32, EngineError>;
+    /// Idempotently records a guardian's refusal. Returns the total rejection count.
+    async fn add_recovery_rejection(&self, recovery_id: &Uuid, guardian_id: &Uuid) -> Result<u32, EngineError>;
+    async fn update_recovery_status(&self, recovery: &Recovery) -> Result<(), EngineError>;
+
+    /// Installs the recovery key in one transaction, see `RecoveryCompletion`. Fails (and
+    /// stores nothing) if the recovery is no longer approved or the key or status changed.
+    async fn complete_recovery(&self, write: &RecoveryCompletion<'_>) -> Result<(), EngineError>;
+
+    async fn log_recovery_event(
+        &self,
+        recovery_id: &Uuid,
+        identity_id: &Uuid,
+        event: &str,
+        actor_id: Option<&Uuid>,
+    ) -> Result<(), EngineError>;
+    async fn get_recovery_events(&self, recovery_id: &Uuid) -> Result<Vec<RecoveryEvent>, EngineError>;
+}
+
+/// Partner ↔ pairwise ID links. A pairwise ID is only meaningful together with its partner.
+#[async_trait]
+pub trait PairwiseStorage: Send + Sync {
+    async fn get_pairwise_id(&self, partner_id: &str, identity_id: &Uuid) -> Result<Option<Uuid>, EngineError>;
+    /// Idempotent: re-linking an existing (partner, identity) pair is a no-op.
+    async fn link_pairwise(&self, partner_id: &str, pairwise_id: &Uuid, identity_id: &Uuid) -> Result<(), EngineError>;
+    async fn resolve_pairwise(&self, partner_id: &str, pairwise_id: &Uuid) -> Result<Option<Uuid>, EngineError>;
+}
+
+/// Per-identity Privacy Pass issuance ledger.
+#[async_trait]
+pub trait PrivacyPassStorage: Send + Sync {
+    /// Atomically records a batch of `count` tokens at `now` if the last batch is older than
+    /// `min_interval`. Returns `false` (and records nothing) when the identity is still inside the window.
+    async fn claim_token_issuance(&self, identity_id: &Uuid, count: u32, min_interval: Duration, now: DateTime<Utc>) -> Result<bool, EngineError>;
+}
+
+/// A transparency log entry, appended by the storage call that stores the change it
+/// records (same transaction), so the log never misses or invents a change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRecord {
+    pub kind: LogEventKind,
+    pub identity_id: Uuid,
+    /// See `LogEntry::public_key_hash`.
+    pub public_key_hash: Vec<u8>,
+}
+
+/// Append-only transparency log. Entries are never updated or deleted.
+#[async_trait]
+pub trait TransparencyStorage: Send + Sync {
+    /// Appends at the next index (appends are serialized) and returns the stored entry.
+    async fn append_log_entry(&self, kind: LogEventKind, identity_id: &Uuid, public_key_hash: &[u8]) -> Result<LogEntry, EngineError>;
+    /// Entries with `start <= index < end`, ordered by index.
+    async fn get_log_entries(&self, start: u64, end: u64) -> Result<Vec<LogEntry>, EngineError>;
+    async fn get_log_size(&self) -> Result<u64, EngineError>;
+}
+
+/// Identity status changes. Apart from the bulk reaper, status is only written here or by
+/// a port call that bundles the transition (`record_revocation`, `record_attestation_refresh`),
+/// always together with its audit row.
+#[async_trait]
+pub trait LifecycleStorage: Send + Sync {
+    /// Atomically moves the identity from `transition.from` to `transition.to` and appends the row.
+    /// Returns `false` (and records nothing) if the stored status is no longer `transition.from`.
+    async fn record_transition(&self, transition: &IdentityTransition) -> Result<bool, EngineError>;
+    /// Oldest first.
+    async fn get_transitions(&self, identity_id: &Uuid) -> Result<Vec<IdentityTransition>, EngineError>;
+}
+
+/// Revocation records and the per-partner feed.
+#[async_trait]
+pub trait RevocationStorage: Send + Sync {
+    /// In one transaction: records `transition` (to Revoked; compare-and-set like
+    /// `record_transition`), appends the revocation with the next feed sequence and appends
+    /// `log`. The transition's actor and time are the revocation's. Fails (and stores
+    /// nothing) if the stored status is no longer `transition.from`.
+    async fn record_revocation(&self, transition: &IdentityTransition, reason: RevocationReason, log: &LogRecord) -> Result<Revocation, EngineError>;
+    /// Revocations of identities linked to `partner_id`, mapped to their pairwise IDs,
+    /// with `sequence > after_sequence`, ordered by sequence. Sequences follow commit order:
+    /// a revocation never appears below one a partner has already read.
+    async fn get_partner_revocations(&self, partner_id: &str, after_sequence: u64, limit: u32) -> Result<Vec<RevocationNotice>, EngineError>;
+}
+
+/// Dead man's switch configuration and state (columns of `identities`).
+#[async_trait]
+pub trait SwitchStorage: Send + Sync {
+    async fn get_switch(&self, identity_id: &Uuid) -> Result<Option<DeadMansSwitch>, EngineError>;
+    /// Overwrites status, threshold and beneficiary (arm / disarm).
+    async fn save_switch(&self, switch: &DeadMansSwitch) -> Result<(), EngineError>;
+    /// Armed switches whose holder has been silent past the threshold at `now`,
+    /// plus every pending switch (which may need cancelling or triggering).
+    async fn get_due_switches(&self, now: DateTime<Utc>) -> Result<Vec<DeadMansSwitch>, EngineError>;
+    /// Stores `switch` only if the stored status is still `from`. Returns `false` otherwise.
+    async fn transition_switch(&self, switch: &DeadMansSwitch, from: SwitchStatus) -> Result<bool, EngineError>;
+}
+
+/// Streak counter, last credited day, banked freezes and time zone.
+#[async_trait]
+pub trait StreakStorage: Send + Sync {
+    async fn get_streak(&self, identity_id: &Uuid) -> Result<Option<StreakState>, EngineError>;
+    async fn save_streak(&self, identity_id: &Uuid, state: &StreakState) -> Result<(), EngineError>;
+}
+
+/// Inputs of the trust score that live outside the identity row.
+#[async_trait]
+pub trait TrustStorage: Send + Sync {
+    /// Key rotations, completed recoveries and reactivations since `since`.
+    async fn get_recent_activity(&self, identity_id: &Uuid, since: DateTime<Utc>) -> Result<RecentActivity, EngineError>;
+}
+
+/// Inputs and results of the Sybil cluster scan.
+#[async_trait]
+pub trait SybilStorage: Send + Sync {
+    /// Stores the attestation-derived signals of the identity's latest attestation.
+    async fn record_attestation_fingerprint(&self, identity_id: &Uuid, fingerprint: &AttestationFingerprint) -> Result<(), EngineError>;
+    /// Clustering signals of every identity that is not revoked.
+    async fn get_fingerprints(&self) -> Result<Vec<IdentityFingerprint>, EngineError>;
+    /// Replaces the previous scan's clusters atomically.
+    async fn replace_sybil_clusters(&self, clusters: &[SybilCluster]) -> Result<(), EngineError>;
+    /// Clusters at or above `min_confidence`, strongest first.
+    async fn get_sybil_clusters(&self, min_confidence: SybilConfidence, limit: u32) -> Result<Vec<SybilCluster>, EngineError>;
+    async fn get_sybil_clusters_for(&self, identity_id: &Uuid) -> Result<Vec<SybilCluster>, EngineError>;
+}
+
+/// Everything a fresh attestation stores, whether it came on its own or piggy-backed on a
+/// heartbeat. Written in one transaction, so a failure leaves nothing behind for a retry
+/// to trip over.
+pub struct AttestationRefresh<'a> {
+    /// Carries the new trust timer and (for the primary key) security and patch level.
+    pub identity: &'a Identity,
+    /// The signing device with its refreshed attestation, if it has a device row.
+    pub device: Option<&'a Device>,
+    /// The heartbeat that carried the attestation, counted like `log_heartbeat`. `None`
+    /// when the reactivation it causes starts a cooling-off period.
+    pub heartbeat: Option<&'a Heartbeat>,
+    /// Engine time of the refresh (the new `last_heartbeat` when a heartbeat is counted).
+    pub at: DateTime<Utc>,
+    /// Dormancy rules applied to a dormant identity (written like `record_reactivation`).
+    pub reactivation: Option<(Reactivation, StreakState)>,
+    /// Stale/Dormant → Active, compare-and-set like `record_transition`.
+    pub transition: Option<IdentityTransition>,
+    /// Attestation fingerprint of the primary key.
+    pub fingerprint: Option<AttestationFingerprint>,
+    pub log: LogRecord,
+}
+
+/// Everything a key rotation stores, in one transaction.
+pub struct KeyRotation<'a> {
+    /// Carries the new key, its hardware metadata, levels and trust timer.
+    pub identity: &'a Identity,
+    /// Archived in the key history; the swap is guarded on it.
+    pub previous_public_key: &'a [u8],
+    /// Attestation fingerprint of the new key.
+    pub fingerprint: AttestationFingerprint,
+    /// Dormancy rules applied to a dormant identity (written like `AttestationRefresh`).
+    pub reactivation: Option<(Reactivation, StreakState)>,
+    /// Stale/Dormant → Active, compare-and-set like `record_transition`.
+    pub transition: Option<IdentityTransition>,
+    pub log: LogRecord,
+}
+
+/// Everything a finalized recovery stores, in one transaction: the recovery marked
+/// `Completed` with its "completed" event, the recovery key installed on the identity
+/// and its primary device, and the old key archived.
+pub struct RecoveryCompletion<'a> {
+    pub recovery: &'a Recovery,
+    /// Archived in the key history; the swap is guarded on it.
+    pub previous_public_key: &'a [u8],
+    /// Engine time of the swap, the new key's trust timer (`last_attestation`).
+    pub at: DateTime<Utc>,
+    /// Dormancy rules applied to a dormant identity (written like `AttestationRefresh`).
+    pub reactivation: Option<(Reactivation, StreakState)>,
+    /// Stale/Dormant → Active, compare-and-set like `record_transition`.
+    pub transition: Option<IdentityTransition>,
+    pub log: LogRecord,
+}
+
+/// Everything a testnet → mainnet migration stores, in one transaction: the mainnet
+/// identity with its primary device, streak and fingerprint, the link between both
+/// records, and the testnet identity revoked (`migrated`).
+pub struct MigrationHandover<'a> {
+    pub identity: &'a Identity,
+    pub device: &'a Device,
+    pub streak: &'a StreakState,
+    pub migration: &'a IdentityMigration,
+    /// Attestation fingerprint of the mainnet key.
+    pub fingerprint: AttestationFingerprint,
+    /// The testnet identity → Revoked, compare-and-set like `record_revocation`.
+    pub revocation: IdentityTransition,
+    /// The mainnet `Migration` entry, then the testnet `Revocation` entry.
+    pub log: [LogRecord; 2],
+}
+
+/// Device keys of an identity. The primary device's row follows `Identity.public_key`
+/// through key rotation and recovery.
+#[async_trait]
+pub trait DeviceStorage: Send + Sync {
+    /// Every device, revoked ones included, oldest first.
+    async fn get_devices(&self, identity_id: &Uuid) -> Result<Vec<Device>, EngineError>;
+    /// Stores `device` and appends `log`, unless the identity already holds `max_active`
+    /// active devices. Returns `false` (and stores nothing) at the limit.
+    async fn add_device(&self, device: &Device, max_active: u32, log: &LogRecord) -> Result<bool, EngineError>;
+    /// Stores a fresh attestation of the device (trust timer, security and patch level).
+    async fn update_device_attestation(&self, device: &Device) -> Result<(), EngineError>;
+    async fn record_device_heartbeat(&self, device_id: &Uuid, at: DateTime<Utc>) -> Result<(), EngineError>;
+    /// Stores a fresh attestation in one transaction: the identity's trust timer and levels,
+    /// the attesting device, the heartbeat that carried it (if any, counted like
+    /// `log_heartbeat`), the reactivation, the status transition, the fingerprint and the
+    /// log entry. Returns the stored continuity score.
+    /// Fails (and stores nothing) if the transition's `from` is no longer the stored status.
+    async fn record_attestation_refresh(&self, write: &AttestationRefresh<'_>) -> Result<u64, EngineError>;
+    /// Marks `device` revoked and appends `log`. With `promote`, that device becomes the
+    /// primary in the same transaction: its key and hardware metadata move onto the
+    /// identity and the old primary key is archived in the key history.
+    async fn revoke_device(&self, device: &Device, promote: Option<&Device>, log: &[LogRecord]) -> Result<(), EngineError>;
+}
+
+/// Testnet-to-mainnet migrations, at most one per testnet identity.
+#[async_trait]
+pub trait MigrationStorage: Send + Sync {
+    /// Stores a migration in one transaction, see `MigrationHandover`. Returns `false` (and
+    /// stores nothing) if the testnet identity has already migrated.
+    async fn record_migration(&self, write: &MigrationHandover<'_>) -> Result<bool, EngineError>;
+    /// The migration `identity_id` took part in, on either side.
+    async fn get_migration(&self, identity_id: &Uuid) -> Result<Option<IdentityMigration>, EngineError>;
+}
+
+/// Genesis version upgrades of re-attested identities.
+#[async_trait]
+pub trait UpgradeStorage: Send + Sync {
+    /// Atomically moves the identity onto `upgrade.to_version` and appends the record.
+    /// Returns `false` (and records nothing) if the stored version is no longer `upgrade.from_version`.
+    async fn record_genesis_upgrade(&self, upgrade: &GenesisUpgrade) -> Result<bool, EngineError>;
+    /// Non-revoked identities per genesis version, lowest version first.
+    async fn get_genesis_versions(&self) -> Result<Vec<GenesisVersionStats>, EngineError>;
+}
+
+/// Reactivations of dormant identities.
+#[async_trait]
+pub trait ReactivationStorage: Send + Sync {
+    /// Stores the identity's post-dormancy score (`reactivation.score_after`) and `streak`,
+    /// and appends the record, in one transaction.
+    async fn record_reactivation(&self, reactivation: &Reactivation, streak: &StreakState) -> Result<(), EngineError>;
+    /// Most recent first.
+    async fn get_reactivations(&self, identity_id: &Uuid) -> Result<Vec<Reactivation>, EngineError>;
+}
+
+/// Last genesis eligibility report per identity.
+#[async_trait]
+pub trait EligibilityStorage: Send + Sync {
+    /// Stores the report and mirrors `report.eligible` onto `Identity.is_genesis_eligible`.
+    async fn save_eligibility(&self, identity_id: &Uuid, report: &EligibilityReport) -> Result<(), EngineError>;
+    async fn get_eligibility(&self, identity_id: &Uuid) -> Result<Option<EligibilityReport>, EngineError>;
+    /// Identities without a report, with one older than `before`, or still eligible
+    /// although no longer active. Oldest reports first. A revoked identity stops being
+    /// due once its report says ineligible.
+    async fn get_eligibility_due(&self, before: DateTime<Utc>, limit: u32) -> Result<Vec<Uuid>, EngineError>;
+}
@@ -0,0 +1,134 @@
+// crates/invariant_engine/src/privacy_pass.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1) 
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use invariant_shared::{IdentityStatus, TokenIssuanceRequest};
+use invariant_shared::privacy_pass::{self, TOKEN_TYPE_BLIND_RSA, TOKEN_REQUEST_LEN, BLIND_RSA_NK};
+use invariant_shared::signing;
+use blind_rsa_signatures::{KeyPair, Options, SecretKey};
+use blind_rsa_signatures::reexports::rsa::PublicKeyParts;
+use crate::core::InvariantEngine;
+use crate::ports::{IdentityStorage, NonceStorage, PrivacyPassStorage};
+use crate::error::EngineError;
+use crate::crypto;
+use chrono::Duration;
+
+pub const MAX_TOKENS_PER_BATCH: usize = 10;
+
+/// The node's Blind RSA issuing key (token type `0x0002`).
+pub struct PrivacyPassIssuer {
+    secret_key: SecretKey,
+    spki: Vec<u8>,
+    token_key_id: [u8; 32],
+}
+
+impl PrivacyPassIssuer {
+    /// Loads a PKCS#8 / PKCS#1 PEM RSA key. Only 2048-bit keys are valid for token type `0x0002`.
+    pub fn from_pem(pem: &str) -> Result<Self, EngineError> {
+        let secret_key = SecretKey::from_pem(pem)
+            .map_err(|e| EngineError::Storage(format!("Invalid Privacy Pass key: {}", e)))?;
+        Self::from_secret_key(secret_key)
+    }
+
+    pub fn generate() -> Result<Self, EngineError> {
+        let key_pair = KeyPair::generate(&mut rand::thread_rng(), BLIND_RSA_NK * 8)
+            .map_err(|e| EngineError::Storage(format!("Privacy Pass keygen failed: {}", e)))?;
+        Self::from_secret_key(key_pair.sk)
+    }
+
+    fn from_secret_key(secret_key: SecretKey) -> Result<Self, EngineError> {
+        let public_key = secret_key.public_key()
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+        if public_key.0.size() != BLIND_RSA_NK {
+            return Err(EngineError::Storage("Privacy Pass key must be RSA-2048".into()));
+        }
+        let spki = public_key.to_spki(Some(&Options::default()))
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+        let token_key_id = privacy_pass::token_key_id(&spki);
+        Ok(Self { secret_key, spki, token_key_id })
+    }
+
+    /// The RSASSA-PSS SPKI published in the issuer directory (`token-key`).
+    pub fn public_key_spki(&self) -> &[u8] { &self.spki }
+
+    pub fn token_key_id(&self) -> &[u8; 32] { &self.token_key_id }
+
+    fn blind_sign(&self, token_request: &[u8]) -> Result<Vec<u8>, EngineError> {
+        if token_request.len() != TOKEN_REQUEST_LEN {
+            return Err(EngineError::InvalidTokenRequest("Wrong token request length".into()));
+        }
+        if u16::from_be_bytes([token_request[0], token_request[1]]) != TOKEN_TYPE_BLIND_RSA {
+            return Err(EngineError::InvalidTokenRequest("Unsupported token type".into()));
+        }
+        if token_request[2] != self.token_key_id[31] {
+            return Err(EngineError::InvalidTokenRequest("Unknown issuer key".into()));
+        }
+
+        self.secret_key
+            .blind_sign(&mut rand::thread_rng(), &token_request[3..], &Options::default())
+            .map(|sig| sig.0)
+            .map_err(|_| EngineError::InvalidTokenRequest("Blinded message rejected".into()))
+    }
+}
+
+/// 🎟️ ANONYMOUS TOKENS (Privacy Pass)
+/// An active identity trades one hardware-signed request per issuance window for a batch of
+/// blind signatures. The node never sees the finalized tokens, so a partner
+/// redeeming one learns "hardware-verified human" and nothing else.
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + PrivacyPassStorage,
+    N: NonceStorage,
+{
+    pub async fn issue_privacy_pass_tokens(
+        &self,
+        issuer: &PrivacyPassIssuer,
+        request: TokenIssuanceRequest,
+    ) -> Result<Vec<Vec<u8>>, EngineError> {
+        let identity = self.load_identity(&request.identity_id).await?;
+
+        // 1. Only live, hardware-fresh identities vouch for a human
+        if identity.status != IdentityStatus::Active {
+            return Err(EngineError::InvalidTokenRequest("Identity is not active".into()));
+        }
+        // Decay is only written to the status on the next heartbeat; an expired proof counts already.
+        let attestation_ttl = Duration::days(self.config.params.attestation_ttl_days);
+        if self.now().signed_duration_since(identity.last_attestation) > attestation_ttl {
+            return Err(EngineError::AttestationRequired);
+        }
+
+        // 2. Batch shape
+        let count = request.token_requests.len();
+        if count == 0 || count > MAX_TOKENS_PER_BATCH {
+            return Err(EngineError::InvalidTokenRequest(format!("Batch size must be 1..={}", MAX_TOKENS_PER_BATCH)));
+        }
+
+        // 3. Nonce Finality + Hardware Authorization of the exact batch
+        if !self.nonce_storage.consume_nonce(&request.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+        let payload = signing::token_issuance_payload(
+            &request.identity_id,
+            &request.nonce,
+            &privacy_pass::requests_digest(&request.token_requests),
+        );
+        crypto::verify_signature(&identity.public_key, &payload, &request.signature)?;
+
+        // 4. Sign everything before claiming the window, so a malformed batch doesn't burn the day
+        let responses = request.token_requests.iter()
+            .map(|token_request| issuer.blind_sign(token_request))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // 5. Rate Limit (atomic claim of the issuance window)
+        let min_interval = Duration::minutes(self.config.params.token_issuance_interval_minutes);
+        if !self.storage.claim_token_issuance(&identity.id, count as u32, min_interval, self.now()).await? {
+            return Err(EngineError::RateLimitExceeded);
+        }
+
+        Ok(responses)
+    }
+}
@@ -0,0 +1,109 @@
+// crates/invariant_engine/src/reactivation.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use chrono::{DateTime, Duration, Utc};
+use invariant_shared::{DomainEventKind, Identity, IdentityStatus, Reactivation, StreakState, TransitionReason};
+use uuid::Uuid;
+use crate::core::InvariantEngine;
+use crate::ports::{IdentityStorage, LifecycleStorage, NonceStorage, ReactivationStorage, StreakStorage};
+use crate::error::EngineError;
+use crate::params::ProtocolParameters;
+use crate::streak;
+
+/// Continuity score left after `dormant_for` under the policy's weekly decay.
+pub fn decayed_score(score: u64, dormant_for: Duration, params: &ProtocolParameters) -> u64 {
+    let weeks = dormant_for.num_weeks().max(0);
+    let percent = weeks.saturating_mul(params.dormancy_score_decay_percent_per_week).min(100) as u64;
+    score - score.saturating_mul(percent) / 100
+}
+
+/// Streak after dormancy. Kept streaks resume: the next heartbeat (from `at`'s local day
+/// on) continues them as if the dormant days had not been missed.
+pub fn streak_after_dormancy(state: &StreakState, at: DateTime<Utc>, params: &ProtocolParameters) -> StreakState {
+    if params.dormancy_keeps_streak {
+        let yesterday = streak::local_day(at, &state.timezone) - Duration::days(1);
+        StreakState { last_day: state.last_day.map(|last| last.max(yesterday)), ..state.clone() }
+    } else {
+        StreakState { streak: 0, ..state.clone() }
+    }
+}
+
+/// 🌅 DORMANCY & REACTIVATION
+/// The reaper marks identities dormant after `reaper_window_days` of silence. A heartbeat
+/// does not wake them: only a fresh attestation does (re-attestation, also riding on a
+/// heartbeat, key rotation or recovery). Reactivation applies the policy's dormancy rules
+/// to score and streak, records how long the identity slept and may start a cooling-off
+/// period during which heartbeats are not counted.
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + LifecycleStorage + StreakStorage + ReactivationStorage,
+    N: NonceStorage,
+{
+    /// The dormancy rules `reason` applies to a dormant identity it is about to move back to
+    /// active, for the storage call that writes them together with the attestation and the
+    /// transition. Call after the attestation is verified. `None` unless the identity is dormant.
+    pub(crate) async fn plan_reactivation(&self, identity: &Identity, reason: TransitionReason) -> Result<Option<(Reactivation, StreakState)>, EngineError> {
+        if identity.status != IdentityStatus::Dormant {
+            return Ok(None);
+        }
+
+        let now = self.now();
+        let params = &self.config.params;
+        // Identities written dormant without a transition row count from their last heartbeat.
+        let dormant_since = self.storage.get_transitions(&identity.id).await?.into_iter()
+            .rev()
+            .find(|t| t.to == IdentityStatus::Dormant)
+            .map_or(identity.last_heartbeat, |t| t.created_at);
+        let dormant_for = now.signed_duration_since(dormant_since);
+
+        let streak_before = self.storage.get_streak(&identity.id).await?.unwrap_or_default();
+        let streak = streak_after_dormancy(&streak_before, now, params);
+        let reactivation = Reactivation {
+            identity_id: identity.id,
+            reason,
+            silent_since: identity.last_heartbeat,
+            dormant_since,
+            reactivated_at: now,
+            score_before: identity.continuity_score,
+            score_after: decayed_score(identity.continuity_score, dormant_for, params),
+            streak_before: streak_before.streak,
+            streak_after: streak.streak,
+            cooling_off_until: (params.reactivation_cooldown_hours > 0).then(|| now + params.reactivation_cooldown()),
+        };
+        Ok(Some((reactivation, streak)))
+    }
+
+    /// Moves a stored reactivation onto `identity` and announces it.
+    pub(crate) async fn reactivated(&self, identity: &mut Identity, reactivation: &Reactivation) {
+        identity.continuity_score = reactivation.score_after;
+        identity.streak = reactivation.streak_after;
+        self.emit(identity.id, DomainEventKind::IdentityReactivated {
+            dormant_days: reactivation.reactivated_at.signed_duration_since(reactivation.dormant_since).num_days(),
+            continuity_score: reactivation.score_after,
+        }).await;
+    }
+
+    /// Refuses heartbeats inside the cooling-off period of the latest reactivation.
+    pub(crate) async fn check_cooling_off(&self, identity: &Identity) -> Result<(), EngineError> {
+        if self.config.params.reactivation_cooldown_hours == 0 {
+            return Ok(());
+        }
+        let latest = self.storage.get_reactivations(&identity.id).await?.into_iter().next();
+        match latest.and_then(|r| r.cooling_off_until) {
+            Some(until) if self.now() < until => Err(EngineError::CoolingOff(until)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reactivations of an identity, most recent first.
+    pub async fn reactivation_history(&self, identity_id: Uuid) -> Result<Vec<Reactivation>, EngineError> {
+        self.storage.get_identity(&identity_id).await?
+            .ok_or(EngineError::IdentityNotFound(identity_id))?;
+        self.storage.get_reactivations(&identity_id).await
+    }
+}
@@ -0,0 +1,309 @@
+// crates/invariant_engine/src/recovery.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1) 
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use invariant_shared::{
+    GuardianSetRequest, RecoveryInitRequest, RecoveryApproval, RecoveryRejection, RecoveryCancelRequest,
+    Recovery, RecoveryStatus, IdentityStatus, LogEventKind, TransitionReason,
+};
+use invariant_shared::signing;
+use crate::core::InvariantEngine;
+use crate::ports::{IdentityStorage, NonceStorage, RecoveryCompletion, RecoveryStorage, TransparencyStorage, LifecycleStorage, StreakStorage, ReactivationStorage};
+use crate::error::EngineError;
+use crate::crypto;
+use crate::transparency::log_record;
+use crate::attestation;
+use chrono::Duration;
+use uuid::Uuid;
+
+const MAX_GUARDIANS: usize = 7;
+
+/// 🛟 SOCIAL RECOVERY
+/// An identity designates N guardian identities. Once M of them approve with their
+/// own hardware keys, the recovery key is installed after `recovery_delay_hours`,
+/// unless the original holder cancels in the meantime. An attempt that does not reach
+/// the threshold within `recovery_expiry_hours`, or that enough guardians reject, closes.
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + RecoveryStorage + TransparencyStorage + LifecycleStorage + StreakStorage + ReactivationStorage,
+    N: NonceStorage,
+{
+    pub async fn set_guardians(&self, request: GuardianSetRequest) -> Result<(), EngineError> {
+        let identity = self.load_identity(&request.id).await?;
+
+        if identity.status == IdentityStatus::Revoked {
+            return Err(EngineError::IdentityRevoked(identity.id));
+        }
+
+        // 1. Shape of the set
+        let n = request.guardian_ids.len();
+        if n == 0 || n > MAX_GUARDIANS {
+            return Err(EngineError::RecoveryRejected(format!("Guardian count must be 1..={}", MAX_GUARDIANS)));
+        }
+        if request.threshold == 0 || request.threshold as usize > n {
+            return Err(EngineError::RecoveryRejected("Threshold must be between 1 and the guardian count".into()));
+        }
+        let mut unique = request.guardian_ids.clone();
+        unique.sort();
+        unique.dedup();
+        if unique.len() != n || request.guardian_ids.contains(&request.id) {
+            return Err(EngineError::RecoveryRejected("Guardians must be distinct and exclude the identity itself".into()));
+        }
+
+        // 2. Nonce Finality + Owner Authorization
+        if !self.nonce_storage.consume_nonce(&request.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+        let payload = signing::guardian_set_payload(&request.id, &request.guardian_ids, request.threshold, &request.nonce);
+        crypto::verify_signature(&identity.public_key, &payload, &request.signature)?;
+
+        // 3. Guardians must be live, hardware-verified identities
+        for guardian_id in &request.guardian_ids {
+            let guardian = self.load_identity(guardian_id).await?;
+            if guardian.status == IdentityStatus::Revoked {
+                return Err(EngineError::RecoveryRejected(format!("Guardian {} is revoked", guardian_id)));
+            }
+        }
+
+        // A new guardian set must not retro-actively change an in-flight recovery.
+        if self.storage.get_open_recovery(&request.id, self.now()).await?.is_some() {
+            return Err(EngineError::RecoveryRejected("A recovery is in progress".into()));
+        }
+
+        self.storage.set_guardians(&request.id, &request.guardian_ids, request.threshold).await
+    }
+
+    pub async fn initiate_recovery(&self, request: RecoveryInitRequest) -> Result<Recovery, EngineError> {
+        let identity = self.load_identity(&request.id).await?;
+
+        if identity.status == IdentityStatus::Revoked {
+            return Err(EngineError::IdentityRevoked(identity.id));
+        }
+
+        let (_, threshold) = self.storage.get_guardians(&request.id).await?
+            .ok_or_else(|| EngineError::RecoveryRejected("No guardians designated".into()))?;
+
+        if self.storage.get_open_recovery(&request.id, self.now()).await?.is_some() {
+            return Err(EngineError::RecoveryRejected("A recovery is already in progress".into()));
+        }
+
+        if !self.nonce_storage.consume_nonce(&request.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+
+        if self.storage.get_identity_by_public_key(&request.new_public_key).await?.is_some()
+            || self.storage.is_key_retired(&request.new_public_key).await?
+        {
+            return Err(EngineError::AlreadyExists);
+        }
+
+        // The recovery key must be hardware-backed just like a Genesis key.
+        let metadata = attestation::validate_attestation_chain(
+            &request.attestation_chain,
+            &request.new_public_key,
+            Some(&request.nonce)
+        )?;
+
+        let initiated_at = self.now();
+        let recovery = Recovery {
+            id: Uuid::new_v4(),
+            identity_id: request.id,
+            new_public_key: request.new_public_key,
+            hardware_brand: metadata.brand,
+            hardware_device: metadata.device,
+            hardware_product: metadata.product,
+            security_level: metadata.security_level,
+            os_patch_level: metadata.os_patch_level,
+            status: RecoveryStatus::Pending,
+            approvals: 0,
+            rejections: 0,
+            threshold,
+            initiated_at,
+            expires_at: initiated_at + Duration::hours(self.config.params.recovery_expiry_hours),
+            executable_at: None,
+        };
+
+        self.storage.create_recovery(&recovery).await?;
+        self.storage.log_recovery_event(&recovery.id, &recovery.identity_id, "initiated", None).await?;
+
+        Ok(recovery)
+    }
+
+    pub async fn approve_recovery(&self, approval: RecoveryApproval) -> Result<Recovery, EngineError> {
+        let mut recovery = self.storage.get_recovery(&approval.recovery_id).await?
+            .ok_or(EngineError::RecoveryNotFound(approval.recovery_id))?;
+
+        if recovery.status != RecoveryStatus::Pending && recovery.status != RecoveryStatus::Approved {
+            return Err(EngineError::RecoveryRejected(format!("Recovery is {:?}", recovery.status)));
+        }
+        self.check_not_expired(&recovery)?;
+
+        let (guardian_ids, _) = self.storage.get_guardians(&recovery.identity_id).await?
+            .ok_or_else(|| EngineError::RecoveryRejected("No guardians designated".into()))?;
+        if !guardian_ids.contains(&approval.guardian_id) {
+            return Err(EngineError::RecoveryRejected("Not a guardian of this identity".into()));
+        }
+
+        let guardian = self.load_identity(&approval.guardian_id).await?;
+        if guardian.status == IdentityStatus::Revoked {
+            return Err(EngineError::RecoveryRejected("Guardian is revoked".into()));
+        }
+
+        if !self.nonce_storage.consume_nonce(&approval.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+
+        let payload = signing::recovery_approval_payload(
+            &recovery.id,
+            &recovery.identity_id,
+            &recovery.new_public_key,
+            &approval.nonce
+        );
+        crypto::verify_signature(&guardian.public_key, &payload, &approval.signature)?;
+
+        recovery.approvals = self.storage.add_recovery_approval(&recovery.id, &approval.guardian_id).await?;
+        self.storage.log_recovery_event(&recovery.id, &recovery.identity_id, "approved", Some(&approval.guardian_id)).await?;
+
+        // Threshold reached: start the cancellation window.
+        if recovery.status == RecoveryStatus::Pending && recovery.approvals >= recovery.threshold as u32 {
+            recovery.status = RecoveryStatus::Approved;
+            recovery.executable_at = Some(self.now() + Duration::hours(self.config.params.recovery_delay_hours));
+            self.storage.update_recovery_status(&recovery).await?;
+            self.storage.log_recovery_event(&recovery.id, &recovery.identity_id, "threshold_met", None).await?;
+        }
+
+        Ok(recovery)
+    }
+
+    /// A guardian refuses a pending recovery. Once so many guardians refuse that the
+    /// threshold can no longer be met, the recovery closes as `Rejected`.
+    pub async fn reject_recovery(&self, rejection: RecoveryRejection) -> Result<Recovery, EngineError> {
+        let mut recovery = self.storage.get_recovery(&rejection.recovery_id).await?
+            .ok_or(EngineError::RecoveryNotFound(rejection.recovery_id))?;
+
+        // Past the threshold, only the holder's cancellation can stop it.
+        if recovery.status != RecoveryStatus::Pending {
+            return Err(EngineError::RecoveryRejected(format!("Recovery is {:?}", recovery.status)));
+        }
+        self.check_not_expired(&recovery)?;
+
+        let (guardian_ids, _) = self.storage.get_guardians(&recovery.identity_id).await?
+            .ok_or_else(|| EngineError::RecoveryRejected("No guardians designated".into()))?;
+        if !guardian_ids.contains(&rejection.guardian_id) {
+            return Err(EngineError::RecoveryRejected("Not a guardian of this identity".into()));
+        }
+
+        let guardian = self.load_identity(&rejection.guardian_id).await?;
+        if guardian.status == IdentityStatus::Revoked {
+            return Err(EngineError::RecoveryRejected("Guardian is revoked".into()));
+        }
+
+        if !self.nonce_storage.consume_nonce(&rejection.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+
+        let payload = signing::recovery_rejection_payload(
+            &recovery.id,
+            &recovery.identity_id,
+            &recovery.new_public_key,
+            &rejection.nonce
+        );
+        crypto::verify_signature(&guardian.public_key, &payload, &rejection.signature)?;
+
+        recovery.rejections = self.storage.add_recovery_rejection(&recovery.id, &rejection.guardian_id).await?;
+        self.storage.log_recovery_event(&recovery.id, &recovery.identity_id, "rejected", Some(&rejection.guardian_id)).await?;
+
+        if recovery.rejections as usize > guardian_ids.len().saturating_sub(recovery.threshold as usize) {
+            recovery.status = RecoveryStatus::Rejected;
+            self.storage.update_recovery_status(&recovery).await?;
+        }
+
+        Ok(recovery)
+    }
+
+    fn check_not_expired(&self, recovery: &Recovery) -> Result<(), EngineError> {
+        if recovery.status == RecoveryStatus::Pending && self.now() >= recovery.expires_at {
+            return Err(EngineError::RecoveryRejected("Recovery has expired".into()));
+        }
+        Ok(())
+    }
+
+    pub async fn cancel_recovery(&self, request: RecoveryCancelRequest) -> Result<Recovery, EngineError> {
+        let mut recovery = self.storage.get_recovery(&request.recovery_id).await?
+            .ok_or(EngineError::RecoveryNotFound(request.recovery_id))?;
+
+        if recovery.status != RecoveryStatus::Pending && recovery.status != RecoveryStatus::Approved {
+            return Err(EngineError::RecoveryRejected(format!("Recovery is {:?}", recovery.status)));
+        }
+
+        let identity = self.load_identity(&recovery.identity_id).await?;
+
+        if !self.nonce_storage.consume_nonce(&request.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+
+        // Only the key being replaced can veto (proves the device is NOT lost).
+        let payload = signing::recovery_cancel_payload(&recovery.id, &request.nonce);
+        crypto::verify_signature(&identity.public_key, &payload, &request.signature)?;
+
+        recovery.status = RecoveryStatus::Cancelled;
+        self.storage.update_recovery_status(&recovery).await?;
+        self.storage.log_recovery_event(&recovery.id, &recovery.identity_id, "cancelled", Some(&identity.id)).await?;
+
+        Ok(recovery)
+    }
+
+    /// Installs the recovery key once the threshold is met and the window has elapsed.
+    /// Unauthenticated by design: the outcome is fully determined by the recorded approvals.
+    pub async fn finalize_recovery(&self, recovery_id: Uuid) -> Result<Recovery, EngineError> {
+        let mut recovery = self.storage.get_recovery(&recovery_id).await?
+            .ok_or(EngineError::RecoveryNotFound(recovery_id))?;
+
+        if recovery.status != RecoveryStatus::Approved {
+            return Err(EngineError::RecoveryRejected(format!("Recovery is {:?}", recovery.status)));
+        }
+
+        match recovery.executable_at {
+            Some(at) if self.now() >= at => {},
+            _ => return Err(EngineError::RecoveryRejected("Cancellation window has not elapsed".into())),
+        }
+
+        let identity = self.load_identity(&recovery.identity_id).await?;
+
+        if identity.status == IdentityStatus::Revoked {
+            return Err(EngineError::IdentityRevoked(identity.id));
+        }
+
+        // Someone may have registered the key since initiation.
+        if self.storage.get_identity_by_public_key(&recovery.new_public_key).await?.is_some()
+            || self.storage.is_key_retired(&recovery.new_public_key).await?
+        {
+            return Err(EngineError::AlreadyExists);
+        }
+
+        // Key swap, audit event and Stale/Dormant -> Active in one transaction
+        let reactivation = self.plan_reactivation(&identity, TransitionReason::Recovery).await?;
+        let transition = self.transition_for(&identity, TransitionReason::Recovery, None)?;
+        self.storage.complete_recovery(&RecoveryCompletion {
+            recovery: &recovery,
+            previous_public_key: &identity.public_key,
+            at: self.now(),
+            reactivation: reactivation.clone(),
+            transition,
+            log: log_record(LogEventKind::Recovery, identity.id, &recovery.new_public_key),
+        }).await?;
+        recovery.status = RecoveryStatus::Completed;
+
+        if let Some((reactivation, _)) = &reactivation {
+            let mut recovered = identity;
+            recovered.public_key = recovery.new_public_key.clone();
+            self.reactivated(&mut recovered, reactivation).await;
+        }
+
+        Ok(recovery)
+    }
+}
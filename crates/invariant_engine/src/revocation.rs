@@ -0,0 +1,69 @@
+// crates/invariant_engine/src/revocation.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use invariant_shared::{Revocation, RevocationReason, RevocationNotice, SelfRevocationRequest, LogEventKind, TransitionReason};
+use invariant_shared::signing;
+use crate::core::InvariantEngine;
+use crate::ports::{IdentityStorage, NonceStorage, TransparencyStorage, LifecycleStorage, RevocationStorage};
+use crate::error::EngineError;
+use crate::transparency::log_record;
+use crate::{crypto, lifecycle, pairwise};
+use uuid::Uuid;
+
+pub const MAX_REVOCATIONS_PER_PAGE: u32 = 500;
+
+/// ⛔ REVOCATION
+/// Revoked is terminal: the identity stops mining, its tokens stop verifying and
+/// every partner that knows it sees the revocation in its feed (under its own
+/// pairwise ID). Each revocation is also a transparency log event.
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + TransparencyStorage + LifecycleStorage + RevocationStorage,
+    N: NonceStorage,
+{
+    /// Operator revocation. `revoked_by` is the acting identity, or None for the node operator.
+    pub async fn revoke_identity(
+        &self,
+        identity_id: Uuid,
+        reason: RevocationReason,
+        revoked_by: Option<Uuid>,
+    ) -> Result<Revocation, EngineError> {
+        let identity = self.storage.get_identity(&identity_id).await?
+            .ok_or(EngineError::IdentityNotFound(identity_id))?;
+
+        let transition = self.transition_for(&identity, TransitionReason::Revocation, revoked_by)?
+            .ok_or(EngineError::IdentityRevoked(identity_id))?;
+        let log = log_record(LogEventKind::Revocation, identity_id, &identity.public_key);
+        self.storage.record_revocation(&transition, reason, &log).await
+    }
+
+    /// Holder revocation, signed by the identity's own hardware key.
+    pub async fn process_self_revocation(&self, request: SelfRevocationRequest) -> Result<Revocation, EngineError> {
+        let identity = self.load_identity(&request.id).await?;
+
+        if !request.reason.is_self_service() {
+            return Err(EngineError::InvalidRevocation(format!("'{}' is reserved for operators", request.reason.tag())));
+        }
+        lifecycle::check_transition(&identity, TransitionReason::Revocation)?;
+
+        if !self.nonce_storage.consume_nonce(&request.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+        let payload = signing::revocation_payload(&request.id, request.reason, &request.nonce);
+        crypto::verify_signature(&identity.public_key, &payload, &request.signature)?;
+
+        self.revoke_identity(request.id, request.reason, Some(request.id)).await
+    }
+
+    /// Revocations of identities linked to `partner_id`, with `sequence > after`, oldest first.
+    pub async fn partner_revocation_feed(&self, partner_id: &str, after: u64, limit: u32) -> Result<Vec<RevocationNotice>, EngineError> {
+        pairwise::validate_partner_id(partner_id)?;
+        let limit = limit.clamp(1, MAX_REVOCATIONS_PER_PAGE);
+        self.storage.get_partner_revocations(partner_id, after, limit).await
+    }
+}
@@ -0,0 +1,124 @@
+// crates/invariant_engine/src/streak.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use invariant_shared::{StreakState, StreakTimezoneRequest};
+use invariant_shared::signing;
+use crate::core::InvariantEngine;
+use crate::params::ProtocolParameters;
+use crate::ports::{IdentityStorage, NonceStorage, StreakStorage};
+use crate::error::EngineError;
+use crate::crypto;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use uuid::Uuid;
+
+/// What a counted heartbeat did to the streak.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreakUpdate {
+    /// This local day was already credited.
+    SameDay,
+    /// Consecutive day (possibly through the grace window).
+    Extended,
+    /// Missed days bridged by this many freezes.
+    Frozen(u32),
+    /// First day, or a gap no freeze could cover: the streak (re)starts at 1.
+    Started,
+}
+
+/// Local calendar day of `at` in the IANA zone `timezone` (UTC if unknown).
+pub fn local_day(at: DateTime<Utc>, timezone: &str) -> NaiveDate {
+    let tz: Tz = timezone.parse().unwrap_or(Tz::UTC);
+    at.with_timezone(&tz).date_naive()
+}
+
+/// Credits the heartbeat at `at` to the streak.
+///
+/// A heartbeat counts for its local day, except inside the grace window just after
+/// midnight, where it fills a missed previous day instead. Remaining missed days
+/// consume freezes; if there are not enough, the streak restarts. A freeze is earned
+/// every `streak_freeze_interval_days` consecutive days, up to `max_streak_freezes`.
+pub fn advance_streak(state: &StreakState, at: DateTime<Utc>, params: &ProtocolParameters) -> (StreakState, StreakUpdate) {
+    let today = local_day(at, &state.timezone);
+    let mut next = state.clone();
+
+    let update = match state.last_day {
+        // Same day, or a time zone change moved "today" behind the last credited day.
+        Some(last) if today <= last => return (next, StreakUpdate::SameDay),
+        Some(last) => {
+            let expected = last + Duration::days(1);
+            let grace_day = local_day(at - Duration::minutes(params.streak_grace_minutes), &state.timezone);
+            let day = if today > expected && grace_day == expected { expected } else { today };
+            let missed = (day - last).num_days() - 1;
+
+            next.last_day = Some(day);
+            if missed == 0 {
+                next.streak += 1;
+                StreakUpdate::Extended
+            } else if missed <= state.freezes as i64 {
+                next.freezes -= missed as u32;
+                next.streak += 1;
+                StreakUpdate::Frozen(missed as u32)
+            } else {
+                next.streak = 1;
+                StreakUpdate::Started
+            }
+        }
+        None => {
+            next.last_day = Some(today);
+            next.streak = 1;
+            StreakUpdate::Started
+        }
+    };
+
+    if next.streak.is_multiple_of(params.streak_freeze_interval_days as u64) && (next.freezes as i64) < params.max_streak_freezes {
+        next.freezes += 1;
+    }
+    (next, update)
+}
+
+/// 🔥 STREAKS
+/// Consecutive days of presence, counted in the holder's own time zone.
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + StreakStorage,
+    N: NonceStorage,
+{
+    pub async fn streak(&self, identity_id: Uuid) -> Result<StreakState, EngineError> {
+        self.storage.get_streak(&identity_id).await?
+            .ok_or(EngineError::IdentityNotFound(identity_id))
+    }
+
+    /// Changes the zone that defines the holder's days. The last credited day is kept
+    /// as is, so a change can neither skip nor repeat a day.
+    pub async fn set_streak_timezone(&self, request: StreakTimezoneRequest) -> Result<StreakState, EngineError> {
+        let identity = self.load_identity(&request.id).await?;
+
+        if request.timezone.parse::<Tz>().is_err() {
+            return Err(EngineError::InvalidTimezone(request.timezone));
+        }
+
+        if !self.nonce_storage.consume_nonce(&request.nonce, self.config.params.nonce_ttl_seconds).await? {
+            return Err(EngineError::ReplayDetected);
+        }
+        let payload = signing::streak_timezone_payload(&request.id, &request.timezone, &request.nonce);
+        crypto::verify_signature(&identity.public_key, &payload, &request.signature)?;
+
+        let state = StreakState { timezone: request.timezone, ..self.streak(request.id).await? };
+        self.storage.save_streak(&request.id, &state).await?;
+        Ok(state)
+    }
+
+    /// Called for every counted heartbeat.
+    pub(crate) async fn record_streak_day(&self, identity_id: Uuid, at: DateTime<Utc>) -> Result<StreakUpdate, EngineError> {
+        let (state, update) = advance_streak(&self.streak(identity_id).await?, at, &self.config.params);
+        if update != StreakUpdate::SameDay {
+            self.storage.save_streak(&identity_id, &state).await?;
+        }
+        Ok(update)
+    }
+}
@@ -0,0 +1,169 @@
+// crates/invariant_engine/src/sybil.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use std::collections::{BTreeSet, HashMap};
+use std::net::IpAddr;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use invariant_shared::{SybilCluster, SybilConfidence, SybilSignal};
+use crate::attestation::AttestationMetadata;
+use crate::core::InvariantEngine;
+use crate::ports::{IdentityStorage, NonceStorage, SybilStorage};
+use crate::error::EngineError;
+
+/// Hashed attestation values kept for clustering (never the raw key or certificate).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AttestationFingerprint {
+    pub boot_key_hash: Option<String>,
+    pub intermediate_hash: Option<String>,
+}
+
+impl AttestationFingerprint {
+    pub fn of(metadata: &AttestationMetadata) -> Self {
+        Self {
+            boot_key_hash: metadata.verified_boot_key.as_ref().map(|key| hex::encode(Sha256::digest(key))),
+            intermediate_hash: metadata.intermediate_fingerprint.clone(),
+        }
+    }
+}
+
+/// Every clustering signal of one identity. A None field is simply not compared.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IdentityFingerprint {
+    pub identity_id: Uuid,
+    pub device_hash: Option<String>,
+    pub boot_key_hash: Option<String>,
+    pub intermediate_hash: Option<String>,
+    pub genesis_ip_prefix: Option<String>,
+    pub heartbeat_timing: Option<String>,
+}
+
+impl IdentityFingerprint {
+    fn signals(&self) -> impl Iterator<Item = (SybilSignal, &str)> {
+        [
+            (SybilSignal::DeviceHash, &self.device_hash),
+            (SybilSignal::BootKey, &self.boot_key_hash),
+            (SybilSignal::AttestationIntermediate, &self.intermediate_hash),
+            (SybilSignal::GenesisIpPrefix, &self.genesis_ip_prefix),
+            (SybilSignal::HeartbeatTiming, &self.heartbeat_timing),
+        ].into_iter().filter_map(|(signal, value)| value.as_deref().map(|v| (signal, v)))
+    }
+}
+
+/// Network an address belongs to for clustering: /24 for IPv4, /48 for IPv6.
+pub fn ip_prefix(addr: IpAddr) -> String {
+    match addr {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            format!("{}.{}.{}.0/24", a, b, c)
+        }
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => ip_prefix(IpAddr::V4(v4)),
+            None => {
+                let s = v6.segments();
+                format!("{:x}:{:x}:{:x}::/48", s[0], s[1], s[2])
+            }
+        },
+    }
+}
+
+/// 🕸️ SYBIL CLUSTERING
+/// Two identities are linked when they share a signal value. A value shared by more than
+/// `max_group_size` identities is a population trait (a popular phone model, an OEM boot
+/// key, a carrier NAT) rather than evidence, and is ignored. Clusters are the connected
+/// components of the remaining links; confidence is the number of distinct signals shared
+/// by the most closely linked pair.
+pub fn find_clusters(fingerprints: &[IdentityFingerprint], max_group_size: usize, now: DateTime<Utc>) -> Vec<SybilCluster> {
+    let mut groups: HashMap<(SybilSignal, &str), Vec<Uuid>> = HashMap::new();
+    for fingerprint in fingerprints {
+        for (signal, value) in fingerprint.signals() {
+            groups.entry((signal, value)).or_default().push(fingerprint.identity_id);
+        }
+    }
+
+    let mut links: HashMap<(Uuid, Uuid), BTreeSet<SybilSignal>> = HashMap::new();
+    for ((signal, _), members) in groups.iter().filter(|(_, m)| (2..=max_group_size).contains(&m.len())) {
+        for (i, a) in members.iter().enumerate() {
+            for b in &members[i + 1..] {
+                links.entry((*a.min(b), *a.max(b))).or_default().insert(*signal);
+            }
+        }
+    }
+
+    let mut parent: HashMap<Uuid, Uuid> = HashMap::new();
+    for (a, b) in links.keys() {
+        let (ra, rb) = (find_root(&mut parent, *a), find_root(&mut parent, *b));
+        if ra != rb {
+            parent.insert(ra.max(rb), ra.min(rb));
+        }
+    }
+
+    let mut components: HashMap<Uuid, (BTreeSet<Uuid>, BTreeSet<SybilSignal>, usize)> = HashMap::new();
+    for ((a, b), signals) in &links {
+        let root = find_root(&mut parent, *a);
+        let entry = components.entry(root).or_default();
+        entry.0.extend([*a, *b]);
+        entry.1.extend(signals.iter().copied());
+        entry.2 = entry.2.max(signals.len());
+    }
+
+    let mut clusters: Vec<SybilCluster> = components.into_values().map(|(members, signals, strongest)| SybilCluster {
+        id: Uuid::new_v4(),
+        confidence: match strongest {
+            0 | 1 => SybilConfidence::Low,
+            2 => SybilConfidence::Medium,
+            _ => SybilConfidence::High,
+        },
+        signals: signals.into_iter().collect(),
+        members: members.into_iter().collect(),
+        detected_at: now,
+    }).collect();
+
+    clusters.sort_by(|a, b| b.confidence.cmp(&a.confidence)
+        .then(b.members.len().cmp(&a.members.len()))
+        .then(a.members.cmp(&b.members)));
+    clusters
+}
+
+fn find_root(parent: &mut HashMap<Uuid, Uuid>, id: Uuid) -> Uuid {
+    let mut root = id;
+    while let Some(next) = parent.get(&root).copied() {
+        root = next;
+    }
+    // Path compression
+    let mut current = id;
+    while current != root {
+        current = parent.insert(current, root).unwrap_or(root);
+    }
+    root
+}
+
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + SybilStorage,
+    N: NonceStorage,
+{
+    /// Background scan: recomputes every cluster and replaces the previous results.
+    pub async fn detect_sybil_clusters(&self) -> Result<Vec<SybilCluster>, EngineError> {
+        let fingerprints = self.storage.get_fingerprints().await?;
+        let clusters = find_clusters(&fingerprints, self.config.params.sybil_max_group_size as usize, self.now());
+        self.storage.replace_sybil_clusters(&clusters).await?;
+        Ok(clusters)
+    }
+
+    pub async fn sybil_clusters(&self, min_confidence: SybilConfidence, limit: u32) -> Result<Vec<SybilCluster>, EngineError> {
+        self.storage.get_sybil_clusters(min_confidence, limit).await
+    }
+
+    /// Highest confidence of any cluster the identity belongs to (None = not clustered).
+    pub async fn sybil_flag(&self, identity_id: &Uuid) -> Result<Option<SybilConfidence>, EngineError> {
+        let clusters = self.storage.get_sybil_clusters_for(identity_id).await?;
+        Ok(clusters.iter().map(|c| c.confidence).max())
+    }
+}
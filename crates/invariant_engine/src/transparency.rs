@@ -0,0 +1,104 @@
+// crates/invariant_engine/src/transparency.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1) 
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use invariant_shared::{LogEntry, LogEventKind, TreeHead};
+use invariant_shared::transparency::{Hash, MerkleTree};
+use crate::core::InvariantEngine;
+use crate::ports::{IdentityStorage, LogRecord, NonceStorage, TransparencyStorage};
+use crate::error::EngineError;
+use sha2::{Sha256, Digest};
+use std::sync::{PoisonError, RwLockReadGuard};
+use uuid::Uuid;
+
+/// Upper bound for a single `entries` page.
+pub const MAX_ENTRIES_PER_PAGE: u64 = 1000;
+
+/// 📜 TRANSPARENCY LOG
+/// Append-only Merkle log of identity lifecycle events, each appended in the storage
+/// transaction of the change it records (`log_record`). The engine keeps every
+/// complete subtree hash in `log_tree` and only fetches entries appended since the
+/// last request, so heads and proofs never reload the whole log.
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + TransparencyStorage,
+    N: NonceStorage,
+{
+    pub async fn tree_head(&self) -> Result<TreeHead, EngineError> {
+        let size = self.sync_log_tree(None).await?;
+        Ok(TreeHead {
+            tree_size: size as u64,
+            root_hash: self.log_tree().root(size).to_vec(),
+            timestamp: self.now(),
+        })
+    }
+
+    pub async fn log_entries(&self, start: u64, end: u64) -> Result<Vec<LogEntry>, EngineError> {
+        if end <= start || end - start > MAX_ENTRIES_PER_PAGE {
+            return Err(EngineError::InvalidLogRange(format!("Range must hold 1..={} entries", MAX_ENTRIES_PER_PAGE)));
+        }
+        self.storage.get_log_entries(start, end).await
+    }
+
+    /// Audit path for entry `index` in the tree of size `tree_size`.
+    pub async fn inclusion_proof(&self, index: u64, tree_size: u64) -> Result<Vec<Vec<u8>>, EngineError> {
+        if index >= tree_size {
+            return Err(EngineError::InvalidLogRange("Index must be below tree_size".into()));
+        }
+        let size = self.sync_log_tree(Some(tree_size)).await?;
+        Ok(to_vecs(self.log_tree().inclusion_proof(index as usize, size)))
+    }
+
+    /// Proof that the tree of size `old_size` is a prefix of the tree of size `new_size`.
+    pub async fn consistency_proof(&self, old_size: u64, new_size: u64) -> Result<Vec<Vec<u8>>, EngineError> {
+        if old_size == 0 || old_size > new_size {
+            return Err(EngineError::InvalidLogRange("Require 0 < old_size <= new_size".into()));
+        }
+        let size = self.sync_log_tree(Some(new_size)).await?;
+        Ok(to_vecs(self.log_tree().consistency_proof(old_size as usize, size)))
+    }
+
+    /// Brings the cached tree up to `tree_size` (default: the current log size), one
+    /// page at a time, and returns the size to serve. The lock is never held across
+    /// storage calls; a page fetched by a racing request is simply dropped.
+    async fn sync_log_tree(&self, tree_size: Option<u64>) -> Result<usize, EngineError> {
+        let current = self.storage.get_log_size().await?;
+        let size = tree_size.unwrap_or(current);
+        if size > current {
+            return Err(EngineError::InvalidLogRange(format!("Log only has {} entries", current)));
+        }
+        loop {
+            let cached = self.log_tree().len() as u64;
+            if cached >= size {
+                return Ok(size as usize);
+            }
+            let end = size.min(cached + MAX_ENTRIES_PER_PAGE);
+            let entries = self.storage.get_log_entries(cached, end).await?;
+            if entries.len() as u64 != end - cached {
+                return Err(EngineError::Storage(format!("Transparency log is missing entries in {}..{}", cached, end)));
+            }
+            let mut tree = self.log_tree.write().unwrap_or_else(PoisonError::into_inner);
+            if tree.len() as u64 == cached {
+                entries.iter().for_each(|entry| tree.push(entry.leaf_hash()));
+            }
+        }
+    }
+
+    fn log_tree(&self) -> RwLockReadGuard<'_, MerkleTree> {
+        self.log_tree.read().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The log entry for an event of `identity_id` whose key (after the event) is `public_key`.
+/// Handed to the storage call that stores the event.
+pub(crate) fn log_record(kind: LogEventKind, identity_id: Uuid, public_key: &[u8]) -> LogRecord {
+    LogRecord { kind, identity_id, public_key_hash: Sha256::digest(public_key).to_vec() }
+}
+
+fn to_vecs(hashes: Vec<Hash>) -> Vec<Vec<u8>> {
+    hashes.into_iter().map(|h| h.to_vec()).collect()
+}
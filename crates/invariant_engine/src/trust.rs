@@ -0,0 +1,297 @@
+// crates/invariant_engine/src/trust.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use std::collections::{HashMap, HashSet};
+use chrono::{DateTime, Datelike, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use invariant_shared::{Identity, SecurityLevel, TrustScore, TrustSignal, SignalContribution};
+use crate::attestation::AttestationMetadata;
+use crate::core::InvariantEngine;
+use crate::params::ProtocolParameters;
+use crate::ports::{IdentityStorage, NonceStorage, TrustStorage};
+use crate::error::EngineError;
+
+/// A patch this recent counts in full.
+const PATCH_FRESH_MONTHS: i64 = 3;
+/// A patch this old counts for nothing.
+const PATCH_EXPIRED_MONTHS: i64 = 24;
+/// Streak length (days) that earns the full signal.
+const STREAK_SATURATION: u64 = 30;
+/// Continuity score that earns the full signal.
+const CONTINUITY_SATURATION: u64 = 180;
+/// Each recent key rotation, recovery or reactivation removes this much of the velocity signal.
+const VELOCITY_PENALTY_PER_EVENT: f64 = 0.25;
+
+/// Relative weight of each signal. Only the ratios matter; a weight of 0 disables a signal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrustWeights {
+    pub security_level: f64,
+    pub patch_age: f64,
+    pub attestation_age: f64,
+    pub streak: f64,
+    pub continuity_score: f64,
+    pub device_catalog: f64,
+    pub velocity: f64,
+}
+
+impl Default for TrustWeights {
+    fn default() -> Self {
+        Self {
+            security_level: 20.0,
+            patch_age: 15.0,
+            attestation_age: 15.0,
+            streak: 10.0,
+            continuity_score: 15.0,
+            device_catalog: 10.0,
+            velocity: 15.0,
+        }
+    }
+}
+
+impl TrustWeights {
+    /// Parses a weights file. Omitted signals keep their default weight.
+    pub fn from_json(json: &str) -> Result<Self, EngineError> {
+        let weights: Self = serde_json::from_str(json).map_err(|e| EngineError::InvalidPolicy(e.to_string()))?;
+        weights.validate()?;
+        Ok(weights)
+    }
+
+    pub fn validate(&self) -> Result<(), EngineError> {
+        let all = [
+            self.security_level, self.patch_age, self.attestation_age, self.streak,
+            self.continuity_score, self.device_catalog, self.velocity,
+        ];
+        if all.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err(EngineError::InvalidPolicy("trust weights must be finite and not negative".into()));
+        }
+        if all.iter().sum::<f64>() <= 0.0 {
+            return Err(EngineError::InvalidPolicy("at least one trust weight must be positive".into()));
+        }
+        Ok(())
+    }
+
+    fn weight(&self, signal: TrustSignal) -> f64 {
+        match signal {
+            TrustSignal::SecurityLevel => self.security_level,
+            TrustSignal::PatchAge => self.patch_age,
+            TrustSignal::AttestationAge => self.attestation_age,
+            TrustSignal::Streak => self.streak,
+            TrustSignal::ContinuityScore => self.continuity_score,
+            TrustSignal::DeviceCatalog => self.device_catalog,
+            TrustSignal::Velocity => self.velocity,
+        }
+    }
+}
+
+/// Known brand → product pairs. Attestation IDs are device-provided, so a product
+/// the catalog has never seen for that brand is a spoofing signal.
+#[derive(Debug, Clone, Default)]
+pub struct DeviceCatalog {
+    products: HashMap<String, HashSet<String>>,
+}
+
+impl DeviceCatalog {
+    /// `{ "google": ["husky", "shiba"], "samsung": ["dm3qxxx"] }`
+    pub fn from_json(json: &str) -> Result<Self, EngineError> {
+        let raw: HashMap<String, Vec<String>> = serde_json::from_str(json)
+            .map_err(|e| EngineError::InvalidPolicy(format!("device catalog: {}", e)))?;
+        let products = raw.into_iter()
+            .map(|(brand, products)| (brand.to_lowercase(), products.into_iter().map(|p| p.to_lowercase()).collect()))
+            .collect();
+        Ok(Self { products })
+    }
+
+    /// Some(true) if the product is listed for the brand, Some(false) if the brand is
+    /// known but the product is not, None if the catalog cannot tell.
+    pub fn check(&self, brand: Option<&str>, product: Option<&str>) -> Option<bool> {
+        let known = self.products.get(&brand?.to_lowercase())?;
+        Some(known.contains(&product?.to_lowercase()))
+    }
+}
+
+/// Recent identity events that are individually legitimate but suspicious in bursts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecentActivity {
+    pub key_rotations: u32,
+    pub recoveries: u32,
+    /// Dormant → active transitions.
+    pub reactivations: u32,
+}
+
+impl RecentActivity {
+    pub fn events(&self) -> u32 { self.key_rotations + self.recoveries + self.reactivations }
+}
+
+/// Raw inputs to the scorer. A None field is an unavailable signal.
+#[derive(Debug, Clone, Default)]
+pub struct TrustSignals {
+    pub security_level: Option<SecurityLevel>,
+    /// YYYYMM
+    pub os_patch_level: Option<u32>,
+    pub attestation_age: Option<Duration>,
+    pub streak: Option<u64>,
+    pub continuity_score: Option<u64>,
+    pub brand: Option<String>,
+    pub product: Option<String>,
+    pub activity: Option<RecentActivity>,
+}
+
+impl TrustSignals {
+    pub fn from_identity(identity: &Identity, now: DateTime<Utc>, activity: RecentActivity) -> Self {
+        Self {
+            security_level: identity.security_level,
+            os_patch_level: identity.os_patch_level,
+            attestation_age: Some(now - identity.last_attestation),
+            streak: Some(identity.streak),
+            continuity_score: Some(identity.continuity_score),
+            brand: identity.hardware_brand.clone(),
+            product: identity.hardware_product.clone(),
+            activity: Some(activity),
+        }
+    }
+
+    /// Hardware-only view of a fresh attestation (stateless `/verify`).
+    pub fn from_attestation(metadata: &AttestationMetadata) -> Self {
+        Self {
+            security_level: metadata.security_level,
+            os_patch_level: metadata.os_patch_level,
+            attestation_age: Some(Duration::zero()),
+            brand: metadata.brand.clone(),
+            product: metadata.product.clone(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Months between a YYYYMM patch level and `now` (negative if from the future).
+pub fn patch_age_months(os_patch_level: u32, now: DateTime<Utc>) -> Option<i64> {
+    // Some vendors report YYYYMMDD.
+    let level = if os_patch_level > 999_999 { os_patch_level / 100 } else { os_patch_level };
+    let (year, month) = ((level / 100) as i64, (level % 100) as i64);
+    if !(1..=12).contains(&month) || year < 2000 {
+        return None;
+    }
+    Some((now.year() as i64 * 12 + now.month0() as i64) - (year * 12 + month - 1))
+}
+
+/// ⚖️ TRUST SCORER
+/// Weighted, explainable 0..=100 score. Each available signal is normalized to 0..=1 and
+/// contributes `100 * weight * value / (sum of available weights)` points, so a missing
+/// signal neither helps nor hurts.
+#[derive(Debug, Clone, Default)]
+pub struct TrustScorer {
+    weights: TrustWeights,
+    catalog: DeviceCatalog,
+}
+
+impl TrustScorer {
+    pub fn new(weights: TrustWeights, catalog: DeviceCatalog) -> Self {
+        Self { weights, catalog }
+    }
+
+    pub fn weights(&self) -> &TrustWeights { &self.weights }
+
+    pub fn score(&self, signals: &TrustSignals, now: DateTime<Utc>, params: &ProtocolParameters) -> TrustScore {
+        let evaluated = [
+            (TrustSignal::SecurityLevel, security_level(signals)),
+            (TrustSignal::PatchAge, patch_age(signals, now)),
+            (TrustSignal::AttestationAge, attestation_age(signals, params)),
+            (TrustSignal::Streak, signals.streak.map(|s| (
+                (s.min(STREAK_SATURATION) as f64) / STREAK_SATURATION as f64,
+                format!("{} day streak", s),
+            ))),
+            (TrustSignal::ContinuityScore, signals.continuity_score.map(|c| (
+                (c.min(CONTINUITY_SATURATION) as f64) / CONTINUITY_SATURATION as f64,
+                format!("{} verified heartbeats", c),
+            ))),
+            (TrustSignal::DeviceCatalog, self.device_catalog(signals)),
+            (TrustSignal::Velocity, velocity(signals, params)),
+        ];
+
+        let available: f64 = evaluated.iter()
+            .filter(|(_, v)| v.is_some())
+            .map(|(signal, _)| self.weights.weight(*signal))
+            .sum();
+
+        let contributions: Vec<SignalContribution> = evaluated.into_iter().map(|(signal, evaluation)| {
+            let weight = self.weights.weight(signal);
+            match evaluation {
+                Some((value, detail)) => SignalContribution {
+                    signal,
+                    weight,
+                    value: Some(value),
+                    points: if available > 0.0 { 100.0 * weight * value / available } else { 0.0 },
+                    detail,
+                },
+                None => SignalContribution { signal, weight, value: None, points: 0.0, detail: "unavailable".into() },
+            }
+        }).collect();
+
+        let score = contributions.iter().map(|c| c.points).sum::<f64>().clamp(0.0, 100.0);
+        TrustScore { score, contributions }
+    }
+
+    fn device_catalog(&self, signals: &TrustSignals) -> Option<(f64, String)> {
+        let consistent = self.catalog.check(signals.brand.as_deref(), signals.product.as_deref())?;
+        Some(if consistent {
+            (1.0, "brand and product match the catalog".into())
+        } else {
+            (0.0, format!("product not in catalog for brand {}", signals.brand.as_deref().unwrap_or_default()))
+        })
+    }
+}
+
+fn security_level(signals: &TrustSignals) -> Option<(f64, String)> {
+    Some(match signals.security_level? {
+        SecurityLevel::StrongBox => (1.0, "StrongBox secure element".into()),
+        SecurityLevel::Tee => (0.6, "TEE (TrustZone)".into()),
+    })
+}
+
+fn patch_age(signals: &TrustSignals, now: DateTime<Utc>) -> Option<(f64, String)> {
+    let months = patch_age_months(signals.os_patch_level?, now)?.max(0);
+    let value = 1.0 - (months - PATCH_FRESH_MONTHS).max(0) as f64 / (PATCH_EXPIRED_MONTHS - PATCH_FRESH_MONTHS) as f64;
+    Some((value.clamp(0.0, 1.0), format!("patch {} months old", months)))
+}
+
+fn attestation_age(signals: &TrustSignals, params: &ProtocolParameters) -> Option<(f64, String)> {
+    let age = signals.attestation_age?.max(Duration::zero());
+    let ttl = Duration::days(params.attestation_ttl_days);
+    let value = 1.0 - age.num_seconds() as f64 / ttl.num_seconds() as f64;
+    Some((value.clamp(0.0, 1.0), format!("attested {} days ago", age.num_days())))
+}
+
+fn velocity(signals: &TrustSignals, params: &ProtocolParameters) -> Option<(f64, String)> {
+    let activity = signals.activity?;
+    let value = 1.0 - VELOCITY_PENALTY_PER_EVENT * activity.events() as f64;
+    Some((value.clamp(0.0, 1.0), format!(
+        "{} key rotations, {} recoveries, {} reactivations in {} days",
+        activity.key_rotations, activity.recoveries, activity.reactivations, params.velocity_window_days
+    )))
+}
+
+impl<S: IdentityStorage, N: NonceStorage> InvariantEngine<S, N> {
+    /// Hardware-only score of a fresh attestation.
+    pub fn score_attestation(&self, metadata: &AttestationMetadata) -> TrustScore {
+        self.trust.score(&TrustSignals::from_attestation(metadata), self.now(), &self.config.params)
+    }
+}
+
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + TrustStorage,
+    N: NonceStorage,
+{
+    pub async fn trust_score(&self, identity: &Identity) -> Result<TrustScore, EngineError> {
+        let now = self.now();
+        let since = now - Duration::days(self.config.params.velocity_window_days);
+        let activity = self.storage.get_recent_activity(&identity.id, since).await?;
+        Ok(self.trust.score(&TrustSignals::from_identity(identity, now, activity), now, &self.config.params))
+    }
+}
@@ -0,0 +1,72 @@
+// crates/invariant_engine/src/upgrade.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use invariant_shared::{DomainEventKind, GenesisUpgrade, GenesisUpgradeProgress, Identity};
+use crate::core::InvariantEngine;
+use crate::ports::{IdentityStorage, NonceStorage, UpgradeStorage};
+use crate::error::EngineError;
+
+/// ⬆️ GENESIS VERSION UPGRADES
+/// Every identity is stamped with the genesis version it was minted under. Versions
+/// below the policy's `min_genesis_version` are retired: such an identity's heartbeats
+/// are refused until it re-attests (explicitly or with a chain on the heartbeat), which
+/// moves it onto the node's current version. Any fresh attestation upgrades an older
+/// identity, retired or not.
+impl<S, N> InvariantEngine<S, N>
+where
+    S: IdentityStorage + UpgradeStorage,
+    N: NonceStorage,
+{
+    /// Whether the identity must re-attest before its heartbeats count again.
+    pub fn requires_genesis_upgrade(&self, identity: &Identity) -> bool {
+        identity.genesis_version < self.config.params.min_genesis_version
+    }
+
+    /// Moves a freshly re-attested identity onto the current genesis version.
+    pub(crate) async fn upgrade_genesis(&self, identity: &mut Identity) -> Result<(), EngineError> {
+        let current = self.config.genesis_version;
+        if identity.genesis_version >= current {
+            return Ok(());
+        }
+
+        let upgrade = GenesisUpgrade {
+            identity_id: identity.id,
+            from_version: identity.genesis_version,
+            to_version: current,
+            upgraded_at: self.now(),
+        };
+        // A concurrent re-attestation got there first
+        if !self.storage.record_genesis_upgrade(&upgrade).await? {
+            return Ok(());
+        }
+
+        identity.genesis_version = current;
+        self.emit(identity.id, DomainEventKind::GenesisUpgraded {
+            from_version: upgrade.from_version,
+            to_version: upgrade.to_version,
+        }).await;
+        Ok(())
+    }
+
+    /// Identities per genesis version and how many are still below the minimum.
+    pub async fn genesis_upgrade_progress(&self) -> Result<GenesisUpgradeProgress, EngineError> {
+        let versions = self.storage.get_genesis_versions().await?;
+        let min_version = self.config.params.min_genesis_version;
+        let pending = versions.iter()
+            .filter(|v| v.genesis_version < min_version)
+            .map(|v| v.identities)
+            .sum();
+
+        Ok(GenesisUpgradeProgress {
+            current_version: self.config.genesis_version,
+            min_version,
+            pending,
+            versions,
+        })
+    }
+}
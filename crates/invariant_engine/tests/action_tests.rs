@@ -0,0 +1,117 @@
+// crates/invariant_engine/tests/action_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+    use p256::ecdsa::{SigningKey, signature::Signer};
+    use p256::pkcs8::EncodePublicKey;
+    use rand_core::OsRng;
+
+    use invariant_engine::{InvariantEngine, IdentityStorage, EngineError, core::EngineConfig, ProtocolParameters};
+    use invariant_shared::{
+        Identity, IdentityStatus, Network, ActionChallengeRequest, ActionVerifyRequest, ReceiptVerdict, PairwiseLinkRequest,
+    };
+    use invariant_shared::signing;
+    use crate::common::{MockStorage, MockNonceStorage};
+
+    const SECRET: &[u8] = &[42u8; 32];
+    const PARTNER: &str = "https://bank.example";
+
+    fn new_engine() -> InvariantEngine<MockStorage, MockNonceStorage> {
+        let config = EngineConfig { network: Network::Mainnet, genesis_version: 1, params: ProtocolParameters::default() };
+        InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config)
+    }
+
+    /// Returns (internal id, partner subject id).
+    async fn mint(engine: &InvariantEngine<MockStorage, MockNonceStorage>, key: &SigningKey, status: IdentityStatus) -> (Uuid, Uuid) {
+        let identity = Identity {
+            id: Uuid::new_v4(),
+            public_key: key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec(),
+            continuity_score: 5, streak: 5,
+            created_at: Utc::now(),
+            last_heartbeat: Utc::now(),
+            last_attestation: Utc::now(),
+            status,
+            username: None, is_genesis_eligible: false, fcm_token: None,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            genesis_version: 1, network: Network::Mainnet,
+        };
+        engine.get_storage().save_identity(&identity).await.unwrap();
+        let nonce = Uuid::new_v4().as_bytes().to_vec();
+        let signature: p256::ecdsa::Signature = key.sign(&signing::pairwise_link_payload(&identity.id, PARTNER, &nonce));
+        let link = PairwiseLinkRequest { partner_id: PARTNER.into(), nonce, signature: signature.to_der().as_bytes().to_vec() };
+        let subject_id = engine.pairwise_id_for(SECRET, &identity.id, &link).await.unwrap();
+        (identity.id, subject_id)
+    }
+
+    fn approve(key: &SigningKey, identity_id: Uuid, subject_id: Uuid, nonce: &[u8], payload_hash: &[u8]) -> ActionVerifyRequest {
+        let signature: p256::ecdsa::Signature = key.sign(&signing::action_payload(&identity_id, nonce, payload_hash));
+        ActionVerifyRequest {
+            partner_id: PARTNER.into(),
+            subject_id,
+            nonce: nonce.to_vec(),
+            payload_hash: payload_hash.to_vec(),
+            signature: signature.to_der().as_bytes().to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_signed_action_is_accepted_once() {
+        let engine = new_engine();
+        let key = SigningKey::random(&mut OsRng);
+        let (id, subject_id) = mint(&engine, &key, IdentityStatus::Active).await;
+        let payload_hash = vec![0x42; 32];
+
+        let challenge = engine.issue_action_challenge(&ActionChallengeRequest {
+            partner_id: PARTNER.into(), subject_id, payload_hash: payload_hash.clone(),
+        }).await.unwrap();
+        assert_eq!(challenge.nonce.len(), 32);
+        assert!(challenge.expires_at > Utc::now());
+
+        let request = approve(&key, id, subject_id, &challenge.nonce, &payload_hash);
+        let verdict = engine.verify_action(&request).await.unwrap();
+        assert_eq!(verdict.verdict, ReceiptVerdict::Accepted);
+        assert_eq!((verdict.subject_id, verdict.payload_hash.clone()), (subject_id, payload_hash));
+
+        // Replaying the same approval must fail, not be accepted again.
+        assert!(matches!(engine.verify_action(&request).await, Err(EngineError::ReplayDetected)));
+    }
+
+    #[tokio::test]
+    async fn test_signature_over_another_action_is_rejected() {
+        let engine = new_engine();
+        let key = SigningKey::random(&mut OsRng);
+        let (id, subject_id) = mint(&engine, &key, IdentityStatus::Active).await;
+
+        let mut request = approve(&key, id, subject_id, &[7u8; 32], &[0x01; 32]);
+        request.payload_hash = vec![0x02; 32];
+        assert_eq!(engine.verify_action(&request).await.unwrap().verdict, ReceiptVerdict::Rejected);
+
+        // The nonce is burnt even though the signature failed.
+        let retry = approve(&key, id, subject_id, &[7u8; 32], &[0x02; 32]);
+        assert!(matches!(engine.verify_action(&retry).await, Err(EngineError::ReplayDetected)));
+    }
+
+    #[tokio::test]
+    async fn test_challenge_requires_known_trusted_subject_and_sha256() {
+        let engine = new_engine();
+        let key = SigningKey::random(&mut OsRng);
+        let (_, subject_id) = mint(&engine, &key, IdentityStatus::Active).await;
+        let (_, stale_subject) = mint(&engine, &key, IdentityStatus::Stale).await;
+        let (_, dormant_subject) = mint(&engine, &key, IdentityStatus::Dormant).await;
+
+        let request = |subject_id, payload_hash: Vec<u8>| ActionChallengeRequest { partner_id: PARTNER.into(), subject_id, payload_hash };
+
+        assert!(matches!(engine.issue_action_challenge(&request(subject_id, vec![1; 20])).await, Err(EngineError::InvalidAction(_))));
+        assert!(matches!(engine.issue_action_challenge(&request(Uuid::new_v4(), vec![1; 32])).await, Err(EngineError::IdentityNotFound(_))));
+        assert!(matches!(engine.issue_action_challenge(&request(stale_subject, vec![1; 32])).await, Err(EngineError::AttestationRequired)));
+        assert!(matches!(engine.issue_action_challenge(&request(dormant_subject, vec![1; 32])).await, Err(EngineError::AttestationRequired)));
+    }
+}
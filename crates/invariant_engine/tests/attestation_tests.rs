@@ -3,115 +3,22 @@
  * Copyright (c) 2026 Invariant Protocol.
  */
 
+mod common;
+
 #[cfg(test)]
 mod tests {
-    use async_trait::async_trait;
     use chrono::{Utc, Duration};
-    use std::collections::{HashMap, HashSet};
-    use tokio::sync::RwLock;
     use uuid::Uuid;
     use p256::ecdsa::{SigningKey, signature::Signer};
     use rand_core::OsRng; 
     use p256::pkcs8::EncodePublicKey;
 
-    use invariant_engine::{InvariantEngine, IdentityStorage, EngineError, attestation, core::EngineConfig};
-    use invariant_engine::ports::NonceStorage; // 👈 NEW TRAIT IMPORT
-    use invariant_shared::{Identity, IdentityStatus, Heartbeat, GenesisRequest, Network};
-
-    // --- MOCK STORAGE IMPLEMENTATION (Postgres) ---
-    #[derive(Default)]
-    struct MockStorage {
-        identities: RwLock<HashMap<Uuid, Identity>>,
-        heartbeats: RwLock<Vec<Heartbeat>>,
-    }
-
-    #[async_trait]
-    impl IdentityStorage for MockStorage {
-        async fn get_identity(&self, id: &Uuid) -> Result<Option<Identity>, EngineError> {
-            Ok(self.identities.read().await.get(id).cloned())
-        }
-
-        async fn get_identity_by_public_key(&self, pk: &[u8]) -> Result<Option<Identity>, EngineError> {
-            let map = self.identities.read().await;
-            for identity in map.values() {
-                if identity.public_key == pk {
-                    return Ok(Some(identity.clone()));
-                }
-            }
-            Ok(None)
-        }
-
-        async fn save_identity(&self, identity: &Identity) -> Result<(), EngineError> {
-            self.identities.write().await.insert(identity.id, identity.clone());
-            Ok(())
-        }
-
-        async fn log_heartbeat(&self, identity: &Identity, heartbeat: &Heartbeat) -> Result<u64, EngineError> {
-            let mut map = self.identities.write().await;
-            if let Some(id_ref) = map.get_mut(&identity.id) {
-                id_ref.continuity_score += 1;
-                id_ref.last_heartbeat = heartbeat.timestamp;
-                self.heartbeats.write().await.push(heartbeat.clone());
-                return Ok(id_ref.continuity_score);
-            }
-            Err(EngineError::IdentityNotFound(identity.id))
-        }
-
-        async fn run_reaper(&self) -> Result<u64, EngineError> { Ok(0) }
-        
-        async fn set_username(&self, id: &Uuid, username: &str) -> Result<bool, EngineError> {
-            let mut map = self.identities.write().await;
-            // Check uniqueness
-            for identity in map.values() {
-                if let Some(existing) = &identity.username {
-                    if existing == username { return Ok(false); }
-                }
-            }
-            if let Some(id_ref) = map.get_mut(id) {
-                if id_ref.username.is_some() { return Ok(false); }
-                id_ref.username = Some(username.to_string());
-                return Ok(true);
-            }
-            Ok(false)
-        }
-
-        async fn get_leaderboard(&self, limit: i64) -> Result<Vec<Identity>, EngineError> {
-            let map = self.identities.read().await;
-            let mut list: Vec<Identity> = map.values().cloned().collect();
-            list.sort_by(|a, b| b.continuity_score.cmp(&a.continuity_score));
-            Ok(list.into_iter().take(limit as usize).collect())
-        }
-
-        async fn update_fcm_token(&self, id: &Uuid, token: &str) -> Result<(), EngineError> {
-            let mut map = self.identities.write().await;
-            if let Some(id_ref) = map.get_mut(id) {
-                id_ref.fcm_token = Some(token.to_string());
-            }
-            Ok(())
-        }
-
-        async fn get_late_fcm_tokens(&self, _minutes: i64) -> Result<Vec<String>, EngineError> {
-            Ok(vec![])
-        }
-    }
-
-    // --- MOCK NONCE STORAGE (Redis) ---
-    #[derive(Default)]
-    struct MockNonceStorage {
-        used_nonces: RwLock<HashSet<Vec<u8>>>,
-    }
-
-    #[async_trait]
-    impl NonceStorage for MockNonceStorage {
-        async fn consume_nonce(&self, nonce: &[u8], _ttl: u64) -> Result<bool, EngineError> {
-            let mut set = self.used_nonces.write().await;
-            if set.contains(nonce) {
-                return Ok(false); // Already used -> Replay Detected
-            }
-            set.insert(nonce.to_vec());
-            Ok(true) // Fresh -> Consumed
-        }
-    }
+    use invariant_engine::{InvariantEngine, IdentityStorage, EngineError, attestation, core::EngineConfig, ProtocolParameters};
+    use invariant_engine::ports::Clock; // 👈 NEW TRAIT IMPORT
+    use invariant_engine::clock::AdjustableClock;
+    use invariant_shared::{Identity, IdentityStatus, Heartbeat, GenesisRequest, KeyRotationRequest, Network};
+    use invariant_shared::signing;
+    use crate::common::{MockStorage, MockNonceStorage};
 
     // --- HELPER: Manual DER Construction for Attestation Tests ---
     fn encode_test_extension(is_software: bool, challenge_bytes: &[u8]) -> Vec<u8> {
@@ -217,7 +124,7 @@ mod tests {
     async fn test_genesis_idempotency() {
         let storage = MockStorage::default();
         let nonce_storage = MockNonceStorage::default();
-        let config = EngineConfig { network: Network::Testnet, genesis_version: 1 };
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
         let engine = InvariantEngine::new(storage, nonce_storage, config);
         
         let pk = vec![0xAA, 0xBB, 0xCC];
@@ -233,6 +140,7 @@ mod tests {
             status: IdentityStatus::Active,
             username: None, streak: 10, is_genesis_eligible: true, fcm_token: None,
             hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
             genesis_version: 1, network: Network::Testnet,
         };
         engine.get_storage().save_identity(&identity).await.unwrap();
@@ -252,7 +160,7 @@ mod tests {
     async fn test_heartbeat_success() {
         let storage = MockStorage::default();
         let nonce_storage = MockNonceStorage::default();
-        let config = EngineConfig { network: Network::Testnet, genesis_version: 1 };
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
         let engine = InvariantEngine::new(storage, nonce_storage, config);
 
         let id = Uuid::new_v4();
@@ -271,6 +179,7 @@ mod tests {
             status: IdentityStatus::Active,
             username: None, is_genesis_eligible: true, fcm_token: None, streak: 0,
             hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
             genesis_version: 1, network: Network::Testnet,
         };
         engine.get_storage().save_identity(&identity).await.unwrap();
@@ -278,15 +187,16 @@ mod tests {
         let hb_time = Utc::now();
         let nonce = vec![0xDE, 0xAD, 0xBE, 0xEF];
         
-        // Format: ID | NONCE_HEX | TIMESTAMP_ISO
-        let payload_str = format!("{}|{}|{}", id, hex::encode(&nonce), hb_time.to_rfc3339());
-        let signature: p256::ecdsa::Signature = signing_key.sign(payload_str.as_bytes());
+        // Format: INVARIANT | v1 | heartbeat | ID | NONCE_HEX | TIMESTAMP_ISO
+        let payload = signing::heartbeat_payload(&id, &nonce, &hb_time);
+        let signature: p256::ecdsa::Signature = signing_key.sign(&payload);
         
         let hb = Heartbeat {
             identity_id: id,
             device_signature: signature.to_der().as_bytes().to_vec(),
             nonce: nonce.clone(),
             timestamp: hb_time,
+            attestation_chain: None,
         };
 
         let score = engine.process_heartbeat(hb).await.expect("Valid heartbeat failed");
@@ -297,7 +207,7 @@ mod tests {
     async fn test_heartbeat_rate_limit() {
         let storage = MockStorage::default();
         let nonce_storage = MockNonceStorage::default();
-        let config = EngineConfig { network: Network::Testnet, genesis_version: 1 };
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
         let engine = InvariantEngine::new(storage, nonce_storage, config);
 
         let id = Uuid::new_v4();
@@ -314,6 +224,7 @@ mod tests {
             status: IdentityStatus::Active,
             username: None, is_genesis_eligible: true, fcm_token: None, streak: 0,
             hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
             genesis_version: 1, network: Network::Testnet,
         };
         engine.get_storage().save_identity(&identity).await.unwrap();
@@ -323,6 +234,7 @@ mod tests {
             device_signature: vec![],
             nonce: vec![0x00],
             timestamp: now,
+            attestation_chain: None,
         };
 
         match engine.process_heartbeat(hb).await {
@@ -331,11 +243,103 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_heartbeat_follows_network_cadence() {
+        // Same identity, same heartbeat: 2h after the last one is too early on
+        // Mainnet (23h) but counts on Testnet (1h).
+        let signing_key = SigningKey::random(&mut OsRng);
+        let public_key_der = signing_key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec();
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+
+        for (network, accepted) in [(Network::Mainnet, false), (Network::Testnet, true)] {
+            let params = ProtocolParameters::for_network(&network);
+            let config = EngineConfig { network: network.clone(), genesis_version: 1, params };
+            let engine = InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config);
+
+            let identity = Identity {
+                id,
+                public_key: public_key_der.clone(),
+                continuity_score: 1,
+                created_at: now - Duration::days(2),
+                last_heartbeat: now - Duration::hours(2),
+                last_attestation: now,
+                status: IdentityStatus::Active,
+                username: None, is_genesis_eligible: true, fcm_token: None, streak: 0,
+                hardware_brand: None, hardware_device: None, hardware_product: None,
+                security_level: None, os_patch_level: None,
+                genesis_version: 1, network: network.clone(),
+            };
+            engine.get_storage().save_identity(&identity).await.unwrap();
+
+            let nonce = vec![0xCA, 0xFE];
+            let signature: p256::ecdsa::Signature = signing_key.sign(&signing::heartbeat_payload(&id, &nonce, &now));
+            let hb = Heartbeat {
+                identity_id: id,
+                device_signature: signature.to_der().as_bytes().to_vec(),
+                nonce,
+                timestamp: now,
+                attestation_chain: None,
+            };
+
+            match engine.process_heartbeat(hb).await {
+                Ok(score) => {
+                    assert!(accepted, "{:?} should rate limit", network);
+                    assert_eq!(score, 2);
+                }
+                Err(EngineError::RateLimitExceeded) => assert!(!accepted, "{:?} should accept", network),
+                Err(e) => panic!("Unexpected error: {:?}", e),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_cadence_with_adjustable_clock() {
+        let clock = AdjustableClock::new(Utc::now());
+        let config = EngineConfig { network: Network::Mainnet, genesis_version: 1, params: ProtocolParameters::default() };
+        let engine = InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config)
+            .with_clock(clock.clone());
+
+        let signing_key = SigningKey::random(&mut OsRng);
+        let id = Uuid::new_v4();
+        let identity = Identity {
+            id,
+            public_key: signing_key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec(),
+            continuity_score: 1,
+            created_at: clock.now() - Duration::days(1),
+            last_heartbeat: clock.now() - Duration::days(1),
+            last_attestation: clock.now(),
+            status: IdentityStatus::Active,
+            username: None, is_genesis_eligible: true, fcm_token: None, streak: 0,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            genesis_version: 1, network: Network::Mainnet,
+        };
+        engine.get_storage().save_identity(&identity).await.unwrap();
+
+        let tap = |nonce: u8| {
+            let timestamp = clock.now();
+            let signature: p256::ecdsa::Signature = signing_key.sign(&signing::heartbeat_payload(&id, &[nonce], &timestamp));
+            Heartbeat { identity_id: id, device_signature: signature.to_der().as_bytes().to_vec(), nonce: vec![nonce], timestamp, attestation_chain: None }
+        };
+
+        // Day 1: counted. One hour later: too early. A day later: counted again.
+        assert_eq!(engine.process_heartbeat(tap(1)).await.unwrap(), 2);
+        clock.advance(Duration::hours(1));
+        assert!(matches!(engine.process_heartbeat(tap(2)).await, Err(EngineError::RateLimitExceeded)));
+        clock.advance(Duration::hours(23));
+        assert_eq!(engine.process_heartbeat(tap(3)).await.unwrap(), 3);
+
+        // A week and a day without re-attesting: trust has decayed.
+        clock.advance(Duration::days(7));
+        assert!(matches!(engine.process_heartbeat(tap(4)).await, Err(EngineError::AttestationRequired)));
+    }
+
     #[tokio::test]
     async fn test_heartbeat_revoked_identity() {
         let storage = MockStorage::default();
         let nonce_storage = MockNonceStorage::default();
-        let config = EngineConfig { network: Network::Testnet, genesis_version: 1 };
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
         let engine = InvariantEngine::new(storage, nonce_storage, config);
 
         let id = Uuid::new_v4();
@@ -350,6 +354,7 @@ mod tests {
             status: IdentityStatus::Revoked, // Revoked
             username: None, is_genesis_eligible: true, fcm_token: None, streak: 0, 
             hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
             genesis_version: 1, network: Network::Testnet,
         };
         engine.get_storage().save_identity(&identity).await.unwrap();
@@ -359,11 +364,12 @@ mod tests {
             device_signature: vec![],
             nonce: vec![0x00],
             timestamp: Utc::now(),
+            attestation_chain: None,
         };
 
         let result = engine.process_heartbeat(hb).await;
         match result {
-            Err(EngineError::Storage(msg)) => assert_eq!(msg, "Identity is Revoked"),
+            Err(EngineError::IdentityRevoked(revoked)) => assert_eq!(revoked, id),
             _ => panic!("Expected Revoked Error, got {:?}", result),
         }
     }
@@ -372,16 +378,18 @@ mod tests {
     async fn test_heartbeat_trust_decay() {
         let storage = MockStorage::default();
         let nonce_storage = MockNonceStorage::default();
-        let config = EngineConfig { network: Network::Testnet, genesis_version: 1 };
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
         let engine = InvariantEngine::new(storage, nonce_storage, config);
 
         let id = Uuid::new_v4();
         let now = Utc::now();
+        let signing_key = SigningKey::random(&mut OsRng);
+        let public_key_der = signing_key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec();
 
         // 🛡️ Setup: Identity attested 8 days ago (> 7 Day TTL)
         let identity = Identity {
             id,
-            public_key: vec![],
+            public_key: public_key_der,
             continuity_score: 10,
             created_at: now - Duration::days(10),
             last_heartbeat: now - Duration::days(1),
@@ -389,18 +397,22 @@ mod tests {
             status: IdentityStatus::Active,
             username: None, is_genesis_eligible: true, fcm_token: None, streak: 5,
             hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
             genesis_version: 1, network: Network::Testnet,
         };
         engine.get_storage().save_identity(&identity).await.unwrap();
 
+        let nonce = vec![0x99]; // Fresh nonce
+        let signature: p256::ecdsa::Signature = signing_key.sign(&signing::heartbeat_payload(&id, &nonce, &now));
         let hb = Heartbeat {
             identity_id: id,
-            device_signature: vec![],
-            nonce: vec![0x99], // Fresh nonce
+            device_signature: signature.to_der().as_bytes().to_vec(),
+            nonce,
             timestamp: now,
+            attestation_chain: None,
         };
 
-        // Expect Rejection: AttestationRequired (before Crypto Check)
+        // Expect Rejection: AttestationRequired (the decay is recorded only for a signed heartbeat)
         match engine.process_heartbeat(hb).await {
             Err(EngineError::AttestationRequired) => (),
             res => panic!("Expected Trust Decay Error, got {:?}", res),
@@ -411,7 +423,7 @@ mod tests {
     async fn test_heartbeat_replay_protection() {
         let storage = MockStorage::default();
         let nonce_storage = MockNonceStorage::default();
-        let config = EngineConfig { network: Network::Testnet, genesis_version: 1 };
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
         let engine = InvariantEngine::new(storage, nonce_storage, config);
 
         let id = Uuid::new_v4();
@@ -427,6 +439,7 @@ mod tests {
             status: IdentityStatus::Active,
             username: None, is_genesis_eligible: true, fcm_token: None, streak: 0,
             hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
             genesis_version: 1, network: Network::Testnet,
         };
         engine.get_storage().save_identity(&identity).await.unwrap();
@@ -437,6 +450,7 @@ mod tests {
             device_signature: vec![],
             nonce: nonce.clone(),
             timestamp: now,
+            attestation_chain: None,
         };
 
         // 1. First attempt: Consumes nonce.
@@ -456,7 +470,7 @@ mod tests {
     async fn test_heartbeat_invalid_signature() {
         let storage = MockStorage::default();
         let nonce_storage = MockNonceStorage::default();
-        let config = EngineConfig { network: Network::Testnet, genesis_version: 1 };
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
         let engine = InvariantEngine::new(storage, nonce_storage, config);
 
         let id = Uuid::new_v4();
@@ -477,23 +491,244 @@ mod tests {
             status: IdentityStatus::Active,
             username: None, is_genesis_eligible: true, fcm_token: None, streak: 0,
             hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
             genesis_version: 1, network: Network::Testnet,
         };
         engine.get_storage().save_identity(&identity).await.unwrap();
 
         let hb_time = Utc::now();
         let nonce = vec![0x01];
-        let payload_str = format!("{}|{}|{}", id, hex::encode(&nonce), hb_time.to_rfc3339());
-        let signature: p256::ecdsa::Signature = wrong_key.sign(payload_str.as_bytes());
+        let payload = signing::heartbeat_payload(&id, &nonce, &hb_time);
+        let signature: p256::ecdsa::Signature = wrong_key.sign(&payload);
 
         let hb = Heartbeat {
             identity_id: id,
             device_signature: signature.to_der().as_bytes().to_vec(),
-            nonce: nonce,
+            nonce,
             timestamp: hb_time,
+            attestation_chain: None,
         };
 
         let result = engine.process_heartbeat(hb).await;
         assert!(matches!(result, Err(EngineError::InvalidSignature)));
     }
-}
\ No newline at end of file
+    #[tokio::test]
+    async fn test_signature_purpose_separation() {
+        let storage = MockStorage::default();
+        let nonce_storage = MockNonceStorage::default();
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
+        let engine = InvariantEngine::new(storage, nonce_storage, config);
+
+        let id = Uuid::new_v4();
+        let signing_key = SigningKey::random(&mut OsRng);
+        let public_key_der = signing_key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec();
+
+        let identity = Identity {
+            id,
+            public_key: public_key_der,
+            continuity_score: 1,
+            created_at: Utc::now(),
+            last_heartbeat: Utc::now() - Duration::hours(25),
+            last_attestation: Utc::now(),
+            status: IdentityStatus::Active,
+            username: None, is_genesis_eligible: true, fcm_token: None, streak: 0,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            genesis_version: 1, network: Network::Testnet,
+        };
+        engine.get_storage().save_identity(&identity).await.unwrap();
+
+        let nonce = vec![0xAB, 0xCD];
+        let payload_hash = [0x42u8; 32];
+
+        // 1. A genuine action approval verifies as an action...
+        let action_sig: p256::ecdsa::Signature = signing_key.sign(&signing::action_payload(&id, &nonce, &payload_hash));
+        let action_sig = action_sig.to_der().as_bytes().to_vec();
+        assert!(engine.validate_action_signature(id, &payload_hash, &nonce, &action_sig).await.unwrap());
+
+        // ...exactly once.
+        assert!(matches!(
+            engine.validate_action_signature(id, &payload_hash, &nonce, &action_sig).await,
+            Err(EngineError::ReplayDetected)
+        ));
+
+        // 2. A heartbeat signature over a fresh challenge is not an action approval.
+        let nonce = vec![0xAB, 0xCE];
+        let hb_time = Utc::now();
+        let hb_sig: p256::ecdsa::Signature = signing_key.sign(&signing::heartbeat_payload(&id, &nonce, &hb_time));
+        let hb_sig = hb_sig.to_der().as_bytes().to_vec();
+        assert!(!engine.validate_action_signature(id, &payload_hash, &nonce, &hb_sig).await.unwrap());
+
+        // 3. The legacy unversioned heartbeat format is no longer accepted.
+        let nonce = vec![0xAB, 0xCF];
+        let legacy = format!("{}|{}|{}", id, hex::encode(&nonce), hb_time.to_rfc3339());
+        let legacy_sig: p256::ecdsa::Signature = signing_key.sign(legacy.as_bytes());
+        let hb = Heartbeat {
+            identity_id: id,
+            device_signature: legacy_sig.to_der().as_bytes().to_vec(),
+            nonce,
+            timestamp: hb_time,
+            attestation_chain: None,
+        };
+        assert!(matches!(engine.process_heartbeat(hb).await, Err(EngineError::InvalidSignature)));
+    }
+    #[tokio::test]
+    async fn test_key_rotation_requires_old_key_signature() {
+        let storage = MockStorage::default();
+        let nonce_storage = MockNonceStorage::default();
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
+        let engine = InvariantEngine::new(storage, nonce_storage, config);
+
+        let id = Uuid::new_v4();
+        let old_key = SigningKey::random(&mut OsRng);
+        let old_pk = old_key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec();
+        let new_key = SigningKey::random(&mut OsRng);
+        let new_pk = new_key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec();
+
+        let identity = Identity {
+            id,
+            public_key: old_pk.clone(),
+            continuity_score: 42,
+            created_at: Utc::now() - Duration::days(30),
+            last_heartbeat: Utc::now() - Duration::hours(2),
+            last_attestation: Utc::now() - Duration::days(3),
+            status: IdentityStatus::Active,
+            username: None, is_genesis_eligible: true, fcm_token: None, streak: 3,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            genesis_version: 1, network: Network::Testnet,
+        };
+        engine.get_storage().save_identity(&identity).await.unwrap();
+
+        // 1. Statement signed by the NEW key (attacker holding only the new device) is rejected.
+        let nonce = vec![0x10, 0x20];
+        let forged: p256::ecdsa::Signature = new_key.sign(&signing::key_rotation_payload(&id, &new_pk, &nonce));
+        let request = KeyRotationRequest {
+            id,
+            new_public_key: new_pk.clone(),
+            attestation_chain: vec![],
+            nonce,
+            rotation_signature: forged.to_der().as_bytes().to_vec(),
+        };
+        match engine.process_key_rotation(request).await {
+            Err(EngineError::InvalidSignature) => (),
+            res => panic!("Expected InvalidSignature, got {:?}", res),
+        }
+
+        // 2. Valid old-key statement still needs a valid attestation of the new key.
+        let nonce = vec![0x30, 0x40];
+        let authorized: p256::ecdsa::Signature = old_key.sign(&signing::key_rotation_payload(&id, &new_pk, &nonce));
+        let request = KeyRotationRequest {
+            id,
+            new_public_key: new_pk,
+            attestation_chain: vec![],
+            nonce,
+            rotation_signature: authorized.to_der().as_bytes().to_vec(),
+        };
+        match engine.process_key_rotation(request).await {
+            Err(EngineError::InvalidAttestation(_)) => (),
+            res => panic!("Expected InvalidAttestation, got {:?}", res),
+        }
+
+        // 3. Nothing was swapped.
+        let stored = engine.get_storage().get_identity(&id).await.unwrap().unwrap();
+        assert_eq!(stored.public_key, old_pk);
+        assert_eq!(stored.continuity_score, 42);
+    }
+
+    #[tokio::test]
+    async fn test_key_rotation_rejects_registered_key() {
+        let storage = MockStorage::default();
+        let nonce_storage = MockNonceStorage::default();
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
+        let engine = InvariantEngine::new(storage, nonce_storage, config);
+
+        let old_key = SigningKey::random(&mut OsRng);
+        let old_pk = old_key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec();
+        let taken_pk = vec![0xAA, 0xBB, 0xCC];
+
+        for (id, pk) in [(Uuid::new_v4(), old_pk.clone()), (Uuid::new_v4(), taken_pk.clone())] {
+            let identity = Identity {
+                id,
+                public_key: pk,
+                continuity_score: 1,
+                created_at: Utc::now(),
+                last_heartbeat: Utc::now(),
+                last_attestation: Utc::now(),
+                status: IdentityStatus::Active,
+                username: None, is_genesis_eligible: true, fcm_token: None, streak: 0,
+                hardware_brand: None, hardware_device: None, hardware_product: None,
+                security_level: None, os_patch_level: None,
+                genesis_version: 1, network: Network::Testnet,
+            };
+            engine.get_storage().save_identity(&identity).await.unwrap();
+        }
+        let id = engine.get_storage().get_identity_by_public_key(&old_pk).await.unwrap().unwrap().id;
+
+        let nonce = vec![0x55];
+        let signature: p256::ecdsa::Signature = old_key.sign(&signing::key_rotation_payload(&id, &taken_pk, &nonce));
+        let request = KeyRotationRequest {
+            id,
+            new_public_key: taken_pk,
+            attestation_chain: vec![],
+            nonce,
+            rotation_signature: signature.to_der().as_bytes().to_vec(),
+        };
+
+        match engine.process_key_rotation(request).await {
+            Err(EngineError::AlreadyExists) => (),
+            res => panic!("Expected AlreadyExists, got {:?}", res),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retired_key_cannot_come_back() {
+        let storage = MockStorage::default();
+        let nonce_storage = MockNonceStorage::default();
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
+        let engine = InvariantEngine::new(storage, nonce_storage, config);
+
+        let old_key = SigningKey::random(&mut OsRng);
+        let old_pk = old_key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec();
+        let retired_pk = vec![0xDE, 0xAD];
+        engine.get_storage().retired_keys.write().await.push(retired_pk.clone());
+
+        let id = Uuid::new_v4();
+        let identity = Identity {
+            id,
+            public_key: old_pk,
+            continuity_score: 1,
+            created_at: Utc::now(),
+            last_heartbeat: Utc::now(),
+            last_attestation: Utc::now(),
+            status: IdentityStatus::Active,
+            username: None, is_genesis_eligible: true, fcm_token: None, streak: 0,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            genesis_version: 1, network: Network::Testnet,
+        };
+        engine.get_storage().save_identity(&identity).await.unwrap();
+
+        // Rotating back onto an archived key
+        let nonce = vec![0x56];
+        let signature: p256::ecdsa::Signature = old_key.sign(&signing::key_rotation_payload(&id, &retired_pk, &nonce));
+        let request = KeyRotationRequest {
+            id,
+            new_public_key: retired_pk.clone(),
+            attestation_chain: vec![],
+            nonce,
+            rotation_signature: signature.to_der().as_bytes().to_vec(),
+        };
+        match engine.process_key_rotation(request).await {
+            Err(EngineError::AlreadyExists) => (),
+            res => panic!("Expected AlreadyExists, got {:?}", res),
+        }
+
+        // Anchoring a fresh identity on it
+        let request = GenesisRequest { public_key: retired_pk, attestation_chain: vec![], nonce: vec![0x57] };
+        match engine.process_genesis(request).await {
+            Err(EngineError::AlreadyExists) => (),
+            res => panic!("Expected AlreadyExists, got {:?}", res),
+        }
+    }
+}
@@ -15,13 +15,15 @@
  * ARTIFACT: INVARIANT_AUDIT_REPORT.json
  */
 
-use invariant_engine::{attestation, InvariantEngine, IdentityStorage, EngineError, core::EngineConfig, crypto};
-use invariant_engine::ports::NonceStorage;
+mod common;
+
+use invariant_engine::{attestation, InvariantEngine, IdentityStorage, EngineError, core::EngineConfig, ProtocolParameters, crypto};
+use invariant_engine::clock::AdjustableClock;
+use invariant_engine::ports::Clock;
 use invariant_shared::{Identity, IdentityStatus, Heartbeat, Network};
-use async_trait::async_trait;
+use invariant_shared::signing;
 use chrono::{Utc, Duration, DateTime};
-use std::collections::{HashMap, HashSet};
-use tokio::sync::{RwLock, Barrier};
+use tokio::sync::Barrier;
 use uuid::Uuid;
 use std::fs::{self, File};
 use std::io::Write;
@@ -32,6 +34,7 @@ use rand::{Rng, SeedableRng, RngCore};
 use rand_chacha::ChaCha8Rng;
 use p256::ecdsa::{SigningKey, Signature, signature::Signer};
 use p256::pkcs8::{EncodePublicKey};
+use common::{MockStorage, MockNonceStorage};
 
 // ======================================================================================
 // 1. GLOBAL AUDIT STATE & REPORTING
@@ -122,60 +125,7 @@ fn save_failure_artifact(test_name: &str, description: &str, raw_data: &[u8], me
 // ======================================================================================
 // 2. MOCK INFRASTRUCTURE
 // ======================================================================================
-
-#[derive(Default, Clone)]
-struct MockStorage {
-    identities: Arc<RwLock<HashMap<Uuid, Identity>>>,
-    heartbeats: Arc<RwLock<Vec<Heartbeat>>>,
-}
-
-#[async_trait]
-impl IdentityStorage for MockStorage {
-    async fn get_identity(&self, id: &Uuid) -> Result<Option<Identity>, EngineError> {
-        Ok(self.identities.read().await.get(id).cloned())
-    }
-    async fn get_identity_by_public_key(&self, pk: &[u8]) -> Result<Option<Identity>, EngineError> {
-        let map = self.identities.read().await;
-        for identity in map.values() {
-            if identity.public_key == pk { return Ok(Some(identity.clone())); }
-        }
-        Ok(None)
-    }
-    async fn save_identity(&self, identity: &Identity) -> Result<(), EngineError> {
-        self.identities.write().await.insert(identity.id, identity.clone());
-        Ok(())
-    }
-    async fn log_heartbeat(&self, identity: &Identity, heartbeat: &Heartbeat) -> Result<u64, EngineError> {
-        let mut map = self.identities.write().await;
-        if let Some(id_ref) = map.get_mut(&identity.id) {
-            id_ref.continuity_score += 1;
-            id_ref.last_heartbeat = heartbeat.timestamp;
-            self.heartbeats.write().await.push(heartbeat.clone());
-            return Ok(id_ref.continuity_score);
-        }
-        Err(EngineError::IdentityNotFound(identity.id))
-    }
-    async fn run_reaper(&self) -> Result<u64, EngineError> { Ok(0) }
-    async fn set_username(&self, _: &Uuid, _: &str) -> Result<bool, EngineError> { Ok(true) }
-    async fn get_leaderboard(&self, _: i64) -> Result<Vec<Identity>, EngineError> { Ok(vec![]) }
-    async fn update_fcm_token(&self, _: &Uuid, _: &str) -> Result<(), EngineError> { Ok(()) }
-    async fn get_late_fcm_tokens(&self, _: i64) -> Result<Vec<String>, EngineError> { Ok(vec![]) }
-}
-
-#[derive(Default, Clone)]
-struct MockNonceStorage {
-    used_nonces: Arc<RwLock<HashSet<Vec<u8>>>>,
-}
-
-#[async_trait]
-impl NonceStorage for MockNonceStorage {
-    async fn consume_nonce(&self, nonce: &[u8], _ttl: u64) -> Result<bool, EngineError> {
-        let mut set = self.used_nonces.write().await;
-        if set.contains(nonce) { return Ok(false); }
-        set.insert(nonce.to_vec());
-        Ok(true)
-    }
-}
+// Shared with the other engine tests: see `common/mod.rs`.
 
 // ======================================================================================
 // 3. HELPERS (DER Construction)
@@ -420,38 +370,45 @@ async fn audit_asn1_deep_mutation() {
 
 #[tokio::test]
 async fn audit_trust_decay_boundaries() {
-    let storage = MockStorage::default();
-    let nonce_storage = MockNonceStorage::default();
-    let config = EngineConfig { network: Network::Testnet, genesis_version: 1 };
-    let engine = InvariantEngine::new(storage.clone(), nonce_storage.clone(), config);
+    let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
+    let attested_at = Utc::now();
+    let clock = AdjustableClock::new(attested_at);
+    let engine = InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config).with_clock(clock.clone());
     let id = Uuid::new_v4();
-    let now = Utc::now();
     let ttl = Duration::days(7);
-    
+    let signing_key = SigningKey::random(&mut ChaCha8Rng::seed_from_u64(0x7715));
+    let pk_der = signing_key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec();
+
+    // Attested once, then time moves forward (no identity mutation).
+    let identity = Identity {
+        id,
+        public_key: pk_der,
+        continuity_score: 10, streak: 5,
+        created_at: attested_at - Duration::days(400),
+        last_heartbeat: attested_at - Duration::days(1),
+        last_attestation: attested_at,
+        status: IdentityStatus::Active,
+        username: None, is_genesis_eligible: true, fcm_token: None,
+        hardware_brand: None, hardware_device: None, hardware_product: None,
+        security_level: None, os_patch_level: None,
+        genesis_version: 1, network: Network::Testnet,
+    };
+    engine.get_storage().save_identity(&identity).await.unwrap();
+
     let scenarios = vec![
         ("Valid (T-1m)", ttl - Duration::minutes(1), true),
         ("Expired (T+1m)", ttl + Duration::minutes(1), false),
     ];
 
     for (name, offset, should_pass) in scenarios {
-        let identity = Identity {
-            id,
-            public_key: vec![],
-            continuity_score: 10, streak: 5,
-            created_at: now - Duration::days(400),
-            last_heartbeat: now - Duration::hours(1),
-            last_attestation: now - offset, 
-            status: IdentityStatus::Active,
-            username: None, is_genesis_eligible: true, fcm_token: None,
-            hardware_brand: None, hardware_device: None, hardware_product: None,
-            genesis_version: 1, network: Network::Testnet,
-        };
-        engine.get_storage().save_identity(&identity).await.unwrap();
+        clock.set(attested_at + offset);
 
         let mut n = [0u8; 4]; rand::thread_rng().fill_bytes(&mut n);
         let nonce = n.to_vec();
-        let hb = Heartbeat { identity_id: id, timestamp: now, nonce, device_signature: vec![] };
+        let signature: Signature = signing_key.sign(&signing::heartbeat_payload(&id, &nonce, &clock.now()));
+        let hb = Heartbeat { identity_id: id, timestamp: clock.now(), nonce, device_signature: signature.to_der().as_bytes().to_vec(), attestation_chain: None };
 
+        // A signed heartbeat inside the TTL is accepted; past it, only a refresh will do.
         let res = engine.process_heartbeat(hb).await;
         match res {
             Err(EngineError::AttestationRequired) => {
@@ -460,13 +417,13 @@ async fn audit_trust_decay_boundaries() {
                     panic!("Premature expiration!");
                 }
             },
-            Err(EngineError::InvalidSignature) => {
+            Ok(_) => {
                 if !should_pass {
                     log_event("Logic", "Trust Decay", "FAIL", &format!("Scenario {} allowed expired identity", name));
                     panic!("Leaked expired identity!");
                 }
             },
-            _ => {}
+            other => panic!("Scenario {} hit an unexpected path: {:?}", name, other),
         }
     }
     log_event("Logic", "Trust Decay", "PASS", "Boundary conditions enforced correctly.");
@@ -477,9 +434,7 @@ async fn audit_concurrency_race() {
     let (_, _, concurrency) = get_config();
     println!(">>> 🏎️ Starting Race Condition Test ({} Threads)", concurrency);
 
-    let storage = MockStorage::default();
-    let nonce_storage = MockNonceStorage::default();
-    let config = EngineConfig { network: Network::Testnet, genesis_version: 1 };
+    let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
     
     let id = Uuid::new_v4();
     let mut rng = ChaCha8Rng::seed_from_u64(0x1234);
@@ -494,11 +449,11 @@ async fn audit_concurrency_race() {
         last_attestation: Utc::now(), status: IdentityStatus::Active,
         username: None, is_genesis_eligible: true, fcm_token: None,
         hardware_brand: None, hardware_device: None, hardware_product: None,
+        security_level: None, os_patch_level: None,
         genesis_version: 1, network: Network::Testnet,
     };
-    storage.save_identity(&identity).await.unwrap();
-
-    let engine = Arc::new(InvariantEngine::new(storage.clone(), nonce_storage.clone(), config));
+    let engine = Arc::new(InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config));
+    engine.get_storage().save_identity(&identity).await.unwrap();
     let barrier = Arc::new(Barrier::new(concurrency));
     let mut handles = vec![];
     let replay_nonce = vec![0xFF, 0xFF, 0xFF];
@@ -519,10 +474,10 @@ async fn audit_concurrency_race() {
                 n.to_vec() 
             };
             let ts = Utc::now();
-            let payload = format!("{}|{}|{}", my_id, hex::encode(&nonce), ts.to_rfc3339());
-            let signature: Signature = my_key.sign(payload.as_bytes());
+            let payload = signing::heartbeat_payload(&my_id, &nonce, &ts);
+            let signature: Signature = my_key.sign(&payload);
             let sig_bytes = signature.to_der().as_bytes().to_vec();
-            let hb = Heartbeat { identity_id: my_id, nonce, timestamp: ts, device_signature: sig_bytes };
+            let hb = Heartbeat { identity_id: my_id, nonce, timestamp: ts, device_signature: sig_bytes, attestation_chain: None };
             
             bar.wait().await;
             eng.process_heartbeat(hb).await
@@ -542,7 +497,7 @@ async fn audit_concurrency_race() {
     }
 
     println!("    Race Results: Success={}, ReplayBlocked={}", success_count, replay_blocked);
-    let final_id = storage.get_identity(&id).await.unwrap().unwrap();
+    let final_id = engine.get_storage().get_identity(&id).await.unwrap().unwrap();
     
     if final_id.continuity_score != success_count as u64 {
         log_event("Concurrency", "Atomic Increment", "FAIL", "DB Score mismatch");
@@ -647,10 +602,8 @@ fn regression_metadata_parsing() {
 
 #[tokio::test]
 async fn regression_heartbeat_invalid_signature() {
-    let storage = MockStorage::default();
-    let nonce_storage = MockNonceStorage::default();
-    let config = EngineConfig { network: Network::Testnet, genesis_version: 1 };
-    let engine = InvariantEngine::new(storage.clone(), nonce_storage.clone(), config);
+    let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
+    let engine = InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config);
 
     let id = Uuid::new_v4();
     let signing_key = SigningKey::random(&mut rand::thread_rng()); 
@@ -663,15 +616,16 @@ async fn regression_heartbeat_invalid_signature() {
         last_attestation: Utc::now(), status: IdentityStatus::Active,
         username: None, is_genesis_eligible: true, fcm_token: None,
         hardware_brand: None, hardware_device: None, hardware_product: None,
+        security_level: None, os_patch_level: None,
         genesis_version: 1, network: Network::Testnet,
     };
     engine.get_storage().save_identity(&identity).await.unwrap();
 
     let hb_time = Utc::now();
     let nonce = vec![0x01];
-    let payload = format!("{}|{}|{}", id, hex::encode(&nonce), hb_time.to_rfc3339());
-    let signature: Signature = wrong_key.sign(payload.as_bytes());
-    let hb = Heartbeat { identity_id: id, device_signature: signature.to_der().as_bytes().to_vec(), nonce, timestamp: hb_time };
+    let payload = signing::heartbeat_payload(&id, &nonce, &hb_time);
+    let signature: Signature = wrong_key.sign(&payload);
+    let hb = Heartbeat { identity_id: id, device_signature: signature.to_der().as_bytes().to_vec(), nonce, timestamp: hb_time, attestation_chain: None };
 
     let result = engine.process_heartbeat(hb).await;
     
@@ -0,0 +1,629 @@
+// crates/invariant_engine/tests/common/mod.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+//! In-memory storage shared by the engine's integration tests.
+//!
+//! `MockStorage` implements every storage port over plain collections, the way the
+//! Postgres adapter does (compare-and-set transitions, one "transaction" per port call).
+//! Tests seed or inspect state through the public fields. Behavior a test needs to change
+//! is a named field on the mock, never a private copy of it.
+
+#![allow(dead_code)]
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use invariant_engine::{
+    AttestationRefresh, KeyRotation, MigrationHandover, RecoveryCompletion, InvariantEngine, IdentityStorage, RecoveryStorage, PairwiseStorage, PrivacyPassStorage, TransparencyStorage, LogRecord,
+    LifecycleStorage, RevocationStorage, SwitchStorage, StreakStorage, TrustStorage, SybilStorage, EligibilityStorage,
+    DeviceStorage, MigrationStorage, UpgradeStorage, ReactivationStorage, EngineError, ProtocolParameters,
+};
+use invariant_engine::ports::NonceStorage;
+use invariant_engine::sybil::{AttestationFingerprint, IdentityFingerprint};
+use invariant_engine::trust::RecentActivity;
+use invariant_shared::{
+    Identity, IdentityStatus, IdentityTransition, TransitionReason, Heartbeat, Recovery, RecoveryStatus, RecoveryEvent, LogEntry, LogEventKind,
+    Revocation, RevocationReason, RevocationNotice, DeadMansSwitch, SwitchStatus, StreakState, SybilCluster, SybilConfidence,
+    EligibilityReport, Device, DeviceStatus, IdentityMigration, GenesisUpgrade, GenesisVersionStats, Reactivation,
+};
+
+pub type Engine = InvariantEngine<MockStorage, MockNonceStorage>;
+
+#[derive(Default)]
+pub struct MockStorage {
+    pub identities: RwLock<HashMap<Uuid, Identity>>,
+    pub heartbeats: RwLock<Vec<Heartbeat>>,
+    pub log: RwLock<Vec<LogEntry>>,
+    pub transitions: RwLock<Vec<IdentityTransition>>,
+    pub streaks: RwLock<HashMap<Uuid, StreakState>>,
+    pub devices: RwLock<Vec<Device>>,
+    /// Key history: every public key archived by rotation, recovery or device removal.
+    pub retired_keys: RwLock<Vec<Vec<u8>>>,
+    pub fingerprints: RwLock<Vec<IdentityFingerprint>>,
+    pub clusters: RwLock<Vec<SybilCluster>>,
+    pub reports: RwLock<HashMap<Uuid, EligibilityReport>>,
+    pub upgrades: RwLock<Vec<GenesisUpgrade>>,
+    pub reactivations: RwLock<Vec<Reactivation>>,
+    pub revocations: RwLock<Vec<Revocation>>,
+    pub migrations: RwLock<Vec<IdentityMigration>>,
+    /// (partner_id, pairwise_id, identity_id)
+    pub links: RwLock<Vec<(String, Uuid, Uuid)>>,
+    pub issuances: RwLock<HashMap<Uuid, DateTime<Utc>>>,
+    pub activity: RwLock<HashMap<Uuid, RecentActivity>>,
+    pub switches: RwLock<HashMap<Uuid, DeadMansSwitch>>,
+    pub guardians: RwLock<HashMap<Uuid, (Vec<Uuid>, u8)>>,
+    pub recoveries: RwLock<HashMap<Uuid, Recovery>>,
+    /// (recovery_id, guardian_id) → approve; the first vote sticks.
+    pub votes: RwLock<HashMap<(Uuid, Uuid), bool>>,
+    pub recovery_events: RwLock<Vec<(Uuid, RecoveryEvent)>>,
+}
+
+impl MockStorage {
+    /// The log half of a port call that appends in its transaction.
+    async fn append_log(&self, records: &[LogRecord]) -> Result<(), EngineError> {
+        for record in records {
+            self.append_log_entry(record.kind, &record.identity_id, &record.public_key_hash).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl IdentityStorage for MockStorage {
+    async fn get_identity(&self, id: &Uuid) -> Result<Option<Identity>, EngineError> {
+        Ok(self.identities.read().await.get(id).cloned())
+    }
+    async fn get_identity_by_public_key(&self, pk: &[u8]) -> Result<Option<Identity>, EngineError> {
+        let identities = self.identities.read().await;
+        let by_device = self.devices.read().await.iter()
+            .find(|d| d.status == DeviceStatus::Active && d.public_key == pk)
+            .and_then(|d| identities.get(&d.identity_id).cloned());
+        Ok(by_device.or_else(|| identities.values().find(|i| i.public_key == pk).cloned()))
+    }
+    async fn save_identity(&self, identity: &Identity) -> Result<(), EngineError> {
+        self.identities.write().await.insert(identity.id, identity.clone());
+        Ok(())
+    }
+    async fn record_genesis(&self, identity: &Identity, device: &Device, fingerprint: &AttestationFingerprint, log: &LogRecord) -> Result<(), EngineError> {
+        self.save_identity(identity).await?;
+        self.devices.write().await.push(device.clone());
+        self.record_attestation_fingerprint(&identity.id, fingerprint).await?;
+        self.append_log(std::slice::from_ref(log)).await
+    }
+    async fn log_heartbeat(&self, identity: &Identity, heartbeat: &Heartbeat, at: DateTime<Utc>) -> Result<u64, EngineError> {
+        let mut map = self.identities.write().await;
+        let id_ref = map.get_mut(&identity.id).ok_or(EngineError::IdentityNotFound(identity.id))?;
+        id_ref.continuity_score += 1;
+        id_ref.last_heartbeat = at;
+        self.heartbeats.write().await.push(heartbeat.clone());
+        Ok(id_ref.continuity_score)
+    }
+    async fn rotate_public_key(&self, write: &KeyRotation<'_>) -> Result<(), EngineError> {
+        let mut identity = write.identity.clone();
+        match self.identities.read().await.get(&identity.id) {
+            Some(stored) if stored.public_key == write.previous_public_key => {}
+            Some(_) => return Err(EngineError::Storage("Key rotation conflict".into())),
+            None => return Err(EngineError::IdentityNotFound(identity.id)),
+        }
+        if let Some(transition) = &write.transition {
+            if !self.record_transition(transition).await? {
+                return Err(EngineError::Storage("Concurrent status change".into()));
+            }
+            identity.status = transition.to.clone();
+        }
+        if let Some((reactivation, streak)) = &write.reactivation {
+            self.record_reactivation(reactivation, streak).await?;
+            identity.continuity_score = reactivation.score_after;
+            identity.streak = streak.streak;
+        }
+        self.save_identity(&identity).await?;
+        self.retired_keys.write().await.push(write.previous_public_key.to_vec());
+        self.record_attestation_fingerprint(&identity.id, &write.fingerprint).await?;
+        self.append_log(std::slice::from_ref(&write.log)).await
+    }
+    async fn is_key_retired(&self, public_key: &[u8]) -> Result<bool, EngineError> {
+        Ok(self.retired_keys.read().await.iter().any(|k| k == public_key))
+    }
+    async fn run_reaper(&self, now: DateTime<Utc>) -> Result<u64, EngineError> {
+        let params = ProtocolParameters::default();
+        let cutoff = now - Duration::days(params.reaper_window_days);
+        let mut reaped = 0;
+        for identity in self.identities.write().await.values_mut() {
+            if !matches!(identity.status, IdentityStatus::Active | IdentityStatus::Stale) || identity.last_heartbeat >= cutoff {
+                continue;
+            }
+            self.transitions.write().await.push(IdentityTransition {
+                identity_id: identity.id,
+                from: identity.status.clone(),
+                to: IdentityStatus::Dormant,
+                reason: TransitionReason::Inactivity,
+                actor_id: None,
+                created_at: now,
+            });
+            identity.status = IdentityStatus::Dormant;
+            if !params.dormancy_keeps_streak {
+                identity.streak = 0;
+            }
+            reaped += 1;
+        }
+        Ok(reaped)
+    }
+    async fn set_username(&self, id: &Uuid, username: &str) -> Result<bool, EngineError> {
+        let mut map = self.identities.write().await;
+        if map.values().any(|i| i.username.as_deref() == Some(username)) {
+            return Ok(false);
+        }
+        match map.get_mut(id) {
+            Some(id_ref) if id_ref.username.is_none() => {
+                id_ref.username = Some(username.to_string());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+    async fn get_leaderboard(&self, limit: i64) -> Result<Vec<Identity>, EngineError> {
+        let mut list: Vec<Identity> = self.identities.read().await.values().cloned().collect();
+        list.sort_by_key(|i| std::cmp::Reverse(i.continuity_score));
+        Ok(list.into_iter().take(limit as usize).collect())
+    }
+    async fn update_fcm_token(&self, id: &Uuid, token: &str) -> Result<(), EngineError> {
+        if let Some(id_ref) = self.identities.write().await.get_mut(id) {
+            id_ref.fcm_token = Some(token.to_string());
+        }
+        Ok(())
+    }
+    async fn get_late_fcm_tokens(&self, _: i64, _: DateTime<Utc>) -> Result<Vec<String>, EngineError> { Ok(vec![]) }
+}
+
+#[async_trait]
+impl RecoveryStorage for MockStorage {
+    async fn set_guardians(&self, identity_id: &Uuid, guardian_ids: &[Uuid], threshold: u8) -> Result<(), EngineError> {
+        self.guardians.write().await.insert(*identity_id, (guardian_ids.to_vec(), threshold));
+        Ok(())
+    }
+    async fn get_guardians(&self, identity_id: &Uuid) -> Result<Option<(Vec<Uuid>, u8)>, EngineError> {
+        Ok(self.guardians.read().await.get(identity_id).cloned())
+    }
+    async fn create_recovery(&self, recovery: &Recovery) -> Result<(), EngineError> {
+        let mut map = self.recoveries.write().await;
+        for r in map.values_mut() {
+            if r.identity_id == recovery.identity_id && r.status == RecoveryStatus::Pending && r.expires_at <= recovery.initiated_at {
+                r.status = RecoveryStatus::Expired;
+            }
+        }
+        map.insert(recovery.id, recovery.clone());
+        Ok(())
+    }
+    async fn get_recovery(&self, recovery_id: &Uuid) -> Result<Option<Recovery>, EngineError> {
+        Ok(self.recoveries.read().await.get(recovery_id).cloned())
+    }
+    async fn get_open_recovery(&self, identity_id: &Uuid, now: DateTime<Utc>) -> Result<Option<Recovery>, EngineError> {
+        Ok(self.recoveries.read().await.values()
+            .find(|r| &r.identity_id == identity_id && match r.status {
+                RecoveryStatus::Approved => true,
+                RecoveryStatus::Pending => r.expires_at > now,
+                _ => false,
+            })
+            .cloned())
+    }
+    async fn add_recovery_approval(&self, recovery_id: &Uuid, guardian_id: &Uuid) -> Result<u32, EngineError> {
+        let mut votes = self.votes.write().await;
+        votes.entry((*recovery_id, *guardian_id)).or_insert(true);
+        Ok(votes.iter().filter(|((r, _), approve)| r == recovery_id && **approve).count() as u32)
+    }
+    async fn add_recovery_rejection(&self, recovery_id: &Uuid, guardian_id: &Uuid) -> Result<u32, EngineError> {
+        let mut votes = self.votes.write().await;
+        votes.entry((*recovery_id, *guardian_id)).or_insert(false);
+        Ok(votes.iter().filter(|((r, _), approve)| r == recovery_id && !**approve).count() as u32)
+    }
+    async fn update_recovery_status(&self, recovery: &Recovery) -> Result<(), EngineError> {
+        self.recoveries.write().await.insert(recovery.id, recovery.clone());
+        Ok(())
+    }
+    async fn complete_recovery(&self, write: &RecoveryCompletion<'_>) -> Result<(), EngineError> {
+        let recovery = write.recovery;
+        match self.identities.read().await.get(&recovery.identity_id) {
+            Some(stored) if stored.public_key == write.previous_public_key => {}
+            Some(_) => return Err(EngineError::Storage("Key rotation conflict".into())),
+            None => return Err(EngineError::IdentityNotFound(recovery.identity_id)),
+        }
+        if let Some(transition) = &write.transition {
+            if !self.record_transition(transition).await? {
+                return Err(EngineError::Storage("Concurrent status change".into()));
+            }
+        }
+        if let Some((reactivation, streak)) = &write.reactivation {
+            self.record_reactivation(reactivation, streak).await?;
+        }
+        if let Some(identity) = self.identities.write().await.get_mut(&recovery.identity_id) {
+            identity.public_key = recovery.new_public_key.clone();
+            identity.last_attestation = write.at;
+        }
+        self.retired_keys.write().await.push(write.previous_public_key.to_vec());
+        let mut completed = recovery.clone();
+        completed.status = RecoveryStatus::Completed;
+        self.recoveries.write().await.insert(recovery.id, completed);
+        self.log_recovery_event(&recovery.id, &recovery.identity_id, "completed", None).await?;
+        self.append_log(std::slice::from_ref(&write.log)).await
+    }
+    async fn log_recovery_event(&self, recovery_id: &Uuid, _: &Uuid, event: &str, actor_id: Option<&Uuid>) -> Result<(), EngineError> {
+        self.recovery_events.write().await.push((*recovery_id, RecoveryEvent {
+            event: event.to_string(),
+            actor_id: actor_id.copied(),
+            created_at: Utc::now(),
+        }));
+        Ok(())
+    }
+    async fn get_recovery_events(&self, recovery_id: &Uuid) -> Result<Vec<RecoveryEvent>, EngineError> {
+        Ok(self.recovery_events.read().await.iter().filter(|(r, _)| r == recovery_id).map(|(_, e)| e.clone()).collect())
+    }
+}
+
+#[async_trait]
+impl PairwiseStorage for MockStorage {
+    async fn get_pairwise_id(&self, partner_id: &str, identity_id: &Uuid) -> Result<Option<Uuid>, EngineError> {
+        Ok(self.links.read().await.iter().find(|(p, _, i)| p == partner_id && i == identity_id).map(|(_, s, _)| *s))
+    }
+    async fn link_pairwise(&self, partner_id: &str, pairwise_id: &Uuid, identity_id: &Uuid) -> Result<(), EngineError> {
+        let mut links = self.links.write().await;
+        if !links.iter().any(|(p, _, i)| p == partner_id && i == identity_id) {
+            links.push((partner_id.to_string(), *pairwise_id, *identity_id));
+        }
+        Ok(())
+    }
+    async fn resolve_pairwise(&self, partner_id: &str, pairwise_id: &Uuid) -> Result<Option<Uuid>, EngineError> {
+        Ok(self.links.read().await.iter().find(|(p, s, _)| p == partner_id && s == pairwise_id).map(|(_, _, i)| *i))
+    }
+}
+
+#[async_trait]
+impl PrivacyPassStorage for MockStorage {
+    async fn claim_token_issuance(&self, identity_id: &Uuid, _: u32, min_interval: Duration, now: DateTime<Utc>) -> Result<bool, EngineError> {
+        let mut ledger = self.issuances.write().await;
+        if ledger.get(identity_id).is_some_and(|last| now - *last < min_interval) {
+            return Ok(false);
+        }
+        ledger.insert(*identity_id, now);
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl TransparencyStorage for MockStorage {
+    async fn append_log_entry(&self, kind: LogEventKind, identity_id: &Uuid, public_key_hash: &[u8]) -> Result<LogEntry, EngineError> {
+        let mut log = self.log.write().await;
+        let entry = LogEntry {
+            index: log.len() as u64,
+            kind,
+            identity_id: *identity_id,
+            public_key_hash: public_key_hash.to_vec(),
+            logged_at: Utc::now(),
+        };
+        log.push(entry.clone());
+        Ok(entry)
+    }
+    async fn get_log_entries(&self, start: u64, end: u64) -> Result<Vec<LogEntry>, EngineError> {
+        Ok(self.log.read().await.iter().filter(|e| e.index >= start && e.index < end).cloned().collect())
+    }
+    async fn get_log_size(&self) -> Result<u64, EngineError> {
+        Ok(self.log.read().await.len() as u64)
+    }
+}
+
+#[async_trait]
+impl LifecycleStorage for MockStorage {
+    async fn record_transition(&self, transition: &IdentityTransition) -> Result<bool, EngineError> {
+        let mut map = self.identities.write().await;
+        match map.get_mut(&transition.identity_id) {
+            Some(id_ref) if id_ref.status == transition.from => {
+                id_ref.status = transition.to.clone();
+                self.transitions.write().await.push(transition.clone());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+    async fn get_transitions(&self, identity_id: &Uuid) -> Result<Vec<IdentityTransition>, EngineError> {
+        Ok(self.transitions.read().await.iter().filter(|t| t.identity_id == *identity_id).cloned().collect())
+    }
+}
+
+#[async_trait]
+impl RevocationStorage for MockStorage {
+    async fn record_revocation(&self, transition: &IdentityTransition, reason: RevocationReason, log: &LogRecord) -> Result<Revocation, EngineError> {
+        if !self.record_transition(transition).await? {
+            return Err(EngineError::Storage("Concurrent status change".into()));
+        }
+        let mut revocations = self.revocations.write().await;
+        let revocation = Revocation {
+            sequence: revocations.len() as u64 + 1,
+            identity_id: transition.identity_id,
+            reason,
+            revoked_by: transition.actor_id,
+            revoked_at: transition.created_at,
+        };
+        revocations.push(revocation.clone());
+        self.append_log(std::slice::from_ref(log)).await?;
+        Ok(revocation)
+    }
+    async fn get_partner_revocations(&self, partner_id: &str, after_sequence: u64, limit: u32) -> Result<Vec<RevocationNotice>, EngineError> {
+        let links = self.links.read().await;
+        Ok(self.revocations.read().await.iter()
+            .filter(|r| r.sequence > after_sequence)
+            .filter_map(|r| links.iter().find(|(p, _, i)| p == partner_id && *i == r.identity_id).map(|(_, pairwise, _)| RevocationNotice {
+                sequence: r.sequence,
+                subject_id: *pairwise,
+                reason: r.reason,
+                revoked_at: r.revoked_at,
+            }))
+            .take(limit as usize)
+            .collect())
+    }
+}
+
+#[async_trait]
+impl SwitchStorage for MockStorage {
+    /// Every identity has a switch, inactive until armed (like the `identities` columns).
+    async fn get_switch(&self, identity_id: &Uuid) -> Result<Option<DeadMansSwitch>, EngineError> {
+        if let Some(switch) = self.switches.read().await.get(identity_id) {
+            return Ok(Some(switch.clone()));
+        }
+        Ok(self.identities.read().await.contains_key(identity_id).then_some(DeadMansSwitch {
+            identity_id: *identity_id,
+            status: SwitchStatus::Inactive,
+            threshold_days: 365,
+            beneficiary_id: None,
+            pending_since: None,
+            triggered_at: None,
+        }))
+    }
+    async fn save_switch(&self, switch: &DeadMansSwitch) -> Result<(), EngineError> {
+        self.switches.write().await.insert(switch.identity_id, switch.clone());
+        Ok(())
+    }
+    async fn get_due_switches(&self, now: DateTime<Utc>) -> Result<Vec<DeadMansSwitch>, EngineError> {
+        let identities = self.identities.read().await;
+        Ok(self.switches.read().await.values()
+            .filter(|s| match s.status {
+                SwitchStatus::Armed => identities.get(&s.identity_id)
+                    .is_some_and(|i| i.last_heartbeat <= now - Duration::days(s.threshold_days as i64)),
+                SwitchStatus::Pending => true,
+                _ => false,
+            })
+            .cloned()
+            .collect())
+    }
+    async fn transition_switch(&self, switch: &DeadMansSwitch, from: SwitchStatus) -> Result<bool, EngineError> {
+        let stored = self.get_switch(&switch.identity_id).await?;
+        if stored.is_none_or(|s| s.status != from) {
+            return Ok(false);
+        }
+        self.save_switch(switch).await?;
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl StreakStorage for MockStorage {
+    async fn get_streak(&self, identity_id: &Uuid) -> Result<Option<StreakState>, EngineError> {
+        if !self.identities.read().await.contains_key(identity_id) { return Ok(None); }
+        Ok(Some(self.streaks.read().await.get(identity_id).cloned().unwrap_or_default()))
+    }
+    async fn save_streak(&self, identity_id: &Uuid, state: &StreakState) -> Result<(), EngineError> {
+        self.streaks.write().await.insert(*identity_id, state.clone());
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TrustStorage for MockStorage {
+    async fn get_recent_activity(&self, identity_id: &Uuid, _: DateTime<Utc>) -> Result<RecentActivity, EngineError> {
+        Ok(self.activity.read().await.get(identity_id).copied().unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl SybilStorage for MockStorage {
+    async fn record_attestation_fingerprint(&self, _: &Uuid, _: &AttestationFingerprint) -> Result<(), EngineError> { Ok(()) }
+    async fn get_fingerprints(&self) -> Result<Vec<IdentityFingerprint>, EngineError> {
+        Ok(self.fingerprints.read().await.clone())
+    }
+    async fn replace_sybil_clusters(&self, clusters: &[SybilCluster]) -> Result<(), EngineError> {
+        *self.clusters.write().await = clusters.to_vec();
+        Ok(())
+    }
+    async fn get_sybil_clusters(&self, min_confidence: SybilConfidence, limit: u32) -> Result<Vec<SybilCluster>, EngineError> {
+        Ok(self.clusters.read().await.iter().filter(|c| c.confidence >= min_confidence).take(limit as usize).cloned().collect())
+    }
+    async fn get_sybil_clusters_for(&self, identity_id: &Uuid) -> Result<Vec<SybilCluster>, EngineError> {
+        Ok(self.clusters.read().await.iter().filter(|c| c.members.contains(identity_id)).cloned().collect())
+    }
+}
+
+#[async_trait]
+impl DeviceStorage for MockStorage {
+    async fn get_devices(&self, identity_id: &Uuid) -> Result<Vec<Device>, EngineError> {
+        Ok(self.devices.read().await.iter().filter(|d| d.identity_id == *identity_id).cloned().collect())
+    }
+    async fn add_device(&self, device: &Device, max_active: u32, log: &LogRecord) -> Result<bool, EngineError> {
+        let mut devices = self.devices.write().await;
+        let active = devices.iter().filter(|d| d.identity_id == device.identity_id && d.status == DeviceStatus::Active).count();
+        if active as u32 >= max_active { return Ok(false); }
+        devices.push(device.clone());
+        self.append_log(std::slice::from_ref(log)).await?;
+        Ok(true)
+    }
+    async fn update_device_attestation(&self, device: &Device) -> Result<(), EngineError> {
+        if let Some(d) = self.devices.write().await.iter_mut().find(|d| d.id == device.id) {
+            *d = device.clone();
+        }
+        Ok(())
+    }
+    async fn record_device_heartbeat(&self, device_id: &Uuid, at: DateTime<Utc>) -> Result<(), EngineError> {
+        if let Some(d) = self.devices.write().await.iter_mut().find(|d| d.id == *device_id) {
+            d.last_heartbeat = Some(at);
+        }
+        Ok(())
+    }
+    async fn record_attestation_refresh(&self, write: &AttestationRefresh<'_>) -> Result<u64, EngineError> {
+        let mut identity = write.identity.clone();
+        if let Some(transition) = &write.transition {
+            if !self.record_transition(transition).await? {
+                return Err(EngineError::Storage("Concurrent status change".into()));
+            }
+            identity.status = transition.to.clone();
+        }
+        if let Some((reactivation, streak)) = &write.reactivation {
+            self.record_reactivation(reactivation, streak).await?;
+            identity.continuity_score = reactivation.score_after;
+            identity.streak = streak.streak;
+        }
+        if let Some(device) = write.device {
+            self.update_device_attestation(device).await?;
+            if write.heartbeat.is_some() {
+                self.record_device_heartbeat(&device.id, write.at).await?;
+            }
+        }
+        if let Some(fingerprint) = &write.fingerprint {
+            self.record_attestation_fingerprint(&identity.id, fingerprint).await?;
+        }
+        self.save_identity(&identity).await?;
+        self.append_log(std::slice::from_ref(&write.log)).await?;
+        match write.heartbeat {
+            Some(heartbeat) => self.log_heartbeat(&identity, heartbeat, write.at).await,
+            None => Ok(identity.continuity_score),
+        }
+    }
+    async fn revoke_device(&self, device: &Device, promote: Option<&Device>, log: &[LogRecord]) -> Result<(), EngineError> {
+        if let Some(d) = self.devices.write().await.iter_mut().find(|d| d.id == device.id) {
+            *d = device.clone();
+        }
+        self.retired_keys.write().await.push(device.public_key.clone());
+        if let Some(successor) = promote {
+            if let Some(identity) = self.identities.write().await.get_mut(&device.identity_id) {
+                identity.public_key = successor.public_key.clone();
+            }
+        }
+        self.append_log(log).await
+    }
+}
+
+#[async_trait]
+impl MigrationStorage for MockStorage {
+    async fn record_migration(&self, write: &MigrationHandover<'_>) -> Result<bool, EngineError> {
+        let mut migrations = self.migrations.write().await;
+        if migrations.iter().any(|m| m.testnet_id == write.migration.testnet_id) { return Ok(false); }
+        if !self.record_transition(&write.revocation).await? {
+            return Err(EngineError::Storage("Concurrent status change".into()));
+        }
+        migrations.push(write.migration.clone());
+        self.save_identity(write.identity).await?;
+        self.devices.write().await.push(write.device.clone());
+        self.save_streak(&write.identity.id, write.streak).await?;
+        self.record_attestation_fingerprint(&write.identity.id, &write.fingerprint).await?;
+        let mut revocations = self.revocations.write().await;
+        let revocation = Revocation {
+            sequence: revocations.len() as u64 + 1,
+            identity_id: write.revocation.identity_id,
+            reason: RevocationReason::Migrated,
+            revoked_by: write.revocation.actor_id,
+            revoked_at: write.revocation.created_at,
+        };
+        revocations.push(revocation);
+        self.append_log(&write.log).await
+            .map(|_| true)
+    }
+    async fn get_migration(&self, identity_id: &Uuid) -> Result<Option<IdentityMigration>, EngineError> {
+        Ok(self.migrations.read().await.iter().find(|m| m.testnet_id == *identity_id || m.mainnet_id == *identity_id).cloned())
+    }
+}
+
+#[async_trait]
+impl UpgradeStorage for MockStorage {
+    async fn record_genesis_upgrade(&self, upgrade: &GenesisUpgrade) -> Result<bool, EngineError> {
+        let mut map = self.identities.write().await;
+        match map.get_mut(&upgrade.identity_id) {
+            Some(id_ref) if id_ref.genesis_version == upgrade.from_version => {
+                id_ref.genesis_version = upgrade.to_version;
+                self.upgrades.write().await.push(upgrade.clone());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+    async fn get_genesis_versions(&self) -> Result<Vec<GenesisVersionStats>, EngineError> {
+        let upgrades = self.upgrades.read().await;
+        let mut versions: Vec<GenesisVersionStats> = vec![];
+        for identity in self.identities.read().await.values().filter(|i| i.status != IdentityStatus::Revoked) {
+            let upgraded = upgrades.iter().any(|u| u.identity_id == identity.id && u.to_version == identity.genesis_version);
+            match versions.iter_mut().find(|v| v.genesis_version == identity.genesis_version) {
+                Some(stats) => {
+                    stats.identities += 1;
+                    stats.upgraded += upgraded as u64;
+                }
+                None => versions.push(GenesisVersionStats { genesis_version: identity.genesis_version, identities: 1, upgraded: upgraded as u64 }),
+            }
+        }
+        versions.sort_by_key(|v| v.genesis_version);
+        Ok(versions)
+    }
+}
+
+#[async_trait]
+impl ReactivationStorage for MockStorage {
+    async fn record_reactivation(&self, reactivation: &Reactivation, streak: &StreakState) -> Result<(), EngineError> {
+        if let Some(identity) = self.identities.write().await.get_mut(&reactivation.identity_id) {
+            identity.continuity_score = reactivation.score_after;
+            identity.streak = streak.streak;
+        }
+        self.save_streak(&reactivation.identity_id, streak).await?;
+        self.reactivations.write().await.push(reactivation.clone());
+        Ok(())
+    }
+    async fn get_reactivations(&self, identity_id: &Uuid) -> Result<Vec<Reactivation>, EngineError> {
+        Ok(self.reactivations.read().await.iter().rev().filter(|r| r.identity_id == *identity_id).cloned().collect())
+    }
+}
+
+#[async_trait]
+impl EligibilityStorage for MockStorage {
+    async fn save_eligibility(&self, id: &Uuid, report: &EligibilityReport) -> Result<(), EngineError> {
+        if let Some(identity) = self.identities.write().await.get_mut(id) {
+            identity.is_genesis_eligible = report.eligible;
+        }
+        self.reports.write().await.insert(*id, report.clone());
+        Ok(())
+    }
+    async fn get_eligibility(&self, id: &Uuid) -> Result<Option<EligibilityReport>, EngineError> {
+        Ok(self.reports.read().await.get(id).cloned())
+    }
+    async fn get_eligibility_due(&self, before: DateTime<Utc>, limit: u32) -> Result<Vec<Uuid>, EngineError> {
+        let reports = self.reports.read().await;
+        let mut due: Vec<(Option<DateTime<Utc>>, Uuid)> = self.identities.read().await.values()
+            .filter(|i| match reports.get(&i.id) {
+                None => true,
+                Some(r) => (r.evaluated_at < before && i.status != IdentityStatus::Revoked) || (r.eligible && i.status != IdentityStatus::Active),
+            })
+            .map(|i| (reports.get(&i.id).map(|r| r.evaluated_at), i.id))
+            .collect();
+        due.sort();
+        Ok(due.into_iter().take(limit as usize).map(|(_, id)| id).collect())
+    }
+}
+
+#[derive(Default)]
+pub struct MockNonceStorage {
+    pub used_nonces: RwLock<HashSet<Vec<u8>>>,
+}
+
+#[async_trait]
+impl NonceStorage for MockNonceStorage {
+    async fn consume_nonce(&self, nonce: &[u8], _ttl: u64) -> Result<bool, EngineError> {
+        Ok(self.used_nonces.write().await.insert(nonce.to_vec()))
+    }
+}
@@ -0,0 +1,216 @@
+// crates/invariant_engine/tests/device_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use uuid::Uuid;
+    use sha2::{Digest, Sha256};
+    use p256::ecdsa::{SigningKey, signature::Signer};
+    use p256::pkcs8::EncodePublicKey;
+    use rand_core::OsRng;
+
+    use invariant_engine::{
+        InvariantEngine, IdentityStorage,
+        EngineError, core::EngineConfig, ProtocolParameters,
+    };
+    use invariant_engine::clock::AdjustableClock;
+    use invariant_shared::{
+        Identity, IdentityStatus, Heartbeat, Network, LogEventKind,
+        Device, DeviceStatus, DeviceEnrollmentRequest, DeviceRevocationRequest,
+    };
+    use invariant_shared::signing;
+    use crate::common::{Engine, MockStorage, MockNonceStorage};
+
+    fn new_engine(clock: &AdjustableClock) -> Engine {
+        let config = EngineConfig { network: Network::Mainnet, genesis_version: 1, params: ProtocolParameters::default() };
+        InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config).with_clock(clock.clone())
+    }
+
+    fn new_key() -> (SigningKey, Vec<u8>) {
+        let key = SigningKey::random(&mut OsRng);
+        let public_key = key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec();
+        (key, public_key)
+    }
+
+    fn sign(key: &SigningKey, payload: &[u8]) -> Vec<u8> {
+        let signature: p256::ecdsa::Signature = key.sign(payload);
+        signature.to_der().as_bytes().to_vec()
+    }
+
+    /// Stores a device row attested `attested_days_ago`.
+    async fn add_device(engine: &Engine, identity_id: Uuid, public_key: &[u8], attested_days_ago: i64) -> Uuid {
+        let now = engine.now();
+        let device = Device {
+            id: Uuid::new_v4(), identity_id, public_key: public_key.to_vec(), label: None,
+            status: DeviceStatus::Active, primary: false,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            added_at: now - Duration::days(attested_days_ago),
+            last_attestation: now - Duration::days(attested_days_ago),
+            last_heartbeat: None, revoked_at: None,
+        };
+        engine.get_storage().devices.write().await.push(device.clone());
+        device.id
+    }
+
+    /// An active identity whose primary device is `public_key`.
+    async fn mint(engine: &Engine, public_key: &[u8]) -> Uuid {
+        let now = engine.now();
+        let identity = Identity {
+            id: Uuid::new_v4(),
+            public_key: public_key.to_vec(),
+            continuity_score: 0, streak: 0,
+            created_at: now, last_heartbeat: now, last_attestation: now,
+            status: IdentityStatus::Active,
+            username: None, is_genesis_eligible: false, fcm_token: None,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            genesis_version: 1, network: Network::Mainnet,
+        };
+        engine.get_storage().save_identity(&identity).await.unwrap();
+        add_device(engine, identity.id, public_key, 0).await;
+        identity.id
+    }
+
+    async fn tap(engine: &Engine, key: &SigningKey, id: Uuid, nonce: u8) -> Result<u64, EngineError> {
+        let timestamp = engine.now();
+        let device_signature = sign(key, &signing::heartbeat_payload(&id, &[nonce], &timestamp));
+        engine.process_heartbeat(Heartbeat { identity_id: id, device_signature, nonce: vec![nonce], timestamp, attestation_chain: None }).await
+    }
+
+    fn enrollment(id: Uuid, authorizer: &SigningKey, public_key: &[u8], nonce: u8) -> DeviceEnrollmentRequest {
+        DeviceEnrollmentRequest {
+            identity_id: id,
+            public_key: public_key.to_vec(),
+            attestation_chain: vec![],
+            nonce: vec![nonce],
+            label: Some("Tablet".into()),
+            authorization_signature: sign(authorizer, &signing::device_enrollment_payload(&id, public_key, &[nonce])),
+        }
+    }
+
+    fn revocation(id: Uuid, signer: &SigningKey, device_id: Uuid, nonce: u8) -> DeviceRevocationRequest {
+        DeviceRevocationRequest {
+            identity_id: id,
+            device_id,
+            nonce: vec![nonce],
+            signature: sign(signer, &signing::device_revocation_payload(&id, &device_id, &[nonce])),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_any_active_device_heartbeats_for_the_identity() {
+        let clock = AdjustableClock::new(Utc.with_ymd_and_hms(2026, 6, 1, 9, 0, 0).unwrap());
+        let engine = new_engine(&clock);
+        let (phone, phone_pk) = new_key();
+        let (tablet, tablet_pk) = new_key();
+        let id = mint(&engine, &phone_pk).await;
+        let tablet_id = add_device(&engine, id, &tablet_pk, 0).await;
+
+        // The tablet's heartbeat counts toward the identity and is recorded on the tablet.
+        assert_eq!(tap(&engine, &tablet, id, 1).await.unwrap(), 1);
+        let devices = engine.list_devices(id).await.unwrap();
+        assert_eq!(devices.iter().map(|d| (d.primary, d.last_heartbeat.is_some())).collect::<Vec<_>>(), vec![(true, false), (false, true)]);
+
+        // One counted heartbeat per interval for the identity, whichever device sends it.
+        assert!(matches!(tap(&engine, &phone, id, 2).await, Err(EngineError::RateLimitExceeded)));
+        clock.advance(Duration::days(1));
+        assert_eq!(tap(&engine, &phone, id, 3).await.unwrap(), 2);
+
+        // Keys that are not (or no longer) devices of the identity do not count.
+        let (stranger, _) = new_key();
+        clock.advance(Duration::days(1));
+        assert!(matches!(tap(&engine, &stranger, id, 4).await, Err(EngineError::InvalidSignature)));
+        engine.revoke_device(revocation(id, &phone, tablet_id, 5)).await.unwrap();
+        assert!(matches!(tap(&engine, &tablet, id, 6).await, Err(EngineError::InvalidSignature)));
+        assert_eq!(tap(&engine, &phone, id, 7).await.unwrap(), 3);
+
+        // A retired device key is archived and cannot be enrolled again.
+        assert!(matches!(engine.enroll_device(enrollment(id, &phone, &tablet_pk, 8)).await, Err(EngineError::AlreadyExists)));
+    }
+
+    #[tokio::test]
+    async fn test_each_device_decays_on_its_own() {
+        let clock = AdjustableClock::new(Utc.with_ymd_and_hms(2026, 6, 1, 9, 0, 0).unwrap());
+        let engine = new_engine(&clock);
+        let (phone, phone_pk) = new_key();
+        let (tablet, tablet_pk) = new_key();
+        let id = mint(&engine, &phone_pk).await;
+        add_device(&engine, id, &tablet_pk, ProtocolParameters::default().attestation_ttl_days + 1).await;
+
+        // The stale tablet must re-attest; the identity itself is still trusted.
+        assert!(matches!(tap(&engine, &tablet, id, 1).await, Err(EngineError::AttestationRequired)));
+        assert_eq!(engine.get_storage().identities.read().await[&id].status, IdentityStatus::Active);
+        assert_eq!(tap(&engine, &phone, id, 2).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_enrollment_is_authorized_and_capped() {
+        let clock = AdjustableClock::new(Utc.with_ymd_and_hms(2026, 6, 1, 9, 0, 0).unwrap());
+        let engine = new_engine(&clock);
+        let (phone, phone_pk) = new_key();
+        let (tablet, tablet_pk) = new_key();
+        let (_, new_pk) = new_key();
+        let id = mint(&engine, &phone_pk).await;
+        add_device(&engine, id, &tablet_pk, 0).await;
+
+        // Only an active device of the identity can vouch for a new key.
+        let (stranger, _) = new_key();
+        assert!(matches!(engine.enroll_device(enrollment(id, &stranger, &new_pk, 1)).await, Err(EngineError::InvalidSignature)));
+        assert!(matches!(engine.enroll_device(enrollment(id, &phone, &tablet_pk, 2)).await, Err(EngineError::AlreadyExists)));
+
+        // A secondary device may authorize; the new key still needs its own attestation.
+        assert!(matches!(engine.enroll_device(enrollment(id, &tablet, &new_pk, 3)).await, Err(EngineError::InvalidAttestation(_))));
+        assert!(matches!(engine.enroll_device(enrollment(id, &tablet, &new_pk, 3)).await, Err(EngineError::ReplayDetected)));
+
+        // The policy caps active devices (the primary included) before any attestation work.
+        let (_, watch_pk) = new_key();
+        add_device(&engine, id, &watch_pk, 0).await;
+        let max = ProtocolParameters::default().max_devices_per_identity as u32;
+        assert!(matches!(engine.enroll_device(enrollment(id, &phone, &new_pk, 4)).await, Err(EngineError::DeviceLimitReached(m)) if m == max));
+
+        engine.get_storage().identities.write().await.get_mut(&id).unwrap().status = IdentityStatus::Stale;
+        assert!(matches!(engine.enroll_device(enrollment(id, &phone, &new_pk, 5)).await, Err(EngineError::InvalidDevice(_))));
+        assert!(engine.get_storage().log.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_revoking_the_primary_promotes_the_freshest_device() {
+        let clock = AdjustableClock::new(Utc.with_ymd_and_hms(2026, 6, 1, 9, 0, 0).unwrap());
+        let engine = new_engine(&clock);
+        let (phone, phone_pk) = new_key();
+        let (tablet, tablet_pk) = new_key();
+        let (watch, watch_pk) = new_key();
+        let id = mint(&engine, &phone_pk).await;
+        let phone_id = engine.list_devices(id).await.unwrap()[0].id;
+        let tablet_id = add_device(&engine, id, &tablet_pk, 2).await;
+        let watch_id = add_device(&engine, id, &watch_pk, 1).await;
+
+        // A secondary device cannot depose the primary (a lost primary goes through recovery).
+        assert!(matches!(engine.revoke_device(revocation(id, &tablet, phone_id, 0)).await, Err(EngineError::InvalidSignature)));
+
+        // The phone retires itself and the most recently attested device takes over.
+        let revoked = engine.revoke_device(revocation(id, &phone, phone_id, 1)).await.unwrap();
+        assert_eq!((revoked.status, revoked.revoked_at), (DeviceStatus::Revoked, Some(engine.now())));
+        assert_eq!(engine.get_storage().identities.read().await[&id].public_key, watch_pk);
+        let log: Vec<_> = engine.get_storage().log.read().await.iter().map(|e| (e.kind, e.public_key_hash.clone())).collect();
+        assert_eq!(log, vec![
+            (LogEventKind::DeviceRevoked, Sha256::digest(&phone_pk).to_vec()),
+            (LogEventKind::KeyRotation, Sha256::digest(&watch_pk).to_vec()),
+        ]);
+        let primary: Vec<_> = engine.list_devices(id).await.unwrap().into_iter().filter(|d| d.primary).map(|d| d.id).collect();
+        assert_eq!(primary, vec![watch_id]);
+
+        // The old key is gone for good, and the last device cannot be removed.
+        assert!(matches!(engine.revoke_device(revocation(id, &phone, tablet_id, 2)).await, Err(EngineError::InvalidSignature)));
+        assert!(matches!(engine.revoke_device(revocation(id, &watch, phone_id, 3)).await, Err(EngineError::InvalidDevice(_))));
+        engine.revoke_device(revocation(id, &watch, tablet_id, 4)).await.unwrap();
+        assert!(matches!(engine.revoke_device(revocation(id, &watch, watch_id, 5)).await, Err(EngineError::InvalidDevice(_))));
+        assert_eq!(tap(&engine, &watch, id, 6).await.unwrap(), 1);
+    }
+}
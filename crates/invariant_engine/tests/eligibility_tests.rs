@@ -0,0 +1,167 @@
+// crates/invariant_engine/tests/eligibility_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use uuid::Uuid;
+    use p256::ecdsa::{SigningKey, signature::Signer};
+    use p256::pkcs8::EncodePublicKey;
+    use rand_core::OsRng;
+
+    use invariant_engine::{
+        InvariantEngine, IdentityStorage,
+        EngineError, EligibilityPolicy, core::EngineConfig, ProtocolParameters,
+    };
+    use invariant_engine::clock::AdjustableClock;
+    use invariant_shared::{
+        Identity, IdentityStatus, Heartbeat, Network, SecurityLevel, SybilCluster, SybilConfidence, SybilSignal, EligibilityRule,
+    };
+    use invariant_shared::signing;
+    use crate::common::{MockStorage, MockNonceStorage};
+
+    fn identity(created_days_ago: i64, continuity_score: u64, security_level: Option<SecurityLevel>, now: DateTime<Utc>) -> Identity {
+        Identity {
+            id: Uuid::new_v4(),
+            public_key: vec![],
+            continuity_score, streak: 0,
+            created_at: now - Duration::days(created_days_ago),
+            last_heartbeat: now,
+            last_attestation: now,
+            status: IdentityStatus::Active,
+            username: None, is_genesis_eligible: false, fcm_token: None,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level, os_patch_level: None,
+            genesis_version: 1, network: Network::Mainnet,
+        }
+    }
+
+    fn new_engine(clock: &AdjustableClock, policy: EligibilityPolicy) -> InvariantEngine<MockStorage, MockNonceStorage> {
+        let config = EngineConfig { network: Network::Mainnet, genesis_version: 1, params: ProtocolParameters::default() };
+        InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config)
+            .with_clock(clock.clone())
+            .with_eligibility_policy(policy)
+    }
+
+    async fn mint(engine: &InvariantEngine<MockStorage, MockNonceStorage>, key: &SigningKey) -> Uuid {
+        let mut identity = identity(0, 0, None, engine.now());
+        identity.public_key = key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec();
+        engine.get_storage().save_identity(&identity).await.unwrap();
+        identity.id
+    }
+
+    async fn tap(engine: &InvariantEngine<MockStorage, MockNonceStorage>, key: &SigningKey, id: Uuid, nonce: u8) -> Result<u64, EngineError> {
+        let timestamp = engine.now();
+        // Keep trust fresh: these tests are about eligibility, not attestation.
+        engine.get_storage().identities.write().await.get_mut(&id).unwrap().last_attestation = timestamp;
+        let signature: p256::ecdsa::Signature = key.sign(&signing::heartbeat_payload(&id, &[nonce], &timestamp));
+        engine.process_heartbeat(Heartbeat { identity_id: id, device_signature: signature.to_der().as_bytes().to_vec(), nonce: vec![nonce], timestamp, attestation_chain: None }).await
+    }
+
+    #[test]
+    fn test_rules_report_their_reasons() {
+        let now = Utc.with_ymd_and_hms(2026, 6, 1, 12, 0, 0).unwrap();
+        let policy = EligibilityPolicy::default();
+
+        let veteran = identity(45, 60, Some(SecurityLevel::StrongBox), now);
+        let report = policy.evaluate(&veteran, 9, None, now);
+        assert!(report.eligible);
+        assert_eq!(report.rules.len(), 6);
+        assert_eq!(report.evaluated_at, now);
+
+        let newcomer = identity(3, 60, Some(SecurityLevel::Tee), now);
+        let report = policy.evaluate(&newcomer, 3, None, now);
+        assert!(!report.eligible);
+        assert_eq!(report.failed_rules(), vec![EligibilityRule::MinStreak, EligibilityRule::StrongBox, EligibilityRule::MinAccountAge]);
+        let streak = report.rules.iter().find(|r| r.rule == EligibilityRule::MinStreak).unwrap();
+        assert_eq!(streak.detail, "streak 3 of 7 days");
+
+        // Sybil flags count from the configured confidence up.
+        assert_eq!(policy.evaluate(&veteran, 9, Some(SybilConfidence::Low), now).failed_rules(), vec![EligibilityRule::NoSybilFlag]);
+        let lenient = EligibilityPolicy { sybil_disqualify_at: Some(SybilConfidence::High), ..EligibilityPolicy::default() };
+        assert!(lenient.evaluate(&veteran, 9, Some(SybilConfidence::Medium), now).eligible);
+
+        // Disabled rules are not listed; status is always checked.
+        let open = EligibilityPolicy { min_streak_days: 0, min_continuity_score: 0, require_strongbox: false, sybil_disqualify_at: None, min_account_age_days: 0 };
+        let mut dormant = newcomer.clone();
+        dormant.status = IdentityStatus::Dormant;
+        let report = open.evaluate(&dormant, 0, Some(SybilConfidence::High), now);
+        assert_eq!(report.failed_rules(), vec![EligibilityRule::Active]);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].detail, "status dormant");
+    }
+
+    #[test]
+    fn test_policy_file() {
+        let policy = EligibilityPolicy::from_json(r#"{ "min_streak_days": 3, "sybil_disqualify_at": "high" }"#).unwrap();
+        assert_eq!(policy.min_streak_days, 3);
+        assert_eq!(policy.sybil_disqualify_at, Some(SybilConfidence::High));
+        assert_eq!(policy.min_account_age_days, EligibilityPolicy::default().min_account_age_days);
+
+        assert!(EligibilityPolicy::from_json(r#"{ "sybil_disqualify_at": null }"#).unwrap().sybil_disqualify_at.is_none());
+        assert!(matches!(EligibilityPolicy::from_json(r#"{ "min_streak": 3 }"#), Err(EngineError::InvalidPolicy(_))));
+        assert!(matches!(EligibilityPolicy::from_json(r#"{ "min_account_age_days": -1 }"#), Err(EngineError::InvalidPolicy(_))));
+    }
+
+    #[tokio::test]
+    async fn test_heartbeats_and_sweep_keep_eligibility_current() {
+        let clock = AdjustableClock::new(Utc.with_ymd_and_hms(2026, 6, 1, 9, 0, 0).unwrap());
+        let policy = EligibilityPolicy { min_streak_days: 2, min_continuity_score: 2, require_strongbox: false, min_account_age_days: 3, ..EligibilityPolicy::default() };
+        let engine = new_engine(&clock, policy);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key).await;
+
+        // Every counted heartbeat stores a fresh report.
+        tap(&engine, &key, id, 1).await.unwrap();
+        let report = engine.eligibility_for(id).await.unwrap();
+        assert_eq!(report.failed_rules(), vec![EligibilityRule::MinStreak, EligibilityRule::MinContinuityScore, EligibilityRule::MinAccountAge]);
+
+        clock.advance(Duration::days(1));
+        tap(&engine, &key, id, 2).await.unwrap();
+        let report = engine.eligibility_for(id).await.unwrap();
+        assert_eq!(report.failed_rules(), vec![EligibilityRule::MinAccountAge]);
+        assert_eq!(report.evaluated_at, engine.now());
+
+        // Account age matures without any heartbeat: the periodic job picks it up.
+        clock.advance(Duration::days(2));
+        let sweep = engine.refresh_eligibility(100).await.unwrap();
+        assert_eq!((sweep.evaluated, sweep.eligible), (1, 1));
+        assert!(engine.get_storage().identities.read().await[&id].is_genesis_eligible);
+        assert_eq!(engine.refresh_eligibility(100).await.unwrap().evaluated, 0);
+
+        // A Sybil flag is picked up at the next refresh.
+        engine.get_storage().clusters.write().await.push(SybilCluster {
+            id: Uuid::new_v4(), confidence: SybilConfidence::Medium, signals: vec![SybilSignal::BootKey],
+            members: vec![id, Uuid::new_v4()], detected_at: engine.now(),
+        });
+        clock.advance(Duration::hours(ProtocolParameters::default().eligibility_refresh_hours + 1));
+        let sweep = engine.refresh_eligibility(100).await.unwrap();
+        assert_eq!((sweep.evaluated, sweep.eligible), (1, 0));
+        let report = engine.eligibility_for(id).await.unwrap();
+        assert_eq!(report.failed_rules(), vec![EligibilityRule::NoSybilFlag]);
+        assert!(!engine.get_storage().identities.read().await[&id].is_genesis_eligible);
+    }
+
+    #[tokio::test]
+    async fn test_status_change_invalidates_an_eligible_report() {
+        let clock = AdjustableClock::new(Utc.with_ymd_and_hms(2026, 6, 1, 9, 0, 0).unwrap());
+        let open = EligibilityPolicy { min_streak_days: 0, min_continuity_score: 0, require_strongbox: false, sybil_disqualify_at: None, min_account_age_days: 0 };
+        let engine = new_engine(&clock, open);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key).await;
+
+        tap(&engine, &key, id, 1).await.unwrap();
+        assert!(engine.eligibility_for(id).await.unwrap().eligible);
+
+        // Revoked elsewhere (operator action): the stored report is no longer trusted.
+        engine.get_storage().identities.write().await.get_mut(&id).unwrap().status = IdentityStatus::Revoked;
+        assert_eq!(engine.refresh_eligibility(100).await.unwrap().evaluated, 1);
+        let report = engine.eligibility_for(id).await.unwrap();
+        assert_eq!(report.failed_rules(), vec![EligibilityRule::Active]);
+        assert!(!engine.get_storage().identities.read().await[&id].is_genesis_eligible);
+    }
+}
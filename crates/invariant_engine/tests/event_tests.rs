@@ -0,0 +1,144 @@
+// crates/invariant_engine/tests/event_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use uuid::Uuid;
+    use p256::ecdsa::{SigningKey, signature::Signer};
+    use p256::pkcs8::EncodePublicKey;
+    use rand_core::OsRng;
+
+    use invariant_engine::{
+        InvariantEngine, IdentityStorage,
+        EngineError, core::EngineConfig, ProtocolParameters,
+    };
+    use invariant_engine::clock::AdjustableClock;
+    use invariant_engine::events::{FailingEventSink, RecordingEventSink};
+    use invariant_shared::{
+        Identity, IdentityStatus, Heartbeat, Network, DomainEvent, DomainEventKind, HeartbeatRejection,
+    };
+    use invariant_shared::signing;
+    use crate::common::{MockStorage, MockNonceStorage};
+
+    fn new_engine(clock: &AdjustableClock, sink: &RecordingEventSink) -> InvariantEngine<MockStorage, MockNonceStorage> {
+        let config = EngineConfig { network: Network::Mainnet, genesis_version: 1, params: ProtocolParameters::default() };
+        InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config)
+            .with_clock(clock.clone())
+            .with_event_sink(sink.clone())
+    }
+
+    async fn mint(engine: &InvariantEngine<MockStorage, MockNonceStorage>, key: &SigningKey) -> Uuid {
+        let now = engine.now();
+        let identity = Identity {
+            id: Uuid::new_v4(),
+            public_key: key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec(),
+            continuity_score: 0, streak: 0,
+            created_at: now,
+            last_heartbeat: now,
+            last_attestation: now,
+            status: IdentityStatus::Active,
+            username: None, is_genesis_eligible: false, fcm_token: None,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            genesis_version: 1, network: Network::Mainnet,
+        };
+        engine.get_storage().save_identity(&identity).await.unwrap();
+        identity.id
+    }
+
+    async fn tap(engine: &InvariantEngine<MockStorage, MockNonceStorage>, key: &SigningKey, id: Uuid, nonce: u8) -> Result<u64, EngineError> {
+        let timestamp = engine.now();
+        let signature: p256::ecdsa::Signature = key.sign(&signing::heartbeat_payload(&id, &[nonce], &timestamp));
+        engine.process_heartbeat(Heartbeat { identity_id: id, device_signature: signature.to_der().as_bytes().to_vec(), nonce: vec![nonce], timestamp, attestation_chain: None }).await
+    }
+
+    fn rejected(reason: HeartbeatRejection) -> DomainEventKind {
+        DomainEventKind::HeartbeatRejected { reason }
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_outcomes_are_published() {
+        let clock = AdjustableClock::new(Utc.with_ymd_and_hms(2026, 5, 1, 9, 0, 0).unwrap());
+        let sink = RecordingEventSink::new();
+        let engine = new_engine(&clock, &sink);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key).await;
+
+        assert_eq!(tap(&engine, &key, id, 1).await.unwrap(), 1);
+        assert!(matches!(tap(&engine, &key, id, 2).await, Err(EngineError::RateLimitExceeded)));
+        assert!(matches!(tap(&engine, &key, id, 1).await, Err(EngineError::ReplayDetected)));
+
+        assert_eq!(sink.kinds(), vec![
+            DomainEventKind::HeartbeatAccepted { continuity_score: 1 },
+            rejected(HeartbeatRejection::RateLimited),
+            rejected(HeartbeatRejection::Replay),
+        ]);
+        let events = sink.events();
+        assert!(events.iter().all(|e| e.identity_id == id && e.occurred_at == engine.now()));
+        assert_ne!(events[0].id, events[1].id);
+
+        // A heartbeat for an unknown identity is not about anyone: nothing is published.
+        assert!(tap(&engine, &key, Uuid::new_v4(), 3).await.is_err());
+        assert_eq!(sink.events().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_trust_decay_is_published_once() {
+        let clock = AdjustableClock::new(Utc.with_ymd_and_hms(2026, 5, 1, 9, 0, 0).unwrap());
+        let sink = RecordingEventSink::new();
+        let engine = new_engine(&clock, &sink);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key).await;
+
+        clock.advance(Duration::days(ProtocolParameters::default().attestation_ttl_days + 1));
+        assert!(matches!(tap(&engine, &key, id, 1).await, Err(EngineError::AttestationRequired)));
+        assert!(matches!(tap(&engine, &key, id, 2).await, Err(EngineError::AttestationRequired)));
+
+        assert_eq!(sink.kinds(), vec![
+            DomainEventKind::TrustDecayed,
+            rejected(HeartbeatRejection::AttestationRequired),
+            rejected(HeartbeatRejection::AttestationRequired),
+        ]);
+        assert_eq!(engine.get_storage().identities.read().await[&id].status, IdentityStatus::Stale);
+    }
+
+    #[tokio::test]
+    async fn test_refused_events_are_counted_not_fatal() {
+        let config = EngineConfig { network: Network::Mainnet, genesis_version: 1, params: ProtocolParameters::default() };
+        let engine = InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config)
+            .with_event_sink(FailingEventSink);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key).await;
+
+        assert_eq!(tap(&engine, &key, id, 1).await.unwrap(), 1);
+        assert!(matches!(tap(&engine, &key, id, 1).await, Err(EngineError::ReplayDetected)));
+
+        assert_eq!(engine.dropped_events(), 2);
+        assert_eq!(engine.get_storage().identities.read().await[&id].continuity_score, 1);
+    }
+
+    #[test]
+    fn test_event_wire_format() {
+        let event = DomainEvent {
+            id: Uuid::new_v4(),
+            identity_id: Uuid::new_v4(),
+            occurred_at: Utc.with_ymd_and_hms(2026, 5, 1, 9, 0, 0).unwrap(),
+            kind: rejected(HeartbeatRejection::StaleTimestamp),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "heartbeat_rejected");
+        assert_eq!(json["reason"], "stale_timestamp");
+        assert_eq!(json["type"], event.kind.tag());
+        assert_eq!(serde_json::from_value::<DomainEvent>(json).unwrap(), event);
+
+        let minted = DomainEventKind::IdentityMinted { network: Network::Testnet, genesis_version: 2 };
+        let json = serde_json::to_value(&minted).unwrap();
+        assert_eq!(json, serde_json::json!({ "type": "identity_minted", "network": "testnet", "genesis_version": 2 }));
+        assert_eq!(serde_json::to_value(DomainEventKind::TrustDecayed).unwrap(), serde_json::json!({ "type": "trust_decayed" }));
+    }
+}
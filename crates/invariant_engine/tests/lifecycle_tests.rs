@@ -0,0 +1,320 @@
+// crates/invariant_engine/tests/lifecycle_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use uuid::Uuid;
+    use p256::ecdsa::{SigningKey, signature::Signer};
+    use p256::pkcs8::EncodePublicKey;
+    use rand_core::OsRng;
+
+    use invariant_engine::{InvariantEngine, IdentityStorage, ReactivationStorage, EngineError, core::EngineConfig, ProtocolParameters};
+    use invariant_engine::clock::AdjustableClock;
+    use invariant_engine::lifecycle::next_status;
+    use invariant_engine::reactivation::{decayed_score, streak_after_dormancy};
+    use invariant_engine::streak::{advance_streak, local_day, StreakUpdate};
+    use invariant_engine::ports::Clock;
+    use invariant_shared::{
+        Identity, IdentityStatus, TransitionReason, Heartbeat, Network,
+        ReAttestationRequest, StreakState, GenesisVersionStats, Reactivation,
+    };
+    use invariant_shared::signing;
+    use crate::common::{MockStorage, MockNonceStorage};
+
+    fn new_engine(clock: &AdjustableClock) -> InvariantEngine<MockStorage, MockNonceStorage> {
+        let config = EngineConfig { network: Network::Mainnet, genesis_version: 1, params: ProtocolParameters::default() };
+        InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config).with_clock(clock.clone())
+    }
+
+    /// Node on genesis version 2 that has retired version 1.
+    fn upgrading_engine(clock: &AdjustableClock) -> InvariantEngine<MockStorage, MockNonceStorage> {
+        let params = ProtocolParameters { min_genesis_version: 2, ..ProtocolParameters::default() };
+        let config = EngineConfig { network: Network::Mainnet, genesis_version: 2, params };
+        InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config).with_clock(clock.clone())
+    }
+
+    async fn set_genesis_version(engine: &InvariantEngine<MockStorage, MockNonceStorage>, id: Uuid, version: u16) {
+        let mut identity = engine.get_storage().get_identity(&id).await.unwrap().unwrap();
+        identity.genesis_version = version;
+        engine.get_storage().save_identity(&identity).await.unwrap();
+    }
+
+    async fn mint(engine: &InvariantEngine<MockStorage, MockNonceStorage>, key: &SigningKey, status: IdentityStatus) -> Uuid {
+        let now = engine.now();
+        let identity = Identity {
+            id: Uuid::new_v4(),
+            public_key: key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec(),
+            continuity_score: 5, streak: 0,
+            created_at: now - Duration::days(60),
+            last_heartbeat: now - Duration::days(2),
+            last_attestation: now,
+            status,
+            username: None, is_genesis_eligible: false, fcm_token: None,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            genesis_version: 1, network: Network::Mainnet,
+        };
+        engine.get_storage().save_identity(&identity).await.unwrap();
+        identity.id
+    }
+
+    fn tap(clock: &AdjustableClock, key: &SigningKey, id: Uuid, nonce: u8) -> Heartbeat {
+        let timestamp = clock.now();
+        let signature: p256::ecdsa::Signature = key.sign(&signing::heartbeat_payload(&id, &[nonce], &timestamp));
+        Heartbeat { identity_id: id, device_signature: signature.to_der().as_bytes().to_vec(), nonce: vec![nonce], timestamp, attestation_chain: None }
+    }
+
+    #[test]
+    fn test_transition_table() {
+        use IdentityStatus::*;
+        use TransitionReason::*;
+
+        assert_eq!(next_status(&Active, AttestationExpired), Some(Stale));
+        assert_eq!(next_status(&Active, Heartbeat), Some(Active));
+        assert_eq!(next_status(&Stale, Reattestation), Some(Active));
+        assert_eq!(next_status(&Dormant, KeyRotation), Some(Active));
+        assert_eq!(next_status(&Stale, Inactivity), Some(Dormant));
+        assert_eq!(next_status(&Dormant, Revocation), Some(Revoked));
+
+        // A stale or dormant identity must re-prove hardware; a heartbeat is not enough.
+        assert_eq!(next_status(&Stale, Heartbeat), None);
+        assert_eq!(next_status(&Dormant, Heartbeat), None);
+        assert_eq!(next_status(&Dormant, AttestationExpired), None);
+        assert_eq!(next_status(&Dormant, Inactivity), None);
+        // Revoked is terminal.
+        for reason in [Heartbeat, AttestationExpired, Reattestation, KeyRotation, Recovery, Inactivity, Revocation] {
+            assert_eq!(next_status(&Revoked, reason), None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trust_decay_marks_stale_once() {
+        let clock = AdjustableClock::new(Utc::now());
+        let engine = new_engine(&clock);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key, IdentityStatus::Active).await;
+
+        // Anyone can name the identity; only a heartbeat the holder signed records the decay.
+        clock.advance(Duration::days(8));
+        let stranger = SigningKey::random(&mut OsRng);
+        assert!(matches!(engine.process_heartbeat(tap(&clock, &stranger, id, 0)).await, Err(EngineError::InvalidSignature)));
+        assert!(engine.transition_history(id).await.unwrap().is_empty());
+
+        assert!(matches!(engine.process_heartbeat(tap(&clock, &key, id, 1)).await, Err(EngineError::AttestationRequired)));
+        assert!(matches!(engine.process_heartbeat(tap(&clock, &key, id, 2)).await, Err(EngineError::AttestationRequired)));
+
+        let stored = engine.get_storage().get_identity(&id).await.unwrap().unwrap();
+        assert_eq!(stored.status, IdentityStatus::Stale);
+
+        let history = engine.transition_history(id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!((history[0].from.clone(), history[0].to.clone()), (IdentityStatus::Active, IdentityStatus::Stale));
+        assert_eq!(history[0].reason, TransitionReason::AttestationExpired);
+        assert_eq!(history[0].actor_id, None);
+        assert_eq!(history[0].created_at, clock.now());
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_with_attestation_skips_decay_until_verified() {
+        let clock = AdjustableClock::new(Utc::now());
+        let engine = new_engine(&clock);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key, IdentityStatus::Active).await;
+
+        // An expired timer is not a 426 when the refresh rides along; a chain that fails
+        // verification rejects the whole heartbeat without touching the status or score.
+        clock.advance(Duration::days(8));
+        let mut heartbeat = tap(&clock, &key, id, 1);
+        heartbeat.attestation_chain = Some(vec![vec![0x30, 0x00]]);
+        assert!(matches!(engine.process_heartbeat(heartbeat).await, Err(EngineError::InvalidAttestation(_))));
+
+        let stored = engine.get_storage().get_identity(&id).await.unwrap().unwrap();
+        assert_eq!(stored.status, IdentityStatus::Active);
+        assert_eq!(stored.continuity_score, 5);
+        assert!(engine.transition_history(id).await.unwrap().is_empty());
+
+        // Without the chain the usual decay applies.
+        assert!(matches!(engine.process_heartbeat(tap(&clock, &key, id, 2)).await, Err(EngineError::AttestationRequired)));
+        assert_eq!(engine.get_storage().get_identity(&id).await.unwrap().unwrap().status, IdentityStatus::Stale);
+    }
+
+    #[tokio::test]
+    async fn test_retired_genesis_version_requires_reattestation() {
+        let clock = AdjustableClock::new(Utc::now());
+        let engine = upgrading_engine(&clock);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key, IdentityStatus::Active).await;
+        let identity = engine.get_storage().get_identity(&id).await.unwrap().unwrap();
+        assert!(engine.requires_genesis_upgrade(&identity));
+
+        match engine.process_heartbeat(tap(&clock, &key, id, 1)).await {
+            Err(EngineError::GenesisUpgradeRequired { version: 1, minimum: 2 }) => (),
+            other => panic!("Expected GenesisUpgradeRequired, got {:?}", other),
+        }
+
+        // A chain on the heartbeat gets past the gate and is verified like a re-attestation.
+        let mut heartbeat = tap(&clock, &key, id, 2);
+        heartbeat.attestation_chain = Some(vec![vec![0x30, 0x00]]);
+        assert!(matches!(engine.process_heartbeat(heartbeat).await, Err(EngineError::InvalidAttestation(_))));
+
+        let stored = engine.get_storage().get_identity(&id).await.unwrap().unwrap();
+        assert_eq!((stored.genesis_version, stored.continuity_score, stored.status), (1, 5, IdentityStatus::Active));
+
+        // Identities on a supported version are unaffected.
+        set_genesis_version(&engine, id, 2).await;
+        assert_eq!(engine.process_heartbeat(tap(&clock, &key, id, 3)).await.unwrap(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_genesis_upgrade_progress() {
+        let clock = AdjustableClock::new(Utc::now());
+        let engine = upgrading_engine(&clock);
+        for status in [IdentityStatus::Active, IdentityStatus::Dormant, IdentityStatus::Revoked] {
+            mint(&engine, &SigningKey::random(&mut OsRng), status).await;
+        }
+        let current = mint(&engine, &SigningKey::random(&mut OsRng), IdentityStatus::Active).await;
+        set_genesis_version(&engine, current, 2).await;
+
+        let progress = engine.genesis_upgrade_progress().await.unwrap();
+        assert_eq!((progress.current_version, progress.min_version, progress.pending), (2, 2, 2));
+        assert_eq!(progress.versions, vec![
+            GenesisVersionStats { genesis_version: 1, identities: 2, upgraded: 0 },
+            GenesisVersionStats { genesis_version: 2, identities: 1, upgraded: 0 },
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_does_not_wake_dormant_identity() {
+        let clock = AdjustableClock::new(Utc::now());
+        let engine = new_engine(&clock);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key, IdentityStatus::Dormant).await;
+
+        // Even with a fresh attestation timer, waking up takes a new proof.
+        assert!(matches!(engine.process_heartbeat(tap(&clock, &key, id, 1)).await, Err(EngineError::AttestationRequired)));
+        let mut heartbeat = tap(&clock, &key, id, 2);
+        heartbeat.attestation_chain = Some(vec![vec![0x30, 0x00]]);
+        assert!(matches!(engine.process_heartbeat(heartbeat).await, Err(EngineError::InvalidAttestation(_))));
+
+        let stored = engine.get_storage().get_identity(&id).await.unwrap().unwrap();
+        assert_eq!((stored.status, stored.continuity_score), (IdentityStatus::Dormant, 5));
+        assert!(engine.transition_history(id).await.unwrap().is_empty());
+        assert!(engine.reactivation_history(id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reaper_follows_the_engine_clock() {
+        let clock = AdjustableClock::new(Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap());
+        let engine = new_engine(&clock);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key, IdentityStatus::Active).await;
+        engine.process_heartbeat(tap(&clock, &key, id, 1)).await.unwrap();
+        let window = Duration::days(ProtocolParameters::default().reaper_window_days);
+
+        // Silence is measured from the last heartbeat on the engine clock, not the wall clock.
+        clock.advance(window - Duration::hours(1));
+        assert_eq!(engine.reap_inactive().await.unwrap(), 0);
+        clock.advance(Duration::hours(2));
+        assert_eq!(engine.reap_inactive().await.unwrap(), 1);
+        assert_eq!(engine.reap_inactive().await.unwrap(), 0);
+
+        let history = engine.transition_history(id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!((history[0].reason, history[0].created_at), (TransitionReason::Inactivity, clock.now()));
+        assert_eq!(engine.get_storage().get_identity(&id).await.unwrap().unwrap().status, IdentityStatus::Dormant);
+        assert!(matches!(engine.process_heartbeat(tap(&clock, &key, id, 2)).await, Err(EngineError::AttestationRequired)));
+    }
+
+    #[test]
+    fn test_dormancy_rules() {
+        let params = ProtocolParameters { dormancy_score_decay_percent_per_week: 15, ..ProtocolParameters::default() };
+        assert_eq!(decayed_score(200, Duration::days(6), &params), 200);
+        assert_eq!(decayed_score(200, Duration::days(20), &params), 140);
+        assert_eq!(decayed_score(200, Duration::days(70), &params), 0);
+        assert_eq!(decayed_score(200, Duration::days(70), &ProtocolParameters::default()), 200);
+
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        let state = StreakState {
+            streak: 12,
+            last_day: Some(local_day(now - Duration::days(40), "UTC")),
+            freezes: 1,
+            timezone: "UTC".into(),
+        };
+
+        // Default: the streak restarts, banked freezes stay.
+        let reset = streak_after_dormancy(&state, now, &ProtocolParameters::default());
+        assert_eq!((reset.streak, reset.freezes), (0, 1));
+
+        // Kept: the first day back continues the streak.
+        let keep = ProtocolParameters { dormancy_keeps_streak: true, ..ProtocolParameters::default() };
+        let resumed = streak_after_dormancy(&state, now, &keep);
+        let (next, update) = advance_streak(&resumed, now, &keep);
+        assert_eq!((next.streak, next.freezes, update), (13, 1, StreakUpdate::Extended));
+    }
+
+    #[tokio::test]
+    async fn test_cooling_off_refuses_heartbeats() {
+        let clock = AdjustableClock::new(Utc::now());
+        let params = ProtocolParameters { reactivation_cooldown_hours: 48, ..ProtocolParameters::default() };
+        let config = EngineConfig { network: Network::Mainnet, genesis_version: 1, params };
+        let engine = InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config).with_clock(clock.clone());
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key, IdentityStatus::Active).await;
+
+        // As left by a reactivation (a real one needs a Google-rooted attestation chain).
+        let now = clock.now();
+        let until = now + Duration::hours(48);
+        engine.get_storage().record_reactivation(&Reactivation {
+            identity_id: id,
+            reason: TransitionReason::Reattestation,
+            silent_since: now - Duration::days(45),
+            dormant_since: now - Duration::days(15),
+            reactivated_at: now,
+            score_before: 5, score_after: 5,
+            streak_before: 0, streak_after: 0,
+            cooling_off_until: Some(until),
+        }, &StreakState::default()).await.unwrap();
+
+        match engine.process_heartbeat(tap(&clock, &key, id, 1)).await {
+            Err(EngineError::CoolingOff(at)) => assert_eq!(at, until),
+            other => panic!("Expected CoolingOff, got {:?}", other),
+        }
+
+        clock.advance(Duration::hours(48));
+        assert_eq!(engine.process_heartbeat(tap(&clock, &key, id, 2)).await.unwrap(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_revoked_identity_cannot_reattest() {
+        let clock = AdjustableClock::new(Utc::now());
+        let engine = new_engine(&clock);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key, IdentityStatus::Revoked).await;
+        let identity = engine.get_storage().get_identity(&id).await.unwrap().unwrap();
+
+        // Rejected by the state machine before the (expensive) attestation check.
+        let request = ReAttestationRequest {
+            id,
+            public_key: identity.public_key,
+            attestation_chain: vec![],
+            nonce: vec![1],
+        };
+        match engine.process_reattestation(request).await {
+            Err(EngineError::IllegalTransition(_)) => (),
+            other => panic!("Expected IllegalTransition, got {:?}", other),
+        }
+        assert!(engine.transition_history(id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transition_history_requires_identity() {
+        let clock = AdjustableClock::new(Utc::now());
+        let engine = new_engine(&clock);
+        assert!(matches!(engine.transition_history(Uuid::new_v4()).await, Err(EngineError::IdentityNotFound(_))));
+    }
+}
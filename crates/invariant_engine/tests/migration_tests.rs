@@ -0,0 +1,153 @@
+// crates/invariant_engine/tests/migration_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+    use p256::ecdsa::{SigningKey, signature::Signer};
+    use p256::pkcs8::EncodePublicKey;
+    use rand_core::OsRng;
+
+    use invariant_engine::{
+        InvariantEngine, IdentityStorage, EngineError, core::EngineConfig, ProtocolParameters,
+    };
+    use invariant_engine::clock::AdjustableClock;
+    use invariant_shared::{
+        Identity, IdentityStatus, Heartbeat, Network, GenesisRequest, IdentityMigration, MigrationRequest,
+    };
+    use invariant_shared::signing;
+    use crate::common::{Engine, MockStorage, MockNonceStorage};
+
+    fn new_engine(network: Network) -> Engine {
+        let clock = AdjustableClock::new(Utc.with_ymd_and_hms(2026, 6, 1, 9, 0, 0).unwrap());
+        let config = EngineConfig { params: ProtocolParameters::for_network(&network), network, genesis_version: 1 };
+        InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config).with_clock(clock)
+    }
+
+    fn new_key() -> (SigningKey, Vec<u8>) {
+        let key = SigningKey::random(&mut OsRng);
+        let public_key = key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec();
+        (key, public_key)
+    }
+
+    fn sign(key: &SigningKey, payload: &[u8]) -> Vec<u8> {
+        let signature: p256::ecdsa::Signature = key.sign(payload);
+        signature.to_der().as_bytes().to_vec()
+    }
+
+    async fn mint(engine: &Engine, public_key: &[u8], network: Network, status: IdentityStatus) -> Uuid {
+        let now = engine.now();
+        let identity = Identity {
+            id: Uuid::new_v4(),
+            public_key: public_key.to_vec(),
+            continuity_score: 40, streak: 12,
+            created_at: now, last_heartbeat: now, last_attestation: now,
+            status,
+            username: None, is_genesis_eligible: false, fcm_token: None,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            genesis_version: 1, network,
+        };
+        engine.get_storage().save_identity(&identity).await.unwrap();
+        identity.id
+    }
+
+    fn migration(testnet_id: Uuid, signer: &SigningKey, new_public_key: &[u8], nonce: u8) -> MigrationRequest {
+        MigrationRequest {
+            testnet_id,
+            new_public_key: new_public_key.to_vec(),
+            attestation_chain: vec![],
+            nonce: vec![nonce],
+            signature: sign(signer, &signing::migration_payload(&testnet_id, new_public_key, &[nonce])),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_operations_are_confined_to_the_node_network() {
+        let engine = new_engine(Network::Mainnet);
+        let (key, public_key) = new_key();
+        let id = mint(&engine, &public_key, Network::Testnet, IdentityStatus::Active).await;
+
+        let timestamp = engine.now();
+        let heartbeat = Heartbeat {
+            identity_id: id,
+            device_signature: sign(&key, &signing::heartbeat_payload(&id, &[1], &timestamp)),
+            nonce: vec![1],
+            timestamp,
+            attestation_chain: None,
+        };
+        match engine.process_heartbeat(heartbeat).await {
+            Err(EngineError::NetworkMismatch { expected: Network::Mainnet, found: Network::Testnet }) => (),
+            other => panic!("Expected NetworkMismatch, got {:?}", other),
+        }
+
+        // Genesis with a testnet key does not hand back the testnet identity.
+        let genesis = GenesisRequest { public_key, attestation_chain: vec![], nonce: vec![2] };
+        assert!(matches!(engine.process_genesis(genesis).await, Err(EngineError::NetworkMismatch { .. })));
+        assert!(engine.streak(id).await.is_ok(), "reads are not gated");
+    }
+
+    #[tokio::test]
+    async fn test_migration_is_served_by_mainnet_nodes_for_testnet_identities() {
+        let testnet_node = new_engine(Network::Testnet);
+        let (key, public_key) = new_key();
+        let (_, new_public_key) = new_key();
+        let id = mint(&testnet_node, &public_key, Network::Testnet, IdentityStatus::Active).await;
+        assert!(matches!(testnet_node.process_migration(migration(id, &key, &new_public_key, 1)).await, Err(EngineError::InvalidMigration(_))));
+
+        let engine = new_engine(Network::Mainnet);
+        let mainnet_id = mint(&engine, &public_key, Network::Mainnet, IdentityStatus::Active).await;
+        assert!(matches!(engine.process_migration(migration(mainnet_id, &key, &new_public_key, 2)).await, Err(EngineError::InvalidMigration(_))));
+
+        let (revoked_key, revoked_pk) = new_key();
+        let revoked_id = mint(&engine, &revoked_pk, Network::Testnet, IdentityStatus::Revoked).await;
+        assert!(matches!(engine.process_migration(migration(revoked_id, &revoked_key, &new_public_key, 3)).await, Err(EngineError::IllegalTransition(_))));
+    }
+
+    #[tokio::test]
+    async fn test_migration_requires_testnet_signature_and_new_key_attestation() {
+        let engine = new_engine(Network::Mainnet);
+        let (key, public_key) = new_key();
+        let (stranger, _) = new_key();
+        let (_, new_public_key) = new_key();
+        let id = mint(&engine, &public_key, Network::Testnet, IdentityStatus::Active).await;
+
+        assert!(matches!(engine.process_migration(migration(id, &stranger, &new_public_key, 1)).await, Err(EngineError::InvalidSignature)));
+        assert!(matches!(engine.process_migration(migration(id, &key, &new_public_key, 1)).await, Err(EngineError::ReplayDetected)));
+
+        // A key already anchoring an identity cannot be the mainnet key.
+        assert!(matches!(engine.process_migration(migration(id, &key, &public_key, 2)).await, Err(EngineError::AlreadyExists)));
+
+        // Signed, but the new key carries no valid attestation: nothing is minted or retired.
+        assert!(matches!(engine.process_migration(migration(id, &key, &new_public_key, 3)).await, Err(EngineError::InvalidAttestation(_))));
+        assert_eq!(engine.get_storage().identities.read().await.len(), 1);
+        assert_eq!(engine.get_storage().get_identity(&id).await.unwrap().unwrap().status, IdentityStatus::Active);
+        assert!(engine.migration_for(id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_identity_migrates_once() {
+        let engine = new_engine(Network::Mainnet);
+        let (key, public_key) = new_key();
+        let (_, new_public_key) = new_key();
+        let id = mint(&engine, &public_key, Network::Testnet, IdentityStatus::Active).await;
+        let link = IdentityMigration { testnet_id: id, mainnet_id: Uuid::new_v4(), carried_score: 0, carried_streak: 0, migrated_at: engine.now() };
+        engine.get_storage().migrations.write().await.push(link.clone());
+
+        assert_eq!(engine.migration_for(id).await.unwrap(), Some(link.clone()));
+        assert!(matches!(engine.process_migration(migration(id, &key, &new_public_key, 1)).await, Err(EngineError::InvalidMigration(_))));
+    }
+
+    #[test]
+    fn test_carry_share_is_a_percentage() {
+        let params = ProtocolParameters { migration_score_carry_percent: 101, ..ProtocolParameters::default() };
+        assert!(matches!(params.validate(), Err(EngineError::InvalidPolicy(_))));
+        let params = ProtocolParameters { migration_score_carry_percent: 100, migration_carry_streak: true, ..ProtocolParameters::default() };
+        assert!(params.validate().is_ok());
+    }
+}
@@ -0,0 +1,153 @@
+// crates/invariant_engine/tests/pairwise_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+    use p256::ecdsa::{SigningKey, Signature, signature::Signer};
+    use p256::pkcs8::EncodePublicKey;
+    use rand_core::OsRng;
+
+    use invariant_engine::{InvariantEngine, IdentityStorage, EngineError, core::EngineConfig, ProtocolParameters};
+    use invariant_engine::pairwise::derive_pairwise_id;
+    use invariant_shared::{Identity, IdentityStatus, Network, PairwiseLinkRequest, ProofTokenRequest};
+    use invariant_shared::signing;
+    use crate::common::{MockStorage, MockNonceStorage};
+
+    const SECRET: &[u8] = &[42u8; 32];
+
+    fn new_engine() -> InvariantEngine<MockStorage, MockNonceStorage> {
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
+        InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config)
+    }
+
+    async fn mint(engine: &InvariantEngine<MockStorage, MockNonceStorage>) -> (Uuid, SigningKey) {
+        let key = SigningKey::random(&mut OsRng);
+        let identity = Identity {
+            id: Uuid::new_v4(),
+            public_key: key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec(),
+            continuity_score: 5,
+            created_at: Utc::now(),
+            last_heartbeat: Utc::now(),
+            last_attestation: Utc::now(),
+            status: IdentityStatus::Active,
+            username: None, is_genesis_eligible: false, fcm_token: None, streak: 5,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            genesis_version: 1, network: Network::Testnet,
+        };
+        engine.get_storage().save_identity(&identity).await.unwrap();
+        (identity.id, key)
+    }
+
+    async fn link(
+        engine: &InvariantEngine<MockStorage, MockNonceStorage>,
+        secret: &[u8],
+        id: &Uuid,
+        key: &SigningKey,
+        partner_id: &str,
+    ) -> Result<Uuid, EngineError> {
+        let nonce = Uuid::new_v4().as_bytes().to_vec();
+        let signature: Signature = key.sign(&signing::pairwise_link_payload(id, partner_id, &nonce));
+        let request = PairwiseLinkRequest {
+            partner_id: partner_id.into(),
+            nonce,
+            signature: signature.to_der().as_bytes().to_vec(),
+        };
+        engine.pairwise_id_for(secret, id, &request).await
+    }
+
+    fn token_request(key: &SigningKey, id: &Uuid, partner_id: &str) -> ProofTokenRequest {
+        let nonce = Uuid::new_v4().as_bytes().to_vec();
+        let signature: Signature = key.sign(&signing::proof_token_payload(id, partner_id, &nonce));
+        ProofTokenRequest { nonce, signature: signature.to_der().as_bytes().to_vec() }
+    }
+
+    #[test]
+    fn test_derivation_is_keyed_and_partner_scoped() {
+        let id = Uuid::new_v4();
+        let a = derive_pairwise_id(SECRET, "bank.example", &id);
+
+        assert_eq!(a, derive_pairwise_id(SECRET, "bank.example", &id), "Derivation must be deterministic");
+        assert_ne!(a, id, "Global UUID must never be exposed");
+        assert_ne!(a, derive_pairwise_id(SECRET, "game.example", &id), "Partners must get different IDs");
+        assert_ne!(a, derive_pairwise_id(&[7u8; 32], "bank.example", &id), "Derivation must depend on the secret");
+    }
+
+    #[tokio::test]
+    async fn test_partners_cannot_join_on_identifier() {
+        let engine = new_engine();
+        let (id, key) = mint(&engine).await;
+
+        let bank = link(&engine, SECRET, &id, &key, "bank.example").await.unwrap();
+        let game = link(&engine, SECRET, &id, &key, "game.example").await.unwrap();
+        assert_ne!(bank, game);
+
+        // Stable across calls (no duplicate links).
+        assert_eq!(link(&engine, SECRET, &id, &key, "bank.example").await.unwrap(), bank);
+        assert_eq!(engine.get_storage().links.read().await.len(), 2);
+
+        // Each partner resolves only its own identifier.
+        assert_eq!(engine.resolve_pairwise("bank.example", &bank).await.unwrap().id, id);
+        assert!(matches!(engine.resolve_pairwise("game.example", &bank).await, Err(EngineError::IdentityNotFound(_))));
+        assert!(matches!(engine.resolve_pairwise("bank.example", &id).await, Err(EngineError::IdentityNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_stored_link_survives_secret_rotation() {
+        let engine = new_engine();
+        let (id, key) = mint(&engine).await;
+
+        let before = link(&engine, SECRET, &id, &key, "bank.example").await.unwrap();
+        let after = link(&engine, &[9u8; 32], &id, &key, "bank.example").await.unwrap();
+        assert_eq!(before, after, "Existing partner relationships must not be re-keyed");
+
+        assert!(matches!(link(&engine, SECRET, &id, &key, "").await, Err(EngineError::InvalidPartner(_))));
+        assert!(matches!(link(&engine, SECRET, &Uuid::new_v4(), &key, "bank.example").await, Err(EngineError::IdentityNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_proof_token_needs_holder_signature() {
+        let engine = new_engine();
+        let (id, key) = mint(&engine).await;
+        let (_, stranger) = mint(&engine).await;
+        let bank = link(&engine, SECRET, &id, &key, "bank.example").await.unwrap();
+
+        let request = token_request(&key, &id, "bank.example");
+        assert_eq!(engine.authorize_proof_token("bank.example", &bank, &request).await.unwrap().id, id);
+
+        // Single use
+        assert!(matches!(engine.authorize_proof_token("bank.example", &bank, &request).await, Err(EngineError::ReplayDetected)));
+
+        // Anyone who merely knows the pairwise ID cannot mint one
+        let forged = token_request(&stranger, &id, "bank.example");
+        assert!(matches!(engine.authorize_proof_token("bank.example", &bank, &forged).await, Err(EngineError::InvalidSignature)));
+
+        // A signature for one audience does not mint a token for another
+        let game = link(&engine, SECRET, &id, &key, "game.example").await.unwrap();
+        let for_bank = token_request(&key, &id, "bank.example");
+        assert!(matches!(engine.authorize_proof_token("game.example", &game, &for_bank).await, Err(EngineError::InvalidSignature)));
+    }
+
+    #[tokio::test]
+    async fn test_link_needs_holder_signature() {
+        let engine = new_engine();
+        let (id, _) = mint(&engine).await;
+        let (_, stranger) = mint(&engine).await;
+
+        // Knowing the global ID is not enough to learn (or create) a partner handle
+        assert!(matches!(link(&engine, SECRET, &id, &stranger, "bank.example").await, Err(EngineError::InvalidSignature)));
+        assert!(engine.get_storage().links.read().await.is_empty());
+
+        // Nor can a signature for one partner be replayed for another
+        let nonce = Uuid::new_v4().as_bytes().to_vec();
+        let signature: Signature = stranger.sign(&signing::pairwise_link_payload(&id, "bank.example", &nonce));
+        let request = PairwiseLinkRequest { partner_id: "game.example".into(), nonce, signature: signature.to_der().as_bytes().to_vec() };
+        assert!(matches!(engine.pairwise_id_for(SECRET, &id, &request).await, Err(EngineError::InvalidSignature)));
+    }
+}
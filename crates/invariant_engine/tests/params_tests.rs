@@ -0,0 +1,66 @@
+// crates/invariant_engine/tests/params_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+#[cfg(test)]
+mod tests {
+    use invariant_engine::{EngineError, ProtocolParameters};
+    use invariant_shared::Network;
+
+    #[test]
+    fn test_networks_have_distinct_cadences() {
+        let mainnet = ProtocolParameters::for_network(&Network::Mainnet);
+        let testnet = ProtocolParameters::for_network(&Network::Testnet);
+
+        assert_eq!(mainnet, ProtocolParameters::default());
+        assert_eq!(mainnet.heartbeat_interval_minutes, 1380);
+        assert!(testnet.heartbeat_interval_minutes < mainnet.heartbeat_interval_minutes);
+        // Security windows are not relaxed on Testnet.
+        assert_eq!(testnet.nonce_ttl_seconds, mainnet.nonce_ttl_seconds);
+        assert_eq!(testnet.max_timestamp_drift_seconds, mainnet.max_timestamp_drift_seconds);
+        assert!(mainnet.validate().is_ok() && testnet.validate().is_ok());
+    }
+
+    #[test]
+    fn test_policy_sections_override_per_network() {
+        let policy = r#"{
+            "mainnet": { "attestation_ttl_days": 14 },
+            "testnet": { "heartbeat_interval_minutes": 15, "reaper_window_days": 2 }
+        }"#;
+
+        let mainnet = ProtocolParameters::from_policy(policy, &Network::Mainnet).unwrap();
+        assert_eq!(mainnet.attestation_ttl_days, 14);
+        assert_eq!(mainnet.heartbeat_interval_minutes, 1380);
+
+        let testnet = ProtocolParameters::from_policy(policy, &Network::Testnet).unwrap();
+        assert_eq!(testnet.heartbeat_interval_minutes, 15);
+        assert_eq!(testnet.reaper_window_days, 2);
+        assert_eq!(testnet.recovery_delay_hours, ProtocolParameters::for_network(&Network::Testnet).recovery_delay_hours);
+
+        // No section: built-ins.
+        let dev = ProtocolParameters::from_policy(policy, &Network::Dev).unwrap();
+        assert_eq!(dev, ProtocolParameters::for_network(&Network::Dev));
+    }
+
+    #[test]
+    fn test_policy_rejects_bad_input() {
+        let cases = [
+            ("not json", Network::Mainnet),
+            (r#"{ "mainnet": { "heartbeat_interval_minute": 60 } }"#, Network::Mainnet), // typo
+            (r#"{ "mainnet": { "heartbeat_interval_minutes": 0 } }"#, Network::Mainnet),
+            (r#"{ "testnet": { "max_future_skew_seconds": -1 } }"#, Network::Testnet),
+            (r#"{ "mainnet": { "min_genesis_version": 0 } }"#, Network::Mainnet),
+            (r#"{ "mainnet": { "dormancy_score_decay_percent_per_week": 101 } }"#, Network::Mainnet),
+            (r#"{ "testnet": { "reactivation_cooldown_hours": -1 } }"#, Network::Testnet),
+            (r#"{ "testnet": { "nonce_ttl_seconds": "300" } }"#, Network::Testnet),
+            (r#"{ "testnet": 60 }"#, Network::Testnet),
+        ];
+        for (policy, network) in cases {
+            match ProtocolParameters::from_policy(policy, &network) {
+                Err(EngineError::InvalidPolicy(_)) => (),
+                other => panic!("Expected InvalidPolicy for {}: {:?}", policy, other),
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+// crates/invariant_engine/tests/privacy_pass_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Utc, Duration};
+    use uuid::Uuid;
+    use once_cell::sync::Lazy;
+    use p256::ecdsa::{SigningKey, Signature, signature::Signer};
+    use rand_core::OsRng;
+    use p256::pkcs8::EncodePublicKey;
+    use blind_rsa_signatures::{BlindSignature, Options, PublicKey};
+
+    use invariant_engine::{InvariantEngine, IdentityStorage, EngineError, core::EngineConfig, ProtocolParameters};
+    use invariant_engine::privacy_pass::{PrivacyPassIssuer, MAX_TOKENS_PER_BATCH};
+    use invariant_shared::{Identity, IdentityStatus, Network, TokenIssuanceRequest, PrivacyPassToken, verify_privacy_pass_token};
+    use invariant_shared::privacy_pass::{self, TOKEN_TYPE_BLIND_RSA};
+    use invariant_shared::signing;
+    use crate::common::{MockStorage, MockNonceStorage};
+
+    // RSA-2048 keygen is slow in debug builds: share one issuer across tests.
+    static ISSUER: Lazy<PrivacyPassIssuer> = Lazy::new(|| PrivacyPassIssuer::generate().unwrap());
+
+    // --- HELPERS ---
+
+    fn new_engine() -> InvariantEngine<MockStorage, MockNonceStorage> {
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
+        InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config)
+    }
+
+    async fn mint(engine: &InvariantEngine<MockStorage, MockNonceStorage>, status: IdentityStatus) -> (Uuid, SigningKey) {
+        let key = SigningKey::random(&mut OsRng);
+        let identity = Identity {
+            id: Uuid::new_v4(),
+            public_key: key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec(),
+            continuity_score: 30,
+            created_at: Utc::now() - Duration::days(30),
+            last_heartbeat: Utc::now() - Duration::hours(2),
+            last_attestation: Utc::now() - Duration::days(1),
+            status,
+            username: None, is_genesis_eligible: false, fcm_token: None, streak: 30,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            genesis_version: 1, network: Network::Testnet,
+        };
+        engine.get_storage().save_identity(&identity).await.unwrap();
+        (identity.id, key)
+    }
+
+    /// Client side of RFC 9578: blinds token inputs and keeps what it needs to finalize.
+    struct PendingToken {
+        token: PrivacyPassToken,
+        secret: blind_rsa_signatures::Secret,
+        request: Vec<u8>,
+    }
+
+    fn prepare(issuer_pk: &PublicKey, issuer: &PrivacyPassIssuer) -> PendingToken {
+        let token = PrivacyPassToken {
+            token_type: TOKEN_TYPE_BLIND_RSA,
+            nonce: rand::random(),
+            challenge_digest: rand::random(),
+            token_key_id: *issuer.token_key_id(),
+            authenticator: vec![],
+        };
+        let blinded = issuer_pk.blind(&mut rand::thread_rng(), token.token_input(), false, &Options::default()).unwrap();
+
+        let mut request = TOKEN_TYPE_BLIND_RSA.to_be_bytes().to_vec();
+        request.push(issuer.token_key_id()[31]);
+        request.extend_from_slice(&blinded.blind_msg.0);
+        PendingToken { token, secret: blinded.secret, request }
+    }
+
+    fn signed_request(id: Uuid, key: &SigningKey, token_requests: Vec<Vec<u8>>) -> TokenIssuanceRequest {
+        let nonce = Uuid::new_v4().as_bytes().to_vec();
+        let payload = signing::token_issuance_payload(&id, &nonce, &privacy_pass::requests_digest(&token_requests));
+        let signature: Signature = key.sign(&payload);
+        TokenIssuanceRequest { identity_id: id, nonce, token_requests, signature: signature.to_der().as_bytes().to_vec() }
+    }
+
+    #[tokio::test]
+    async fn test_issued_tokens_verify_and_are_unlinkable() {
+        let engine = new_engine();
+        let (id, key) = mint(&engine, IdentityStatus::Active).await;
+        let issuer = &*ISSUER;
+        let issuer_pk = PublicKey::from_spki(issuer.public_key_spki(), None).unwrap();
+
+        let pending: Vec<PendingToken> = (0..3).map(|_| prepare(&issuer_pk, issuer)).collect();
+        let request = signed_request(id, &key, pending.iter().map(|p| p.request.clone()).collect());
+
+        let responses = engine.issue_privacy_pass_tokens(issuer, request).await.unwrap();
+        assert_eq!(responses.len(), 3);
+
+        for (p, blind_sig) in pending.into_iter().zip(responses) {
+            let sig = issuer_pk.finalize(&BlindSignature(blind_sig), &p.secret, None, p.token.token_input(), &Options::default()).unwrap();
+            let token = PrivacyPassToken { authenticator: sig.0, ..p.token };
+
+            // What the partner sees: no identity, just a token that verifies.
+            let wire = token.to_bytes();
+            let parsed = PrivacyPassToken::from_bytes(&wire).unwrap();
+            assert!(verify_privacy_pass_token(&parsed, issuer.public_key_spki()));
+
+            let mut forged = parsed.clone();
+            forged.challenge_digest[0] ^= 0xff;
+            assert!(!verify_privacy_pass_token(&forged, issuer.public_key_spki()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_one_batch_per_day() {
+        let engine = new_engine();
+        let (id, key) = mint(&engine, IdentityStatus::Active).await;
+        let issuer = &*ISSUER;
+        let issuer_pk = PublicKey::from_spki(issuer.public_key_spki(), None).unwrap();
+
+        let first = signed_request(id, &key, vec![prepare(&issuer_pk, issuer).request]);
+        assert!(engine.issue_privacy_pass_tokens(issuer, first).await.is_ok());
+
+        let second = signed_request(id, &key, vec![prepare(&issuer_pk, issuer).request]);
+        assert!(matches!(engine.issue_privacy_pass_tokens(issuer, second).await, Err(EngineError::RateLimitExceeded)));
+
+        // The window reopens after 23h.
+        engine.get_storage().issuances.write().await.insert(id, Utc::now() - Duration::hours(24));
+        let third = signed_request(id, &key, vec![prepare(&issuer_pk, issuer).request]);
+        assert!(engine.issue_privacy_pass_tokens(issuer, third).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_inactive_oversized_and_unsigned_batches() {
+        let engine = new_engine();
+        let issuer = &*ISSUER;
+        let issuer_pk = PublicKey::from_spki(issuer.public_key_spki(), None).unwrap();
+
+        let (stale_id, stale_key) = mint(&engine, IdentityStatus::Stale).await;
+        let req = signed_request(stale_id, &stale_key, vec![prepare(&issuer_pk, issuer).request]);
+        assert!(matches!(engine.issue_privacy_pass_tokens(issuer, req).await, Err(EngineError::InvalidTokenRequest(_))));
+
+        // Still Active on paper, but the hardware proof is past its TTL.
+        let (expired_id, expired_key) = mint(&engine, IdentityStatus::Active).await;
+        engine.get_storage().identities.write().await.get_mut(&expired_id).unwrap().last_attestation =
+            Utc::now() - Duration::days(ProtocolParameters::default().attestation_ttl_days + 1);
+        let req = signed_request(expired_id, &expired_key, vec![prepare(&issuer_pk, issuer).request]);
+        assert!(matches!(engine.issue_privacy_pass_tokens(issuer, req).await, Err(EngineError::AttestationRequired)));
+        assert!(engine.get_storage().issuances.read().await.get(&expired_id).is_none());
+
+        let (id, key) = mint(&engine, IdentityStatus::Active).await;
+        let too_many = vec![vec![0u8; privacy_pass::TOKEN_REQUEST_LEN]; MAX_TOKENS_PER_BATCH + 1];
+        let req = signed_request(id, &key, too_many);
+        assert!(matches!(engine.issue_privacy_pass_tokens(issuer, req).await, Err(EngineError::InvalidTokenRequest(_))));
+
+        // Signature covers the batch: swapping requests after signing fails.
+        let mut req = signed_request(id, &key, vec![prepare(&issuer_pk, issuer).request]);
+        req.token_requests = vec![prepare(&issuer_pk, issuer).request];
+        assert!(matches!(engine.issue_privacy_pass_tokens(issuer, req).await, Err(EngineError::InvalidSignature)));
+
+        // A malformed batch must not burn the day's window.
+        let req = signed_request(id, &key, vec![vec![0u8; 10]]);
+        assert!(matches!(engine.issue_privacy_pass_tokens(issuer, req).await, Err(EngineError::InvalidTokenRequest(_))));
+        assert!(engine.get_storage().issuances.read().await.get(&id).is_none());
+    }
+}
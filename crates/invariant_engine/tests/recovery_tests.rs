@@ -0,0 +1,381 @@
+// crates/invariant_engine/tests/recovery_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Utc, Duration};
+    use uuid::Uuid;
+    use p256::ecdsa::{SigningKey, Signature, signature::Signer};
+    use rand_core::OsRng;
+    use p256::pkcs8::EncodePublicKey;
+
+    use invariant_engine::{InvariantEngine, IdentityStorage, RecoveryStorage, TransparencyStorage, EngineError, core::EngineConfig, ProtocolParameters};
+    use invariant_engine::{clock::AdjustableClock, Clock};
+    use invariant_shared::{
+        Heartbeat, Identity, IdentityStatus, Network, GuardianSetRequest, RecoveryApproval, RecoveryRejection,
+        RecoveryCancelRequest, Recovery, RecoveryStatus, LogEventKind, TransitionReason,
+    };
+    use invariant_shared::signing;
+    use crate::common::{MockStorage, MockNonceStorage};
+
+    // --- HELPERS ---
+
+    fn new_engine() -> InvariantEngine<MockStorage, MockNonceStorage> {
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
+        InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config)
+    }
+
+    async fn mint(engine: &InvariantEngine<MockStorage, MockNonceStorage>) -> (Uuid, SigningKey) {
+        let key = SigningKey::random(&mut OsRng);
+        let identity = Identity {
+            id: Uuid::new_v4(),
+            public_key: key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec(),
+            continuity_score: 90,
+            created_at: Utc::now() - Duration::days(120),
+            last_heartbeat: Utc::now() - Duration::days(40),
+            last_attestation: Utc::now() - Duration::days(40),
+            status: IdentityStatus::Active,
+            username: None, is_genesis_eligible: true, fcm_token: None, streak: 12,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            genesis_version: 1, network: Network::Testnet,
+        };
+        engine.get_storage().save_identity(&identity).await.unwrap();
+        (identity.id, key)
+    }
+
+    fn sign(key: &SigningKey, payload: &[u8]) -> Vec<u8> {
+        let signature: Signature = key.sign(payload);
+        signature.to_der().as_bytes().to_vec()
+    }
+
+    fn nonce() -> Vec<u8> {
+        Uuid::new_v4().as_bytes().to_vec()
+    }
+
+    async fn designate(
+        engine: &InvariantEngine<MockStorage, MockNonceStorage>,
+        id: Uuid,
+        key: &SigningKey,
+        guardian_ids: Vec<Uuid>,
+        threshold: u8,
+    ) -> Result<(), EngineError> {
+        let n = nonce();
+        let signature = sign(key, &signing::guardian_set_payload(&id, &guardian_ids, threshold, &n));
+        engine.set_guardians(GuardianSetRequest { id, guardian_ids, threshold, nonce: n, signature }).await
+    }
+
+    /// Seeds a Pending recovery directly (a real one needs a Google-rooted attestation chain).
+    async fn seed_recovery(engine: &InvariantEngine<MockStorage, MockNonceStorage>, identity_id: Uuid, threshold: u8) -> (Recovery, SigningKey) {
+        let new_key = SigningKey::random(&mut OsRng);
+        let recovery = Recovery {
+            id: Uuid::new_v4(),
+            identity_id,
+            new_public_key: new_key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec(),
+            hardware_brand: Some("Google".into()), hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            status: RecoveryStatus::Pending,
+            approvals: 0,
+            rejections: 0,
+            threshold,
+            initiated_at: Utc::now(),
+            expires_at: Utc::now() + Duration::days(7),
+            executable_at: None,
+        };
+        engine.get_storage().create_recovery(&recovery).await.unwrap();
+        (recovery, new_key)
+    }
+
+    async fn approve(
+        engine: &InvariantEngine<MockStorage, MockNonceStorage>,
+        recovery: &Recovery,
+        guardian_id: Uuid,
+        guardian_key: &SigningKey,
+    ) -> Result<Recovery, EngineError> {
+        let n = nonce();
+        let payload = signing::recovery_approval_payload(&recovery.id, &recovery.identity_id, &recovery.new_public_key, &n);
+        engine.approve_recovery(RecoveryApproval {
+            recovery_id: recovery.id,
+            guardian_id,
+            nonce: n,
+            signature: sign(guardian_key, &payload),
+        }).await
+    }
+
+    async fn reject(
+        engine: &InvariantEngine<MockStorage, MockNonceStorage>,
+        recovery: &Recovery,
+        guardian_id: Uuid,
+        guardian_key: &SigningKey,
+    ) -> Result<Recovery, EngineError> {
+        let n = nonce();
+        let payload = signing::recovery_rejection_payload(&recovery.id, &recovery.identity_id, &recovery.new_public_key, &n);
+        engine.reject_recovery(RecoveryRejection {
+            recovery_id: recovery.id,
+            guardian_id,
+            nonce: n,
+            signature: sign(guardian_key, &payload),
+        }).await
+    }
+
+    // --- TESTS ---
+
+    #[tokio::test]
+    async fn test_guardian_set_validation() {
+        let engine = new_engine();
+        let (id, key) = mint(&engine).await;
+        let (g1, _) = mint(&engine).await;
+        let (g2, _) = mint(&engine).await;
+
+        // Threshold above N
+        assert!(matches!(designate(&engine, id, &key, vec![g1, g2], 3).await, Err(EngineError::RecoveryRejected(_))));
+        // Self-guardianship
+        assert!(matches!(designate(&engine, id, &key, vec![g1, id], 1).await, Err(EngineError::RecoveryRejected(_))));
+        // Duplicate guardian
+        assert!(matches!(designate(&engine, id, &key, vec![g1, g1], 1).await, Err(EngineError::RecoveryRejected(_))));
+        // Unknown guardian
+        assert!(matches!(designate(&engine, id, &key, vec![g1, Uuid::new_v4()], 1).await, Err(EngineError::IdentityNotFound(_))));
+
+        // Signed by someone else's key
+        let intruder = SigningKey::random(&mut OsRng);
+        assert!(matches!(designate(&engine, id, &intruder, vec![g1, g2], 2).await, Err(EngineError::InvalidSignature)));
+
+        designate(&engine, id, &key, vec![g1, g2], 2).await.expect("Valid guardian set rejected");
+        let (stored, threshold) = engine.get_storage().get_guardians(&id).await.unwrap().unwrap();
+        assert_eq!(stored, vec![g1, g2]);
+        assert_eq!(threshold, 2);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_threshold_delay_and_finalize() {
+        let engine = new_engine();
+        let (id, key) = mint(&engine).await;
+        let (g1, k1) = mint(&engine).await;
+        let (g2, k2) = mint(&engine).await;
+        let (g3, _) = mint(&engine).await;
+        designate(&engine, id, &key, vec![g1, g2, g3], 2).await.unwrap();
+
+        let (recovery, _) = seed_recovery(&engine, id, 2).await;
+
+        // 1. Non-guardian cannot vote
+        let (outsider, outsider_key) = mint(&engine).await;
+        assert!(matches!(approve(&engine, &recovery, outsider, &outsider_key).await, Err(EngineError::RecoveryRejected(_))));
+
+        // 2. Guardian ID with someone else's key
+        assert!(matches!(approve(&engine, &recovery, g1, &k2).await, Err(EngineError::InvalidSignature)));
+
+        // 3. First approval (and a duplicate) keeps it Pending
+        let r = approve(&engine, &recovery, g1, &k1).await.unwrap();
+        assert_eq!((r.approvals, r.status.clone()), (1, RecoveryStatus::Pending));
+        let r = approve(&engine, &recovery, g1, &k1).await.unwrap();
+        assert_eq!((r.approvals, r.status.clone()), (1, RecoveryStatus::Pending));
+
+        // 4. Second guardian meets the threshold and opens the cancellation window
+        let r = approve(&engine, &recovery, g2, &k2).await.unwrap();
+        assert_eq!(r.status, RecoveryStatus::Approved);
+        assert!(r.executable_at.unwrap() > Utc::now() + Duration::hours(71));
+
+        // 5. Too early
+        assert!(matches!(engine.finalize_recovery(recovery.id).await, Err(EngineError::RecoveryRejected(_))));
+
+        // 6. Time-warp past the window
+        let mut warped = engine.get_storage().get_recovery(&recovery.id).await.unwrap().unwrap();
+        warped.executable_at = Some(Utc::now() - Duration::minutes(1));
+        engine.get_storage().update_recovery_status(&warped).await.unwrap();
+
+        let done = engine.finalize_recovery(recovery.id).await.expect("Finalize failed");
+        assert_eq!(done.status, RecoveryStatus::Completed);
+
+        // Key swapped, continuity preserved
+        let identity = engine.get_storage().get_identity(&id).await.unwrap().unwrap();
+        assert_eq!(identity.public_key, recovery.new_public_key);
+        assert_eq!(identity.continuity_score, 90);
+
+        let events: Vec<String> = engine.get_storage().get_recovery_events(&recovery.id).await.unwrap()
+            .into_iter().map(|e| e.event).collect();
+        assert_eq!(events, vec!["approved", "approved", "approved", "threshold_met", "completed"]);
+
+        // Publicly auditable: the key swap is in the transparency log
+        let log = engine.get_storage().get_log_entries(0, 10).await.unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].kind, LogEventKind::Recovery);
+        assert_eq!(log[0].identity_id, id);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_reactivates_dormant_identity() {
+        let params = ProtocolParameters { dormancy_score_decay_percent_per_week: 10, ..ProtocolParameters::default() };
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params };
+        let engine = InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config);
+        let (id, _) = mint(&engine).await;
+        let mut identity = engine.get_storage().get_identity(&id).await.unwrap().unwrap();
+        identity.status = IdentityStatus::Dormant;
+        engine.get_storage().save_identity(&identity).await.unwrap();
+
+        let (mut recovery, _) = seed_recovery(&engine, id, 1).await;
+        recovery.status = RecoveryStatus::Approved;
+        recovery.executable_at = Some(Utc::now() - Duration::minutes(1));
+        engine.get_storage().update_recovery_status(&recovery).await.unwrap();
+        engine.finalize_recovery(recovery.id).await.expect("Finalize failed");
+
+        // Silent for 40 days (no reaper row): 5 full weeks at 10% each, and the streak restarts.
+        let identity = engine.get_storage().get_identity(&id).await.unwrap().unwrap();
+        assert_eq!((identity.status, identity.continuity_score, identity.streak), (IdentityStatus::Active, 45, 0));
+
+        let history = engine.reactivation_history(id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].reason, TransitionReason::Recovery);
+        assert_eq!(history[0].dormant_since, history[0].silent_since);
+        assert_eq!((history[0].score_before, history[0].score_after), (90, 45));
+        assert_eq!((history[0].streak_before, history[0].streak_after), (0, 0));
+        assert_eq!(history[0].cooling_off_until, None);
+    }
+
+    #[tokio::test]
+    async fn test_recovered_key_trust_dates_from_finalize() {
+        let clock = AdjustableClock::new(Utc::now());
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
+        let ttl = Duration::days(config.params.attestation_ttl_days);
+        let engine = InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config)
+            .with_clock(clock.clone());
+        let (id, _) = mint(&engine).await;
+
+        // Approvals and the cancellation window took longer than an attestation TTL.
+        let (mut recovery, new_key) = seed_recovery(&engine, id, 1).await;
+        clock.advance(ttl + Duration::days(3));
+        recovery.status = RecoveryStatus::Approved;
+        recovery.executable_at = Some(clock.now());
+        engine.get_storage().update_recovery_status(&recovery).await.unwrap();
+        let finalized_at = clock.now();
+        engine.finalize_recovery(recovery.id).await.expect("Finalize failed");
+
+        let identity = engine.get_storage().get_identity(&id).await.unwrap().unwrap();
+        assert_eq!(identity.last_attestation, finalized_at);
+
+        let heartbeat = |key: &SigningKey| {
+            let n = nonce();
+            let timestamp = clock.now();
+            Heartbeat {
+                identity_id: id,
+                device_signature: sign(key, &signing::heartbeat_payload(&id, &n, &timestamp)),
+                nonce: n,
+                timestamp,
+                attestation_chain: None,
+            }
+        };
+
+        // The new key counts for a full TTL from the swap...
+        clock.advance(ttl);
+        engine.process_heartbeat(heartbeat(&new_key)).await.expect("Heartbeat at the TTL boundary rejected");
+
+        // ...and not a second longer.
+        clock.advance(Duration::seconds(1));
+        assert!(matches!(engine.process_heartbeat(heartbeat(&new_key)).await, Err(EngineError::AttestationRequired)));
+    }
+
+    #[tokio::test]
+    async fn test_recovery_cancelled_by_original_key() {
+        let engine = new_engine();
+        let (id, key) = mint(&engine).await;
+        let (g1, k1) = mint(&engine).await;
+        designate(&engine, id, &key, vec![g1], 1).await.unwrap();
+
+        let (recovery, new_key) = seed_recovery(&engine, id, 1).await;
+        approve(&engine, &recovery, g1, &k1).await.unwrap();
+
+        // The attacker's new key cannot cancel on the holder's behalf...
+        let n = nonce();
+        let forged = RecoveryCancelRequest {
+            recovery_id: recovery.id,
+            signature: sign(&new_key, &signing::recovery_cancel_payload(&recovery.id, &n)),
+            nonce: n,
+        };
+        assert!(matches!(engine.cancel_recovery(forged).await, Err(EngineError::InvalidSignature)));
+
+        // ...but the original device can.
+        let n = nonce();
+        let veto = RecoveryCancelRequest {
+            recovery_id: recovery.id,
+            signature: sign(&key, &signing::recovery_cancel_payload(&recovery.id, &n)),
+            nonce: n,
+        };
+        let cancelled = engine.cancel_recovery(veto).await.unwrap();
+        assert_eq!(cancelled.status, RecoveryStatus::Cancelled);
+
+        assert!(matches!(engine.finalize_recovery(recovery.id).await, Err(EngineError::RecoveryRejected(_))));
+        let identity = engine.get_storage().get_identity(&id).await.unwrap().unwrap();
+        assert_ne!(identity.public_key, recovery.new_public_key);
+    }
+
+    #[tokio::test]
+    async fn test_guardians_reject_recovery() {
+        let engine = new_engine();
+        let (id, key) = mint(&engine).await;
+        let (g1, k1) = mint(&engine).await;
+        let (g2, k2) = mint(&engine).await;
+        let (g3, k3) = mint(&engine).await;
+        designate(&engine, id, &key, vec![g1, g2, g3], 2).await.unwrap();
+
+        let (recovery, _) = seed_recovery(&engine, id, 2).await;
+
+        // An approval signature does not count as a rejection
+        let n = nonce();
+        let approval_sig = sign(&k3, &signing::recovery_approval_payload(&recovery.id, &id, &recovery.new_public_key, &n));
+        let forged = RecoveryRejection { recovery_id: recovery.id, guardian_id: g3, nonce: n, signature: approval_sig };
+        assert!(matches!(engine.reject_recovery(forged).await, Err(EngineError::InvalidSignature)));
+
+        // 2-of-3 is still reachable after one refusal, and the refusing guardian cannot flip to approve
+        let r = reject(&engine, &recovery, g3, &k3).await.unwrap();
+        assert_eq!((r.rejections, r.status.clone()), (1, RecoveryStatus::Pending));
+        let r = approve(&engine, &recovery, g3, &k3).await.unwrap();
+        assert_eq!(r.approvals, 0);
+
+        // A second refusal makes the threshold unreachable
+        let r = reject(&engine, &recovery, g2, &k2).await.unwrap();
+        assert_eq!((r.rejections, r.status.clone()), (2, RecoveryStatus::Rejected));
+        assert!(matches!(approve(&engine, &recovery, g1, &k1).await, Err(EngineError::RecoveryRejected(_))));
+
+        // The identity is free again
+        assert!(engine.get_storage().get_open_recovery(&id, Utc::now()).await.unwrap().is_none());
+        designate(&engine, id, &key, vec![g1, g2], 1).await.expect("Guardian set still blocked");
+
+        let events: Vec<String> = engine.get_storage().get_recovery_events(&recovery.id).await.unwrap()
+            .into_iter().map(|e| e.event).collect();
+        assert_eq!(events, vec!["rejected", "approved", "rejected"]);
+    }
+
+    #[tokio::test]
+    async fn test_pending_recovery_expires() {
+        let clock = AdjustableClock::new(Utc::now());
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
+        let engine = InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config)
+            .with_clock(clock.clone());
+        let (id, key) = mint(&engine).await;
+        let (g1, k1) = mint(&engine).await;
+        let (g2, _) = mint(&engine).await;
+        designate(&engine, id, &key, vec![g1, g2], 2).await.unwrap();
+
+        // A squatted recovery that never gathers approvals...
+        let (stale, _) = seed_recovery(&engine, id, 2).await;
+        assert!(matches!(designate(&engine, id, &key, vec![g1], 1).await, Err(EngineError::RecoveryRejected(_))));
+
+        // ...stops blocking once the expiry window has passed.
+        clock.advance(Duration::days(8));
+        assert!(matches!(approve(&engine, &stale, g1, &k1).await, Err(EngineError::RecoveryRejected(_))));
+        assert!(engine.get_storage().get_open_recovery(&id, clock.now()).await.unwrap().is_none());
+        designate(&engine, id, &key, vec![g1, g2], 2).await.expect("Expired recovery still blocks the holder");
+
+        // Opening the next attempt closes the stale one for good.
+        let (mut next, _) = seed_recovery(&engine, id, 2).await;
+        next.id = Uuid::new_v4();
+        next.initiated_at = clock.now();
+        next.expires_at = clock.now() + Duration::days(7);
+        engine.get_storage().create_recovery(&next).await.unwrap();
+        let stale = engine.get_storage().get_recovery(&stale.id).await.unwrap().unwrap();
+        assert_eq!(stale.status, RecoveryStatus::Expired);
+    }
+}
@@ -0,0 +1,156 @@
+// crates/invariant_engine/tests/revocation_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+    use p256::ecdsa::{SigningKey, signature::Signer};
+    use p256::pkcs8::EncodePublicKey;
+    use rand_core::OsRng;
+
+    use invariant_engine::{
+        InvariantEngine, IdentityStorage,
+        EngineError, core::EngineConfig, ProtocolParameters,
+    };
+    use invariant_shared::{
+        Identity, IdentityStatus, TransitionReason, Network, LogEventKind, RevocationReason, SelfRevocationRequest,
+    };
+    use invariant_shared::signing;
+    use crate::common::{MockStorage, MockNonceStorage};
+
+    fn new_engine() -> InvariantEngine<MockStorage, MockNonceStorage> {
+        let config = EngineConfig { network: Network::Mainnet, genesis_version: 1, params: ProtocolParameters::default() };
+        InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config)
+    }
+
+    async fn mint(engine: &InvariantEngine<MockStorage, MockNonceStorage>, key: &SigningKey) -> Uuid {
+        let now = Utc::now();
+        let identity = Identity {
+            id: Uuid::new_v4(),
+            public_key: key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec(),
+            continuity_score: 5, streak: 0,
+            created_at: now - Duration::days(60),
+            last_heartbeat: now - Duration::days(1),
+            last_attestation: now,
+            status: IdentityStatus::Active,
+            username: None, is_genesis_eligible: false, fcm_token: None,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            genesis_version: 1, network: Network::Mainnet,
+        };
+        engine.get_storage().save_identity(&identity).await.unwrap();
+        identity.id
+    }
+
+    fn self_revocation(key: &SigningKey, id: Uuid, reason: RevocationReason, nonce: u8) -> SelfRevocationRequest {
+        let signature: p256::ecdsa::Signature = key.sign(&signing::revocation_payload(&id, reason, &[nonce]));
+        SelfRevocationRequest { id, reason, nonce: vec![nonce], signature: signature.to_der().as_bytes().to_vec() }
+    }
+
+    #[tokio::test]
+    async fn test_operator_revocation_is_logged_and_terminal() {
+        let engine = new_engine();
+        let id = mint(&engine, &SigningKey::random(&mut OsRng)).await;
+
+        let revocation = engine.revoke_identity(id, RevocationReason::Fraud, None).await.unwrap();
+        assert_eq!(revocation.sequence, 1);
+        assert_eq!(revocation.revoked_by, None);
+
+        let stored = engine.get_storage().get_identity(&id).await.unwrap().unwrap();
+        assert_eq!(stored.status, IdentityStatus::Revoked);
+
+        let history = engine.transition_history(id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].reason, TransitionReason::Revocation);
+
+        let log = engine.get_storage().log.read().await.clone();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].kind, LogEventKind::Revocation);
+        assert_eq!(log[0].identity_id, id);
+
+        // A second revocation is rejected and records nothing.
+        match engine.revoke_identity(id, RevocationReason::LeakedKeybox, None).await {
+            Err(EngineError::IllegalTransition(_)) => (),
+            other => panic!("Expected IllegalTransition, got {:?}", other),
+        }
+        assert_eq!(engine.get_storage().revocations.read().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_self_revocation_requires_own_signature() {
+        let engine = new_engine();
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key).await;
+
+        // Signed by another key.
+        let imposter = SigningKey::random(&mut OsRng);
+        let forged = self_revocation(&imposter, id, RevocationReason::CompromisedKey, 1);
+        assert!(engine.process_self_revocation(forged).await.is_err());
+        assert_eq!(engine.get_storage().get_identity(&id).await.unwrap().unwrap().status, IdentityStatus::Active);
+
+        let revocation = engine.process_self_revocation(self_revocation(&key, id, RevocationReason::CompromisedKey, 2)).await.unwrap();
+        assert_eq!(revocation.revoked_by, Some(id));
+        assert_eq!(revocation.reason, RevocationReason::CompromisedKey);
+        assert_eq!(engine.transition_history(id).await.unwrap()[0].actor_id, Some(id));
+    }
+
+    #[tokio::test]
+    async fn test_self_revocation_rejects_operator_reasons() {
+        let engine = new_engine();
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key).await;
+
+        for reason in [RevocationReason::Fraud, RevocationReason::LeakedKeybox] {
+            match engine.process_self_revocation(self_revocation(&key, id, reason, 1)).await {
+                Err(EngineError::InvalidRevocation(_)) => (),
+                other => panic!("Expected InvalidRevocation, got {:?}", other),
+            }
+        }
+        assert!(engine.get_storage().revocations.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_partner_feed_is_scoped_and_paginated() {
+        let engine = new_engine();
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            ids.push(mint(&engine, &SigningKey::random(&mut OsRng)).await);
+        }
+
+        // Partner A knows all three subjects, partner B only the last one.
+        let mut pairwise_a = Vec::new();
+        {
+            let mut links = engine.get_storage().links.write().await;
+            for id in &ids {
+                let pairwise = Uuid::new_v4();
+                links.push(("partner-a".to_string(), pairwise, *id));
+                pairwise_a.push(pairwise);
+            }
+            links.push(("partner-b".to_string(), Uuid::new_v4(), ids[2]));
+        }
+        for id in &ids {
+            engine.revoke_identity(*id, RevocationReason::Fraud, None).await.unwrap();
+        }
+
+        let first = engine.partner_revocation_feed("partner-a", 0, 2).await.unwrap();
+        assert_eq!(first.iter().map(|n| n.subject_id).collect::<Vec<_>>(), pairwise_a[..2]);
+        let rest = engine.partner_revocation_feed("partner-a", first[1].sequence, 2).await.unwrap();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].subject_id, pairwise_a[2]);
+
+        // Partners only ever see their own pairwise IDs.
+        let feed_b = engine.partner_revocation_feed("partner-b", 0, 100).await.unwrap();
+        assert_eq!(feed_b.len(), 1);
+        assert_eq!(feed_b[0].sequence, 3);
+        assert!(feed_b.iter().all(|n| !ids.contains(&n.subject_id) && !pairwise_a.contains(&n.subject_id)));
+
+        // A zero limit is clamped up rather than returning an empty page.
+        assert_eq!(engine.partner_revocation_feed("partner-a", 0, 0).await.unwrap().len(), 1);
+        assert!(engine.partner_revocation_feed("", 0, 10).await.is_err());
+    }
+}
@@ -0,0 +1,180 @@
+// crates/invariant_engine/tests/streak_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+    use uuid::Uuid;
+    use p256::ecdsa::{SigningKey, signature::Signer};
+    use p256::pkcs8::EncodePublicKey;
+    use rand_core::OsRng;
+
+    use invariant_engine::{
+        InvariantEngine, IdentityStorage,
+        EngineError, core::EngineConfig, ProtocolParameters,
+    };
+    use invariant_engine::clock::AdjustableClock;
+    use invariant_engine::streak::{advance_streak, local_day, StreakUpdate};
+    use invariant_shared::{
+        Identity, IdentityStatus, Heartbeat, Network,
+        StreakState, StreakTimezoneRequest,
+    };
+    use invariant_shared::signing;
+    use crate::common::{MockStorage, MockNonceStorage};
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn state(streak: u64, last_day: NaiveDate, freezes: u32, timezone: &str) -> StreakState {
+        StreakState { streak, last_day: Some(last_day), freezes, timezone: timezone.to_string() }
+    }
+
+    fn new_engine(clock: &AdjustableClock) -> InvariantEngine<MockStorage, MockNonceStorage> {
+        let config = EngineConfig { network: Network::Mainnet, genesis_version: 1, params: ProtocolParameters::default() };
+        InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config).with_clock(clock.clone())
+    }
+
+    async fn mint(engine: &InvariantEngine<MockStorage, MockNonceStorage>, key: &SigningKey) -> Uuid {
+        let now = engine.now();
+        let identity = Identity {
+            id: Uuid::new_v4(),
+            public_key: key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec(),
+            continuity_score: 0, streak: 0,
+            created_at: now,
+            last_heartbeat: now,
+            last_attestation: now,
+            status: IdentityStatus::Active,
+            username: None, is_genesis_eligible: false, fcm_token: None,
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            genesis_version: 1, network: Network::Mainnet,
+        };
+        engine.get_storage().save_identity(&identity).await.unwrap();
+        identity.id
+    }
+
+    async fn tap(engine: &InvariantEngine<MockStorage, MockNonceStorage>, key: &SigningKey, id: Uuid, nonce: u8) -> Result<u64, EngineError> {
+        let timestamp = engine.now();
+        // Keep trust fresh: these tests are about days, not attestation.
+        engine.get_storage().identities.write().await.get_mut(&id).unwrap().last_attestation = timestamp;
+        let signature: p256::ecdsa::Signature = key.sign(&signing::heartbeat_payload(&id, &[nonce], &timestamp));
+        engine.process_heartbeat(Heartbeat { identity_id: id, device_signature: signature.to_der().as_bytes().to_vec(), nonce: vec![nonce], timestamp, attestation_chain: None }).await
+    }
+
+    #[test]
+    fn test_days_follow_declared_timezone() {
+        // 00:30 and 23:30 UTC: one UTC day, but two New York days (20:30 and 19:30 EDT).
+        let (early, late) = (utc(2026, 6, 2, 0, 30), utc(2026, 6, 2, 23, 30));
+        assert_eq!(local_day(early, "UTC"), local_day(late, "UTC"));
+        assert_eq!(local_day(early, "America/New_York"), day(2026, 6, 1));
+        assert_eq!(local_day(late, "America/New_York"), day(2026, 6, 2));
+        // Unknown zones fall back to UTC.
+        assert_eq!(local_day(early, "Mars/Olympus_Mons"), day(2026, 6, 2));
+
+        let params = ProtocolParameters::default();
+        let (_, update) = advance_streak(&state(4, day(2026, 6, 2), 0, "UTC"), late, &params);
+        assert_eq!(update, StreakUpdate::SameDay);
+        let (next, update) = advance_streak(&state(4, day(2026, 6, 1), 0, "America/New_York"), late, &params);
+        assert_eq!((next.streak, next.last_day, update), (5, Some(day(2026, 6, 2)), StreakUpdate::Extended));
+    }
+
+    #[test]
+    fn test_grace_window_fills_the_missed_day() {
+        let params = ProtocolParameters::default(); // 180 minutes of grace
+        let paris = state(3, day(2026, 3, 10), 0, "Europe/Paris");
+
+        // 02:30 Paris (01:30 UTC) on the 12th still counts for the 11th.
+        let (next, update) = advance_streak(&paris, utc(2026, 3, 12, 1, 30), &params);
+        assert_eq!((next.streak, next.last_day, update), (4, Some(day(2026, 3, 11)), StreakUpdate::Extended));
+
+        // 03:30 Paris is past the window: the 11th is missed and there is no freeze.
+        let (next, update) = advance_streak(&paris, utc(2026, 3, 12, 2, 30), &params);
+        assert_eq!((next.streak, next.last_day, update), (1, Some(day(2026, 3, 12)), StreakUpdate::Started));
+
+        // Grace never credits a day that was already counted.
+        let (_, update) = advance_streak(&state(3, day(2026, 3, 11), 0, "Europe/Paris"), utc(2026, 3, 12, 1, 30), &params);
+        assert_eq!(update, StreakUpdate::Extended);
+    }
+
+    #[test]
+    fn test_freezes_are_earned_and_spent() {
+        let params = ProtocolParameters::default(); // one freeze per 7 days, at most 2
+
+        let (next, _) = advance_streak(&state(6, day(2026, 1, 6), 0, "UTC"), utc(2026, 1, 7, 12, 0), &params);
+        assert_eq!((next.streak, next.freezes), (7, 1));
+
+        // Banked freezes are capped.
+        let (next, _) = advance_streak(&state(13, day(2026, 1, 13), 2, "UTC"), utc(2026, 1, 14, 12, 0), &params);
+        assert_eq!((next.streak, next.freezes), (14, 2));
+
+        // Two missed days, two freezes.
+        let (next, update) = advance_streak(&state(9, day(2026, 1, 9), 2, "UTC"), utc(2026, 1, 12, 12, 0), &params);
+        assert_eq!((next.streak, next.freezes, update), (10, 0, StreakUpdate::Frozen(2)));
+
+        // Two missed days, one freeze: the streak restarts and keeps the freeze.
+        let (next, update) = advance_streak(&state(9, day(2026, 1, 9), 1, "UTC"), utc(2026, 1, 12, 12, 0), &params);
+        assert_eq!((next.streak, next.freezes, update), (1, 1, StreakUpdate::Started));
+    }
+
+    #[tokio::test]
+    async fn test_daily_heartbeats_build_a_streak() {
+        let clock = AdjustableClock::new(utc(2026, 5, 1, 8, 0));
+        let engine = new_engine(&clock);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key).await;
+
+        // Previously the streak could never pass 1 at a 23h cadence.
+        for n in 1..=8u8 {
+            tap(&engine, &key, id, n).await.unwrap();
+            clock.advance(Duration::hours(24));
+        }
+        let streak = engine.streak(id).await.unwrap();
+        assert_eq!((streak.streak, streak.freezes, streak.last_day), (8, 1, Some(day(2026, 5, 8))));
+
+        // Skip a day: the earned freeze keeps the streak alive.
+        clock.advance(Duration::hours(24));
+        tap(&engine, &key, id, 9).await.unwrap();
+        let streak = engine.streak(id).await.unwrap();
+        assert_eq!((streak.streak, streak.freezes), (9, 0));
+
+        // Skip another: nothing left to spend.
+        clock.advance(Duration::hours(48));
+        tap(&engine, &key, id, 10).await.unwrap();
+        assert_eq!(engine.streak(id).await.unwrap().streak, 1);
+    }
+
+    #[tokio::test]
+    async fn test_set_streak_timezone() {
+        let clock = AdjustableClock::new(utc(2026, 5, 1, 8, 0));
+        let engine = new_engine(&clock);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key).await;
+
+        let request = |timezone: &str, nonce: u8, key: &SigningKey| {
+            let signature: p256::ecdsa::Signature = key.sign(&signing::streak_timezone_payload(&id, timezone, &[nonce]));
+            StreakTimezoneRequest { id, timezone: timezone.to_string(), nonce: vec![nonce], signature: signature.to_der().as_bytes().to_vec() }
+        };
+
+        assert!(matches!(engine.set_streak_timezone(request("Europe/Atlantis", 1, &key)).await, Err(EngineError::InvalidTimezone(_))));
+        assert!(engine.set_streak_timezone(request("Asia/Tokyo", 2, &SigningKey::random(&mut OsRng))).await.is_err());
+        assert_eq!(engine.streak(id).await.unwrap().timezone, "UTC");
+
+        let updated = engine.set_streak_timezone(request("Asia/Tokyo", 3, &key)).await.unwrap();
+        assert_eq!(updated.timezone, "Asia/Tokyo");
+        assert_eq!(engine.streak(id).await.unwrap(), updated);
+
+        // 08:00 UTC is 17:00 in Tokyo.
+        tap(&engine, &key, id, 4).await.unwrap();
+        assert_eq!(engine.streak(id).await.unwrap().last_day, Some(day(2026, 5, 1)));
+        assert!(matches!(engine.streak(Uuid::new_v4()).await, Err(EngineError::IdentityNotFound(_))));
+    }
+}
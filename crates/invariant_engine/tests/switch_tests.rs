@@ -0,0 +1,193 @@
+// crates/invariant_engine/tests/switch_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+    use p256::ecdsa::{SigningKey, signature::Signer};
+    use p256::pkcs8::EncodePublicKey;
+    use rand_core::OsRng;
+
+    use invariant_engine::{InvariantEngine, IdentityStorage, EngineError, core::EngineConfig, ProtocolParameters};
+    use invariant_engine::clock::AdjustableClock;
+    use invariant_engine::deadmans_switch::next_switch_state;
+    use invariant_engine::ports::Clock;
+    use invariant_shared::{
+        Identity, IdentityStatus, Network,
+        DeadMansSwitch, SwitchStatus, SwitchArmRequest, SwitchDisarmRequest,
+    };
+    use invariant_shared::signing;
+    use crate::common::{MockStorage, MockNonceStorage};
+
+    fn new_engine(clock: &AdjustableClock) -> InvariantEngine<MockStorage, MockNonceStorage> {
+        let config = EngineConfig { network: Network::Mainnet, genesis_version: 1, params: ProtocolParameters::default() };
+        InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config).with_clock(clock.clone())
+    }
+
+    async fn mint(engine: &InvariantEngine<MockStorage, MockNonceStorage>, key: &SigningKey, fcm_token: &str) -> Uuid {
+        let now = engine.now();
+        let identity = Identity {
+            id: Uuid::new_v4(),
+            public_key: key.verifying_key().to_public_key_der().unwrap().as_bytes().to_vec(),
+            continuity_score: 5, streak: 0,
+            created_at: now - Duration::days(60),
+            last_heartbeat: now,
+            last_attestation: now,
+            status: IdentityStatus::Active,
+            username: None, is_genesis_eligible: false, fcm_token: Some(fcm_token.to_string()),
+            hardware_brand: None, hardware_device: None, hardware_product: None,
+            security_level: None, os_patch_level: None,
+            genesis_version: 1, network: Network::Mainnet,
+        };
+        engine.get_storage().save_identity(&identity).await.unwrap();
+        identity.id
+    }
+
+    fn arm_request(key: &SigningKey, id: Uuid, beneficiary_id: Uuid, threshold_days: u32, nonce: u8) -> SwitchArmRequest {
+        let signature: p256::ecdsa::Signature = key.sign(&signing::switch_arm_payload(&id, &beneficiary_id, threshold_days, &[nonce]));
+        SwitchArmRequest { id, beneficiary_id, threshold_days, nonce: vec![nonce], signature: signature.to_der().as_bytes().to_vec() }
+    }
+
+    fn disarm_request(key: &SigningKey, id: Uuid, nonce: u8) -> SwitchDisarmRequest {
+        let signature: p256::ecdsa::Signature = key.sign(&signing::switch_disarm_payload(&id, &[nonce]));
+        SwitchDisarmRequest { id, nonce: vec![nonce], signature: signature.to_der().as_bytes().to_vec() }
+    }
+
+    async fn touch(engine: &InvariantEngine<MockStorage, MockNonceStorage>, id: Uuid) {
+        let mut identities = engine.get_storage().identities.write().await;
+        identities.get_mut(&id).unwrap().last_heartbeat = engine.now();
+    }
+
+    #[test]
+    fn test_switch_state_table() {
+        let now = Utc::now();
+        let grace = Duration::hours(72);
+        let armed = DeadMansSwitch {
+            identity_id: Uuid::new_v4(),
+            status: SwitchStatus::Armed,
+            threshold_days: 30,
+            beneficiary_id: Some(Uuid::new_v4()),
+            pending_since: None,
+            triggered_at: None,
+        };
+
+        assert_eq!(next_switch_state(&armed, now - Duration::days(29), now, grace), None);
+        let pending = next_switch_state(&armed, now - Duration::days(30), now, grace).unwrap();
+        assert_eq!((pending.status, pending.pending_since), (SwitchStatus::Pending, Some(now)));
+
+        // Grace period still running.
+        let later = now + Duration::hours(71);
+        assert_eq!(next_switch_state(&pending, now - Duration::days(30), later, grace), None);
+        // A heartbeat arrived: back to armed.
+        let cancelled = next_switch_state(&pending, later, later, grace).unwrap();
+        assert_eq!((cancelled.status, cancelled.pending_since), (SwitchStatus::Armed, None));
+        // Grace period over.
+        let fired_at = now + grace;
+        let triggered = next_switch_state(&pending, now - Duration::days(30), fired_at, grace).unwrap();
+        assert_eq!((triggered.status, triggered.triggered_at), (SwitchStatus::Triggered, Some(fired_at)));
+
+        for status in [SwitchStatus::Inactive, SwitchStatus::Triggered] {
+            let idle = DeadMansSwitch { status, ..armed.clone() };
+            assert_eq!(next_switch_state(&idle, now - Duration::days(400), now, grace), None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_arm_validates_request() {
+        let clock = AdjustableClock::new(Utc::now());
+        let engine = new_engine(&clock);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key, "holder").await;
+        let beneficiary = mint(&engine, &SigningKey::random(&mut OsRng), "beneficiary").await;
+
+        assert!(matches!(engine.arm_switch(arm_request(&key, id, id, 30, 1)).await, Err(EngineError::InvalidSwitch(_))));
+        assert!(matches!(engine.arm_switch(arm_request(&key, id, beneficiary, 0, 2)).await, Err(EngineError::InvalidSwitch(_))));
+        assert!(matches!(engine.arm_switch(arm_request(&key, id, Uuid::new_v4(), 30, 3)).await, Err(EngineError::IdentityNotFound(_))));
+
+        let imposter = SigningKey::random(&mut OsRng);
+        assert!(engine.arm_switch(arm_request(&imposter, id, beneficiary, 30, 4)).await.is_err());
+        assert_eq!(engine.get_switch(id).await.unwrap().status, SwitchStatus::Inactive);
+
+        let switch = engine.arm_switch(arm_request(&key, id, beneficiary, 30, 5)).await.unwrap();
+        assert_eq!(switch.status, SwitchStatus::Armed);
+        assert_eq!(switch.beneficiary_id, Some(beneficiary));
+        assert_eq!(engine.get_switch(id).await.unwrap(), switch);
+
+        // Replayed nonce.
+        assert!(matches!(engine.arm_switch(arm_request(&key, id, beneficiary, 30, 5)).await, Err(EngineError::ReplayDetected)));
+    }
+
+    #[tokio::test]
+    async fn test_sweep_warns_cancels_then_triggers() {
+        let clock = AdjustableClock::new(Utc::now());
+        let engine = new_engine(&clock);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key, "holder").await;
+        let beneficiary = mint(&engine, &SigningKey::random(&mut OsRng), "beneficiary").await;
+        engine.arm_switch(arm_request(&key, id, beneficiary, 30, 1)).await.unwrap();
+
+        // Keep the beneficiary alive; only the holder goes silent.
+        clock.advance(Duration::days(29));
+        touch(&engine, beneficiary).await;
+        assert!(engine.sweep_switches().await.unwrap().warned.is_empty());
+
+        // Threshold missed: the holder is warned.
+        clock.advance(Duration::days(1));
+        let sweep = engine.sweep_switches().await.unwrap();
+        assert_eq!(sweep.warned.len(), 1);
+        assert_eq!((sweep.warned[0].recipient_id, sweep.warned[0].push_token.as_deref()), (id, Some("holder")));
+        assert_eq!(engine.get_switch(id).await.unwrap().status, SwitchStatus::Pending);
+
+        // A heartbeat inside the grace period cancels it.
+        clock.advance(Duration::hours(10));
+        touch(&engine, id).await;
+        let sweep = engine.sweep_switches().await.unwrap();
+        assert_eq!(sweep.cancelled, 1);
+        assert_eq!(engine.get_switch(id).await.unwrap().status, SwitchStatus::Armed);
+
+        // Silent again: warned, then triggered once the grace period is over.
+        clock.advance(Duration::days(30));
+        assert_eq!(engine.sweep_switches().await.unwrap().warned.len(), 1);
+        clock.advance(Duration::hours(71));
+        assert!(engine.sweep_switches().await.unwrap().triggered.is_empty());
+        clock.advance(Duration::hours(1));
+        let sweep = engine.sweep_switches().await.unwrap();
+        assert_eq!(sweep.triggered.len(), 1);
+        assert_eq!(sweep.triggered[0].holder_id, id);
+        assert_eq!((sweep.triggered[0].recipient_id, sweep.triggered[0].push_token.as_deref()), (beneficiary, Some("beneficiary")));
+
+        let switch = engine.get_switch(id).await.unwrap();
+        assert_eq!((switch.status, switch.triggered_at), (SwitchStatus::Triggered, Some(clock.now())));
+
+        // Triggered is final for the sweep.
+        clock.advance(Duration::days(1));
+        let sweep = engine.sweep_switches().await.unwrap();
+        assert!(sweep.warned.is_empty() && sweep.triggered.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_disarm_during_grace_period() {
+        let clock = AdjustableClock::new(Utc::now());
+        let engine = new_engine(&clock);
+        let key = SigningKey::random(&mut OsRng);
+        let id = mint(&engine, &key, "holder").await;
+        let beneficiary = mint(&engine, &SigningKey::random(&mut OsRng), "beneficiary").await;
+        engine.arm_switch(arm_request(&key, id, beneficiary, 7, 1)).await.unwrap();
+
+        clock.advance(Duration::days(7));
+        assert_eq!(engine.sweep_switches().await.unwrap().warned.len(), 1);
+
+        let switch = engine.disarm_switch(disarm_request(&key, id, 2)).await.unwrap();
+        assert_eq!((switch.status, switch.pending_since), (SwitchStatus::Inactive, None));
+
+        clock.advance(Duration::days(30));
+        let sweep = engine.sweep_switches().await.unwrap();
+        assert!(sweep.warned.is_empty() && sweep.triggered.is_empty());
+        assert_eq!(engine.get_switch(id).await.unwrap().status, SwitchStatus::Inactive);
+    }
+}
@@ -0,0 +1,97 @@
+// crates/invariant_engine/tests/sybil_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use std::net::IpAddr;
+    use uuid::Uuid;
+
+    use invariant_engine::{InvariantEngine, core::EngineConfig, ProtocolParameters};
+    use invariant_engine::sybil::{find_clusters, ip_prefix, IdentityFingerprint};
+    use invariant_shared::{Network, SybilConfidence, SybilSignal};
+    use crate::common::{MockStorage, MockNonceStorage};
+
+    fn fingerprint(device: &str, ip: Option<&str>, timing: Option<&str>) -> IdentityFingerprint {
+        IdentityFingerprint {
+            identity_id: Uuid::new_v4(),
+            device_hash: Some(device.into()),
+            genesis_ip_prefix: ip.map(Into::into),
+            heartbeat_timing: timing.map(Into::into),
+            ..IdentityFingerprint::default()
+        }
+    }
+
+    #[test]
+    fn test_ip_prefix() {
+        assert_eq!(ip_prefix("203.0.113.77".parse::<IpAddr>().unwrap()), "203.0.113.0/24");
+        assert_eq!(ip_prefix("2001:db8:abcd:12::1".parse::<IpAddr>().unwrap()), "2001:db8:abcd::/48");
+        assert_eq!(ip_prefix("::ffff:198.51.100.9".parse::<IpAddr>().unwrap()), "198.51.100.0/24");
+    }
+
+    #[test]
+    fn test_confidence_counts_signals_shared_by_the_closest_pair() {
+        let farm_a = fingerprint("d1", Some("203.0.113.0/24"), Some("09:00,09:00"));
+        let farm_b = fingerprint("d1", Some("203.0.113.0/24"), Some("09:00,09:00"));
+        let neighbour = fingerprint("d2", Some("198.51.100.0/24"), None);
+        let flatmate = fingerprint("d3", Some("198.51.100.0/24"), None);
+        let loner = fingerprint("d4", None, None);
+
+        let clusters = find_clusters(&[farm_a.clone(), farm_b.clone(), neighbour.clone(), flatmate.clone(), loner.clone()], 50, Utc::now());
+        assert_eq!(clusters.len(), 2);
+
+        // Sorted strongest first.
+        let mut farm = vec![farm_a.identity_id, farm_b.identity_id];
+        farm.sort();
+        assert_eq!(clusters[0].members, farm);
+        assert_eq!(clusters[0].confidence, SybilConfidence::High);
+        assert_eq!(clusters[0].signals, vec![SybilSignal::DeviceHash, SybilSignal::GenesisIpPrefix, SybilSignal::HeartbeatTiming]);
+
+        assert_eq!(clusters[1].confidence, SybilConfidence::Low);
+        assert_eq!(clusters[1].signals, vec![SybilSignal::GenesisIpPrefix]);
+        assert!(clusters.iter().all(|c| !c.members.contains(&loner.identity_id)));
+    }
+
+    #[test]
+    fn test_links_are_transitive_and_population_traits_are_ignored() {
+        // a-b share a network, b-c share a timing pattern: one cluster of three.
+        let a = fingerprint("pixel", Some("10.0.0.0/24"), None);
+        let b = fingerprint("pixel", Some("10.0.0.0/24"), Some("03:17"));
+        let c = fingerprint("pixel", None, Some("03:17"));
+        // Same popular model as everyone else, nothing more.
+        let d = fingerprint("pixel", None, None);
+
+        let clusters = find_clusters(&[a.clone(), b.clone(), c.clone(), d.clone()], 3, Utc::now());
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].members.len(), 3);
+        assert!(!clusters[0].members.contains(&d.identity_id));
+        assert!(!clusters[0].signals.contains(&SybilSignal::DeviceHash));
+        assert_eq!(clusters[0].confidence, SybilConfidence::Low);
+    }
+
+    #[tokio::test]
+    async fn test_scan_replaces_clusters_and_flags_members() {
+        let config = EngineConfig { network: Network::Mainnet, genesis_version: 1, params: ProtocolParameters::default() };
+        let engine = InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config);
+
+        let a = fingerprint("d1", Some("203.0.113.0/24"), None);
+        let b = fingerprint("d1", Some("203.0.113.0/24"), None);
+        let c = fingerprint("d9", None, None);
+        *engine.get_storage().fingerprints.write().await = vec![a.clone(), b.clone(), c.clone()];
+
+        let clusters = engine.detect_sybil_clusters().await.unwrap();
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(engine.sybil_flag(&a.identity_id).await.unwrap(), Some(SybilConfidence::Medium));
+        assert_eq!(engine.sybil_flag(&c.identity_id).await.unwrap(), None);
+        assert_eq!(engine.sybil_clusters(SybilConfidence::High, 10).await.unwrap().len(), 0);
+
+        // The next scan no longer sees the link: the stale cluster is gone.
+        *engine.get_storage().fingerprints.write().await = vec![a.clone(), c];
+        assert!(engine.detect_sybil_clusters().await.unwrap().is_empty());
+        assert_eq!(engine.sybil_flag(&a.identity_id).await.unwrap(), None);
+    }
+}
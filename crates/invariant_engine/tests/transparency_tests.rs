@@ -0,0 +1,102 @@
+// crates/invariant_engine/tests/transparency_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use invariant_engine::{InvariantEngine, TransparencyStorage, EngineError, core::EngineConfig, ProtocolParameters};
+    use invariant_shared::{Network, LogEventKind};
+    use invariant_shared::transparency::{merkle_root, verify_inclusion, verify_consistency, Hash};
+    use crate::common::{MockStorage, MockNonceStorage};
+
+    fn new_engine() -> InvariantEngine<MockStorage, MockNonceStorage> {
+        let config = EngineConfig { network: Network::Testnet, genesis_version: 1, params: ProtocolParameters::default() };
+        InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config)
+    }
+
+    async fn append(engine: &InvariantEngine<MockStorage, MockNonceStorage>, n: usize) {
+        for i in 0..n {
+            let kind = if i % 3 == 0 { LogEventKind::Genesis } else { LogEventKind::Reattestation };
+            engine.get_storage().append_log_entry(kind, &Uuid::new_v4(), &[i as u8; 32]).await.unwrap();
+        }
+    }
+
+    fn as_hash(bytes: &[u8]) -> Hash {
+        bytes.try_into().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_auditor_verifies_inclusion_and_growth() {
+        let engine = new_engine();
+        append(&engine, 7).await;
+        let old_head = engine.tree_head().await.unwrap();
+        assert_eq!(old_head.tree_size, 7);
+
+        append(&engine, 6).await;
+        let new_head = engine.tree_head().await.unwrap();
+
+        // Every entry is provably in the new head.
+        let entries = engine.log_entries(0, new_head.tree_size).await.unwrap();
+        for entry in &entries {
+            let proof: Vec<Hash> = engine.inclusion_proof(entry.index, new_head.tree_size).await.unwrap()
+                .iter().map(|p| as_hash(p)).collect();
+            assert!(verify_inclusion(&entry.leaf_hash(), entry.index, new_head.tree_size, &proof, &as_hash(&new_head.root_hash)));
+        }
+
+        // The log only grew.
+        let proof: Vec<Hash> = engine.consistency_proof(old_head.tree_size, new_head.tree_size).await.unwrap()
+            .iter().map(|p| as_hash(p)).collect();
+        assert!(verify_consistency(7, 13, &as_hash(&old_head.root_hash), &as_hash(&new_head.root_hash), &proof));
+    }
+
+    #[tokio::test]
+    async fn test_deleted_entry_breaks_consistency() {
+        let engine = new_engine();
+        append(&engine, 9).await;
+        let old_head = engine.tree_head().await.unwrap();
+
+        // Node operator silently removes an identity's genesis record and restarts
+        // the node (a running node would keep serving its cached subtrees).
+        let mut log = engine.get_storage().log.read().await.clone();
+        log.remove(3);
+        for (i, e) in log.iter_mut().enumerate() { e.index = i as u64; }
+        let restarted = new_engine();
+        *restarted.get_storage().log.write().await = log;
+        append(&restarted, 2).await;
+        let new_head = restarted.tree_head().await.unwrap();
+
+        let proof: Vec<Hash> = restarted.consistency_proof(old_head.tree_size, new_head.tree_size).await.unwrap()
+            .iter().map(|p| as_hash(p)).collect();
+        assert!(!verify_consistency(old_head.tree_size, new_head.tree_size, &as_hash(&old_head.root_hash), &as_hash(&new_head.root_hash), &proof));
+    }
+
+    #[tokio::test]
+    async fn test_cached_tree_syncs_across_pages() {
+        let engine = new_engine();
+        append(&engine, 1500).await;
+        let first = engine.tree_head().await.unwrap();
+        append(&engine, 1200).await;
+        let second = engine.tree_head().await.unwrap();
+        assert_eq!(second.tree_size, 2700);
+
+        let leaves: Vec<Hash> = engine.get_storage().log.read().await.iter().map(|e| e.leaf_hash()).collect();
+        assert_eq!(as_hash(&first.root_hash), merkle_root(&leaves[..1500]));
+        assert_eq!(as_hash(&second.root_hash), merkle_root(&leaves));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_ranges() {
+        let engine = new_engine();
+        append(&engine, 4).await;
+
+        assert!(matches!(engine.inclusion_proof(4, 4).await, Err(EngineError::InvalidLogRange(_))));
+        assert!(matches!(engine.inclusion_proof(0, 5).await, Err(EngineError::InvalidLogRange(_))));
+        assert!(matches!(engine.consistency_proof(0, 4).await, Err(EngineError::InvalidLogRange(_))));
+        assert!(matches!(engine.log_entries(3, 3).await, Err(EngineError::InvalidLogRange(_))));
+    }
+}
@@ -0,0 +1,168 @@
+// crates/invariant_engine/tests/trust_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ */
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use uuid::Uuid;
+
+    use invariant_engine::{
+        InvariantEngine, EngineError, core::EngineConfig, ProtocolParameters,
+        TrustScorer, TrustWeights, DeviceCatalog,
+    };
+    use invariant_engine::clock::AdjustableClock;
+    use invariant_engine::trust::{patch_age_months, RecentActivity, TrustSignals};
+    use invariant_shared::{Identity, IdentityStatus, Network, SecurityLevel, TrustScore, TrustSignal};
+    use crate::common::{MockStorage, MockNonceStorage};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 6, 15, 12, 0, 0).unwrap()
+    }
+
+    fn contribution(score: &TrustScore, signal: TrustSignal) -> &invariant_shared::SignalContribution {
+        score.contributions.iter().find(|c| c.signal == signal).unwrap()
+    }
+
+    fn perfect() -> TrustSignals {
+        TrustSignals {
+            security_level: Some(SecurityLevel::StrongBox),
+            os_patch_level: Some(202605),
+            attestation_age: Some(Duration::zero()),
+            streak: Some(45),
+            continuity_score: Some(400),
+            brand: Some("google".into()),
+            product: Some("husky".into()),
+            activity: Some(RecentActivity::default()),
+        }
+    }
+
+    fn catalog() -> DeviceCatalog {
+        DeviceCatalog::from_json(r#"{ "Google": ["husky", "shiba"] }"#).unwrap()
+    }
+
+    #[test]
+    fn test_perfect_signals_score_100_and_explain_every_signal() {
+        let scorer = TrustScorer::new(TrustWeights::default(), catalog());
+        let score = scorer.score(&perfect(), now(), &ProtocolParameters::default());
+
+        assert!((score.score - 100.0).abs() < 1e-9);
+        assert_eq!(score.contributions.len(), 7);
+        let total: f64 = score.contributions.iter().map(|c| c.points).sum();
+        assert!((total - score.score).abs() < 1e-9);
+        assert!(score.contributions.iter().all(|c| c.value == Some(1.0) && !c.detail.is_empty()));
+    }
+
+    #[test]
+    fn test_unavailable_signals_are_renormalized_out() {
+        let scorer = TrustScorer::new(TrustWeights::default(), DeviceCatalog::default());
+        let signals = TrustSignals { brand: None, activity: None, ..perfect() };
+        let score = scorer.score(&signals, now(), &ProtocolParameters::default());
+
+        // Neither the empty catalog nor the missing activity drags the score down.
+        assert!((score.score - 100.0).abs() < 1e-9);
+        for signal in [TrustSignal::DeviceCatalog, TrustSignal::Velocity] {
+            let c = contribution(&score, signal);
+            assert_eq!((c.value, c.points), (None, 0.0));
+        }
+    }
+
+    #[test]
+    fn test_weights_shape_the_score() {
+        let params = ProtocolParameters::default();
+        // A TEE key on a two-year-old patch, otherwise perfect.
+        let signals = TrustSignals { security_level: Some(SecurityLevel::Tee), os_patch_level: Some(202406), ..perfect() };
+
+        let default = TrustScorer::new(TrustWeights::default(), catalog()).score(&signals, now(), &params);
+        let hardware_only = TrustWeights {
+            security_level: 1.0, patch_age: 1.0, attestation_age: 0.0, streak: 0.0,
+            continuity_score: 0.0, device_catalog: 0.0, velocity: 0.0,
+        };
+        let strict = TrustScorer::new(hardware_only, catalog()).score(&signals, now(), &params);
+
+        assert!(strict.score < default.score);
+        assert_eq!(contribution(&strict, TrustSignal::PatchAge).value, Some(0.0));
+        assert_eq!(contribution(&strict, TrustSignal::Streak).points, 0.0);
+    }
+
+    #[test]
+    fn test_patch_age() {
+        assert_eq!(patch_age_months(202606, now()), Some(0));
+        assert_eq!(patch_age_months(20260105, now()), Some(5));
+        assert_eq!(patch_age_months(202413, now()), None);
+
+        let scorer = TrustScorer::default();
+        let params = ProtocolParameters::default();
+        let value = |level| {
+            let signals = TrustSignals { os_patch_level: Some(level), ..TrustSignals::default() };
+            contribution(&scorer.score(&signals, now(), &params), TrustSignal::PatchAge).value.unwrap()
+        };
+        assert_eq!(value(202603), 1.0);
+        assert!(value(202506) > 0.0 && value(202506) < 1.0);
+        assert_eq!(value(202306), 0.0);
+    }
+
+    #[test]
+    fn test_catalog_mismatch_and_velocity_penalize() {
+        let scorer = TrustScorer::new(TrustWeights::default(), catalog());
+        let params = ProtocolParameters::default();
+        let signals = TrustSignals {
+            product: Some("dm3q".into()),
+            activity: Some(RecentActivity { key_rotations: 2, recoveries: 1, reactivations: 0 }),
+            ..perfect()
+        };
+        let score = scorer.score(&signals, now(), &params);
+
+        assert_eq!(contribution(&score, TrustSignal::DeviceCatalog).value, Some(0.0));
+        assert_eq!(contribution(&score, TrustSignal::Velocity).value, Some(0.25));
+        assert!((score.score - 78.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_weights_file_is_validated() {
+        let weights = TrustWeights::from_json(r#"{ "velocity": 40 }"#).unwrap();
+        assert_eq!((weights.velocity, weights.streak), (40.0, 10.0));
+
+        for bad in [r#"{ "velocity": -1 }"#, r#"{ "unknown": 1 }"#, r#"{
+            "security_level": 0, "patch_age": 0, "attestation_age": 0, "streak": 0,
+            "continuity_score": 0, "device_catalog": 0, "velocity": 0
+        }"#] {
+            assert!(matches!(TrustWeights::from_json(bad), Err(EngineError::InvalidPolicy(_))), "{}", bad);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_engine_scores_stored_identity_with_recent_activity() {
+        let clock = AdjustableClock::new(now());
+        let config = EngineConfig { network: Network::Mainnet, genesis_version: 1, params: ProtocolParameters::default() };
+        let engine = InvariantEngine::new(MockStorage::default(), MockNonceStorage::default(), config)
+            .with_clock(clock.clone())
+            .with_trust_scorer(TrustScorer::new(TrustWeights::default(), catalog()));
+
+        let identity = Identity {
+            id: Uuid::new_v4(),
+            public_key: vec![1],
+            continuity_score: 90, streak: 15,
+            created_at: now() - Duration::days(120),
+            last_heartbeat: now(),
+            last_attestation: now() - Duration::days(7),
+            status: IdentityStatus::Active,
+            username: None, is_genesis_eligible: false, fcm_token: None,
+            hardware_brand: Some("google".into()), hardware_device: None, hardware_product: Some("shiba".into()),
+            security_level: Some(SecurityLevel::StrongBox), os_patch_level: Some(202606),
+            genesis_version: 1, network: Network::Mainnet,
+        };
+        let calm = engine.trust_score(&identity).await.unwrap();
+        assert_eq!(contribution(&calm, TrustSignal::AttestationAge).value, Some(0.0));
+        assert_eq!(contribution(&calm, TrustSignal::Streak).value, Some(0.5));
+
+        engine.get_storage().activity.write().await
+            .insert(identity.id, RecentActivity { key_rotations: 0, recoveries: 0, reactivations: 4 });
+        let busy = engine.trust_score(&identity).await.unwrap();
+        assert_eq!(contribution(&busy, TrustSignal::Velocity).value, Some(0.0));
+        assert!(busy.score < calm.score);
+    }
+}
@@ -0,0 +1,16 @@
+-- crates/invariant_server/migrations/20260201000000_key_history.sql
+-- Archive of hardware keys retired through Key Rotation.
+-- The active key always lives in identities.public_key.
+CREATE TABLE IF NOT EXISTS identity_key_history (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    identity_id UUID NOT NULL REFERENCES identities(id),
+    public_key BYTEA NOT NULL,
+    replaced_by BYTEA NOT NULL,
+    hardware_device_hash TEXT,
+    retired_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_key_history_identity ON identity_key_history(identity_id);
+
+-- A retired key can never be rotated back in.
+CREATE UNIQUE INDEX IF NOT EXISTS ux_key_history_public_key ON identity_key_history(public_key);
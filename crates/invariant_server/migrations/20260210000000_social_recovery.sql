@@ -0,0 +1,54 @@
+-- crates/invariant_server/migrations/20260210000000_social_recovery.sql
+
+-- 1. Guardian Sets (M-of-N)
+CREATE TABLE IF NOT EXISTS guardian_sets (
+    identity_id UUID PRIMARY KEY REFERENCES identities(id),
+    threshold SMALLINT NOT NULL CHECK (threshold > 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS identity_guardians (
+    identity_id UUID NOT NULL REFERENCES guardian_sets(identity_id) ON DELETE CASCADE,
+    guardian_id UUID NOT NULL REFERENCES identities(id),
+    position SMALLINT NOT NULL,
+    PRIMARY KEY (identity_id, guardian_id),
+    CHECK (identity_id <> guardian_id)
+);
+
+-- 2. Recovery Attempts
+CREATE TABLE IF NOT EXISTS recovery_requests (
+    id UUID PRIMARY KEY,
+    identity_id UUID NOT NULL REFERENCES identities(id),
+    new_public_key BYTEA NOT NULL,
+    hardware_brand TEXT,
+    hardware_device_hash TEXT,
+    hardware_product TEXT,
+    status TEXT NOT NULL CHECK (status IN ('pending', 'approved', 'completed', 'cancelled')),
+    threshold SMALLINT NOT NULL,
+    initiated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    executable_at TIMESTAMPTZ
+);
+
+-- Only ONE open recovery per identity (race-proof at the DB level).
+CREATE UNIQUE INDEX IF NOT EXISTS ux_recovery_open
+ON recovery_requests(identity_id)
+WHERE status IN ('pending', 'approved');
+
+CREATE TABLE IF NOT EXISTS recovery_approvals (
+    recovery_id UUID NOT NULL REFERENCES recovery_requests(id),
+    guardian_id UUID NOT NULL REFERENCES identities(id),
+    approved_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (recovery_id, guardian_id)
+);
+
+-- 3. Audit Trail (append-only)
+CREATE TABLE IF NOT EXISTS recovery_events (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    recovery_id UUID NOT NULL REFERENCES recovery_requests(id),
+    identity_id UUID NOT NULL REFERENCES identities(id),
+    event TEXT NOT NULL,
+    actor_id UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_recovery_events_recovery ON recovery_events(recovery_id);
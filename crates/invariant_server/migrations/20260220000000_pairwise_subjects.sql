@@ -0,0 +1,14 @@
+-- crates/invariant_server/migrations/20260220000000_pairwise_subjects.sql
+-- Partner-scoped pseudonymous identifiers.
+-- Partners only ever see pairwise_id; the global identity UUID never leaves the node.
+CREATE TABLE IF NOT EXISTS partner_subjects (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    partner_id TEXT NOT NULL,
+    pairwise_id UUID NOT NULL,
+    identity_id UUID NOT NULL REFERENCES identities(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One identifier per (partner, identity), and pairwise IDs never collide within a partner.
+CREATE UNIQUE INDEX IF NOT EXISTS ux_partner_subjects_identity ON partner_subjects(partner_id, identity_id);
+CREATE UNIQUE INDEX IF NOT EXISTS ux_partner_subjects_pairwise ON partner_subjects(partner_id, pairwise_id);
@@ -0,0 +1,8 @@
+-- crates/invariant_server/migrations/20260301000000_privacy_pass.sql
+-- Privacy Pass issuance ledger: one row per identity, one batch per window.
+-- Stores counts only. Blinded requests and signatures are never persisted.
+CREATE TABLE IF NOT EXISTS token_issuances (
+    identity_id UUID PRIMARY KEY REFERENCES identities(id),
+    last_issued_at TIMESTAMPTZ NOT NULL,
+    total_issued BIGINT NOT NULL DEFAULT 0
+);
@@ -0,0 +1,24 @@
+-- crates/invariant_server/migrations/20260310000000_transparency_log.sql
+-- Append-only transparency log of identity lifecycle events (Merkle leaves).
+-- No FK to identities: the log must outlive anything that happens to the identity row.
+CREATE TABLE IF NOT EXISTS transparency_log (
+    leaf_index BIGINT PRIMARY KEY CHECK (leaf_index >= 0),
+    kind TEXT NOT NULL CHECK (kind IN ('genesis', 'key_rotation', 'recovery', 'reattestation', 'revocation')),
+    identity_id UUID NOT NULL,
+    public_key_hash BYTEA NOT NULL,
+    logged_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_transparency_log_identity ON transparency_log(identity_id);
+
+-- Enforce append-only at the database level, not just in the application.
+CREATE OR REPLACE FUNCTION transparency_log_append_only() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'transparency_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_transparency_log_append_only ON transparency_log;
+CREATE TRIGGER trg_transparency_log_append_only
+    BEFORE UPDATE OR DELETE ON transparency_log
+    FOR EACH ROW EXECUTE FUNCTION transparency_log_append_only();
@@ -0,0 +1,22 @@
+-- crates/invariant_server/migrations/20260320000000_identity_transitions.sql
+-- Explicit identity state machine. The original CHECK never allowed 'stale'.
+ALTER TABLE identities DROP CONSTRAINT IF EXISTS identities_status_check;
+ALTER TABLE identities ADD CONSTRAINT identities_status_check
+    CHECK (status IN ('active', 'stale', 'dormant', 'revoked'));
+
+-- Audit trail: one row per status change, written in the same transaction as the change.
+CREATE TABLE IF NOT EXISTS identity_transitions (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    identity_id UUID NOT NULL REFERENCES identities(id),
+    from_status TEXT NOT NULL CHECK (from_status IN ('active', 'stale', 'dormant', 'revoked')),
+    to_status TEXT NOT NULL CHECK (to_status IN ('active', 'stale', 'dormant', 'revoked')),
+    reason TEXT NOT NULL CHECK (reason IN (
+        'heartbeat', 'attestation_expired', 'reattestation', 'key_rotation', 'recovery', 'inactivity', 'revocation'
+    )),
+    -- NULL for system triggers (trust decay, reaper)
+    actor_id UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (from_status <> to_status)
+);
+
+CREATE INDEX IF NOT EXISTS idx_identity_transitions_identity ON identity_transitions(identity_id, id);
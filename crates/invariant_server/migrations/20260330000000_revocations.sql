@@ -0,0 +1,10 @@
+-- crates/invariant_server/migrations/20260330000000_revocations.sql
+-- Revocation records. `sequence` is the cursor partners poll their feed with.
+CREATE TABLE IF NOT EXISTS revocations (
+    sequence BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    identity_id UUID NOT NULL UNIQUE REFERENCES identities(id),
+    reason TEXT NOT NULL CHECK (reason IN ('compromised_key', 'fraud', 'user_request', 'leaked_keybox')),
+    -- The identity itself for self-revocation, NULL for operator action
+    revoked_by UUID,
+    revoked_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,15 @@
+-- crates/invariant_server/migrations/20260405000000_deadmans_switch_grace.sql
+-- Dead man's switch grace period: 'pending' sits between a missed threshold and the trigger,
+-- so the holder can still cancel (heartbeat or disarm) before the beneficiary is told.
+ALTER TABLE identities DROP CONSTRAINT IF EXISTS identities_switch_status_check;
+ALTER TABLE identities ADD CONSTRAINT identities_switch_status_check
+    CHECK (switch_status IN ('inactive', 'armed', 'pending', 'triggered'));
+
+ALTER TABLE identities
+ADD COLUMN IF NOT EXISTS switch_pending_since TIMESTAMPTZ,
+ADD COLUMN IF NOT EXISTS switch_triggered_at TIMESTAMPTZ;
+
+-- The sweep revisits every pending switch on each run.
+CREATE INDEX IF NOT EXISTS idx_identities_switch_pending
+ON identities(switch_pending_since)
+WHERE switch_status = 'pending';
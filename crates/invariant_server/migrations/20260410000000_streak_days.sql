@@ -0,0 +1,7 @@
+-- crates/invariant_server/migrations/20260410000000_streak_days.sql
+-- Streaks count local days in the holder's time zone (was: "< 6h since the last heartbeat",
+-- which a 23h heartbeat cadence could never satisfy).
+ALTER TABLE identities
+ADD COLUMN IF NOT EXISTS streak_day DATE,
+ADD COLUMN IF NOT EXISTS streak_freezes INTEGER NOT NULL DEFAULT 0 CHECK (streak_freezes >= 0),
+ADD COLUMN IF NOT EXISTS streak_timezone TEXT NOT NULL DEFAULT 'UTC';
@@ -0,0 +1,11 @@
+-- crates/invariant_server/migrations/20260415000000_trust_signals.sql
+-- Hardware inputs of the trust score, taken from the attestation extension at genesis,
+-- re-attestation, key rotation and recovery (was: a "strongbox" substring check on the device name).
+ALTER TABLE identities
+ADD COLUMN IF NOT EXISTS security_level TEXT CHECK (security_level IN ('tee', 'strongbox')),
+ADD COLUMN IF NOT EXISTS os_patch_level INTEGER;
+
+ALTER TABLE recovery_requests
+ADD COLUMN IF NOT EXISTS security_level TEXT CHECK (security_level IN ('tee', 'strongbox')),
+ADD COLUMN IF NOT EXISTS os_patch_level INTEGER;
+
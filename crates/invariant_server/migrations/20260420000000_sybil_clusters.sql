@@ -0,0 +1,30 @@
+-- crates/invariant_server/migrations/20260420000000_sybil_clusters.sql
+-- Sybil cluster detection. Until now the only duplicate protection was unique_public_key.
+
+-- Clustering signals that do not live on the identity row (all hashed or truncated).
+CREATE TABLE IF NOT EXISTS identity_fingerprints (
+    identity_id UUID PRIMARY KEY REFERENCES identities(id),
+    -- SHA-256 of the verified boot key of the latest attestation
+    boot_key_hash TEXT,
+    -- SHA-256 of the certificate that signed the attested key
+    intermediate_hash TEXT,
+    -- /24 (IPv4) or /48 (IPv6) the identity was minted from
+    genesis_ip_prefix TEXT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Results of the latest scan; replaced as a whole by the background job.
+CREATE TABLE IF NOT EXISTS sybil_clusters (
+    id UUID PRIMARY KEY,
+    confidence TEXT NOT NULL CHECK (confidence IN ('low', 'medium', 'high')),
+    signals TEXT[] NOT NULL,
+    detected_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS sybil_cluster_members (
+    cluster_id UUID NOT NULL REFERENCES sybil_clusters(id) ON DELETE CASCADE,
+    identity_id UUID NOT NULL REFERENCES identities(id),
+    PRIMARY KEY (cluster_id, identity_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sybil_cluster_members_identity ON sybil_cluster_members(identity_id);
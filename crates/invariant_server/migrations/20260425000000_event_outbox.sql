@@ -0,0 +1,31 @@
+-- crates/invariant_server/migrations/20260425000000_event_outbox.sql
+-- Transactional outbox for engine domain events (identity_minted, heartbeat_accepted, ...).
+-- Relays read unpublished rows in `seq` order, forward them and set `published_at`.
+
+CREATE TABLE IF NOT EXISTS event_outbox (
+    seq BIGSERIAL PRIMARY KEY,
+    -- DomainEvent.id, so consumers can de-duplicate redeliveries
+    event_id UUID NOT NULL UNIQUE,
+    event_type TEXT NOT NULL,
+    identity_id UUID NOT NULL,
+    -- The full DomainEvent as serialized by invariant_shared
+    payload JSONB NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    published_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_outbox_unpublished ON event_outbox(seq) WHERE published_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_event_outbox_identity ON event_outbox(identity_id, seq);
+
+-- Wake listening relays (LISTEN invariant_events) instead of having them poll.
+CREATE OR REPLACE FUNCTION notify_event_outbox() RETURNS trigger AS $$
+BEGIN
+    PERFORM pg_notify('invariant_events', NEW.seq::text);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_event_outbox_notify ON event_outbox;
+CREATE TRIGGER trg_event_outbox_notify
+    AFTER INSERT ON event_outbox
+    FOR EACH ROW EXECUTE FUNCTION notify_event_outbox();
@@ -0,0 +1,51 @@
+-- crates/invariant_server/migrations/20260430000000_partner_webhooks.sql
+-- Partner webhooks: signed POSTs when a partner's subject is revoked, goes stale or goes dormant.
+-- Events are derived from identity_transitions, so bulk changes (reaper) are covered too.
+
+CREATE TABLE IF NOT EXISTS webhook_endpoints (
+    id UUID PRIMARY KEY,
+    partner_id TEXT NOT NULL,
+    url TEXT NOT NULL,
+    -- HMAC-SHA256 key for the Invariant-Signature header (shown to the partner once)
+    secret TEXT NOT NULL,
+    events TEXT[] NOT NULL CHECK (
+        cardinality(events) > 0
+        AND events <@ ARRAY['identity_revoked', 'identity_stale', 'identity_dormant']
+    ),
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_endpoints_partner ON webhook_endpoints(partner_id) WHERE active;
+
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id UUID PRIMARY KEY,
+    endpoint_id UUID NOT NULL REFERENCES webhook_endpoints(id) ON DELETE CASCADE,
+    -- WebhookPayload.id: shared by every endpoint of the partner, stable across retries
+    event_id UUID NOT NULL,
+    -- Exact JSON body sent on every attempt
+    payload TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'delivered', 'dead')),
+    attempts INT NOT NULL DEFAULT 0,
+    -- NULL once delivered or dead
+    next_attempt_at TIMESTAMPTZ,
+    last_status_code INT,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at TIMESTAMPTZ,
+    UNIQUE (endpoint_id, event_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due ON webhook_deliveries(next_attempt_at) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_endpoint ON webhook_deliveries(endpoint_id, created_at DESC);
+
+-- Last identity_transitions.id turned into deliveries. Starts at the current head:
+-- history from before webhooks existed is not replayed to anyone.
+CREATE TABLE IF NOT EXISTS webhook_cursor (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    last_transition_id BIGINT NOT NULL
+);
+
+INSERT INTO webhook_cursor (id, last_transition_id)
+SELECT TRUE, COALESCE(MAX(id), 0) FROM identity_transitions
+ON CONFLICT (id) DO NOTHING;
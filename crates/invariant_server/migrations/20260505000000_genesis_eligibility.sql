@@ -0,0 +1,17 @@
+-- crates/invariant_server/migrations/20260505000000_genesis_eligibility.sql
+-- Genesis eligibility is computed by the engine's rule evaluator. The old column
+-- default (TRUE) made every identity eligible without ever being evaluated.
+ALTER TABLE identities ALTER COLUMN is_genesis_eligible SET DEFAULT FALSE;
+UPDATE identities SET is_genesis_eligible = FALSE WHERE is_genesis_eligible IS DISTINCT FROM FALSE;
+ALTER TABLE identities ALTER COLUMN is_genesis_eligible SET NOT NULL;
+
+-- Latest report per identity. The background job evaluates identities without one first.
+CREATE TABLE IF NOT EXISTS identity_eligibility (
+    identity_id UUID PRIMARY KEY REFERENCES identities(id),
+    eligible BOOLEAN NOT NULL,
+    -- [{ "rule": "min_streak", "passed": false, "detail": "streak 3 of 7 days" }, ...]
+    rules JSONB NOT NULL,
+    evaluated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_identity_eligibility_evaluated ON identity_eligibility(evaluated_at);
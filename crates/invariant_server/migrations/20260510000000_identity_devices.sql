@@ -0,0 +1,40 @@
+-- crates/invariant_server/migrations/20260510000000_identity_devices.sql
+-- Multiple attested device keys per identity. The primary device is the row whose key is
+-- identities.public_key; key rotation and recovery move that row along with the identity.
+
+CREATE TABLE IF NOT EXISTS identity_devices (
+    id UUID PRIMARY KEY,
+    identity_id UUID NOT NULL REFERENCES identities(id) ON DELETE CASCADE,
+    public_key BYTEA NOT NULL,
+    label TEXT CHECK (char_length(label) <= 64),
+    status TEXT NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'revoked')),
+    hardware_brand TEXT,
+    hardware_device_hash TEXT,
+    hardware_product TEXT,
+    security_level TEXT CHECK (security_level IN ('tee', 'strongbox')),
+    os_patch_level INTEGER,
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_attestation TIMESTAMPTZ NOT NULL,
+    last_heartbeat TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+-- A key is active on at most one identity; a retired key does not block anything.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_identity_devices_active_key ON identity_devices(public_key) WHERE status = 'active';
+CREATE INDEX IF NOT EXISTS idx_identity_devices_identity ON identity_devices(identity_id, added_at);
+
+-- Every existing identity starts with its current key as the primary device.
+INSERT INTO identity_devices (
+    id, identity_id, public_key, hardware_brand, hardware_device_hash, hardware_product,
+    security_level, os_patch_level, added_at, last_attestation, last_heartbeat
+)
+SELECT gen_random_uuid(), i.id, i.public_key, i.hardware_brand, i.hardware_device_hash, i.hardware_product,
+       i.security_level, i.os_patch_level, i.created_at, i.last_attestation, i.last_heartbeat
+FROM identities i
+WHERE NOT EXISTS (SELECT 1 FROM identity_devices d WHERE d.identity_id = i.id);
+
+-- Device enrollment and removal are transparency log events.
+ALTER TABLE transparency_log DROP CONSTRAINT IF EXISTS transparency_log_kind_check;
+ALTER TABLE transparency_log ADD CONSTRAINT transparency_log_kind_check CHECK (
+    kind IN ('genesis', 'key_rotation', 'recovery', 'reattestation', 'revocation', 'device_enrolled', 'device_revoked')
+);
@@ -0,0 +1,21 @@
+-- crates/invariant_server/migrations/20260515000000_identity_migrations.sql
+-- Testnet-to-mainnet migrations. The testnet identity is revoked ('migrated') and lives on
+-- as the mainnet identity; each side appears in at most one migration.
+
+CREATE TABLE IF NOT EXISTS identity_migrations (
+    testnet_id UUID PRIMARY KEY REFERENCES identities(id),
+    mainnet_id UUID NOT NULL UNIQUE REFERENCES identities(id),
+    carried_score BIGINT NOT NULL CHECK (carried_score >= 0),
+    carried_streak BIGINT NOT NULL CHECK (carried_streak >= 0),
+    migrated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE revocations DROP CONSTRAINT IF EXISTS revocations_reason_check;
+ALTER TABLE revocations ADD CONSTRAINT revocations_reason_check CHECK (
+    reason IN ('compromised_key', 'fraud', 'user_request', 'leaked_keybox', 'migrated')
+);
+
+ALTER TABLE transparency_log DROP CONSTRAINT IF EXISTS transparency_log_kind_check;
+ALTER TABLE transparency_log ADD CONSTRAINT transparency_log_kind_check CHECK (
+    kind IN ('genesis', 'key_rotation', 'recovery', 'reattestation', 'revocation', 'device_enrolled', 'device_revoked', 'migration')
+);
@@ -0,0 +1,14 @@
+-- crates/invariant_server/migrations/20260520000000_genesis_upgrades.sql
+-- Genesis version upgrades: an identity re-attests under the current policy and is
+-- re-stamped with the node's genesis version. One row per upgrade, append-only.
+
+CREATE TABLE IF NOT EXISTS genesis_upgrades (
+    id BIGSERIAL PRIMARY KEY,
+    identity_id UUID NOT NULL REFERENCES identities(id) ON DELETE CASCADE,
+    from_version SMALLINT NOT NULL,
+    to_version SMALLINT NOT NULL CHECK (to_version > from_version),
+    upgraded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_genesis_upgrades_identity ON genesis_upgrades(identity_id, upgraded_at);
+CREATE INDEX IF NOT EXISTS idx_identities_genesis_version ON identities(genesis_version);
@@ -0,0 +1,19 @@
+-- crates/invariant_server/migrations/20260525000000_identity_reactivations.sql
+-- Reactivations of dormant identities: how long they slept, what the dormancy rules did
+-- to score and streak, and the cooling-off period. One row per reactivation, append-only.
+
+CREATE TABLE IF NOT EXISTS identity_reactivations (
+    id BIGSERIAL PRIMARY KEY,
+    identity_id UUID NOT NULL REFERENCES identities(id) ON DELETE CASCADE,
+    reason TEXT NOT NULL CHECK (reason IN ('reattestation', 'key_rotation', 'recovery')),
+    silent_since TIMESTAMPTZ NOT NULL,
+    dormant_since TIMESTAMPTZ NOT NULL,
+    reactivated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    score_before BIGINT NOT NULL CHECK (score_before >= 0),
+    score_after BIGINT NOT NULL CHECK (score_after >= 0 AND score_after <= score_before),
+    streak_before BIGINT NOT NULL CHECK (streak_before >= 0),
+    streak_after BIGINT NOT NULL CHECK (streak_after >= 0),
+    cooling_off_until TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_identity_reactivations_identity ON identity_reactivations(identity_id, reactivated_at DESC);
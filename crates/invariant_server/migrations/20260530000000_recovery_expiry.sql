@@ -0,0 +1,18 @@
+-- crates/invariant_server/migrations/20260530000000_recovery_expiry.sql
+-- Recoveries that never reach their threshold no longer hold the identity's open slot forever:
+-- a Pending recovery expires at `expires_at`, and guardians can reject one outright.
+
+ALTER TABLE recovery_requests ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ;
+UPDATE recovery_requests SET expires_at = initiated_at + INTERVAL '7 days' WHERE expires_at IS NULL;
+ALTER TABLE recovery_requests ALTER COLUMN expires_at SET NOT NULL;
+
+ALTER TABLE recovery_requests DROP CONSTRAINT IF EXISTS recovery_requests_status_check;
+ALTER TABLE recovery_requests ADD CONSTRAINT recovery_requests_status_check
+    CHECK (status IN ('pending', 'approved', 'completed', 'cancelled', 'rejected', 'expired'));
+
+-- A partial index cannot compare against NOW(), so expiry is made explicit: creating a recovery
+-- first flips the identity's overdue Pending rows to 'expired', which drops them out of ux_recovery_open.
+UPDATE recovery_requests SET status = 'expired' WHERE status = 'pending' AND expires_at <= NOW();
+
+-- One vote per guardian and recovery: an approval or a rejection.
+ALTER TABLE recovery_approvals ADD COLUMN IF NOT EXISTS approve BOOLEAN NOT NULL DEFAULT TRUE;
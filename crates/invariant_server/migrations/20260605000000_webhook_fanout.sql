@@ -0,0 +1,15 @@
+-- crates/invariant_server/migrations/20260605000000_webhook_fanout.sql
+-- Webhook fan-out tracks each transition instead of a high-water mark on identity_transitions.id:
+-- identity ids are handed out at insert time, so a transaction that commits late lands below a
+-- cursor that has already moved past it and its events would never be delivered.
+
+-- Rows already behind the cursor (or from before webhooks existed) count as handled.
+ALTER TABLE identity_transitions ADD COLUMN IF NOT EXISTS webhooks_enqueued BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE identity_transitions ALTER COLUMN webhooks_enqueued SET DEFAULT FALSE;
+
+UPDATE identity_transitions SET webhooks_enqueued = FALSE
+WHERE id > (SELECT last_transition_id FROM webhook_cursor WHERE id);
+
+CREATE INDEX IF NOT EXISTS idx_identity_transitions_webhooks_pending ON identity_transitions(id) WHERE NOT webhooks_enqueued;
+
+DROP TABLE IF EXISTS webhook_cursor;
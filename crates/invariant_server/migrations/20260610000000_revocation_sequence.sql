@@ -0,0 +1,6 @@
+-- crates/invariant_server/migrations/20260610000000_revocation_sequence.sql
+-- Partners page their revocation feed with `sequence > cursor`. Identity values are handed out
+-- at insert time, so a revocation that commits late could land below a cursor that has already
+-- moved past it and never reach the partner. Sequences are now assigned under a table lock held
+-- until commit (like transparency_log leaf indexes), so they become visible in order.
+ALTER TABLE revocations ALTER COLUMN sequence DROP IDENTITY IF EXISTS;
@@ -6,7 +6,24 @@
  */
 
 use utoipa::OpenApi;
-use invariant_shared::{GenesisRequest, Heartbeat, Identity, IdentityStatus, Network};
+use invariant_shared::{
+    GenesisRequest, Heartbeat, Identity, IdentityStatus, IdentityTransition, TransitionReason, KeyRotationRequest, Network,
+    GuardianSetRequest, RecoveryInitRequest, RecoveryApproval, RecoveryRejection, RecoveryCancelRequest, RecoveryStatus, RecoveryEvent,
+    Receipt, ReceiptKind, ReceiptVerdict, SignedReceipt, ProofTokenClaims, ProofTokenRequest, Jwk, JwkSet, PairwiseLinkRequest, TokenIssuanceRequest,
+    LogEntry, LogEventKind, TreeHead, SignedTreeHead,
+    Revocation, RevocationReason, RevocationNotice, SelfRevocationRequest,
+    DeadMansSwitch, SwitchStatus, SwitchArmRequest, SwitchDisarmRequest,
+    Device, DeviceStatus, DeviceEnrollmentRequest, DeviceRevocationRequest,
+    MigrationRequest, IdentityMigration,
+    GenesisUpgrade, GenesisVersionStats, GenesisUpgradeProgress, Reactivation,
+    StreakState, StreakTimezoneRequest,
+    SecurityLevel, TrustScore, TrustSignal, SignalContribution,
+    SybilCluster, SybilConfidence, SybilSignal,
+    EligibilityRule, RuleOutcome, EligibilityReport,
+    ActionChallengeRequest, ActionChallenge, ActionVerifyRequest, ActionVerdict, SignedActionVerdict,
+    WebhookEventType, WebhookEndpointRequest, WebhookEndpoint, WebhookPayload, WebhookDelivery, WebhookDeliveryStatus,
+};
+use crate::handlers::revocation::AdminRevocationRequest;
 
 #[derive(OpenApi)]
 #[openapi(
@@ -17,9 +34,68 @@ use invariant_shared::{GenesisRequest, Heartbeat, Identity, IdentityStatus, Netw
         crate::handlers::genesis::get_challenge_handler,
         crate::handlers::heartbeat::heartbeat_handler,
         crate::handlers::heartbeat::get_heartbeat_challenge_handler,
+        crate::handlers::identity::rotate_key_handler,
+        crate::handlers::identity::get_transitions_handler,
+        crate::handlers::identity::get_reactivations_handler,
+        crate::handlers::recovery::set_guardians_handler,
+        crate::handlers::recovery::initiate_recovery_handler,
+        crate::handlers::recovery::approve_recovery_handler,
+        crate::handlers::recovery::reject_recovery_handler,
+        crate::handlers::recovery::cancel_recovery_handler,
+        crate::handlers::recovery::finalize_recovery_handler,
+        crate::handlers::recovery::get_recovery_handler,
+        crate::handlers::receipts::get_receipt_key_handler,
+        crate::handlers::identity::pairwise_link_handler,
+        crate::handlers::identity::issue_token_handler,
+        crate::handlers::identity::jwks_handler,
+        crate::handlers::privacy_pass::issuer_directory_handler,
+        crate::handlers::privacy_pass::token_request_handler,
+        crate::handlers::transparency::get_sth_handler,
+        crate::handlers::transparency::get_entries_handler,
+        crate::handlers::transparency::get_inclusion_proof_handler,
+        crate::handlers::transparency::get_consistency_proof_handler,
+        crate::handlers::revocation::admin_revoke_handler,
+        crate::handlers::revocation::self_revoke_handler,
+        crate::handlers::revocation::partner_revocations_handler,
+        crate::handlers::switch::arm_switch_handler,
+        crate::handlers::switch::disarm_switch_handler,
+        crate::handlers::switch::get_switch_handler,
+        crate::handlers::devices::enroll_device_handler,
+        crate::handlers::devices::revoke_device_handler,
+        crate::handlers::devices::list_devices_handler,
+        crate::handlers::migration::migrate_identity_handler,
+        crate::handlers::migration::get_migration_handler,
+        crate::handlers::streak::get_streak_handler,
+        crate::handlers::streak::set_streak_timezone_handler,
+        crate::handlers::sybil::list_clusters_handler,
+        crate::handlers::upgrades::upgrade_progress_handler,
+        crate::handlers::action::action_challenge_handler,
+        crate::handlers::action::action_verify_handler,
+        crate::handlers::webhooks::register_webhook_handler,
+        crate::handlers::webhooks::list_webhooks_handler,
+        crate::handlers::webhooks::delete_webhook_handler,
+        crate::handlers::webhooks::list_deliveries_handler,
+        crate::handlers::webhooks::replay_delivery_handler,
     ),
     components(
-        schemas(GenesisRequest, Heartbeat, Identity, IdentityStatus, Network)
+        schemas(
+            GenesisRequest, Heartbeat, Identity, IdentityStatus, IdentityTransition, TransitionReason, KeyRotationRequest, Network,
+            GuardianSetRequest, RecoveryInitRequest, RecoveryApproval, RecoveryRejection, RecoveryCancelRequest, RecoveryStatus, RecoveryEvent,
+            Receipt, ReceiptKind, ReceiptVerdict, SignedReceipt,
+            ProofTokenClaims, ProofTokenRequest, Jwk, JwkSet, PairwiseLinkRequest, TokenIssuanceRequest,
+            LogEntry, LogEventKind, TreeHead, SignedTreeHead,
+            Revocation, RevocationReason, RevocationNotice, SelfRevocationRequest, AdminRevocationRequest,
+            DeadMansSwitch, SwitchStatus, SwitchArmRequest, SwitchDisarmRequest,
+            Device, DeviceStatus, DeviceEnrollmentRequest, DeviceRevocationRequest,
+            MigrationRequest, IdentityMigration,
+            GenesisUpgrade, GenesisVersionStats, GenesisUpgradeProgress, Reactivation,
+            StreakState, StreakTimezoneRequest,
+            SecurityLevel, TrustScore, TrustSignal, SignalContribution,
+            SybilCluster, SybilConfidence, SybilSignal,
+            EligibilityRule, RuleOutcome, EligibilityReport,
+            ActionChallengeRequest, ActionChallenge, ActionVerifyRequest, ActionVerdict, SignedActionVerdict,
+            WebhookEventType, WebhookEndpointRequest, WebhookEndpoint, WebhookPayload, WebhookDelivery, WebhookDeliveryStatus
+        )
     ),
     tags(
         (name = "invariant", description = "Invariant Protocol API")
@@ -7,18 +7,67 @@
  */
 
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::{PgConnection, PgPool, Postgres, Row};
+use sqlx::postgres::PgArguments;
+use sqlx::query::Query;
 use uuid::Uuid;
-use invariant_engine::{IdentityStorage, EngineError};
-use invariant_shared::{Identity, Heartbeat, IdentityStatus};
+use invariant_engine::{AttestationRefresh, KeyRotation, MigrationHandover, IdentityStorage, RecoveryStorage, RecoveryCompletion, PairwiseStorage, PrivacyPassStorage, TransparencyStorage, LogRecord, LifecycleStorage, RevocationStorage, SwitchStorage, StreakStorage, TrustStorage, SybilStorage, EligibilityStorage, DeviceStorage, MigrationStorage, UpgradeStorage, ReactivationStorage, EngineError, ProtocolParameters};
+use invariant_engine::trust::RecentActivity;
+use invariant_engine::sybil::{AttestationFingerprint, IdentityFingerprint};
+use invariant_shared::{Identity, Heartbeat, IdentityStatus, IdentityTransition, TransitionReason, Revocation, RevocationReason, RevocationNotice, DeadMansSwitch, SwitchStatus, StreakState, SecurityLevel, Recovery, RecoveryStatus, RecoveryEvent, LogEntry, LogEventKind, SybilCluster, SybilConfidence, SybilSignal, WebhookEventType, WebhookEndpoint, WebhookPayload, WebhookDelivery, WebhookDeliveryStatus, EligibilityReport, Device, DeviceStatus, IdentityMigration, GenesisUpgrade, GenesisVersionStats, Reactivation};
+use chrono::{DateTime, Utc};
 use sha2::{Sha256, Digest};
 
 pub struct PostgresStorage {
     pub pool: PgPool,
+    /// Source of every SQL time window (reaper).
+    pub params: ProtocolParameters,
 }
 
 impl PostgresStorage {
-    pub fn new(pool: PgPool) -> Self { Self { pool } }
+    pub fn new(pool: PgPool, params: ProtocolParameters) -> Self { Self { pool, params } }
+
+    /// Network the identity was minted from. The first recorded prefix is kept.
+    pub async fn record_genesis_ip_prefix(&self, identity_id: &Uuid, prefix: &str) -> Result<(), EngineError> {
+        sqlx::query(r#"
+            INSERT INTO identity_fingerprints (identity_id, genesis_ip_prefix) VALUES ($1, $2)
+            ON CONFLICT (identity_id) DO UPDATE SET
+                genesis_ip_prefix = COALESCE(identity_fingerprints.genesis_ip_prefix, EXCLUDED.genesis_ip_prefix)
+        "#)
+        .bind(identity_id)
+        .bind(prefix)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Records a guardian's vote (the first one sticks) and returns how many votes of that kind the recovery has.
+    async fn add_recovery_vote(&self, recovery_id: &Uuid, guardian_id: &Uuid, approve: bool) -> Result<u32, EngineError> {
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        sqlx::query(r#"
+            INSERT INTO recovery_approvals (recovery_id, guardian_id, approve) VALUES ($1, $2, $3)
+            ON CONFLICT (recovery_id, guardian_id) DO NOTHING
+        "#)
+        .bind(recovery_id)
+        .bind(guardian_id)
+        .bind(approve)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        let count: i64 = sqlx::query("SELECT COUNT(*) AS votes FROM recovery_approvals WHERE recovery_id = $1 AND approve = $2")
+            .bind(recovery_id)
+            .bind(approve)
+            .fetch_one(&mut *tx)
+            .await
+            .and_then(|r| r.try_get("votes"))
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(count as u32)
+    }
 }
 
 #[async_trait]
@@ -26,7 +75,7 @@ impl IdentityStorage for PostgresStorage {
     async fn get_identity(&self, id: &Uuid) -> Result<Option<Identity>, EngineError> {
         let result = sqlx::query(r#"
             SELECT id, public_key, continuity_score, streak, created_at, last_heartbeat, last_attestation, status,
-                   hardware_brand, hardware_device_hash, hardware_product,
+                   hardware_brand, hardware_device_hash, hardware_product, security_level, os_patch_level,
                    genesis_version, network, username, is_genesis_eligible, fcm_token
             FROM identities WHERE id = $1
         "#)
@@ -38,84 +87,71 @@ impl IdentityStorage for PostgresStorage {
     async fn get_identity_by_public_key(&self, public_key: &[u8]) -> Result<Option<Identity>, EngineError> {
         let result = sqlx::query(r#"
             SELECT id, public_key, continuity_score, streak, created_at, last_heartbeat, last_attestation, status,
-                   hardware_brand, hardware_device_hash, hardware_product,
+                   hardware_brand, hardware_device_hash, hardware_product, security_level, os_patch_level,
                    genesis_version, network, username, is_genesis_eligible, fcm_token
-            FROM identities WHERE public_key = $1
+            FROM identities
+            WHERE public_key = $1
+               OR id = (SELECT identity_id FROM identity_devices WHERE public_key = $1 AND status = 'active')
+            LIMIT 1
         "#)
         .bind(public_key).fetch_optional(&self.pool).await.map_err(|e| EngineError::Storage(e.to_string()))?;
         
         map_row_to_identity(result)
     }
 
+    async fn is_key_retired(&self, public_key: &[u8]) -> Result<bool, EngineError> {
+        let retired: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM identity_key_history WHERE public_key = $1)")
+            .bind(public_key)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        Ok(retired)
+    }
+
     async fn save_identity(&self, identity: &Identity) -> Result<(), EngineError> {
-        let status_str = match identity.status {
-            IdentityStatus::Active => "active",
-            IdentityStatus::Stale => "stale",
-            IdentityStatus::Dormant => "dormant",
-            IdentityStatus::Revoked => "revoked",
-        };
-
-        let network_str = identity.network.to_string();
-
-        let device_hash = identity.hardware_device.as_ref().map(|raw| {
-            let mut hasher = Sha256::new();
-            hasher.update(raw.as_bytes());
-            hex::encode(hasher.finalize())
-        });
-
-        sqlx::query(r#"
-            INSERT INTO identities (
-                id, public_key, continuity_score, streak, created_at, last_heartbeat, last_attestation, status,
-                hardware_brand, hardware_device_hash, hardware_product,
-                genesis_version, network, username, is_genesis_eligible, fcm_token
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
-            ON CONFLICT (id) DO UPDATE SET 
-                status = $8, 
-                continuity_score = $3, 
-                last_heartbeat = $6,
-                last_attestation = $7
-        "#)
-        .bind(identity.id)
-        .bind(&identity.public_key)
-        .bind(identity.continuity_score as i64)
-        .bind(identity.streak as i64)
-        .bind(identity.created_at)
-        .bind(identity.last_heartbeat)
-        .bind(identity.last_attestation)
-        .bind(status_str)
-        .bind(&identity.hardware_brand)
-        .bind(device_hash)
-        .bind(&identity.hardware_product)
-        .bind(identity.genesis_version as i16)
-        .bind(network_str)
-        .bind(&identity.username)
-        .bind(identity.is_genesis_eligible)
-        .bind(&identity.fcm_token)
-        .execute(&self.pool)
-        .await
-        .map_err(|e| EngineError::Storage(e.to_string()))?;
+        identity_upsert(identity)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
         
         Ok(())
     }
 
-    async fn log_heartbeat(&self, identity: &Identity, heartbeat: &Heartbeat) -> Result<u64, EngineError> {
+    async fn record_genesis(&self, identity: &Identity, device: &Device, fingerprint: &AttestationFingerprint, log: &LogRecord) -> Result<(), EngineError> {
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        // A concurrent genesis of the same key loses on the unique key here
+        identity_upsert(identity)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_unique_violation)?;
+
+        device_insert(device, 1)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_unique_violation)?;
+
+        upsert_fingerprint(&mut tx, &identity.id, fingerprint).await?;
+        insert_log_entries(&mut tx, std::slice::from_ref(log)).await?;
+
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn log_heartbeat(&self, identity: &Identity, heartbeat: &Heartbeat, at: DateTime<Utc>) -> Result<u64, EngineError> {
         let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
 
         let row = sqlx::query("
             UPDATE identities 
             SET 
                 continuity_score = continuity_score + 1,
-                streak = CASE 
-                    WHEN NOW() - last_heartbeat < INTERVAL '360 minutes' THEN streak + 1 
-                    ELSE 1 
-                END,
-                last_heartbeat = NOW(), 
-                status = 'active'
+                last_heartbeat = $2
             WHERE id = $1
             RETURNING continuity_score
         ")
         .bind(identity.id)
+        .bind(at)
         .fetch_one(&mut *tx)
         .await
         .map_err(|e| EngineError::Storage(e.to_string()))?;
@@ -134,11 +170,113 @@ impl IdentityStorage for PostgresStorage {
         Ok(new_score as u64)
     }
 
-    async fn run_reaper(&self) -> Result<u64, EngineError> {
+    async fn rotate_public_key(&self, write: &KeyRotation<'_>) -> Result<(), EngineError> {
+        let KeyRotation { identity, previous_public_key, .. } = *write;
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        // 1. Archive the outgoing key (with the device it lived on)
+        sqlx::query(r#"
+            INSERT INTO identity_key_history (identity_id, public_key, replaced_by, hardware_device_hash)
+            SELECT id, public_key, $3, hardware_device_hash FROM identities
+            WHERE id = $1 AND public_key = $2
+        "#)
+        .bind(identity.id)
+        .bind(previous_public_key)
+        .bind(&identity.public_key)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        // 2. Swap (guarded on the old key so concurrent rotations cannot both win)
+        let result = sqlx::query(r#"
+            UPDATE identities SET
+                public_key = $3,
+                hardware_brand = $4,
+                hardware_device_hash = $5,
+                hardware_product = $6,
+                last_attestation = $7,
+                security_level = $8,
+                os_patch_level = $9
+            WHERE id = $1 AND public_key = $2
+        "#)
+        .bind(identity.id)
+        .bind(previous_public_key)
+        .bind(&identity.public_key)
+        .bind(&identity.hardware_brand)
+        .bind(hash_device(identity.hardware_device.as_deref()))
+        .bind(&identity.hardware_product)
+        .bind(identity.last_attestation)
+        .bind(identity.security_level.map(|l| l.tag()))
+        .bind(identity.os_patch_level.map(|p| p as i32))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(EngineError::Storage("Key rotation conflict: key already rotated".into()));
+        }
+
+        // 3. Status next: a concurrent change aborts the whole rotation
+        if let Some(transition) = &write.transition {
+            if !insert_transition(&mut tx, transition).await? {
+                return Err(EngineError::Storage("Concurrent status change".into()));
+            }
+        }
+        if let Some((reactivation, streak)) = &write.reactivation {
+            insert_reactivation(&mut tx, reactivation, streak).await?;
+        }
+
+        // 4. The primary device moves onto the new key
+        sqlx::query(r#"
+            UPDATE identity_devices SET
+                public_key = $3,
+                hardware_brand = $4,
+                hardware_device_hash = $5,
+                hardware_product = $6,
+                last_attestation = $7,
+                security_level = $8,
+                os_patch_level = $9
+            WHERE identity_id = $1 AND public_key = $2 AND status = 'active'
+        "#)
+        .bind(identity.id)
+        .bind(previous_public_key)
+        .bind(&identity.public_key)
+        .bind(&identity.hardware_brand)
+        .bind(hash_device(identity.hardware_device.as_deref()))
+        .bind(&identity.hardware_product)
+        .bind(identity.last_attestation)
+        .bind(identity.security_level.map(|l| l.tag()))
+        .bind(identity.os_patch_level.map(|p| p as i32))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        // 5. Sybil fingerprint of the new key, then the log entry
+        upsert_fingerprint(&mut tx, &identity.id, &write.fingerprint).await?;
+        insert_log_entries(&mut tx, std::slice::from_ref(&write.log)).await?;
+
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn run_reaper(&self, now: DateTime<Utc>) -> Result<u64, EngineError> {
         let result = sqlx::query(r#"
-            UPDATE identities SET status = 'dormant', streak = 0
-            WHERE status = 'active' AND last_heartbeat < NOW() - INTERVAL '30 days'
-        "#).execute(&self.pool).await.map_err(|e| EngineError::Storage(e.to_string()))?;
+            WITH silent AS (
+                SELECT id, status FROM identities
+                WHERE status IN ('active', 'stale') AND last_heartbeat < $3 - make_interval(days => $1)
+                FOR UPDATE
+            ), reaped AS (
+                UPDATE identities i SET status = 'dormant', streak = CASE WHEN $2 THEN i.streak ELSE 0 END
+                FROM silent WHERE i.id = silent.id
+                RETURNING i.id, silent.status AS from_status
+            )
+            INSERT INTO identity_transitions (identity_id, from_status, to_status, reason, created_at)
+            SELECT id, from_status, 'dormant', 'inactivity', $3 FROM reaped
+        "#)
+        .bind(self.params.reaper_window_days as i32)
+        .bind(self.params.dormancy_keeps_streak)
+        .bind(now)
+        .execute(&self.pool).await.map_err(|e| EngineError::Storage(e.to_string()))?;
 
         Ok(result.rows_affected())
     }
@@ -157,7 +295,7 @@ impl IdentityStorage for PostgresStorage {
     async fn get_leaderboard(&self, limit: i64) -> Result<Vec<Identity>, EngineError> {
         let rows = sqlx::query(r#"
             SELECT id, public_key, continuity_score, streak, created_at, last_heartbeat, last_attestation, status,
-                   hardware_brand, hardware_device_hash, hardware_product,
+                   hardware_brand, hardware_device_hash, hardware_product, security_level, os_patch_level,
                    genesis_version, network, username, is_genesis_eligible, fcm_token
             FROM identities 
             WHERE status = 'active'
@@ -188,29 +326,1891 @@ impl IdentityStorage for PostgresStorage {
         Ok(())
     }
 
-    async fn get_late_fcm_tokens(&self, minutes: i64) -> Result<Vec<String>, EngineError> {
+    async fn get_late_fcm_tokens(&self, minutes: i64, now: DateTime<Utc>) -> Result<Vec<String>, EngineError> {
         let rows = sqlx::query(r#"
             SELECT fcm_token FROM identities 
             WHERE status = 'active' AND fcm_token IS NOT NULL
-            AND last_heartbeat < NOW() - make_interval(mins => $1)
+            AND last_heartbeat < $2 - make_interval(mins => $1)
         "#)
         .bind(minutes as i32)
+        .bind(now)
         .fetch_all(&self.pool).await.map_err(|e| EngineError::Storage(e.to_string()))?;
 
         Ok(rows.into_iter().filter_map(|r| r.get(0)).collect())
     }
 }
 
-fn map_row_to_identity(row: Option<sqlx::postgres::PgRow>) -> Result<Option<Identity>, EngineError> {
-    match row {
-        Some(row) => {
-            let status_str: String = row.try_get("status").unwrap_or_default();
-            let status = match status_str.as_str() {
-                "active" => IdentityStatus::Active,
-                "stale" => IdentityStatus::Stale,
-                "dormant" => IdentityStatus::Dormant,
-                _ => IdentityStatus::Revoked,
-            };
+#[async_trait]
+impl RecoveryStorage for PostgresStorage {
+    async fn set_guardians(&self, identity_id: &Uuid, guardian_ids: &[Uuid], threshold: u8) -> Result<(), EngineError> {
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        sqlx::query(r#"
+            INSERT INTO guardian_sets (identity_id, threshold) VALUES ($1, $2)
+            ON CONFLICT (identity_id) DO UPDATE SET threshold = $2, updated_at = NOW()
+        "#)
+        .bind(identity_id)
+        .bind(threshold as i16)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        sqlx::query("DELETE FROM identity_guardians WHERE identity_id = $1")
+            .bind(identity_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        for (position, guardian_id) in guardian_ids.iter().enumerate() {
+            sqlx::query("INSERT INTO identity_guardians (identity_id, guardian_id, position) VALUES ($1, $2, $3)")
+                .bind(identity_id)
+                .bind(guardian_id)
+                .bind(position as i16)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| EngineError::Storage(e.to_string()))?;
+        }
+
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_guardians(&self, identity_id: &Uuid) -> Result<Option<(Vec<Uuid>, u8)>, EngineError> {
+        let set = sqlx::query("SELECT threshold FROM guardian_sets WHERE identity_id = $1")
+            .bind(identity_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        let Some(set) = set else { return Ok(None) };
+        let threshold: i16 = set.try_get("threshold").map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        let rows = sqlx::query("SELECT guardian_id FROM identity_guardians WHERE identity_id = $1 ORDER BY position")
+            .bind(identity_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        let guardians = rows.into_iter().filter_map(|r| r.try_get("guardian_id").ok()).collect();
+        Ok(Some((guardians, threshold as u8)))
+    }
+
+    async fn create_recovery(&self, recovery: &Recovery) -> Result<(), EngineError> {
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        // 1. Close overdue attempts so they release the open slot (ux_recovery_open)
+        sqlx::query(r#"
+            WITH expired AS (
+                UPDATE recovery_requests SET status = 'expired'
+                WHERE identity_id = $1 AND status = 'pending' AND expires_at <= $2
+                RETURNING id, identity_id
+            )
+            INSERT INTO recovery_events (recovery_id, identity_id, event, created_at)
+            SELECT id, identity_id, 'expired', $2 FROM expired
+        "#)
+        .bind(recovery.identity_id)
+        .bind(recovery.initiated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        // 2. Open the new one
+        sqlx::query(r#"
+            INSERT INTO recovery_requests (
+                id, identity_id, new_public_key, hardware_brand, hardware_device_hash, hardware_product,
+                status, threshold, initiated_at, executable_at, security_level, os_patch_level, expires_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        "#)
+        .bind(recovery.id)
+        .bind(recovery.identity_id)
+        .bind(&recovery.new_public_key)
+        .bind(&recovery.hardware_brand)
+        .bind(hash_device(recovery.hardware_device.as_deref()))
+        .bind(&recovery.hardware_product)
+        .bind(recovery_status_to_str(&recovery.status))
+        .bind(recovery.threshold as i16)
+        .bind(recovery.initiated_at)
+        .bind(recovery.executable_at)
+        .bind(recovery.security_level.map(|l| l.tag()))
+        .bind(recovery.os_patch_level.map(|p| p as i32))
+        .bind(recovery.expires_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_recovery(&self, recovery_id: &Uuid) -> Result<Option<Recovery>, EngineError> {
+        let row = sqlx::query(&format!("{} WHERE r.id = $1", RECOVERY_SELECT))
+            .bind(recovery_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        row.map(map_row_to_recovery).transpose()
+    }
+
+    async fn get_open_recovery(&self, identity_id: &Uuid, now: chrono::DateTime<chrono::Utc>) -> Result<Option<Recovery>, EngineError> {
+        let row = sqlx::query(&format!(
+            "{} WHERE r.identity_id = $1 AND (r.status = 'approved' OR (r.status = 'pending' AND r.expires_at > $2))",
+            RECOVERY_SELECT
+        ))
+            .bind(identity_id)
+            .bind(now)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        row.map(map_row_to_recovery).transpose()
+    }
+
+    async fn add_recovery_approval(&self, recovery_id: &Uuid, guardian_id: &Uuid) -> Result<u32, EngineError> {
+        self.add_recovery_vote(recovery_id, guardian_id, true).await
+    }
+
+    async fn add_recovery_rejection(&self, recovery_id: &Uuid, guardian_id: &Uuid) -> Result<u32, EngineError> {
+        self.add_recovery_vote(recovery_id, guardian_id, false).await
+    }
+
+    async fn update_recovery_status(&self, recovery: &Recovery) -> Result<(), EngineError> {
+        sqlx::query("UPDATE recovery_requests SET status = $2, executable_at = $3 WHERE id = $1")
+            .bind(recovery.id)
+            .bind(recovery_status_to_str(&recovery.status))
+            .bind(recovery.executable_at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn complete_recovery(&self, write: &RecoveryCompletion<'_>) -> Result<(), EngineError> {
+        let recovery = write.recovery;
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        // 1. Claim the recovery (guards against a concurrent cancel/finalize)
+        let claimed = sqlx::query("UPDATE recovery_requests SET status = 'completed' WHERE id = $1 AND status = 'approved'")
+            .bind(recovery.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        if claimed.rows_affected() == 0 {
+            return Err(EngineError::RecoveryRejected("Recovery is no longer approved".into()));
+        }
+
+        // 2. Archive the lost key
+        sqlx::query(r#"
+            INSERT INTO identity_key_history (identity_id, public_key, replaced_by, hardware_device_hash)
+            SELECT id, public_key, $3, hardware_device_hash FROM identities
+            WHERE id = $1 AND public_key = $2
+        "#)
+        .bind(recovery.identity_id)
+        .bind(write.previous_public_key)
+        .bind(&recovery.new_public_key)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        // 3. Install the recovery key (its trust timer starts now, when the key goes live)
+        let swapped = sqlx::query(r#"
+            UPDATE identities i SET
+                public_key = r.new_public_key,
+                hardware_brand = r.hardware_brand,
+                hardware_device_hash = r.hardware_device_hash,
+                hardware_product = r.hardware_product,
+                security_level = r.security_level,
+                os_patch_level = r.os_patch_level,
+                last_attestation = $3
+            FROM recovery_requests r
+            WHERE r.id = $1 AND i.id = r.identity_id AND i.public_key = $2
+        "#)
+        .bind(recovery.id)
+        .bind(write.previous_public_key)
+        .bind(write.at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        if swapped.rows_affected() == 0 {
+            return Err(EngineError::Storage("Key rotation conflict: key already rotated".into()));
+        }
+
+        // 4. Status next: a concurrent change aborts the whole recovery
+        if let Some(transition) = &write.transition {
+            if !insert_transition(&mut tx, transition).await? {
+                return Err(EngineError::Storage("Concurrent status change".into()));
+            }
+        }
+        if let Some((reactivation, streak)) = &write.reactivation {
+            insert_reactivation(&mut tx, reactivation, streak).await?;
+        }
+
+        // 5. The primary device moves onto the recovery key
+        sqlx::query(r#"
+            UPDATE identity_devices d SET
+                public_key = r.new_public_key,
+                hardware_brand = r.hardware_brand,
+                hardware_device_hash = r.hardware_device_hash,
+                hardware_product = r.hardware_product,
+                security_level = r.security_level,
+                os_patch_level = r.os_patch_level,
+                last_attestation = $3
+            FROM recovery_requests r
+            WHERE r.id = $1 AND d.identity_id = r.identity_id AND d.public_key = $2 AND d.status = 'active'
+        "#)
+        .bind(recovery.id)
+        .bind(write.previous_public_key)
+        .bind(write.at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        insert_recovery_event(&mut tx, &recovery.id, &recovery.identity_id, "completed", None).await?;
+        insert_log_entries(&mut tx, std::slice::from_ref(&write.log)).await?;
+
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn log_recovery_event(
+        &self,
+        recovery_id: &Uuid,
+        identity_id: &Uuid,
+        event: &str,
+        actor_id: Option<&Uuid>,
+    ) -> Result<(), EngineError> {
+        let mut conn = self.pool.acquire().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        insert_recovery_event(&mut conn, recovery_id, identity_id, event, actor_id).await
+    }
+
+    async fn get_recovery_events(&self, recovery_id: &Uuid) -> Result<Vec<RecoveryEvent>, EngineError> {
+        let rows = sqlx::query("SELECT event, actor_id, created_at FROM recovery_events WHERE recovery_id = $1 ORDER BY id")
+            .bind(recovery_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        rows.into_iter().map(|row| Ok(RecoveryEvent {
+            event: row.try_get("event").map_err(|e| EngineError::Storage(e.to_string()))?,
+            actor_id: row.try_get("actor_id").ok(),
+            created_at: row.try_get("created_at").map_err(|e| EngineError::Storage(e.to_string()))?,
+        })).collect()
+    }
+}
+
+#[async_trait]
+impl PairwiseStorage for PostgresStorage {
+    async fn get_pairwise_id(&self, partner_id: &str, identity_id: &Uuid) -> Result<Option<Uuid>, EngineError> {
+        let row = sqlx::query("SELECT pairwise_id FROM partner_subjects WHERE partner_id = $1 AND identity_id = $2")
+            .bind(partner_id)
+            .bind(identity_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        Ok(row.and_then(|r| r.try_get("pairwise_id").ok()))
+    }
+
+    async fn link_pairwise(&self, partner_id: &str, pairwise_id: &Uuid, identity_id: &Uuid) -> Result<(), EngineError> {
+        sqlx::query(r#"
+            INSERT INTO partner_subjects (partner_id, pairwise_id, identity_id) VALUES ($1, $2, $3)
+            ON CONFLICT (partner_id, identity_id) DO NOTHING
+        "#)
+        .bind(partner_id)
+        .bind(pairwise_id)
+        .bind(identity_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn resolve_pairwise(&self, partner_id: &str, pairwise_id: &Uuid) -> Result<Option<Uuid>, EngineError> {
+        let row = sqlx::query("SELECT identity_id FROM partner_subjects WHERE partner_id = $1 AND pairwise_id = $2")
+            .bind(partner_id)
+            .bind(pairwise_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        Ok(row.and_then(|r| r.try_get("identity_id").ok()))
+    }
+}
+
+#[async_trait]
+impl PrivacyPassStorage for PostgresStorage {
+    async fn claim_token_issuance(&self, identity_id: &Uuid, count: u32, min_interval: chrono::Duration, now: DateTime<Utc>) -> Result<bool, EngineError> {
+        // Upsert guarded by the window: a concurrent second batch updates zero rows.
+        let claimed = sqlx::query(r#"
+            INSERT INTO token_issuances (identity_id, last_issued_at, total_issued) VALUES ($1, $4, $2)
+            ON CONFLICT (identity_id) DO UPDATE SET
+                last_issued_at = $4,
+                total_issued = token_issuances.total_issued + EXCLUDED.total_issued
+            WHERE token_issuances.last_issued_at <= $4 - make_interval(secs => $3)
+        "#)
+        .bind(identity_id)
+        .bind(count as i64)
+        .bind(min_interval.num_seconds() as f64)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        Ok(claimed.rows_affected() == 1)
+    }
+}
+
+#[async_trait]
+impl TransparencyStorage for PostgresStorage {
+    async fn append_log_entry(&self, kind: LogEventKind, identity_id: &Uuid, public_key_hash: &[u8]) -> Result<LogEntry, EngineError> {
+        let record = LogRecord { kind, identity_id: *identity_id, public_key_hash: public_key_hash.to_vec() };
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        let mut entries = insert_log_entries(&mut tx, std::slice::from_ref(&record)).await?;
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        entries.pop().ok_or_else(|| EngineError::Storage("Log append returned no entry".into()))
+    }
+
+    async fn get_log_entries(&self, start: u64, end: u64) -> Result<Vec<LogEntry>, EngineError> {
+        let rows = sqlx::query(r#"
+            SELECT leaf_index, kind, identity_id, public_key_hash, logged_at
+            FROM transparency_log WHERE leaf_index >= $1 AND leaf_index < $2 ORDER BY leaf_index
+        "#)
+        .bind(start as i64)
+        .bind(end as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        rows.into_iter().map(map_row_to_log_entry).collect()
+    }
+
+    async fn get_log_size(&self) -> Result<u64, EngineError> {
+        let row = sqlx::query("SELECT COUNT(*) AS size FROM transparency_log")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(row.try_get::<i64, _>("size").unwrap_or(0) as u64)
+    }
+}
+
+#[async_trait]
+impl RevocationStorage for PostgresStorage {
+    async fn record_revocation(&self, transition: &IdentityTransition, reason: RevocationReason, log: &LogRecord) -> Result<Revocation, EngineError> {
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        if !insert_transition(&mut tx, transition).await? {
+            return Err(EngineError::Storage("Concurrent status change".into()));
+        }
+
+        let sequence = insert_revocation(&mut tx, transition, reason).await?;
+
+        insert_log_entries(&mut tx, std::slice::from_ref(log)).await?;
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        Ok(Revocation {
+            sequence,
+            identity_id: transition.identity_id,
+            reason,
+            revoked_by: transition.actor_id,
+            revoked_at: transition.created_at,
+        })
+    }
+
+    async fn get_partner_revocations(&self, partner_id: &str, after_sequence: u64, limit: u32) -> Result<Vec<RevocationNotice>, EngineError> {
+        let rows = sqlx::query(r#"
+            SELECT r.sequence, p.pairwise_id, r.reason, r.revoked_at
+            FROM revocations r
+            JOIN partner_subjects p ON p.identity_id = r.identity_id AND p.partner_id = $1
+            WHERE r.sequence > $2
+            ORDER BY r.sequence
+            LIMIT $3
+        "#)
+        .bind(partner_id)
+        .bind(after_sequence as i64)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        rows.into_iter().map(|row| {
+            let reason: String = row.try_get("reason").map_err(|e| EngineError::Storage(e.to_string()))?;
+            Ok(RevocationNotice {
+                sequence: row.try_get::<i64, _>("sequence").map_err(|e| EngineError::Storage(e.to_string()))? as u64,
+                subject_id: row.try_get("pairwise_id").map_err(|e| EngineError::Storage(e.to_string()))?,
+                reason: str_to_revocation_reason(&reason)?,
+                revoked_at: row.try_get("revoked_at").map_err(|e| EngineError::Storage(e.to_string()))?,
+            })
+        }).collect()
+    }
+}
+
+const SWITCH_COLUMNS: &str =
+    "id, switch_status, switch_threshold_days, switch_beneficiary_id, switch_pending_since, switch_triggered_at";
+
+#[async_trait]
+impl SwitchStorage for PostgresStorage {
+    async fn get_switch(&self, identity_id: &Uuid) -> Result<Option<DeadMansSwitch>, EngineError> {
+        let row = sqlx::query(&format!("SELECT {} FROM identities WHERE id = $1", SWITCH_COLUMNS))
+            .bind(identity_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        row.map(map_row_to_switch).transpose()
+    }
+
+    async fn save_switch(&self, switch: &DeadMansSwitch) -> Result<(), EngineError> {
+        sqlx::query(r#"
+            UPDATE identities
+            SET switch_status = $2, switch_threshold_days = $3, switch_beneficiary_id = $4,
+                switch_pending_since = $5, switch_triggered_at = $6
+            WHERE id = $1
+        "#)
+        .bind(switch.identity_id)
+        .bind(switch.status.tag())
+        .bind(switch.threshold_days as i32)
+        .bind(switch.beneficiary_id)
+        .bind(switch.pending_since)
+        .bind(switch.triggered_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_due_switches(&self, now: chrono::DateTime<chrono::Utc>) -> Result<Vec<DeadMansSwitch>, EngineError> {
+        let rows = sqlx::query(&format!(r#"
+            SELECT {} FROM identities
+            WHERE status <> 'revoked'
+              AND ((switch_status = 'armed' AND last_heartbeat <= $1 - make_interval(days => switch_threshold_days))
+                   OR switch_status = 'pending')
+        "#, SWITCH_COLUMNS))
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        rows.into_iter().map(map_row_to_switch).collect()
+    }
+
+    async fn transition_switch(&self, switch: &DeadMansSwitch, from: SwitchStatus) -> Result<bool, EngineError> {
+        let result = sqlx::query(r#"
+            UPDATE identities
+            SET switch_status = $2, switch_pending_since = $3, switch_triggered_at = $4
+            WHERE id = $1 AND switch_status = $5
+        "#)
+        .bind(switch.identity_id)
+        .bind(switch.status.tag())
+        .bind(switch.pending_since)
+        .bind(switch.triggered_at)
+        .bind(from.tag())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+#[async_trait]
+impl StreakStorage for PostgresStorage {
+    async fn get_streak(&self, identity_id: &Uuid) -> Result<Option<StreakState>, EngineError> {
+        let row = sqlx::query("SELECT streak, streak_day, streak_freezes, streak_timezone FROM identities WHERE id = $1")
+            .bind(identity_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        row.map(|row| Ok(StreakState {
+            streak: row.try_get::<i64, _>("streak").map_err(|e| EngineError::Storage(e.to_string()))?.max(0) as u64,
+            last_day: row.try_get("streak_day").map_err(|e| EngineError::Storage(e.to_string()))?,
+            freezes: row.try_get::<i32, _>("streak_freezes").map_err(|e| EngineError::Storage(e.to_string()))?.max(0) as u32,
+            timezone: row.try_get("streak_timezone").map_err(|e| EngineError::Storage(e.to_string()))?,
+        })).transpose()
+    }
+
+    async fn save_streak(&self, identity_id: &Uuid, state: &StreakState) -> Result<(), EngineError> {
+        sqlx::query(r#"
+            UPDATE identities
+            SET streak = $2, streak_day = $3, streak_freezes = $4, streak_timezone = $5
+            WHERE id = $1
+        "#)
+        .bind(identity_id)
+        .bind(state.streak as i64)
+        .bind(state.last_day)
+        .bind(state.freezes as i32)
+        .bind(&state.timezone)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+}
+
+fn map_row_to_switch(row: sqlx::postgres::PgRow) -> Result<DeadMansSwitch, EngineError> {
+    let status: String = row.try_get("switch_status").map_err(|e| EngineError::Storage(e.to_string()))?;
+    let status = match status.as_str() {
+        "inactive" => SwitchStatus::Inactive,
+        "armed" => SwitchStatus::Armed,
+        "pending" => SwitchStatus::Pending,
+        "triggered" => SwitchStatus::Triggered,
+        other => return Err(EngineError::Storage(format!("Unknown switch status '{}'", other))),
+    };
+    let threshold_days: Option<i32> = row.try_get("switch_threshold_days").map_err(|e| EngineError::Storage(e.to_string()))?;
+
+    Ok(DeadMansSwitch {
+        identity_id: row.try_get("id").map_err(|e| EngineError::Storage(e.to_string()))?,
+        status,
+        threshold_days: threshold_days.unwrap_or(365).max(0) as u32,
+        beneficiary_id: row.try_get("switch_beneficiary_id").map_err(|e| EngineError::Storage(e.to_string()))?,
+        pending_since: row.try_get("switch_pending_since").map_err(|e| EngineError::Storage(e.to_string()))?,
+        triggered_at: row.try_get("switch_triggered_at").map_err(|e| EngineError::Storage(e.to_string()))?,
+    })
+}
+
+fn map_row_to_log_entry(row: sqlx::postgres::PgRow) -> Result<LogEntry, EngineError> {
+    let kind_str: String = row.try_get("kind").map_err(|e| EngineError::Storage(e.to_string()))?;
+    let kind = match kind_str.as_str() {
+        "genesis" => LogEventKind::Genesis,
+        "key_rotation" => LogEventKind::KeyRotation,
+        "recovery" => LogEventKind::Recovery,
+        "reattestation" => LogEventKind::Reattestation,
+        "revocation" => LogEventKind::Revocation,
+        "device_enrolled" => LogEventKind::DeviceEnrolled,
+        "device_revoked" => LogEventKind::DeviceRevoked,
+        "migration" => LogEventKind::Migration,
+        other => return Err(EngineError::Storage(format!("Unknown log event kind: {}", other))),
+    };
+
+    Ok(LogEntry {
+        index: row.try_get::<i64, _>("leaf_index").map_err(|e| EngineError::Storage(e.to_string()))? as u64,
+        kind,
+        identity_id: row.try_get("identity_id").map_err(|e| EngineError::Storage(e.to_string()))?,
+        public_key_hash: row.try_get("public_key_hash").map_err(|e| EngineError::Storage(e.to_string()))?,
+        logged_at: row.try_get("logged_at").map_err(|e| EngineError::Storage(e.to_string()))?,
+    })
+}
+
+const RECOVERY_SELECT: &str = r#"
+    SELECT r.id, r.identity_id, r.new_public_key, r.hardware_brand, r.hardware_device_hash, r.hardware_product,
+           r.security_level, r.os_patch_level, r.status, r.threshold, r.initiated_at, r.executable_at,
+           r.expires_at,
+           (SELECT COUNT(*) FROM recovery_approvals a WHERE a.recovery_id = r.id AND a.approve) AS approvals,
+           (SELECT COUNT(*) FROM recovery_approvals a WHERE a.recovery_id = r.id AND NOT a.approve) AS rejections
+    FROM recovery_requests r
+"#;
+
+fn recovery_status_to_str(status: &RecoveryStatus) -> &'static str {
+    match status {
+        RecoveryStatus::Pending => "pending",
+        RecoveryStatus::Approved => "approved",
+        RecoveryStatus::Completed => "completed",
+        RecoveryStatus::Cancelled => "cancelled",
+        RecoveryStatus::Rejected => "rejected",
+        RecoveryStatus::Expired => "expired",
+    }
+}
+
+fn map_row_to_recovery(row: sqlx::postgres::PgRow) -> Result<Recovery, EngineError> {
+    let status_str: String = row.try_get("status").unwrap_or_default();
+    let status = match status_str.as_str() {
+        "pending" => RecoveryStatus::Pending,
+        "approved" => RecoveryStatus::Approved,
+        "completed" => RecoveryStatus::Completed,
+        "rejected" => RecoveryStatus::Rejected,
+        "expired" => RecoveryStatus::Expired,
+        _ => RecoveryStatus::Cancelled,
+    };
+
+    Ok(Recovery {
+        id: row.try_get("id").map_err(|e| EngineError::Storage(e.to_string()))?,
+        identity_id: row.try_get("identity_id").map_err(|e| EngineError::Storage(e.to_string()))?,
+        new_public_key: row.try_get("new_public_key").map_err(|e| EngineError::Storage(e.to_string()))?,
+        hardware_brand: row.try_get("hardware_brand").ok(),
+        hardware_device: row.try_get("hardware_device_hash").ok(),
+        hardware_product: row.try_get("hardware_product").ok(),
+        security_level: map_security_level(&row),
+        os_patch_level: row.try_get::<i32, _>("os_patch_level").ok().map(|p| p as u32),
+        status,
+        approvals: row.try_get::<i64, _>("approvals").unwrap_or(0) as u32,
+        rejections: row.try_get::<i64, _>("rejections").unwrap_or(0) as u32,
+        threshold: row.try_get::<i16, _>("threshold").unwrap_or(0) as u8,
+        initiated_at: row.try_get("initiated_at").map_err(|e| EngineError::Storage(e.to_string()))?,
+        expires_at: row.try_get("expires_at").map_err(|e| EngineError::Storage(e.to_string()))?,
+        executable_at: row.try_get("executable_at").ok(),
+    })
+}
+
+#[async_trait]
+impl TrustStorage for PostgresStorage {
+    async fn get_recent_activity(&self, identity_id: &Uuid, since: chrono::DateTime<chrono::Utc>) -> Result<RecentActivity, EngineError> {
+        // A completed recovery also archives a key; it is counted as a recovery, not a rotation.
+        let row = sqlx::query(r#"
+            SELECT
+                (SELECT COUNT(*) FROM identity_key_history h
+                 WHERE h.identity_id = $1 AND h.retired_at > $2
+                   AND NOT EXISTS (
+                       SELECT 1 FROM recovery_requests r
+                       WHERE r.identity_id = h.identity_id AND r.new_public_key = h.replaced_by AND r.status = 'completed'
+                   )) AS key_rotations,
+                (SELECT COUNT(*) FROM recovery_requests
+                 WHERE identity_id = $1 AND status = 'completed' AND executable_at > $2) AS recoveries,
+                (SELECT COUNT(*) FROM identity_transitions
+                 WHERE identity_id = $1 AND from_status = 'dormant' AND to_status = 'active' AND created_at > $2) AS reactivations
+        "#)
+        .bind(identity_id)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        let count = |column: &str| row.try_get::<i64, _>(column).map(|n| n.max(0) as u32).map_err(|e| EngineError::Storage(e.to_string()));
+        Ok(RecentActivity {
+            key_rotations: count("key_rotations")?,
+            recoveries: count("recoveries")?,
+            reactivations: count("reactivations")?,
+        })
+    }
+}
+
+#[async_trait]
+impl SybilStorage for PostgresStorage {
+    async fn record_attestation_fingerprint(&self, identity_id: &Uuid, fingerprint: &AttestationFingerprint) -> Result<(), EngineError> {
+        let mut conn = self.pool.acquire().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        upsert_fingerprint(&mut conn, identity_id, fingerprint).await
+    }
+
+    async fn get_fingerprints(&self) -> Result<Vec<IdentityFingerprint>, EngineError> {
+        // Timing fingerprint: UTC minute of each of the last 7 heartbeats, only once there are 7.
+        let rows = sqlx::query(r#"
+            SELECT i.id, i.hardware_device_hash, f.boot_key_hash, f.intermediate_hash, f.genesis_ip_prefix,
+                   (SELECT CASE WHEN COUNT(*) = 7
+                               THEN string_agg(to_char(h.timestamp AT TIME ZONE 'UTC', 'HH24:MI'), ',' ORDER BY h.timestamp)
+                           END
+                    FROM (SELECT timestamp FROM heartbeats WHERE identity_id = i.id ORDER BY timestamp DESC LIMIT 7) h
+                   ) AS heartbeat_timing
+            FROM identities i
+            LEFT JOIN identity_fingerprints f ON f.identity_id = i.id
+            WHERE i.status <> 'revoked'
+        "#)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        rows.into_iter().map(|row| Ok(IdentityFingerprint {
+            identity_id: row.try_get("id").map_err(|e| EngineError::Storage(e.to_string()))?,
+            device_hash: row.try_get("hardware_device_hash").ok(),
+            boot_key_hash: row.try_get("boot_key_hash").ok(),
+            intermediate_hash: row.try_get("intermediate_hash").ok(),
+            genesis_ip_prefix: row.try_get("genesis_ip_prefix").ok(),
+            heartbeat_timing: row.try_get("heartbeat_timing").ok(),
+        })).collect()
+    }
+
+    async fn replace_sybil_clusters(&self, clusters: &[SybilCluster]) -> Result<(), EngineError> {
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        sqlx::query("DELETE FROM sybil_clusters")
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        for cluster in clusters {
+            let signals: Vec<&str> = cluster.signals.iter().map(|s| s.tag()).collect();
+            sqlx::query("INSERT INTO sybil_clusters (id, confidence, signals, detected_at) VALUES ($1, $2, $3, $4)")
+                .bind(cluster.id)
+                .bind(cluster.confidence.tag())
+                .bind(&signals)
+                .bind(cluster.detected_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+            sqlx::query("INSERT INTO sybil_cluster_members (cluster_id, identity_id) SELECT $1, UNNEST($2::uuid[])")
+                .bind(cluster.id)
+                .bind(&cluster.members)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| EngineError::Storage(e.to_string()))?;
+        }
+
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_sybil_clusters(&self, min_confidence: SybilConfidence, limit: u32) -> Result<Vec<SybilCluster>, EngineError> {
+        let rows = sqlx::query(&format!(r#"
+            {} WHERE {} >= {}
+            ORDER BY {} DESC, (SELECT COUNT(*) FROM sybil_cluster_members m WHERE m.cluster_id = c.id) DESC, c.id
+            LIMIT $1
+        "#, SYBIL_CLUSTER_SELECT, CONFIDENCE_RANK, confidence_rank(min_confidence), CONFIDENCE_RANK))
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        rows.into_iter().map(map_row_to_sybil_cluster).collect()
+    }
+
+    async fn get_sybil_clusters_for(&self, identity_id: &Uuid) -> Result<Vec<SybilCluster>, EngineError> {
+        let rows = sqlx::query(&format!(
+            "{} WHERE c.id IN (SELECT cluster_id FROM sybil_cluster_members WHERE identity_id = $1)",
+            SYBIL_CLUSTER_SELECT
+        ))
+            .bind(identity_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        rows.into_iter().map(map_row_to_sybil_cluster).collect()
+    }
+}
+
+const SYBIL_CLUSTER_SELECT: &str = r#"
+    SELECT c.id, c.confidence, c.signals, c.detected_at,
+           ARRAY(SELECT m.identity_id FROM sybil_cluster_members m WHERE m.cluster_id = c.id ORDER BY m.identity_id) AS members
+    FROM sybil_clusters c
+"#;
+
+const CONFIDENCE_RANK: &str = "(CASE c.confidence WHEN 'high' THEN 3 WHEN 'medium' THEN 2 ELSE 1 END)";
+
+fn confidence_rank(confidence: SybilConfidence) -> u8 {
+    match confidence {
+        SybilConfidence::Low => 1,
+        SybilConfidence::Medium => 2,
+        SybilConfidence::High => 3,
+    }
+}
+
+fn map_row_to_sybil_cluster(row: sqlx::postgres::PgRow) -> Result<SybilCluster, EngineError> {
+    let confidence = match row.try_get::<String, _>("confidence").unwrap_or_default().as_str() {
+        "high" => SybilConfidence::High,
+        "medium" => SybilConfidence::Medium,
+        _ => SybilConfidence::Low,
+    };
+    let signals: Vec<String> = row.try_get("signals").map_err(|e| EngineError::Storage(e.to_string()))?;
+
+    Ok(SybilCluster {
+        id: row.try_get("id").map_err(|e| EngineError::Storage(e.to_string()))?,
+        confidence,
+        signals: signals.iter().filter_map(|tag| SybilSignal::from_tag(tag)).collect(),
+        members: row.try_get("members").map_err(|e| EngineError::Storage(e.to_string()))?,
+        detected_at: row.try_get("detected_at").map_err(|e| EngineError::Storage(e.to_string()))?,
+    })
+}
+
+const DEVICE_COLUMNS: &str = r#"
+    id, identity_id, public_key, label, status, hardware_brand, hardware_device_hash, hardware_product,
+    security_level, os_patch_level, added_at, last_attestation, last_heartbeat, revoked_at
+"#;
+
+#[async_trait]
+impl DeviceStorage for PostgresStorage {
+    async fn get_devices(&self, identity_id: &Uuid) -> Result<Vec<Device>, EngineError> {
+        let rows = sqlx::query(&format!("SELECT {} FROM identity_devices WHERE identity_id = $1 ORDER BY added_at, id", DEVICE_COLUMNS))
+            .bind(identity_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        rows.into_iter().map(map_row_to_device).collect()
+    }
+
+    async fn add_device(&self, device: &Device, max_active: u32, log: &LogRecord) -> Result<bool, EngineError> {
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        // Serialize enrollments per identity so two devices cannot both take the last slot
+        sqlx::query("SELECT 1 FROM identities WHERE id = $1 FOR UPDATE")
+            .bind(device.identity_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        let inserted = device_insert(device, max_active)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_unique_violation)?;
+
+        if inserted.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        insert_log_entries(&mut tx, std::slice::from_ref(log)).await?;
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(true)
+    }
+
+    async fn update_device_attestation(&self, device: &Device) -> Result<(), EngineError> {
+        sqlx::query("UPDATE identity_devices SET last_attestation = $2, security_level = $3, os_patch_level = $4 WHERE id = $1")
+            .bind(device.id)
+            .bind(device.last_attestation)
+            .bind(device.security_level.map(|l| l.tag()))
+            .bind(device.os_patch_level.map(|p| p as i32))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn record_device_heartbeat(&self, device_id: &Uuid, at: DateTime<Utc>) -> Result<(), EngineError> {
+        sqlx::query("UPDATE identity_devices SET last_heartbeat = $2 WHERE id = $1")
+            .bind(device_id)
+            .bind(at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn record_attestation_refresh(&self, write: &AttestationRefresh<'_>) -> Result<u64, EngineError> {
+        let AttestationRefresh { identity, heartbeat, at, .. } = *write;
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        // 1. Status first: a concurrent change aborts before anything is written
+        if let Some(transition) = &write.transition {
+            if !insert_transition(&mut tx, transition).await? {
+                return Err(EngineError::Storage("Concurrent status change".into()));
+            }
+        }
+        if let Some((reactivation, streak)) = &write.reactivation {
+            insert_reactivation(&mut tx, reactivation, streak).await?;
+        }
+
+        // 2. Trust timer, plus score and heartbeat when one carried the attestation
+        let heartbeat_at = heartbeat.map(|_| at);
+        let row = sqlx::query("
+            UPDATE identities
+            SET
+                continuity_score = continuity_score + $6,
+                last_heartbeat = COALESCE($5, last_heartbeat),
+                last_attestation = $2,
+                security_level = $3,
+                os_patch_level = $4
+            WHERE id = $1
+            RETURNING continuity_score
+        ")
+        .bind(identity.id)
+        .bind(identity.last_attestation)
+        .bind(identity.security_level.map(|l| l.tag()))
+        .bind(identity.os_patch_level.map(|p| p as i32))
+        .bind(heartbeat_at)
+        .bind(heartbeat.is_some() as i64)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        let new_score: i64 = row.try_get("continuity_score").map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        if let Some(heartbeat) = heartbeat {
+            sqlx::query("INSERT INTO heartbeats (identity_id, device_signature, timestamp) VALUES ($1, $2, $3)")
+                .bind(heartbeat.identity_id)
+                .bind(&heartbeat.device_signature)
+                .bind(heartbeat.timestamp)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| EngineError::Storage(e.to_string()))?;
+        }
+
+        // 3. The attesting device's own timer
+        if let Some(device) = write.device {
+            sqlx::query("
+                UPDATE identity_devices
+                SET last_attestation = $2, security_level = $3, os_patch_level = $4, last_heartbeat = COALESCE($5, last_heartbeat)
+                WHERE id = $1
+            ")
+            .bind(device.id)
+            .bind(device.last_attestation)
+            .bind(device.security_level.map(|l| l.tag()))
+            .bind(device.os_patch_level.map(|p| p as i32))
+            .bind(heartbeat_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+        }
+
+        // 4. Sybil fingerprint of the primary key, then the log entry
+        if let Some(fingerprint) = &write.fingerprint {
+            upsert_fingerprint(&mut tx, &identity.id, fingerprint).await?;
+        }
+        insert_log_entries(&mut tx, std::slice::from_ref(&write.log)).await?;
+
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(new_score as u64)
+    }
+
+    async fn revoke_device(&self, device: &Device, promote: Option<&Device>, log: &[LogRecord]) -> Result<(), EngineError> {
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        // 1. Retire the device (guarded so a concurrent removal cannot run twice)
+        let revoked = sqlx::query("UPDATE identity_devices SET status = 'revoked', revoked_at = $2 WHERE id = $1 AND status = 'active'")
+            .bind(device.id)
+            .bind(device.revoked_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        if revoked.rows_affected() == 0 {
+            return Err(EngineError::InvalidDevice("Device already revoked".into()));
+        }
+
+        // 2. Archive the retired key so it can never be enrolled again
+        sqlx::query(r#"
+            INSERT INTO identity_key_history (identity_id, public_key, replaced_by, hardware_device_hash)
+            SELECT d.identity_id, d.public_key, COALESCE($2, i.public_key), d.hardware_device_hash
+            FROM identity_devices d JOIN identities i ON i.id = d.identity_id
+            WHERE d.id = $1
+        "#)
+        .bind(device.id)
+        .bind(promote.map(|successor| successor.public_key.as_slice()))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        if let Some(successor) = promote {
+            // 3. The successor's key and hardware become the identity's
+            let swapped = sqlx::query(r#"
+                UPDATE identities i SET
+                    public_key = d.public_key,
+                    hardware_brand = d.hardware_brand,
+                    hardware_device_hash = d.hardware_device_hash,
+                    hardware_product = d.hardware_product,
+                    security_level = d.security_level,
+                    os_patch_level = d.os_patch_level
+                FROM identity_devices d
+                WHERE d.id = $2 AND d.status = 'active' AND i.id = d.identity_id AND i.public_key = $1
+            "#)
+            .bind(&device.public_key)
+            .bind(successor.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+            if swapped.rows_affected() == 0 {
+                return Err(EngineError::Storage("Key rotation conflict: key already rotated".into()));
+            }
+        }
+
+        insert_log_entries(&mut tx, log).await?;
+
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+}
+
+fn map_row_to_device(row: sqlx::postgres::PgRow) -> Result<Device, EngineError> {
+    let status: String = row.try_get("status").map_err(|e| EngineError::Storage(e.to_string()))?;
+    Ok(Device {
+        id: row.try_get("id").map_err(|e| EngineError::Storage(e.to_string()))?,
+        identity_id: row.try_get("identity_id").map_err(|e| EngineError::Storage(e.to_string()))?,
+        public_key: row.try_get("public_key").map_err(|e| EngineError::Storage(e.to_string()))?,
+        label: row.try_get("label").map_err(|e| EngineError::Storage(e.to_string()))?,
+        status: if status == "revoked" { DeviceStatus::Revoked } else { DeviceStatus::Active },
+        primary: false,
+        hardware_brand: row.try_get("hardware_brand").map_err(|e| EngineError::Storage(e.to_string()))?,
+        hardware_device: row.try_get("hardware_device_hash").map_err(|e| EngineError::Storage(e.to_string()))?,
+        hardware_product: row.try_get("hardware_product").map_err(|e| EngineError::Storage(e.to_string()))?,
+        security_level: map_security_level(&row),
+        os_patch_level: row.try_get::<Option<i32>, _>("os_patch_level").map_err(|e| EngineError::Storage(e.to_string()))?.map(|p| p as u32),
+        added_at: row.try_get("added_at").map_err(|e| EngineError::Storage(e.to_string()))?,
+        last_attestation: row.try_get("last_attestation").map_err(|e| EngineError::Storage(e.to_string()))?,
+        last_heartbeat: row.try_get("last_heartbeat").map_err(|e| EngineError::Storage(e.to_string()))?,
+        revoked_at: row.try_get("revoked_at").map_err(|e| EngineError::Storage(e.to_string()))?,
+    })
+}
+
+#[async_trait]
+impl MigrationStorage for PostgresStorage {
+    async fn record_migration(&self, write: &MigrationHandover<'_>) -> Result<bool, EngineError> {
+        let MigrationHandover { identity, device, streak, migration, .. } = *write;
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        // 1. The mainnet identity, its primary device and its streak
+        identity_upsert(identity)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_unique_violation)?;
+
+        device_insert(device, 1)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_unique_violation)?;
+
+        sqlx::query("UPDATE identities SET streak = $2, streak_day = $3, streak_freezes = $4, streak_timezone = $5 WHERE id = $1")
+            .bind(identity.id)
+            .bind(streak.streak as i64)
+            .bind(streak.last_day)
+            .bind(streak.freezes as i32)
+            .bind(&streak.timezone)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        // 2. The link (a concurrent migration of the same testnet identity loses here)
+        let linked = sqlx::query(r#"
+            INSERT INTO identity_migrations (testnet_id, mainnet_id, carried_score, carried_streak, migrated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (testnet_id) DO NOTHING
+        "#)
+        .bind(migration.testnet_id)
+        .bind(migration.mainnet_id)
+        .bind(migration.carried_score as i64)
+        .bind(migration.carried_streak as i64)
+        .bind(migration.migrated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        if linked.rows_affected() == 0 {
+            return Ok(false);
+        }
+        upsert_fingerprint(&mut tx, &identity.id, &write.fingerprint).await?;
+
+        // 3. The testnet identity is retired with it
+        if !insert_transition(&mut tx, &write.revocation).await? {
+            return Err(EngineError::Storage("Concurrent status change".into()));
+        }
+        insert_revocation(&mut tx, &write.revocation, RevocationReason::Migrated).await?;
+
+        insert_log_entries(&mut tx, &write.log).await?;
+
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(true)
+    }
+
+    async fn get_migration(&self, identity_id: &Uuid) -> Result<Option<IdentityMigration>, EngineError> {
+        let row = sqlx::query(r#"
+            SELECT testnet_id, mainnet_id, carried_score, carried_streak, migrated_at
+            FROM identity_migrations
+            WHERE testnet_id = $1 OR mainnet_id = $1
+        "#)
+        .bind(identity_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        row.map(|row| Ok(IdentityMigration {
+            testnet_id: row.try_get("testnet_id").map_err(|e| EngineError::Storage(e.to_string()))?,
+            mainnet_id: row.try_get("mainnet_id").map_err(|e| EngineError::Storage(e.to_string()))?,
+            carried_score: row.try_get::<i64, _>("carried_score").map_err(|e| EngineError::Storage(e.to_string()))? as u64,
+            carried_streak: row.try_get::<i64, _>("carried_streak").map_err(|e| EngineError::Storage(e.to_string()))? as u64,
+            migrated_at: row.try_get("migrated_at").map_err(|e| EngineError::Storage(e.to_string()))?,
+        })).transpose()
+    }
+}
+
+#[async_trait]
+impl UpgradeStorage for PostgresStorage {
+    async fn record_genesis_upgrade(&self, upgrade: &GenesisUpgrade) -> Result<bool, EngineError> {
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        // Compare-and-set on the version the engine decided from.
+        let moved = sqlx::query("UPDATE identities SET genesis_version = $3 WHERE id = $1 AND genesis_version = $2")
+            .bind(upgrade.identity_id)
+            .bind(upgrade.from_version as i16)
+            .bind(upgrade.to_version as i16)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        if moved.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query("INSERT INTO genesis_upgrades (identity_id, from_version, to_version, upgraded_at) VALUES ($1, $2, $3, $4)")
+            .bind(upgrade.identity_id)
+            .bind(upgrade.from_version as i16)
+            .bind(upgrade.to_version as i16)
+            .bind(upgrade.upgraded_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(true)
+    }
+
+    async fn get_genesis_versions(&self) -> Result<Vec<GenesisVersionStats>, EngineError> {
+        let rows = sqlx::query(r#"
+            SELECT i.genesis_version,
+                   COUNT(*) AS identities,
+                   COUNT(*) FILTER (WHERE EXISTS (
+                       SELECT 1 FROM genesis_upgrades u
+                       WHERE u.identity_id = i.id AND u.to_version = i.genesis_version
+                   )) AS upgraded
+            FROM identities i
+            WHERE i.status <> 'revoked'
+            GROUP BY i.genesis_version
+            ORDER BY i.genesis_version
+        "#)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        rows.into_iter().map(|row| Ok(GenesisVersionStats {
+            genesis_version: row.try_get::<i16, _>("genesis_version").map_err(|e| EngineError::Storage(e.to_string()))? as u16,
+            identities: row.try_get::<i64, _>("identities").map_err(|e| EngineError::Storage(e.to_string()))? as u64,
+            upgraded: row.try_get::<i64, _>("upgraded").map_err(|e| EngineError::Storage(e.to_string()))? as u64,
+        })).collect()
+    }
+}
+
+#[async_trait]
+impl ReactivationStorage for PostgresStorage {
+    async fn record_reactivation(&self, reactivation: &Reactivation, streak: &StreakState) -> Result<(), EngineError> {
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        insert_reactivation(&mut tx, reactivation, streak).await?;
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_reactivations(&self, identity_id: &Uuid) -> Result<Vec<Reactivation>, EngineError> {
+        let rows = sqlx::query(r#"
+            SELECT identity_id, reason, silent_since, dormant_since, reactivated_at,
+                   score_before, score_after, streak_before, streak_after, cooling_off_until
+            FROM identity_reactivations
+            WHERE identity_id = $1
+            ORDER BY reactivated_at DESC, id DESC
+        "#)
+        .bind(identity_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        rows.into_iter().map(|row| {
+            let reason: String = row.try_get("reason").map_err(|e| EngineError::Storage(e.to_string()))?;
+            Ok(Reactivation {
+                identity_id: row.try_get("identity_id").map_err(|e| EngineError::Storage(e.to_string()))?,
+                reason: str_to_reason(&reason)?,
+                silent_since: row.try_get("silent_since").map_err(|e| EngineError::Storage(e.to_string()))?,
+                dormant_since: row.try_get("dormant_since").map_err(|e| EngineError::Storage(e.to_string()))?,
+                reactivated_at: row.try_get("reactivated_at").map_err(|e| EngineError::Storage(e.to_string()))?,
+                score_before: row.try_get::<i64, _>("score_before").map_err(|e| EngineError::Storage(e.to_string()))? as u64,
+                score_after: row.try_get::<i64, _>("score_after").map_err(|e| EngineError::Storage(e.to_string()))? as u64,
+                streak_before: row.try_get::<i64, _>("streak_before").map_err(|e| EngineError::Storage(e.to_string()))? as u64,
+                streak_after: row.try_get::<i64, _>("streak_after").map_err(|e| EngineError::Storage(e.to_string()))? as u64,
+                cooling_off_until: row.try_get("cooling_off_until").map_err(|e| EngineError::Storage(e.to_string()))?,
+            })
+        }).collect()
+    }
+}
+
+#[async_trait]
+impl EligibilityStorage for PostgresStorage {
+    async fn save_eligibility(&self, identity_id: &Uuid, report: &EligibilityReport) -> Result<(), EngineError> {
+        let rules = serde_json::to_string(&report.rules).map_err(|e| EngineError::Storage(e.to_string()))?;
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        sqlx::query(r#"
+            INSERT INTO identity_eligibility (identity_id, eligible, rules, evaluated_at) VALUES ($1, $2, $3::jsonb, $4)
+            ON CONFLICT (identity_id) DO UPDATE SET
+                eligible = EXCLUDED.eligible, rules = EXCLUDED.rules, evaluated_at = EXCLUDED.evaluated_at
+        "#)
+        .bind(identity_id)
+        .bind(report.eligible)
+        .bind(rules)
+        .bind(report.evaluated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        sqlx::query("UPDATE identities SET is_genesis_eligible = $2 WHERE id = $1")
+            .bind(identity_id)
+            .bind(report.eligible)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_eligibility(&self, identity_id: &Uuid) -> Result<Option<EligibilityReport>, EngineError> {
+        let row = sqlx::query("SELECT eligible, rules::text AS rules, evaluated_at FROM identity_eligibility WHERE identity_id = $1")
+            .bind(identity_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        let Some(row) = row else { return Ok(None) };
+        let rules: String = row.try_get("rules").map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(Some(EligibilityReport {
+            eligible: row.try_get("eligible").map_err(|e| EngineError::Storage(e.to_string()))?,
+            rules: serde_json::from_str(&rules).map_err(|e| EngineError::Storage(e.to_string()))?,
+            evaluated_at: row.try_get("evaluated_at").map_err(|e| EngineError::Storage(e.to_string()))?,
+        }))
+    }
+
+    async fn get_eligibility_due(&self, before: DateTime<Utc>, limit: u32) -> Result<Vec<Uuid>, EngineError> {
+        sqlx::query_scalar(r#"
+            SELECT i.id FROM identities i
+            LEFT JOIN identity_eligibility e ON e.identity_id = i.id
+            WHERE e.identity_id IS NULL
+               OR (e.evaluated_at < $1 AND i.status <> 'revoked')
+               OR (e.eligible AND i.status <> 'active')
+            ORDER BY e.evaluated_at NULLS FIRST
+            LIMIT $2
+        "#)
+        .bind(before)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl LifecycleStorage for PostgresStorage {
+    async fn record_transition(&self, transition: &IdentityTransition) -> Result<bool, EngineError> {
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        if !insert_transition(&mut tx, transition).await? {
+            return Ok(false);
+        }
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(true)
+    }
+
+    async fn get_transitions(&self, identity_id: &Uuid) -> Result<Vec<IdentityTransition>, EngineError> {
+        let rows = sqlx::query(r#"
+            SELECT identity_id, from_status, to_status, reason, actor_id, created_at
+            FROM identity_transitions WHERE identity_id = $1 ORDER BY id
+        "#)
+        .bind(identity_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        rows.into_iter().map(|row| {
+            let from: String = row.try_get("from_status").map_err(|e| EngineError::Storage(e.to_string()))?;
+            let to: String = row.try_get("to_status").map_err(|e| EngineError::Storage(e.to_string()))?;
+            let reason: String = row.try_get("reason").map_err(|e| EngineError::Storage(e.to_string()))?;
+            Ok(IdentityTransition {
+                identity_id: row.try_get("identity_id").map_err(|e| EngineError::Storage(e.to_string()))?,
+                from: str_to_status(&from),
+                to: str_to_status(&to),
+                reason: str_to_reason(&reason)?,
+                actor_id: row.try_get("actor_id").ok(),
+                created_at: row.try_get("created_at").map_err(|e| EngineError::Storage(e.to_string()))?,
+            })
+        }).collect()
+    }
+}
+
+/// A pending delivery handed to the dispatcher, with what it needs to send it.
+pub struct DueWebhookDelivery {
+    pub id: Uuid,
+    pub url: String,
+    pub secret: String,
+    pub payload: String,
+    /// Attempts made before this one.
+    pub attempts: u32,
+}
+
+/// Webhook endpoints and deliveries (server-side only; no engine port).
+impl PostgresStorage {
+    pub async fn create_webhook_endpoint(&self, partner_id: &str, url: &str, events: &[WebhookEventType], secret: &str) -> Result<WebhookEndpoint, EngineError> {
+        let tags: Vec<&str> = events.iter().map(|e| e.tag()).collect();
+        let row = sqlx::query(r#"
+            INSERT INTO webhook_endpoints (id, partner_id, url, secret, events) VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, partner_id, url, events, active, created_at
+        "#)
+        .bind(Uuid::new_v4())
+        .bind(partner_id)
+        .bind(url)
+        .bind(secret)
+        .bind(&tags)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        map_row_to_webhook_endpoint(row)
+    }
+
+    pub async fn get_webhook_endpoints(&self, partner_id: &str) -> Result<Vec<WebhookEndpoint>, EngineError> {
+        let rows = sqlx::query(r#"
+            SELECT id, partner_id, url, events, active, created_at
+            FROM webhook_endpoints WHERE partner_id = $1 ORDER BY created_at
+        "#)
+        .bind(partner_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        rows.into_iter().map(map_row_to_webhook_endpoint).collect()
+    }
+
+    /// Stops an endpoint. Its pending deliveries go to the dead-letter state (replayable).
+    pub async fn deactivate_webhook_endpoint(&self, partner_id: &str, endpoint_id: &Uuid) -> Result<bool, EngineError> {
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        let stopped = sqlx::query("UPDATE webhook_endpoints SET active = FALSE WHERE id = $1 AND partner_id = $2 AND active")
+            .bind(endpoint_id)
+            .bind(partner_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        if stopped.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query(r#"
+            UPDATE webhook_deliveries SET status = 'dead', next_attempt_at = NULL, last_error = 'Endpoint deactivated'
+            WHERE endpoint_id = $1 AND status = 'pending'
+        "#)
+        .bind(endpoint_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(true)
+    }
+
+    /// Turns new status transitions into deliveries for every partner linked to the identity
+    /// whose active endpoints subscribe to the event. Returns the number of deliveries queued.
+    pub async fn enqueue_webhook_deliveries(&self, batch: i64) -> Result<u64, EngineError> {
+        let mut tx = self.pool.begin().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        // Each transition is fanned out exactly once; concurrent fan-outs skip rows another one holds.
+        let transitions = sqlx::query(r#"
+            SELECT id, identity_id, to_status, created_at FROM identity_transitions
+            WHERE NOT webhooks_enqueued ORDER BY id LIMIT $1
+            FOR UPDATE SKIP LOCKED
+        "#)
+        .bind(batch)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        if transitions.is_empty() {
+            return Ok(0);
+        }
+
+        let mut queued = 0;
+        let mut transition_ids = Vec::with_capacity(transitions.len());
+        for transition in &transitions {
+            let transition_id: i64 = transition.try_get("id").map_err(|e| EngineError::Storage(e.to_string()))?;
+            transition_ids.push(transition_id);
+            let to_status: String = transition.try_get("to_status").map_err(|e| EngineError::Storage(e.to_string()))?;
+            let Some(event) = webhook_event_for(&str_to_status(&to_status)) else { continue };
+            let identity_id: Uuid = transition.try_get("identity_id").map_err(|e| EngineError::Storage(e.to_string()))?;
+            let occurred_at: DateTime<Utc> = transition.try_get("created_at").map_err(|e| EngineError::Storage(e.to_string()))?;
+
+            let subscribers = sqlx::query(r#"
+                SELECT ps.partner_id, ps.pairwise_id, ARRAY_AGG(e.id) AS endpoint_ids
+                FROM partner_subjects ps
+                JOIN webhook_endpoints e ON e.partner_id = ps.partner_id AND e.active AND $2 = ANY(e.events)
+                WHERE ps.identity_id = $1
+                GROUP BY ps.partner_id, ps.pairwise_id
+            "#)
+            .bind(identity_id)
+            .bind(event.tag())
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+            for subscriber in subscribers {
+                let partner_id: String = subscriber.try_get("partner_id").map_err(|e| EngineError::Storage(e.to_string()))?;
+                let payload = WebhookPayload {
+                    id: webhook_event_id(transition_id, &partner_id),
+                    event,
+                    partner_id,
+                    subject_id: subscriber.try_get("pairwise_id").map_err(|e| EngineError::Storage(e.to_string()))?,
+                    occurred_at,
+                };
+                let body = serde_json::to_string(&payload).map_err(|e| EngineError::Storage(e.to_string()))?;
+                let endpoint_ids: Vec<Uuid> = subscriber.try_get("endpoint_ids").map_err(|e| EngineError::Storage(e.to_string()))?;
+
+                for endpoint_id in endpoint_ids {
+                    let inserted = sqlx::query(r#"
+                        INSERT INTO webhook_deliveries (id, endpoint_id, event_id, payload, next_attempt_at)
+                        VALUES ($1, $2, $3, $4, NOW())
+                        ON CONFLICT (endpoint_id, event_id) DO NOTHING
+                    "#)
+                    .bind(Uuid::new_v4())
+                    .bind(endpoint_id)
+                    .bind(payload.id)
+                    .bind(&body)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| EngineError::Storage(e.to_string()))?;
+                    queued += inserted.rows_affected();
+                }
+            }
+        }
+
+        sqlx::query("UPDATE identity_transitions SET webhooks_enqueued = TRUE WHERE id = ANY($1)")
+            .bind(&transition_ids)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(queued)
+    }
+
+    /// Claims up to `limit` due deliveries. Claimed rows are pushed `lease_seconds` into the
+    /// future, so a crashed dispatcher's deliveries are retried rather than lost.
+    pub async fn claim_due_webhook_deliveries(&self, limit: i64, lease_seconds: i64) -> Result<Vec<DueWebhookDelivery>, EngineError> {
+        let rows = sqlx::query(r#"
+            UPDATE webhook_deliveries d SET next_attempt_at = NOW() + make_interval(secs => $2)
+            FROM webhook_endpoints e
+            WHERE e.id = d.endpoint_id AND d.id IN (
+                SELECT id FROM webhook_deliveries
+                WHERE status = 'pending' AND next_attempt_at <= NOW()
+                ORDER BY next_attempt_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING d.id, d.payload, d.attempts, e.url, e.secret
+        "#)
+        .bind(limit)
+        .bind(lease_seconds as f64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        rows.into_iter().map(|row| Ok(DueWebhookDelivery {
+            id: row.try_get("id").map_err(|e| EngineError::Storage(e.to_string()))?,
+            url: row.try_get("url").map_err(|e| EngineError::Storage(e.to_string()))?,
+            secret: row.try_get("secret").map_err(|e| EngineError::Storage(e.to_string()))?,
+            payload: row.try_get("payload").map_err(|e| EngineError::Storage(e.to_string()))?,
+            attempts: row.try_get::<i32, _>("attempts").map_err(|e| EngineError::Storage(e.to_string()))? as u32,
+        })).collect()
+    }
+
+    pub async fn mark_webhook_delivered(&self, delivery_id: &Uuid, status_code: u16) -> Result<(), EngineError> {
+        sqlx::query(r#"
+            UPDATE webhook_deliveries SET status = 'delivered', attempts = attempts + 1, last_status_code = $2,
+                   last_error = NULL, next_attempt_at = NULL, delivered_at = NOW()
+            WHERE id = $1
+        "#)
+        .bind(delivery_id)
+        .bind(status_code as i32)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Records a failed attempt. `retry_at = None` moves the delivery to the dead-letter state.
+    pub async fn mark_webhook_failed(&self, delivery_id: &Uuid, status_code: Option<u16>, error: &str, retry_at: Option<DateTime<Utc>>) -> Result<(), EngineError> {
+        sqlx::query(r#"
+            UPDATE webhook_deliveries SET attempts = attempts + 1, last_status_code = $2, last_error = $3,
+                   status = CASE WHEN $4::timestamptz IS NULL THEN 'dead' ELSE 'pending' END,
+                   next_attempt_at = $4
+            WHERE id = $1
+        "#)
+        .bind(delivery_id)
+        .bind(status_code.map(|c| c as i32))
+        .bind(error)
+        .bind(retry_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Most recent deliveries across the partner's endpoints, newest first.
+    pub async fn get_webhook_deliveries(&self, partner_id: &str, status: Option<WebhookDeliveryStatus>, limit: u32) -> Result<Vec<WebhookDelivery>, EngineError> {
+        let rows = sqlx::query(&format!(r#"
+            {} WHERE e.partner_id = $1 AND ($2::text IS NULL OR d.status = $2)
+            ORDER BY d.created_at DESC, d.id
+            LIMIT $3
+        "#, WEBHOOK_DELIVERY_SELECT))
+        .bind(partner_id)
+        .bind(status.map(|s| s.tag()))
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        rows.into_iter().map(map_row_to_webhook_delivery).collect()
+    }
+
+    /// Sends a delivered or dead delivery again, with a fresh retry budget and the same event ID.
+    pub async fn replay_webhook_delivery(&self, partner_id: &str, delivery_id: &Uuid) -> Result<Option<WebhookDelivery>, EngineError> {
+        let replayed = sqlx::query(r#"
+            UPDATE webhook_deliveries d SET status = 'pending', attempts = 0, next_attempt_at = NOW(),
+                   last_status_code = NULL, last_error = NULL, delivered_at = NULL
+            FROM webhook_endpoints e
+            WHERE e.id = d.endpoint_id AND e.active AND e.partner_id = $1 AND d.id = $2 AND d.status <> 'pending'
+        "#)
+        .bind(partner_id)
+        .bind(delivery_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        if replayed.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        let row = sqlx::query(&format!("{} WHERE d.id = $1", WEBHOOK_DELIVERY_SELECT))
+            .bind(delivery_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+        map_row_to_webhook_delivery(row).map(Some)
+    }
+}
+
+const WEBHOOK_DELIVERY_SELECT: &str = r#"
+    SELECT d.id, d.endpoint_id, d.payload, d.status, d.attempts, d.next_attempt_at, d.last_status_code,
+           d.last_error, d.created_at, d.delivered_at
+    FROM webhook_deliveries d
+    JOIN webhook_endpoints e ON e.id = d.endpoint_id
+"#;
+
+/// Status changes partners can subscribe to.
+fn webhook_event_for(status: &IdentityStatus) -> Option<WebhookEventType> {
+    match status {
+        IdentityStatus::Revoked => Some(WebhookEventType::IdentityRevoked),
+        IdentityStatus::Stale => Some(WebhookEventType::IdentityStale),
+        IdentityStatus::Dormant => Some(WebhookEventType::IdentityDormant),
+        IdentityStatus::Active => None,
+    }
+}
+
+/// `WebhookPayload.id` for a transition as seen by one partner. Derived rather than random,
+/// so fanning the same transition out twice lands on the same delivery rows.
+fn webhook_event_id(transition_id: i64, partner_id: &str) -> Uuid {
+    let mut hasher = Sha256::new();
+    hasher.update(b"webhook_event|");
+    hasher.update(transition_id.to_be_bytes());
+    hasher.update(partner_id.as_bytes());
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&hasher.finalize()[..16]);
+    uuid::Builder::from_random_bytes(bytes).into_uuid()
+}
+
+fn map_row_to_webhook_endpoint(row: sqlx::postgres::PgRow) -> Result<WebhookEndpoint, EngineError> {
+    let tags: Vec<String> = row.try_get("events").map_err(|e| EngineError::Storage(e.to_string()))?;
+    Ok(WebhookEndpoint {
+        id: row.try_get("id").map_err(|e| EngineError::Storage(e.to_string()))?,
+        partner_id: row.try_get("partner_id").map_err(|e| EngineError::Storage(e.to_string()))?,
+        url: row.try_get("url").map_err(|e| EngineError::Storage(e.to_string()))?,
+        events: tags.iter().filter_map(|t| WebhookEventType::from_tag(t)).collect(),
+        active: row.try_get("active").map_err(|e| EngineError::Storage(e.to_string()))?,
+        created_at: row.try_get("created_at").map_err(|e| EngineError::Storage(e.to_string()))?,
+    })
+}
+
+fn map_row_to_webhook_delivery(row: sqlx::postgres::PgRow) -> Result<WebhookDelivery, EngineError> {
+    let payload: String = row.try_get("payload").map_err(|e| EngineError::Storage(e.to_string()))?;
+    let status: String = row.try_get("status").map_err(|e| EngineError::Storage(e.to_string()))?;
+    Ok(WebhookDelivery {
+        id: row.try_get("id").map_err(|e| EngineError::Storage(e.to_string()))?,
+        endpoint_id: row.try_get("endpoint_id").map_err(|e| EngineError::Storage(e.to_string()))?,
+        payload: serde_json::from_str(&payload).map_err(|e| EngineError::Storage(e.to_string()))?,
+        status: WebhookDeliveryStatus::from_tag(&status)
+            .ok_or_else(|| EngineError::Storage(format!("Unknown delivery status: {}", status)))?,
+        attempts: row.try_get::<i32, _>("attempts").map_err(|e| EngineError::Storage(e.to_string()))? as u32,
+        next_attempt_at: row.try_get("next_attempt_at").ok(),
+        last_status_code: row.try_get::<i32, _>("last_status_code").ok().map(|c| c as u16),
+        last_error: row.try_get("last_error").ok(),
+        created_at: row.try_get("created_at").map_err(|e| EngineError::Storage(e.to_string()))?,
+        delivered_at: row.try_get("delivered_at").ok(),
+    })
+}
+
+fn status_to_str(status: &IdentityStatus) -> &'static str {
+    match status {
+        IdentityStatus::Active => "active",
+        IdentityStatus::Stale => "stale",
+        IdentityStatus::Dormant => "dormant",
+        IdentityStatus::Revoked => "revoked",
+    }
+}
+
+/// Unknown values fail closed (Revoked).
+fn str_to_status(s: &str) -> IdentityStatus {
+    match s {
+        "active" => IdentityStatus::Active,
+        "stale" => IdentityStatus::Stale,
+        "dormant" => IdentityStatus::Dormant,
+        _ => IdentityStatus::Revoked,
+    }
+}
+
+fn str_to_reason(s: &str) -> Result<TransitionReason, EngineError> {
+    match s {
+        "heartbeat" => Ok(TransitionReason::Heartbeat),
+        "attestation_expired" => Ok(TransitionReason::AttestationExpired),
+        "reattestation" => Ok(TransitionReason::Reattestation),
+        "key_rotation" => Ok(TransitionReason::KeyRotation),
+        "recovery" => Ok(TransitionReason::Recovery),
+        "inactivity" => Ok(TransitionReason::Inactivity),
+        "revocation" => Ok(TransitionReason::Revocation),
+        other => Err(EngineError::Storage(format!("Unknown transition reason: {}", other))),
+    }
+}
+
+fn str_to_revocation_reason(s: &str) -> Result<RevocationReason, EngineError> {
+    match s {
+        "compromised_key" => Ok(RevocationReason::CompromisedKey),
+        "fraud" => Ok(RevocationReason::Fraud),
+        "user_request" => Ok(RevocationReason::UserRequest),
+        "leaked_keybox" => Ok(RevocationReason::LeakedKeybox),
+        "migrated" => Ok(RevocationReason::Migrated),
+        other => Err(EngineError::Storage(format!("Unknown revocation reason: {}", other))),
+    }
+}
+
+/// Insert-or-update of an identity row. Only the mutable columns change on conflict.
+fn identity_upsert(identity: &Identity) -> Query<'_, Postgres, PgArguments> {
+    sqlx::query(r#"
+        INSERT INTO identities (
+            id, public_key, continuity_score, streak, created_at, last_heartbeat, last_attestation, status,
+            hardware_brand, hardware_device_hash, hardware_product,
+            genesis_version, network, username, is_genesis_eligible, fcm_token,
+            security_level, os_patch_level
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
+        ON CONFLICT (id) DO UPDATE SET 
+            continuity_score = $3, 
+            last_heartbeat = $6,
+            last_attestation = $7,
+            security_level = $17,
+            os_patch_level = $18
+    "#)
+    .bind(identity.id)
+    .bind(&identity.public_key)
+    .bind(identity.continuity_score as i64)
+    .bind(identity.streak as i64)
+    .bind(identity.created_at)
+    .bind(identity.last_heartbeat)
+    .bind(identity.last_attestation)
+    .bind(status_to_str(&identity.status))
+    .bind(&identity.hardware_brand)
+    .bind(hash_device(identity.hardware_device.as_deref()))
+    .bind(&identity.hardware_product)
+    .bind(identity.genesis_version as i16)
+    .bind(identity.network.to_string())
+    .bind(&identity.username)
+    .bind(identity.is_genesis_eligible)
+    .bind(&identity.fcm_token)
+    .bind(identity.security_level.map(|l| l.tag()))
+    .bind(identity.os_patch_level.map(|p| p as i32))
+}
+
+/// Stores nothing once the identity already holds `max_active` active devices.
+fn device_insert(device: &Device, max_active: u32) -> Query<'_, Postgres, PgArguments> {
+    sqlx::query(r#"
+        INSERT INTO identity_devices (
+            id, identity_id, public_key, label, status, hardware_brand, hardware_device_hash, hardware_product,
+            security_level, os_patch_level, added_at, last_attestation
+        )
+        SELECT $1, $2, $3, $4, 'active', $5, $6, $7, $8, $9, $10, $11
+        WHERE (SELECT COUNT(*) FROM identity_devices WHERE identity_id = $2 AND status = 'active') < $12
+    "#)
+    .bind(device.id)
+    .bind(device.identity_id)
+    .bind(&device.public_key)
+    .bind(&device.label)
+    .bind(&device.hardware_brand)
+    .bind(hash_device(device.hardware_device.as_deref()))
+    .bind(&device.hardware_product)
+    .bind(device.security_level.map(|l| l.tag()))
+    .bind(device.os_patch_level.map(|p| p as i32))
+    .bind(device.added_at)
+    .bind(device.last_attestation)
+    .bind(max_active as i64)
+}
+
+/// Compare-and-set of the status plus its audit row, inside the caller's transaction.
+async fn insert_transition(conn: &mut PgConnection, transition: &IdentityTransition) -> Result<bool, EngineError> {
+    // Compare-and-set on the status the engine decided from.
+    let moved = sqlx::query("UPDATE identities SET status = $3 WHERE id = $1 AND status = $2")
+        .bind(transition.identity_id)
+        .bind(status_to_str(&transition.from))
+        .bind(status_to_str(&transition.to))
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+    if moved.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    sqlx::query(r#"
+        INSERT INTO identity_transitions (identity_id, from_status, to_status, reason, actor_id, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+    "#)
+    .bind(transition.identity_id)
+    .bind(status_to_str(&transition.from))
+    .bind(status_to_str(&transition.to))
+    .bind(transition.reason.tag())
+    .bind(transition.actor_id)
+    .bind(transition.created_at)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+    Ok(true)
+}
+
+/// Dormancy rules applied to score and streak plus the reactivation row, inside the caller's transaction.
+async fn insert_reactivation(conn: &mut PgConnection, reactivation: &Reactivation, streak: &StreakState) -> Result<(), EngineError> {
+    sqlx::query(r#"
+        UPDATE identities
+        SET continuity_score = $2, streak = $3, streak_day = $4, streak_freezes = $5
+        WHERE id = $1
+    "#)
+    .bind(reactivation.identity_id)
+    .bind(reactivation.score_after as i64)
+    .bind(streak.streak as i64)
+    .bind(streak.last_day)
+    .bind(streak.freezes as i32)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+    sqlx::query(r#"
+        INSERT INTO identity_reactivations (
+            identity_id, reason, silent_since, dormant_since, reactivated_at,
+            score_before, score_after, streak_before, streak_after, cooling_off_until
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+    "#)
+    .bind(reactivation.identity_id)
+    .bind(reactivation.reason.tag())
+    .bind(reactivation.silent_since)
+    .bind(reactivation.dormant_since)
+    .bind(reactivation.reactivated_at)
+    .bind(reactivation.score_before as i64)
+    .bind(reactivation.score_after as i64)
+    .bind(reactivation.streak_before as i64)
+    .bind(reactivation.streak_after as i64)
+    .bind(reactivation.cooling_off_until)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+    Ok(())
+}
+
+/// The revocation row of `transition` (→ Revoked), inside the caller's transaction. Returns its feed sequence.
+async fn insert_revocation(conn: &mut PgConnection, transition: &IdentityTransition, reason: RevocationReason) -> Result<u64, EngineError> {
+    // Serialize until commit so sequences become visible in order: partners page with `sequence > cursor`.
+    sqlx::query("LOCK TABLE revocations IN SHARE ROW EXCLUSIVE MODE")
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+    let row = sqlx::query(r#"
+        INSERT INTO revocations (sequence, identity_id, reason, revoked_by, revoked_at)
+        SELECT COALESCE(MAX(sequence), 0) + 1, $1, $2, $3, $4 FROM revocations
+        RETURNING sequence
+    "#)
+    .bind(transition.identity_id)
+    .bind(reason.tag())
+    .bind(transition.actor_id)
+    .bind(transition.created_at)
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+    Ok(row.try_get::<i64, _>("sequence").map_err(|e| EngineError::Storage(e.to_string()))? as u64)
+}
+
+async fn insert_recovery_event(
+    conn: &mut PgConnection,
+    recovery_id: &Uuid,
+    identity_id: &Uuid,
+    event: &str,
+    actor_id: Option<&Uuid>,
+) -> Result<(), EngineError> {
+    sqlx::query("INSERT INTO recovery_events (recovery_id, identity_id, event, actor_id) VALUES ($1, $2, $3, $4)")
+        .bind(recovery_id)
+        .bind(identity_id)
+        .bind(event)
+        .bind(actor_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+    Ok(())
+}
+
+async fn upsert_fingerprint(conn: &mut PgConnection, identity_id: &Uuid, fingerprint: &AttestationFingerprint) -> Result<(), EngineError> {
+    sqlx::query(r#"
+        INSERT INTO identity_fingerprints (identity_id, boot_key_hash, intermediate_hash) VALUES ($1, $2, $3)
+        ON CONFLICT (identity_id) DO UPDATE SET
+            boot_key_hash = EXCLUDED.boot_key_hash,
+            intermediate_hash = EXCLUDED.intermediate_hash,
+            updated_at = NOW()
+    "#)
+    .bind(identity_id)
+    .bind(&fingerprint.boot_key_hash)
+    .bind(&fingerprint.intermediate_hash)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Appends `records` at the next leaf indexes, inside the caller's transaction. The table
+/// lock serializes appends until commit, so callers append as their last statement.
+async fn insert_log_entries(conn: &mut PgConnection, records: &[LogRecord]) -> Result<Vec<LogEntry>, EngineError> {
+    // Serialize appends so leaf indexes are gapless (identity columns can skip on rollback).
+    sqlx::query("LOCK TABLE transparency_log IN SHARE ROW EXCLUSIVE MODE")
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+
+    let mut entries = Vec::with_capacity(records.len());
+    for record in records {
+        let row = sqlx::query(r#"
+            INSERT INTO transparency_log (leaf_index, kind, identity_id, public_key_hash)
+            SELECT COALESCE(MAX(leaf_index) + 1, 0), $1, $2, $3 FROM transparency_log
+            RETURNING leaf_index, kind, identity_id, public_key_hash, logged_at
+        "#)
+        .bind(record.kind.tag())
+        .bind(record.identity_id)
+        .bind(&record.public_key_hash)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| EngineError::Storage(e.to_string()))?;
+        entries.push(map_row_to_log_entry(row)?);
+    }
+    Ok(entries)
+}
+
+/// A key that is already registered surfaces as `AlreadyExists`.
+fn map_unique_violation(e: sqlx::Error) -> EngineError {
+    match e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => EngineError::AlreadyExists,
+        e => EngineError::Storage(e.to_string()),
+    }
+}
+
+/// Raw device models are never persisted (Privacy).
+fn hash_device(raw: Option<&str>) -> Option<String> {
+    raw.map(|raw| {
+        let mut hasher = Sha256::new();
+        hasher.update(raw.as_bytes());
+        hex::encode(hasher.finalize())
+    })
+}
+
+fn map_security_level(row: &sqlx::postgres::PgRow) -> Option<SecurityLevel> {
+    match row.try_get::<String, _>("security_level").ok()?.as_str() {
+        "strongbox" => Some(SecurityLevel::StrongBox),
+        "tee" => Some(SecurityLevel::Tee),
+        _ => None,
+    }
+}
+
+fn map_row_to_identity(row: Option<sqlx::postgres::PgRow>) -> Result<Option<Identity>, EngineError> {
+    match row {
+        Some(row) => {
+            let status_str: String = row.try_get("status").unwrap_or_default();
+            let status = str_to_status(&status_str);
 
             let net_str: String = row.try_get("network").unwrap_or_else(|_| "testnet".to_string());
             let network = match net_str.as_str() {
@@ -234,6 +2234,8 @@ fn map_row_to_identity(row: Option<sqlx::postgres::PgRow>) -> Result<Option<Iden
                 hardware_brand: row.try_get("hardware_brand").ok(),
                 hardware_device: row.try_get("hardware_device_hash").ok(),
                 hardware_product: row.try_get("hardware_product").ok(),
+                security_level: map_security_level(&row),
+                os_patch_level: row.try_get::<i32, _>("os_patch_level").ok().map(|p| p as u32),
                 genesis_version: row.try_get::<i16, _>("genesis_version").unwrap_or(1) as u16,
                 network,
             }))
@@ -25,9 +25,10 @@ impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         let (status, error_code, message) = match self.0.downcast_ref::<EngineError>() {
             Some(EngineError::IdentityNotFound(_)) => (StatusCode::NOT_FOUND, "IDENTITY_NOT_FOUND", self.0.to_string()),
+            Some(EngineError::IdentityRevoked(_)) => (StatusCode::GONE, "IDENTITY_REVOKED", self.0.to_string()),
             Some(EngineError::AlreadyExists) => (StatusCode::CONFLICT, "IDENTITY_EXISTS", self.0.to_string()),
             Some(EngineError::InvalidSignature) => (StatusCode::UNAUTHORIZED, "INVALID_SIGNATURE", "Cryptographic proof failed.".to_string()),
-            Some(EngineError::RateLimitExceeded) => (StatusCode::TOO_MANY_REQUESTS, "RATE_LIMIT", "Daily verification limit reached (23h).".to_string()),
+            Some(EngineError::RateLimitExceeded) => (StatusCode::TOO_MANY_REQUESTS, "RATE_LIMIT", "Verification limit reached. Retry after next_available.".to_string()),
             Some(EngineError::StaleHeartbeat(msg)) => (StatusCode::BAD_REQUEST, "STALE_TIMESTAMP", msg.clone()),
             Some(EngineError::InvalidAttestation(msg)) => (StatusCode::BAD_REQUEST, "ATTESTATION_FAILED", msg.clone()),
             Some(EngineError::Storage(_)) => (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR", "Storage unavailable.".to_string()),
@@ -43,6 +44,28 @@ impl IntoResponse for AppError {
                 "ATTESTATION_REQUIRED", 
                 "Trust decayed. Please perform background re-attestation.".to_string()
             ),
+
+            Some(EngineError::RecoveryNotFound(_)) => (StatusCode::NOT_FOUND, "RECOVERY_NOT_FOUND", self.0.to_string()),
+            Some(EngineError::RecoveryRejected(msg)) => (StatusCode::CONFLICT, "RECOVERY_REJECTED", msg.clone()),
+            Some(EngineError::InvalidPartner(msg)) => (StatusCode::BAD_REQUEST, "INVALID_PARTNER", msg.clone()),
+            Some(EngineError::InvalidTokenRequest(msg)) => (StatusCode::BAD_REQUEST, "INVALID_TOKEN_REQUEST", msg.clone()),
+            Some(EngineError::InvalidLogRange(msg)) => (StatusCode::BAD_REQUEST, "INVALID_LOG_RANGE", msg.clone()),
+            Some(EngineError::IllegalTransition(msg)) => (StatusCode::CONFLICT, "ILLEGAL_TRANSITION", msg.clone()),
+            Some(EngineError::InvalidRevocation(msg)) => (StatusCode::BAD_REQUEST, "INVALID_REVOCATION", msg.clone()),
+            Some(EngineError::InvalidSwitch(msg)) => (StatusCode::BAD_REQUEST, "INVALID_SWITCH", msg.clone()),
+            Some(EngineError::InvalidTimezone(tz)) => (StatusCode::BAD_REQUEST, "INVALID_TIMEZONE", format!("Unknown time zone: {}", tz)),
+            Some(EngineError::InvalidAction(msg)) => (StatusCode::BAD_REQUEST, "INVALID_ACTION", msg.clone()),
+            Some(EngineError::InvalidDevice(msg)) => (StatusCode::BAD_REQUEST, "INVALID_DEVICE", msg.clone()),
+            Some(EngineError::DeviceLimitReached(_)) => (StatusCode::CONFLICT, "DEVICE_LIMIT", self.0.to_string()),
+            Some(EngineError::NetworkMismatch { .. }) => (StatusCode::CONFLICT, "NETWORK_MISMATCH", self.0.to_string()),
+            Some(EngineError::InvalidMigration(msg)) => (StatusCode::BAD_REQUEST, "INVALID_MIGRATION", msg.clone()),
+            Some(EngineError::GenesisUpgradeRequired { .. }) => (
+                StatusCode::from_u16(426).unwrap(), // 426 Upgrade Required
+                "GENESIS_UPGRADE_REQUIRED",
+                self.0.to_string()
+            ),
+            Some(EngineError::CoolingOff(_)) => (StatusCode::TOO_MANY_REQUESTS, "COOLING_OFF", self.0.to_string()),
+            Some(EngineError::InvalidPolicy(_)) => (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR", "Node misconfigured.".to_string()),
             
             None => (StatusCode::INTERNAL_SERVER_ERROR, "UNKNOWN_ERROR", "An unexpected error occurred.".to_string()),
         };
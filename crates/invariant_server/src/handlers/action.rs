@@ -0,0 +1,94 @@
+// crates/invariant_server/src/handlers/action.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use axum::{Extension, Json, http::StatusCode};
+use redis::AsyncCommands;
+use uuid::Uuid;
+use invariant_shared::{ActionChallengeRequest, ActionVerifyRequest, ReceiptVerdict};
+use crate::state::SharedState;
+use crate::error_response::AppError;
+
+/// What an action challenge was issued for. Stored with the challenge, compared on use.
+fn binding(partner_id: &str, subject_id: &Uuid, payload_hash: &[u8]) -> String {
+    serde_json::json!([partner_id, subject_id, hex::encode(payload_hash)]).to_string()
+}
+
+/// POST /action/challenge
+/// Issues a single-use nonce bound to one subject and one action (payload hash).
+#[utoipa::path(
+    post,
+    path = "/action/challenge",
+    request_body = ActionChallengeRequest,
+    responses(
+        (status = 200, description = "Challenge Issued", body = ActionChallenge),
+        (status = 400, description = "Invalid Partner or Payload Hash"),
+        (status = 404, description = "Unknown Subject"),
+        (status = 410, description = "Identity Revoked"),
+        (status = 426, description = "Subject Must Re-attest")
+    )
+)]
+pub async fn action_challenge_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<ActionChallengeRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    let challenge = state.engine.issue_action_challenge(&payload).await?;
+
+    let mut conn = state.redis.get_multiplexed_async_connection().await
+        .map_err(|e| anyhow::anyhow!("Redis Error: {}", e))?;
+    let redis_key = format!("action:{}", hex::encode(&challenge.nonce));
+    let _: () = conn.set_ex(
+        &redis_key,
+        binding(&payload.partner_id, &payload.subject_id, &payload.payload_hash),
+        state.engine.params().nonce_ttl_seconds,
+    ).await.map_err(|e| anyhow::anyhow!("Redis Set Error: {}", e))?;
+
+    Ok((StatusCode::OK, Json(serde_json::json!(challenge))))
+}
+
+/// POST /action/verify
+/// Verifies the device's signature over the challenge and payload hash and returns a
+/// node-signed verdict. A bad signature is a signed `rejected` verdict, not an error.
+#[utoipa::path(
+    post,
+    path = "/action/verify",
+    request_body = ActionVerifyRequest,
+    responses(
+        (status = 200, description = "Signed Verdict", body = SignedActionVerdict),
+        (status = 400, description = "Invalid Partner or Payload Hash"),
+        (status = 401, description = "Challenge Unknown, Expired or Issued for Another Action"),
+        (status = 404, description = "Unknown Subject"),
+        (status = 409, description = "Challenge Already Used"),
+        (status = 410, description = "Identity Revoked"),
+        (status = 426, description = "Subject Must Re-attest")
+    )
+)]
+pub async fn action_verify_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<ActionVerifyRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    let mut conn = state.redis.get_multiplexed_async_connection().await
+        .map_err(|e| anyhow::anyhow!("Redis Error: {}", e))?;
+    let redis_key = format!("action:{}", hex::encode(&payload.nonce));
+    let issued_for: Option<String> = conn.get_del(&redis_key).await
+        .map_err(|e| anyhow::anyhow!("Redis Auth Error: {}", e))?;
+
+    if issued_for.as_deref() != Some(binding(&payload.partner_id, &payload.subject_id, &payload.payload_hash).as_str()) {
+        tracing::warn!("⚠️ Invalid, Expired or Mismatched Action Challenge");
+        return Ok((StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Invalid or Expired Challenge." }))));
+    }
+
+    let verdict = state.engine.verify_action(&payload).await?;
+    tracing::info!(
+        event = "action_verified",
+        partner_id = %verdict.partner_id,
+        accepted = verdict.verdict == ReceiptVerdict::Accepted,
+        "✍️ Action Verdict"
+    );
+
+    Ok((StatusCode::OK, Json(serde_json::json!(state.receipts.sign_action_verdict(verdict)))))
+}
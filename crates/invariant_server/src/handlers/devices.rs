@@ -0,0 +1,96 @@
+// crates/invariant_server/src/handlers/devices.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use axum::{Extension, Json, http::StatusCode, extract::Path};
+use uuid::Uuid;
+use invariant_shared::{Device, DeviceEnrollmentRequest, DeviceRevocationRequest};
+use crate::state::SharedState;
+use crate::error_response::AppError;
+use tracing::{info, warn};
+
+// Both signed steps take their nonce from `/heartbeat/challenge`.
+
+fn invalid_challenge() -> (StatusCode, Json<serde_json::Value>) {
+    warn!("⚠️ Invalid or Expired Challenge Used (Devices)");
+    (StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Invalid or Expired Challenge." })))
+}
+
+/// POST /identity/devices
+/// Adds a device key to the identity. Authorized by any of its active devices.
+#[utoipa::path(
+    post,
+    path = "/identity/devices",
+    request_body = DeviceEnrollmentRequest,
+    responses(
+        (status = 201, description = "Device Enrolled", body = Device),
+        (status = 400, description = "Invalid Attestation, Label or Identity State"),
+        (status = 401, description = "Invalid Authorization or Challenge"),
+        (status = 404, description = "Identity Not Found"),
+        (status = 409, description = "Key Already Registered or Device Limit Reached")
+    )
+)]
+pub async fn enroll_device_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<DeviceEnrollmentRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        return Ok(invalid_challenge());
+    }
+
+    let device = state.engine.enroll_device(payload).await?;
+    info!(event = "device_enrolled", identity_id = %device.identity_id, device_id = %device.id, "📱 Device Enrolled");
+
+    Ok((StatusCode::CREATED, Json(serde_json::json!(device))))
+}
+
+/// POST /identity/devices/revoke
+/// Retires a device key. Only the primary key can retire the primary, which promotes the
+/// most recently attested device; retired keys can never be enrolled again.
+#[utoipa::path(
+    post,
+    path = "/identity/devices/revoke",
+    request_body = DeviceRevocationRequest,
+    responses(
+        (status = 200, description = "Device Revoked", body = Device),
+        (status = 400, description = "Unknown Device or Last Device"),
+        (status = 401, description = "Invalid Signature or Challenge"),
+        (status = 404, description = "Identity Not Found"),
+        (status = 410, description = "Identity Revoked")
+    )
+)]
+pub async fn revoke_device_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<DeviceRevocationRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        return Ok(invalid_challenge());
+    }
+
+    let device = state.engine.revoke_device(payload).await?;
+    info!(event = "device_revoked", identity_id = %device.identity_id, device_id = %device.id, "📱 Device Revoked");
+
+    Ok((StatusCode::OK, Json(serde_json::json!(device))))
+}
+
+/// GET /identity/:id/devices
+/// Every device of the identity, revoked ones included, oldest first.
+#[utoipa::path(
+    get,
+    path = "/identity/{id}/devices",
+    params(("id" = Uuid, Path, description = "Identity ID")),
+    responses(
+        (status = 200, description = "Devices", body = [Device]),
+        (status = 404, description = "Identity Not Found")
+    )
+)]
+pub async fn list_devices_handler(
+    Path(id): Path<Uuid>,
+    Extension(state): Extension<SharedState>,
+) -> Result<Json<Vec<Device>>, AppError> {
+    Ok(Json(state.engine.list_devices(id).await?))
+}
@@ -8,7 +8,8 @@
 use axum::{Extension, Json, http::StatusCode};
 use axum::extract::ConnectInfo;
 use std::net::SocketAddr;
-use invariant_shared::GenesisRequest;
+use invariant_shared::{GenesisRequest, Receipt, ReceiptKind, ReceiptVerdict};
+use invariant_engine::sybil::ip_prefix;
 use crate::state::SharedState;
 use crate::error_response::AppError;
 use tracing::{error, info, warn, instrument};
@@ -16,7 +17,6 @@ use rand::{Rng, thread_rng};
 use redis::AsyncCommands; 
 use sha2::{Sha256, Digest}; 
 
-const NONCE_TTL_SECONDS: u64 = 300; 
 const CONFIG_KEY_PAUSED: &str = "invariant:config:genesis_paused";
 const MAX_GENESIS_PER_HOUR: i64 = 100;
 
@@ -69,7 +69,7 @@ pub async fn get_challenge_handler(
         (hex_val.clone(), format!("nonce:{}", hex_val))
     };
 
-    let _: () = conn.set_ex(&redis_key, "true", NONCE_TTL_SECONDS).await
+    let _: () = conn.set_ex(&redis_key, "true", state.engine.params().nonce_ttl_seconds).await
         .map_err(|e| anyhow::anyhow!("Challenge Generation Failed: {}", e))?;
 
     Ok(Json(serde_json::json!({ "nonce": nonce_hex })))
@@ -97,6 +97,7 @@ pub async fn get_challenge_handler(
 )]
 pub async fn genesis_handler(
     Extension(state): Extension<SharedState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Json(payload): Json<GenesisRequest>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
     
@@ -132,13 +133,28 @@ pub async fn genesis_handler(
 
     let _: () = conn.del(&redis_key).await.unwrap_or(());
 
+    let nonce = payload.nonce.clone();
     match state.engine.process_genesis(payload).await {
         Ok(identity) => {
             info!("✅ Genesis Success! Minted: {}", identity.id);
+            // Sybil signal only; the identity is already minted.
+            if let Err(e) = state.engine.get_storage().record_genesis_ip_prefix(&identity.id, &ip_prefix(addr.ip())).await {
+                warn!("Failed to record genesis network for {}: {}", identity.id, e);
+            }
+            let receipt = state.receipts.issue(Receipt {
+                kind: ReceiptKind::Genesis,
+                identity_id: Some(identity.id),
+                score: identity.continuity_score,
+                verdict: ReceiptVerdict::Accepted,
+                public_key_hash: Sha256::digest(&identity.public_key).to_vec(),
+                nonce,
+                issued_at: state.engine.now(),
+            });
             Ok((StatusCode::CREATED, Json(serde_json::json!({ 
                 "id": identity.id,
                 "status": "active",
-                "tier": identity.hardware_device.unwrap_or_else(|| "Verified TEE".into()) 
+                "tier": identity.hardware_device.unwrap_or_else(|| "Verified TEE".into()),
+                "receipt": receipt
             }))))
         },
         Err(e) => {
@@ -153,7 +169,7 @@ pub async fn genesis_handler(
     path = "/verify",
     request_body = GenesisRequest,
     responses(
-        (status = 200, description = "Verification Result", body = inline(serde_json::Value))
+        (status = 200, description = "Verification Result with a signed receipt", body = inline(serde_json::Value))
     )
 )]
 // 🚀 FIX: Apply same structured logging to stateless verify
@@ -192,6 +208,17 @@ pub async fn verify_stateless_handler(
         }))));
     }
 
+    // The receipt names the exact key and challenge that were checked.
+    let receipt_for = |verdict| state.receipts.issue(Receipt {
+        kind: ReceiptKind::Verify,
+        identity_id: None,
+        score: 0,
+        verdict,
+        public_key_hash: Sha256::digest(&payload.public_key).to_vec(),
+        nonce: payload.nonce.clone(),
+        issued_at: state.engine.now(),
+    });
+
     match invariant_engine::validate_attestation_chain(
         &payload.attestation_chain,
         &payload.public_key,
@@ -199,6 +226,8 @@ pub async fn verify_stateless_handler(
     ) {
         Ok(metadata) => {
             info!("🔍 Stateless Verification: {} - {}", metadata.trust_tier, metadata.product.as_deref().unwrap_or("Unknown"));
+            let trust = state.engine.score_attestation(&metadata);
+            let receipt = receipt_for(ReceiptVerdict::Accepted);
             
             Ok((StatusCode::OK, Json(serde_json::json!({
                 "verified": true,
@@ -207,16 +236,23 @@ pub async fn verify_stateless_handler(
                 "device_model": metadata.device,
                 "product": metadata.product,
                 "boot_locked": metadata.is_boot_locked,
-                "risk_score": 0.0
+                "security_level": metadata.security_level,
+                "os_patch_level": metadata.os_patch_level,
+                "trust_score": trust.score,
+                "signals": trust.contributions,
+                "risk_score": 100.0 - trust.score,
+                "receipt": receipt
             }))))
         },
         Err(e) => {
             warn!("⚠️ Stateless Verification Failed: {}", e);
+            let receipt = receipt_for(ReceiptVerdict::Rejected);
             Ok((StatusCode::OK, Json(serde_json::json!({
                 "verified": false,
                 "tier": "REJECTED",
                 "error": e.to_string(),
-                "risk_score": 100.0
+                "risk_score": 100.0,
+                "receipt": receipt
             }))))
         }
     }
@@ -6,15 +6,13 @@
  */
 
 use axum::{Extension, Json, http::StatusCode};
-use invariant_shared::Heartbeat;
+use invariant_shared::{Heartbeat, Receipt, ReceiptKind, ReceiptVerdict};
 use crate::state::SharedState;
 use crate::error_response::AppError; 
 use tracing::{info, warn, instrument, Span}; // Removed 'error'
 use rand::{Rng, thread_rng};
 use redis::AsyncCommands;
 
-const CHALLENGE_TTL: u64 = 300; // 5 Minutes
-
 /// GET /heartbeat/challenge
 /// Returns a fresh nonce for the Daily Tap.
 #[utoipa::path(
@@ -38,29 +36,33 @@ pub async fn get_heartbeat_challenge_handler(
         (hex_val.clone(), format!("challenge:{}", hex_val))
     };
 
-    let _: () = conn.set_ex(&redis_key, "true", CHALLENGE_TTL).await
+    let _: () = conn.set_ex(&redis_key, "true", state.engine.params().nonce_ttl_seconds).await
         .map_err(|e| anyhow::anyhow!("Redis Set Error: {}", e))?;
 
     Ok(Json(serde_json::json!({ "nonce": nonce_hex })))
 }
 
 /// POST /heartbeat
-/// Verifies the Daily Tap signal.
+/// Verifies the Daily Tap signal. An `attestation_chain` over the same nonce refreshes an
+/// expired trust timer in the same call.
 #[utoipa::path(
     post,
     path = "/heartbeat",
     request_body = Heartbeat,
     responses(
-        (status = 200, description = "Tap Verified"),
+        (status = 200, description = "Tap Verified", body = inline(serde_json::Value)),
+        (status = 400, description = "Invalid Attestation Chain"),
         (status = 401, description = "Invalid Signature"),
-        (status = 429, description = "Daily Limit Reached")
+        (status = 410, description = "Identity Revoked"),
+        (status = 426, description = "Attestation Expired, Identity Dormant or Genesis Version Retired (resend with an attestation chain)"),
+        (status = 429, description = "Daily Limit Reached or Reactivation Cooling-Off")
     )
 )]
 #[instrument(skip(state, payload), fields(identity_id = tracing::field::Empty))] 
 pub async fn heartbeat_handler(
     Extension(state): Extension<SharedState>,
     Json(payload): Json<Heartbeat>,
-) -> Result<StatusCode, AppError> {
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
     
     // 1. Log ID safely
     Span::current().record("identity_id", &payload.identity_id.to_string());
@@ -78,14 +80,25 @@ pub async fn heartbeat_handler(
 
     if val.is_none() {
         warn!("⚠️ Invalid or Expired Challenge Used");
-        return Ok(StatusCode::UNAUTHORIZED);
+        return Ok((StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Invalid or Expired Challenge" }))));
     }
 
     // 3. Process Engine Logic
+    let identity_id = payload.identity_id;
+    let nonce = payload.nonce.clone();
     match state.engine.process_heartbeat(payload).await {
         Ok(new_score) => {
             info!(event = "heartbeat_accepted", score = new_score, "✅ Daily Verification Verified");
-            Ok(StatusCode::OK)
+            let receipt = state.receipts.issue(Receipt {
+                kind: ReceiptKind::Heartbeat,
+                identity_id: Some(identity_id),
+                score: new_score,
+                verdict: ReceiptVerdict::Accepted,
+                public_key_hash: vec![],
+                nonce,
+                issued_at: state.engine.now(),
+            });
+            Ok((StatusCode::OK, Json(serde_json::json!({ "score": new_score, "receipt": receipt }))))
         }
         Err(e) => Err(e.into()) // Convert to structured AppError
     }
@@ -11,7 +11,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::state::SharedState;
 use invariant_engine::IdentityStorage;
-use invariant_shared::{ReAttestationRequest, IdentityStatus};
+use invariant_shared::{Identity, PairwiseLinkRequest, ProofTokenRequest, DeviceStatus, SecurityLevel, SignalContribution, SybilConfidence, EligibilityReport, ReAttestationRequest, KeyRotationRequest, IdentityStatus, IdentityTransition, Reactivation};
 use crate::error_response::AppError;
 use chrono::{DateTime, Utc};
 
@@ -27,6 +27,10 @@ pub struct UpdatePushTokenRequest {
     pub fcm_token: String,
 }
 
+fn tier_label(identity: &Identity) -> &'static str {
+    identity.security_level.map_or("STEEL", |level| level.tier())
+}
+
 // --- RE-ATTESTATION (RECOVERY) ---
 
 #[utoipa::path(
@@ -48,11 +52,88 @@ pub async fn reattest_handler(
     Ok(StatusCode::OK)
 }
 
+// --- KEY ROTATION (DEVICE MIGRATION) ---
+
+/// POST /identity/rotate_key
+/// Moves the identity onto a new hardware key. The nonce must come from `/heartbeat/challenge`.
+#[utoipa::path(
+    post,
+    path = "/identity/rotate_key",
+    request_body = KeyRotationRequest,
+    responses(
+        (status = 200, description = "Key Rotated", body = inline(serde_json::Value)),
+        (status = 400, description = "Invalid Attestation for New Key"),
+        (status = 401, description = "Invalid Rotation Signature or Challenge"),
+        (status = 404, description = "Identity Not Found"),
+        (status = 409, description = "New Key Already Registered"),
+        (status = 410, description = "Identity Revoked")
+    )
+)]
+pub async fn rotate_key_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<KeyRotationRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        tracing::warn!("⚠️ Invalid or Expired Challenge Used (Key Rotation)");
+        return Ok((StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Invalid or Expired Challenge." }))));
+    }
+
+    let identity = state.engine.process_key_rotation(payload).await?;
+    tracing::info!(event = "key_rotated", identity_id = %identity.id, "🔑 Hardware Key Rotated");
+
+    Ok((StatusCode::OK, Json(serde_json::json!({
+        "id": identity.id,
+        "status": identity.status,
+        "continuity_score": identity.continuity_score,
+        "last_attestation": identity.last_attestation.to_rfc3339()
+    }))))
+}
+
+// --- STATUS HISTORY ---
+
+/// GET /identity/:id/transitions
+/// Audit trail of every status change (reason + actor), oldest first.
+#[utoipa::path(
+    get,
+    path = "/identity/{id}/transitions",
+    params(("id" = Uuid, Path, description = "Identity ID")),
+    responses(
+        (status = 200, description = "Status Transitions", body = [IdentityTransition]),
+        (status = 404, description = "Identity Not Found")
+    )
+)]
+pub async fn get_transitions_handler(
+    Path(id): Path<Uuid>,
+    Extension(state): Extension<SharedState>,
+) -> Result<Json<Vec<IdentityTransition>>, AppError> {
+    Ok(Json(state.engine.transition_history(id).await?))
+}
+
+/// GET /identity/:id/reactivations
+/// Every return from dormancy: how long the identity slept, the score and streak before
+/// and after the dormancy rules, and any cooling-off period. Most recent first.
+#[utoipa::path(
+    get,
+    path = "/identity/{id}/reactivations",
+    params(("id" = Uuid, Path, description = "Identity ID")),
+    responses(
+        (status = 200, description = "Reactivations", body = [Reactivation]),
+        (status = 404, description = "Identity Not Found")
+    )
+)]
+pub async fn get_reactivations_handler(
+    Path(id): Path<Uuid>,
+    Extension(state): Extension<SharedState>,
+) -> Result<Json<Vec<Reactivation>>, AppError> {
+    Ok(Json(state.engine.reactivation_history(id).await?))
+}
+
 // --- FULL ENTERPRISE MANIFEST ---
 
 #[derive(Serialize)]
 pub struct SystemManifest {
-    pub identity_id: Uuid,
+    /// Pairwise ID: only meaningful to the requesting partner.
+    pub subject_id: Uuid,
     pub status: IdentityStatus,
     
     // 🛡️ RISK & TRUST SIGNALS
@@ -74,6 +155,10 @@ pub struct TrustProfile {
     pub continuity_score: u64,  // Total successful verifications
     pub streak: u64,            // Consecutive daily verifications
     pub trust_decay_days: i64,  // Days since last hardware proof (Risk metric)
+    pub score: f64,             // Composite 0..=100 (see `signals`)
+    pub signals: Vec<SignalContribution>,
+    pub sybil_cluster: bool,    // Member of a suspected Sybil cluster
+    pub sybil_confidence: Option<SybilConfidence>,
 }
 
 #[derive(Serialize)]
@@ -82,6 +167,7 @@ pub struct DeviceProfile {
     pub product: Option<String>,
     pub model_hash: Option<String>, // Hashed for privacy, identifiable by partner if they own the salt
     pub hardware_backed: bool,
+    pub active_devices: usize,      // Attested keys currently able to heartbeat (phone, tablet...)
 }
 
 #[derive(Serialize)]
@@ -97,37 +183,45 @@ pub struct MetaProfile {
     pub network: String,
     pub genesis_version: u16,
     pub is_genesis_eligible: bool,
+    pub eligibility: EligibilityReport, // Per-rule reasons behind is_genesis_eligible
 }
 
-/// GET /identity/:id/manifest
+/// GET /partners/:partner_id/subjects/:subject_id/manifest
 /// Returns the FULL technical audit of the Identity.
 /// Used by B2B Risk Engines to make decisions (Block/Allow/Limit).
 pub async fn get_manifest_handler(
-    Path(id): Path<Uuid>,
+    Path((partner_id, subject_id)): Path<(String, Uuid)>,
     Extension(state): Extension<SharedState>,
 ) -> Result<Json<SystemManifest>, AppError> {
     
-    let identity = state.engine.get_storage().get_identity(&id).await?
-        .ok_or(invariant_engine::EngineError::IdentityNotFound(id))?;
+    let identity = state.engine.resolve_pairwise(&partner_id, &subject_id).await?;
 
     // Calculate derived risk metrics
-    let now = Utc::now();
+    let now = state.engine.now();
     let days_since_attest = now.signed_duration_since(identity.last_attestation).num_days();
-    let next_available = identity.last_heartbeat + chrono::Duration::minutes(1380);
+    let next_available = state.engine.params().next_heartbeat_at(identity.last_heartbeat);
+    let trust_score = state.engine.trust_score(&identity).await?;
+    let sybil_confidence = state.engine.sybil_flag(&identity.id).await?;
+    let eligibility = state.engine.genesis_eligibility(&identity).await?;
+    let active_devices = state.engine.list_devices(identity.id).await?
+        .iter().filter(|d| d.status == DeviceStatus::Active).count();
 
     let manifest = SystemManifest {
-        identity_id: identity.id,
+        subject_id,
         status: identity.status.clone(),
         
         trust: TrustProfile {
-            tier: if identity.hardware_device.as_deref().unwrap_or("").contains("strongbox") { 
-                "TITANIUM (StrongBox)".to_string() 
-            } else { 
-                "STEEL (TEE)".to_string() 
+            tier: match identity.security_level {
+                Some(SecurityLevel::StrongBox) => "TITANIUM (StrongBox)".to_string(),
+                _ => "STEEL (TEE)".to_string(),
             },
             continuity_score: identity.continuity_score,
             streak: identity.streak,
             trust_decay_days: days_since_attest, // Critical for Partner Risk Engines
+            score: trust_score.score,
+            signals: trust_score.contributions,
+            sybil_cluster: sybil_confidence.is_some(),
+            sybil_confidence,
         },
         
         device: DeviceProfile {
@@ -135,6 +229,7 @@ pub async fn get_manifest_handler(
             product: identity.hardware_product,
             model_hash: identity.hardware_device, // Mapped to hash in DB layer
             hardware_backed: true, // Invariant: Software keys are rejected at Genesis
+            active_devices,
         },
         
         lifecycle: LifecycleProfile {
@@ -147,13 +242,101 @@ pub async fn get_manifest_handler(
         meta: MetaProfile {
             network: identity.network.to_string(),
             genesis_version: identity.genesis_version,
-            is_genesis_eligible: identity.is_genesis_eligible,
+            is_genesis_eligible: eligibility.eligible,
+            eligibility,
         }
     };
 
     Ok(Json(manifest))
 }
 
+// --- PAIRWISE IDENTIFIERS (UNLINKABILITY) ---
+
+/// POST /identity/:id/pairwise
+/// Called by the holder's device when connecting to a partner, signed by its key.
+/// Returns the only identifier that partner will ever see for this identity.
+#[utoipa::path(
+    post,
+    path = "/identity/{id}/pairwise",
+    params(("id" = Uuid, Path, description = "Identity ID")),
+    request_body = PairwiseLinkRequest,
+    responses(
+        (status = 200, description = "Pairwise ID", body = inline(serde_json::Value)),
+        (status = 400, description = "Invalid Partner"),
+        (status = 401, description = "Invalid Signature or Challenge"),
+        (status = 404, description = "Identity Not Found")
+    )
+)]
+pub async fn pairwise_link_handler(
+    Path(id): Path<Uuid>,
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<PairwiseLinkRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        return Ok((StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Invalid or Expired Challenge." }))));
+    }
+
+    let subject_id = state.engine.pairwise_id_for(&state.pairwise_secret, &id, &payload).await?;
+    Ok((StatusCode::OK, Json(serde_json::json!({ "partner_id": payload.partner_id, "subject_id": subject_id }))))
+}
+
+// --- PROOF TOKENS (OFFLINE PARTNER VERIFICATION) ---
+
+/// POST /partners/:partner_id/subjects/:subject_id/token
+/// Issues a short-lived EdDSA JWT asserting the subject's current state.
+/// `sub` is the pairwise ID and `aud` is the partner, so tokens cannot be joined across partners.
+/// The holder's key must sign the request for this partner.
+#[utoipa::path(
+    post,
+    path = "/partners/{partner_id}/subjects/{subject_id}/token",
+    params(
+        ("partner_id" = String, Path, description = "Partner ID"),
+        ("subject_id" = Uuid, Path, description = "Pairwise ID issued to this partner")
+    ),
+    request_body = ProofTokenRequest,
+    responses(
+        (status = 200, description = "Proof Token Issued", body = inline(serde_json::Value)),
+        (status = 400, description = "Invalid Partner"),
+        (status = 401, description = "Invalid Signature or Challenge"),
+        (status = 404, description = "Unknown Subject")
+    )
+)]
+pub async fn issue_token_handler(
+    Path((partner_id, subject_id)): Path<(String, Uuid)>,
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<ProofTokenRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        return Ok((StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Invalid or Expired Challenge." }))));
+    }
+
+    let identity = state.engine.authorize_proof_token(&partner_id, &subject_id, &payload).await?;
+
+    let (token, claims) = state.tokens.issue(&identity, subject_id, tier_label(&identity), &partner_id, state.engine.now());
+    tracing::info!(event = "token_issued", aud = %partner_id, "🎫 Proof Token Issued");
+
+    Ok((StatusCode::OK, Json(serde_json::json!({
+        "token": token,
+        "token_type": "invariant_proof",
+        "expires_at": claims.exp
+    }))))
+}
+
+/// GET /.well-known/jwks.json
+/// Public keys for offline proof token verification (current + retired).
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    responses(
+        (status = 200, description = "Node JWKS", body = JwkSet)
+    )
+)]
+pub async fn jwks_handler(
+    Extension(state): Extension<SharedState>,
+) -> Json<invariant_shared::JwkSet> {
+    Json(state.tokens.jwks())
+}
+
 // --- EXISTING UTILITY HANDLERS ---
 
 pub async fn claim_username_handler(
@@ -191,10 +374,9 @@ pub async fn get_leaderboard_handler(
             let response: Vec<serde_json::Value> = identities.into_iter().enumerate().map(|(index, id)| {
                 serde_json::json!({
                     "rank": index + 1,
-                    "handle": id.username.unwrap_or_else(|| "ANONYMOUS".to_string()),
+                    "handle": id.username.as_deref().unwrap_or("ANONYMOUS"),
                     "score": id.continuity_score,
-                    "id": id.id,
-                    "tier": if id.hardware_device.as_deref().unwrap_or("").contains("strongbox") { "TITANIUM" } else { "STEEL" }
+                    "tier": tier_label(&id)
                 })
             }).collect();
             (StatusCode::OK, Json(response)).into_response()
@@ -0,0 +1,67 @@
+// crates/invariant_server/src/handlers/migration.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use axum::{Extension, Json, http::StatusCode, extract::Path};
+use uuid::Uuid;
+use invariant_shared::{Identity, IdentityMigration, MigrationRequest};
+use crate::state::SharedState;
+use crate::error_response::AppError;
+use tracing::{info, warn};
+
+/// POST /identity/migrate
+/// Promotes a testnet identity to mainnet onto a fresh attested key (mainnet nodes only).
+/// The nonce comes from `/heartbeat/challenge`.
+#[utoipa::path(
+    post,
+    path = "/identity/migrate",
+    request_body = MigrationRequest,
+    responses(
+        (status = 201, description = "Mainnet Identity Minted", body = Identity),
+        (status = 400, description = "Invalid Attestation, Not a Testnet Identity, Already Migrated or Not a Mainnet Node"),
+        (status = 401, description = "Invalid Signature or Challenge"),
+        (status = 404, description = "Identity Not Found"),
+        (status = 409, description = "Key Already Registered or Identity Revoked")
+    )
+)]
+pub async fn migrate_identity_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<MigrationRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        warn!("⚠️ Invalid or Expired Challenge Used (Migration)");
+        return Ok((StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Invalid or Expired Challenge." }))));
+    }
+
+    let testnet_id = payload.testnet_id;
+    let identity: Identity = state.engine.process_migration(payload).await?;
+    info!(event = "identity_migrated", testnet_id = %testnet_id, mainnet_id = %identity.id, score = identity.continuity_score, "🌐 Identity Migrated to Mainnet");
+
+    Ok((StatusCode::CREATED, Json(serde_json::json!(identity))))
+}
+
+/// GET /identity/:id/migration
+/// The migration the identity took part in, from either side.
+#[utoipa::path(
+    get,
+    path = "/identity/{id}/migration",
+    params(("id" = Uuid, Path, description = "Testnet or Mainnet Identity ID")),
+    responses(
+        (status = 200, description = "Migration", body = IdentityMigration),
+        (status = 404, description = "Identity Not Found or Never Migrated")
+    )
+)]
+pub async fn get_migration_handler(
+    Path(id): Path<Uuid>,
+    Extension(state): Extension<SharedState>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    let migration: Option<IdentityMigration> = state.engine.migration_for(id).await?;
+    match migration {
+        Some(migration) => Ok((StatusCode::OK, Json(serde_json::json!(migration)))),
+        None => Ok((StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": "Identity has not migrated" })))),
+    }
+}
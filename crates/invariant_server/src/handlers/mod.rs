@@ -5,11 +5,10 @@
  * This source code is licensed under the Business Source License (BSL 1.1) 
  * found in the LICENSE.md file in the root directory of this source tree.
  */
-use axum::{Router, routing::{get, post}, extract::Path, http::{StatusCode, HeaderValue, header}, Extension, Json};
+use axum::{Router, routing::{get, post, delete}, extract::Path, http::{StatusCode, HeaderMap, HeaderValue, header}, Extension, Json};
 use crate::state::SharedState;
 use uuid::Uuid;
 use invariant_engine::IdentityStorage;
-use chrono::Duration;
 
 // Middleware
 use tower::ServiceBuilder;
@@ -29,6 +28,49 @@ use crate::api_docs::ApiDoc;
 pub mod genesis;
 pub mod heartbeat;
 pub mod identity;
+pub mod recovery;
+pub mod receipts;
+pub mod privacy_pass;
+pub mod transparency;
+pub mod revocation;
+pub mod switch;
+pub mod streak;
+pub mod sybil;
+pub mod action;
+pub mod webhooks;
+pub mod devices;
+pub mod migration;
+pub mod upgrades;
+
+/// Checks `Authorization: Bearer <token>` against `INVARIANT_ADMIN_TOKEN`.
+/// Digests are compared so the check does not leak the token through timing.
+/// Without a configured token the admin API is closed.
+pub(crate) fn is_admin(state: &SharedState, headers: &HeaderMap) -> bool {
+    use sha2::{Sha256, Digest};
+
+    let (Some(expected), Some(presented)) = (
+        state.admin_token.as_deref(),
+        headers.get(header::AUTHORIZATION).and_then(|v| v.to_str().ok()).and_then(|v| v.strip_prefix("Bearer ")),
+    ) else {
+        return false;
+    };
+    Sha256::digest(expected.as_bytes()) == Sha256::digest(presented.as_bytes())
+}
+
+/// Atomically consumes a challenge issued by `/heartbeat/challenge` (GET + DEL, single use).
+/// Returns `false` if the nonce was never issued or has expired.
+pub(crate) async fn consume_challenge(state: &SharedState, nonce: &[u8]) -> Result<bool, crate::error_response::AppError> {
+    use redis::AsyncCommands;
+
+    let mut conn = state.redis.get_multiplexed_async_connection().await
+        .map_err(|e| anyhow::anyhow!("Redis Error: {}", e))?;
+
+    let redis_key = format!("challenge:{}", hex::encode(nonce));
+    let val: Option<String> = conn.get_del(&redis_key).await
+        .map_err(|e| anyhow::anyhow!("Redis Auth Error: {}", e))?;
+
+    Ok(val.is_some())
+}
 
 async fn check_identity_handler(
     Path(id): Path<Uuid>,
@@ -36,18 +78,19 @@ async fn check_identity_handler(
 ) -> impl axum::response::IntoResponse {
     match state.engine.get_storage().get_identity(&id).await {
         Ok(Some(identity)) => {
-            // Next available = 23 Hours (1380 mins)
-            let next_available = identity.last_heartbeat + Duration::minutes(1380);
+            let next_available = state.engine.params().next_heartbeat_at(identity.last_heartbeat);
+            let eligibility = state.engine.genesis_eligibility(&identity).await.ok();
             (
                 StatusCode::OK,
                 Json(serde_json::json!({
-                    "id": identity.id,
                     "score": identity.continuity_score,
                     "streak": identity.streak,
                     "status": format!("{:?}", identity.status).to_uppercase(),
                     "tier": identity.hardware_device.as_deref().unwrap_or("Hardware TEE"),
                     "username": identity.username, 
-                    "is_genesis_eligible": identity.is_genesis_eligible,
+                    "network": identity.network,
+                    "is_genesis_eligible": eligibility.as_ref().map_or(identity.is_genesis_eligible, |r| r.eligible),
+                    "eligibility": eligibility,
                     "next_available": next_available.to_rfc3339(),
                     // 🛡️ Expose Trust Timer so Client knows when to re-attest
                     "last_attestation": identity.last_attestation.to_rfc3339()
@@ -95,13 +138,80 @@ pub fn app_router(state: SharedState) -> Router {
 
         // Identity Management
         .route("/identity/:id", get(check_identity_handler))
-        .route("/identity/:id/manifest", get(identity::get_manifest_handler)) // 👈 NEW
         .route("/identity/reattest", post(identity::reattest_handler))       // 👈 NEW
+        .route("/identity/rotate_key", post(identity::rotate_key_handler))
+        .route("/identity/:id/pairwise", post(identity::pairwise_link_handler))
+        .route("/identity/:id/transitions", get(identity::get_transitions_handler))
+        .route("/identity/:id/reactivations", get(identity::get_reactivations_handler))
+        .route("/.well-known/jwks.json", get(identity::jwks_handler))
+
+        // Partner API (pairwise IDs only)
+        .route("/partners/:partner_id/subjects/:subject_id/manifest", get(identity::get_manifest_handler))
+        .route("/partners/:partner_id/subjects/:subject_id/token", post(identity::issue_token_handler))
+        .route("/partners/:partner_id/revocations", get(revocation::partner_revocations_handler))
+
+        // Devices (multiple hardware keys per identity)
+        .route("/identity/devices", post(devices::enroll_device_handler))
+        .route("/identity/devices/revoke", post(devices::revoke_device_handler))
+        .route("/identity/:id/devices", get(devices::list_devices_handler))
+
+        // Migration (testnet identities onto mainnet)
+        .route("/identity/migrate", post(migration::migrate_identity_handler))
+        .route("/identity/:id/migration", get(migration::get_migration_handler))
+
+        // Revocation
+        .route("/identity/revoke", post(revocation::self_revoke_handler))
+        .route("/admin/identity/:id/revoke", post(revocation::admin_revoke_handler))
+
+        // Dead Man's Switch
+        .route("/identity/switch/arm", post(switch::arm_switch_handler))
+        .route("/identity/switch/disarm", post(switch::disarm_switch_handler))
+        .route("/identity/:id/switch", get(switch::get_switch_handler))
+
+        // Streaks
+        .route("/identity/:id/streak", get(streak::get_streak_handler))
+        .route("/identity/streak/timezone", post(streak::set_streak_timezone_handler))
+
+        // Action Signing (Partners)
+        .route("/action/challenge", post(action::action_challenge_handler))
+        .route("/action/verify", post(action::action_verify_handler))
+
+        // Sybil Detection
+        .route("/admin/sybil/clusters", get(sybil::list_clusters_handler))
+
+        // Genesis Version Upgrades
+        .route("/admin/genesis/upgrades", get(upgrades::upgrade_progress_handler))
+
+        // Partner Webhooks (Admin)
+        .route("/admin/partners/:partner_id/webhooks", post(webhooks::register_webhook_handler).get(webhooks::list_webhooks_handler))
+        .route("/admin/partners/:partner_id/webhooks/:endpoint_id", delete(webhooks::delete_webhook_handler))
+        .route("/admin/partners/:partner_id/webhooks/deliveries", get(webhooks::list_deliveries_handler))
+        .route("/admin/partners/:partner_id/webhooks/deliveries/:delivery_id/replay", post(webhooks::replay_delivery_handler))
+
+        // Social Recovery
+        .route("/identity/guardians", post(recovery::set_guardians_handler))
+        .route("/recovery/initiate", post(recovery::initiate_recovery_handler))
+        .route("/recovery/approve", post(recovery::approve_recovery_handler))
+        .route("/recovery/reject", post(recovery::reject_recovery_handler))
+        .route("/recovery/cancel", post(recovery::cancel_recovery_handler))
+        .route("/recovery/:id/finalize", post(recovery::finalize_recovery_handler))
+        .route("/recovery/:id", get(recovery::get_recovery_handler))
         
         .route("/identity/claim_username", post(identity::claim_username_handler))
         .route("/identity/push_token", post(identity::update_push_token_handler))
         .route("/leaderboard", get(identity::get_leaderboard_handler))
         .route("/genesis/challenge", get(genesis::get_challenge_handler))
+        .route("/receipts/key", get(receipts::get_receipt_key_handler))
+
+        // Privacy Pass (anonymous tokens)
+        .route("/.well-known/private-token-issuer-directory", get(privacy_pass::issuer_directory_handler))
+        .route("/privacy_pass/token-request", post(privacy_pass::token_request_handler))
+
+        // Transparency Log (auditors)
+        .route("/transparency/sth", get(transparency::get_sth_handler))
+        .route("/transparency/entries", get(transparency::get_entries_handler))
+        .route("/transparency/proof/inclusion", get(transparency::get_inclusion_proof_handler))
+        .route("/transparency/proof/consistency", get(transparency::get_consistency_proof_handler))
         
         // Middleware Stack (Bottom runs first)
         .layer(
@@ -0,0 +1,70 @@
+// crates/invariant_server/src/handlers/privacy_pass.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1) 
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use axum::{Extension, Json, http::StatusCode};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use invariant_shared::TokenIssuanceRequest;
+use invariant_shared::privacy_pass::TOKEN_TYPE_BLIND_RSA;
+use crate::state::SharedState;
+use crate::error_response::AppError;
+use tracing::{info, warn};
+
+const ISSUER_REQUEST_URI: &str = "/privacy_pass/token-request";
+
+/// GET /.well-known/private-token-issuer-directory
+/// RFC 9578 issuer directory: where to send requests and which key verifies tokens.
+#[utoipa::path(
+    get,
+    path = "/.well-known/private-token-issuer-directory",
+    responses(
+        (status = 200, description = "Privacy Pass issuer directory", body = inline(serde_json::Value))
+    )
+)]
+pub async fn issuer_directory_handler(
+    Extension(state): Extension<SharedState>,
+) -> Json<serde_json::Value> {
+    Json(serde_json::json!({
+        "issuer-request-uri": ISSUER_REQUEST_URI,
+        "token-keys": [{
+            "token-type": TOKEN_TYPE_BLIND_RSA,
+            "token-key": URL_SAFE_NO_PAD.encode(state.privacy_pass.public_key_spki())
+        }]
+    }))
+}
+
+/// POST /privacy_pass/token-request
+/// Blind-signs a batch of token requests for an active identity (one batch per issuance window).
+/// The nonce must come from `/heartbeat/challenge`.
+#[utoipa::path(
+    post,
+    path = "/privacy_pass/token-request",
+    request_body = TokenIssuanceRequest,
+    responses(
+        (status = 200, description = "Blind signatures, in request order", body = inline(serde_json::Value)),
+        (status = 400, description = "Malformed Batch or Inactive Identity"),
+        (status = 401, description = "Invalid Signature or Challenge"),
+        (status = 404, description = "Identity Not Found"),
+        (status = 426, description = "Attestation Expired"),
+        (status = 429, description = "Batch Already Issued Today")
+    )
+)]
+pub async fn token_request_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<TokenIssuanceRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        warn!("⚠️ Invalid or Expired Challenge Used (Privacy Pass)");
+        return Ok((StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Invalid or Expired Challenge." }))));
+    }
+
+    let responses = state.engine.issue_privacy_pass_tokens(&state.privacy_pass, payload).await?;
+    // No identity in the log line: issuance volume only.
+    info!(event = "privacy_pass_issued", count = responses.len(), "🎟️ Privacy Pass Batch Issued");
+
+    Ok((StatusCode::OK, Json(serde_json::json!({ "token_responses": responses }))))
+}
@@ -0,0 +1,29 @@
+// crates/invariant_server/src/handlers/receipts.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1) 
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use axum::{Extension, Json};
+use crate::state::SharedState;
+
+/// GET /receipts/key
+/// Publishes the node's Ed25519 receipt key so partners can verify receipts offline.
+#[utoipa::path(
+    get,
+    path = "/receipts/key",
+    responses(
+        (status = 200, description = "Receipt verification key", body = inline(serde_json::Value))
+    )
+)]
+pub async fn get_receipt_key_handler(
+    Extension(state): Extension<SharedState>,
+) -> Json<serde_json::Value> {
+    Json(serde_json::json!({
+        "algorithm": "Ed25519",
+        "key_id": state.receipts.key_id(),
+        "public_key": hex::encode(state.receipts.public_key())
+    }))
+}
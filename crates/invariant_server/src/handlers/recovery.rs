@@ -0,0 +1,224 @@
+// crates/invariant_server/src/handlers/recovery.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1) 
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use axum::{Extension, Json, http::StatusCode, extract::Path};
+use uuid::Uuid;
+use invariant_engine::{RecoveryStorage, EngineError};
+use invariant_shared::{GuardianSetRequest, RecoveryInitRequest, RecoveryApproval, RecoveryRejection, RecoveryCancelRequest, Recovery};
+use crate::state::SharedState;
+use crate::error_response::AppError;
+use tracing::{info, warn};
+
+// All signed recovery steps take their nonce from `/heartbeat/challenge`.
+
+fn invalid_challenge() -> (StatusCode, Json<serde_json::Value>) {
+    warn!("⚠️ Invalid or Expired Challenge Used (Recovery)");
+    (StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Invalid or Expired Challenge." })))
+}
+
+fn recovery_json(recovery: &Recovery) -> serde_json::Value {
+    serde_json::json!({
+        "recovery_id": recovery.id,
+        "identity_id": recovery.identity_id,
+        "status": recovery.status,
+        "approvals": recovery.approvals,
+        "rejections": recovery.rejections,
+        "threshold": recovery.threshold,
+        "initiated_at": recovery.initiated_at.to_rfc3339(),
+        "expires_at": recovery.expires_at.to_rfc3339(),
+        "executable_at": recovery.executable_at.map(|t| t.to_rfc3339()),
+    })
+}
+
+/// POST /identity/guardians
+/// Designates (or replaces) the M-of-N guardian set. Signed by the identity's current key.
+#[utoipa::path(
+    post,
+    path = "/identity/guardians",
+    request_body = GuardianSetRequest,
+    responses(
+        (status = 200, description = "Guardians Designated"),
+        (status = 401, description = "Invalid Signature or Challenge"),
+        (status = 409, description = "Invalid Guardian Set or Recovery In Progress"),
+        (status = 410, description = "Identity Revoked")
+    )
+)]
+pub async fn set_guardians_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<GuardianSetRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        return Ok(invalid_challenge());
+    }
+
+    let id = payload.id;
+    let (guardians, threshold) = (payload.guardian_ids.len(), payload.threshold);
+    state.engine.set_guardians(payload).await?;
+    info!(event = "guardians_set", identity_id = %id, guardians, threshold, "🛟 Guardian Set Updated");
+
+    Ok((StatusCode::OK, Json(serde_json::json!({ "id": id, "guardians": guardians, "threshold": threshold }))))
+}
+
+/// POST /recovery/initiate
+/// Starts a recovery from the NEW device (fresh attestation of the new key).
+#[utoipa::path(
+    post,
+    path = "/recovery/initiate",
+    request_body = RecoveryInitRequest,
+    responses(
+        (status = 201, description = "Recovery Started", body = inline(serde_json::Value)),
+        (status = 400, description = "Invalid Attestation for New Key"),
+        (status = 404, description = "Identity Not Found"),
+        (status = 409, description = "No Guardians or Recovery Already In Progress"),
+        (status = 410, description = "Identity Revoked")
+    )
+)]
+pub async fn initiate_recovery_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<RecoveryInitRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        return Ok(invalid_challenge());
+    }
+
+    let recovery = state.engine.initiate_recovery(payload).await?;
+    info!(event = "recovery_initiated", identity_id = %recovery.identity_id, recovery_id = %recovery.id, "🛟 Recovery Initiated");
+
+    Ok((StatusCode::CREATED, Json(recovery_json(&recovery))))
+}
+
+/// POST /recovery/approve
+/// A guardian approves a pending recovery with their own hardware key.
+#[utoipa::path(
+    post,
+    path = "/recovery/approve",
+    request_body = RecoveryApproval,
+    responses(
+        (status = 200, description = "Approval Recorded", body = inline(serde_json::Value)),
+        (status = 401, description = "Invalid Guardian Signature or Challenge"),
+        (status = 404, description = "Recovery Not Found"),
+        (status = 409, description = "Not a Guardian or Recovery Closed")
+    )
+)]
+pub async fn approve_recovery_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<RecoveryApproval>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        return Ok(invalid_challenge());
+    }
+
+    let guardian_id = payload.guardian_id;
+    let recovery = state.engine.approve_recovery(payload).await?;
+    info!(event = "recovery_approved", recovery_id = %recovery.id, guardian_id = %guardian_id, approvals = recovery.approvals, "🛟 Guardian Approval");
+
+    Ok((StatusCode::OK, Json(recovery_json(&recovery))))
+}
+
+/// POST /recovery/reject
+/// A guardian refuses a pending recovery with their own hardware key.
+#[utoipa::path(
+    post,
+    path = "/recovery/reject",
+    request_body = RecoveryRejection,
+    responses(
+        (status = 200, description = "Rejection Recorded", body = inline(serde_json::Value)),
+        (status = 401, description = "Invalid Guardian Signature or Challenge"),
+        (status = 404, description = "Recovery Not Found"),
+        (status = 409, description = "Not a Guardian or Recovery Not Pending")
+    )
+)]
+pub async fn reject_recovery_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<RecoveryRejection>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        return Ok(invalid_challenge());
+    }
+
+    let guardian_id = payload.guardian_id;
+    let recovery = state.engine.reject_recovery(payload).await?;
+    warn!(event = "recovery_rejected", recovery_id = %recovery.id, guardian_id = %guardian_id, rejections = recovery.rejections, "⛔ Guardian Rejection");
+
+    Ok((StatusCode::OK, Json(recovery_json(&recovery))))
+}
+
+/// POST /recovery/cancel
+/// The original key holder vetoes a recovery (the device was not lost).
+#[utoipa::path(
+    post,
+    path = "/recovery/cancel",
+    request_body = RecoveryCancelRequest,
+    responses(
+        (status = 200, description = "Recovery Cancelled", body = inline(serde_json::Value)),
+        (status = 401, description = "Invalid Signature or Challenge"),
+        (status = 404, description = "Recovery Not Found"),
+        (status = 409, description = "Recovery Closed")
+    )
+)]
+pub async fn cancel_recovery_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<RecoveryCancelRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        return Ok(invalid_challenge());
+    }
+
+    let recovery = state.engine.cancel_recovery(payload).await?;
+    warn!(event = "recovery_cancelled", recovery_id = %recovery.id, identity_id = %recovery.identity_id, "⛔ Recovery Cancelled by Holder");
+
+    Ok((StatusCode::OK, Json(recovery_json(&recovery))))
+}
+
+/// POST /recovery/:id/finalize
+/// Installs the recovery key once the threshold is met and the cancellation window elapsed.
+#[utoipa::path(
+    post,
+    path = "/recovery/{id}/finalize",
+    params(("id" = Uuid, Path, description = "Recovery ID")),
+    responses(
+        (status = 200, description = "Key Recovered", body = inline(serde_json::Value)),
+        (status = 404, description = "Recovery Not Found"),
+        (status = 409, description = "Not Approved or Window Still Open"),
+        (status = 410, description = "Identity Revoked")
+    )
+)]
+pub async fn finalize_recovery_handler(
+    Path(id): Path<Uuid>,
+    Extension(state): Extension<SharedState>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let recovery = state.engine.finalize_recovery(id).await?;
+    info!(event = "recovery_completed", recovery_id = %recovery.id, identity_id = %recovery.identity_id, "✅ Identity Recovered");
+
+    Ok(Json(recovery_json(&recovery)))
+}
+
+/// GET /recovery/:id
+/// Recovery progress plus its full audit trail.
+#[utoipa::path(
+    get,
+    path = "/recovery/{id}",
+    params(("id" = Uuid, Path, description = "Recovery ID")),
+    responses(
+        (status = 200, description = "Recovery Status", body = inline(serde_json::Value)),
+        (status = 404, description = "Recovery Not Found")
+    )
+)]
+pub async fn get_recovery_handler(
+    Path(id): Path<Uuid>,
+    Extension(state): Extension<SharedState>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let storage = state.engine.get_storage();
+    let recovery = storage.get_recovery(&id).await?
+        .ok_or(EngineError::RecoveryNotFound(id))?;
+    let events = storage.get_recovery_events(&id).await?;
+
+    let mut body = recovery_json(&recovery);
+    body["events"] = serde_json::json!(events);
+    Ok(Json(body))
+}
@@ -0,0 +1,115 @@
+// crates/invariant_server/src/handlers/revocation.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use axum::{Extension, Json, http::{StatusCode, HeaderMap}, extract::{Path, Query}};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use invariant_shared::{RevocationReason, RevocationNotice, SelfRevocationRequest};
+use crate::state::SharedState;
+use crate::error_response::AppError;
+
+const DEFAULT_FEED_PAGE: u32 = 100;
+
+#[derive(Deserialize, ToSchema)]
+pub struct AdminRevocationRequest {
+    pub reason: RevocationReason,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct FeedQuery {
+    /// Last `sequence` already processed (0 = from the beginning).
+    pub after: Option<u64>,
+    pub limit: Option<u32>,
+}
+
+/// POST /admin/identity/:id/revoke
+/// Operator revocation. Requires `Authorization: Bearer <INVARIANT_ADMIN_TOKEN>`.
+#[utoipa::path(
+    post,
+    path = "/admin/identity/{id}/revoke",
+    params(("id" = Uuid, Path, description = "Identity ID")),
+    request_body = AdminRevocationRequest,
+    responses(
+        (status = 200, description = "Identity Revoked", body = Revocation),
+        (status = 401, description = "Admin Token Required"),
+        (status = 404, description = "Identity Not Found"),
+        (status = 409, description = "Already Revoked")
+    )
+)]
+pub async fn admin_revoke_handler(
+    Path(id): Path<Uuid>,
+    Extension(state): Extension<SharedState>,
+    headers: HeaderMap,
+    Json(payload): Json<AdminRevocationRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::is_admin(&state, &headers) {
+        return Ok((StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Admin token required." }))));
+    }
+
+    let revocation = state.engine.revoke_identity(id, payload.reason, None).await?;
+    tracing::warn!(event = "identity_revoked", identity_id = %id, reason = payload.reason.tag(), "⛔ Identity Revoked (Operator)");
+
+    Ok((StatusCode::OK, Json(serde_json::json!(revocation))))
+}
+
+/// POST /identity/revoke
+/// Holder revocation signed by the identity's own key. The nonce must come from `/heartbeat/challenge`.
+#[utoipa::path(
+    post,
+    path = "/identity/revoke",
+    request_body = SelfRevocationRequest,
+    responses(
+        (status = 200, description = "Identity Revoked", body = Revocation),
+        (status = 400, description = "Reason Reserved for Operators"),
+        (status = 401, description = "Invalid Signature or Challenge"),
+        (status = 404, description = "Identity Not Found"),
+        (status = 409, description = "Already Revoked")
+    )
+)]
+pub async fn self_revoke_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<SelfRevocationRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        tracing::warn!("⚠️ Invalid or Expired Challenge Used (Revocation)");
+        return Ok((StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Invalid or Expired Challenge." }))));
+    }
+
+    let revocation = state.engine.process_self_revocation(payload).await?;
+    tracing::info!(event = "identity_revoked", identity_id = %revocation.identity_id, reason = revocation.reason.tag(), "⛔ Identity Revoked (Holder)");
+
+    Ok((StatusCode::OK, Json(serde_json::json!(revocation))))
+}
+
+/// GET /partners/:partner_id/revocations?after=&limit=
+/// Revocations of this partner's subjects, oldest first. Poll with the last `sequence` seen.
+#[utoipa::path(
+    get,
+    path = "/partners/{partner_id}/revocations",
+    params(
+        ("partner_id" = String, Path, description = "Partner ID"),
+        FeedQuery
+    ),
+    responses(
+        (status = 200, description = "Revocation Feed Page", body = [RevocationNotice]),
+        (status = 400, description = "Invalid Partner")
+    )
+)]
+pub async fn partner_revocations_handler(
+    Path(partner_id): Path<String>,
+    Extension(state): Extension<SharedState>,
+    Query(query): Query<FeedQuery>,
+) -> Result<Json<Vec<RevocationNotice>>, AppError> {
+    let feed = state.engine.partner_revocation_feed(
+        &partner_id,
+        query.after.unwrap_or(0),
+        query.limit.unwrap_or(DEFAULT_FEED_PAGE),
+    ).await?;
+    Ok(Json(feed))
+}
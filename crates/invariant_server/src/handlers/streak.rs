@@ -0,0 +1,57 @@
+// crates/invariant_server/src/handlers/streak.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use axum::{Extension, Json, http::StatusCode, extract::Path};
+use uuid::Uuid;
+use invariant_shared::{StreakState, StreakTimezoneRequest};
+use crate::state::SharedState;
+use crate::error_response::AppError;
+
+/// GET /identity/:id/streak
+#[utoipa::path(
+    get,
+    path = "/identity/{id}/streak",
+    params(("id" = Uuid, Path, description = "Identity ID")),
+    responses(
+        (status = 200, description = "Streak State", body = StreakState),
+        (status = 404, description = "Identity Not Found")
+    )
+)]
+pub async fn get_streak_handler(
+    Path(id): Path<Uuid>,
+    Extension(state): Extension<SharedState>,
+) -> Result<Json<StreakState>, AppError> {
+    Ok(Json(state.engine.streak(id).await?))
+}
+
+/// POST /identity/streak/timezone
+/// Declares the IANA time zone that defines the holder's streak days.
+/// The nonce must come from `/heartbeat/challenge`.
+#[utoipa::path(
+    post,
+    path = "/identity/streak/timezone",
+    request_body = StreakTimezoneRequest,
+    responses(
+        (status = 200, description = "Time Zone Updated", body = StreakState),
+        (status = 400, description = "Unknown Time Zone"),
+        (status = 401, description = "Invalid Signature or Challenge"),
+        (status = 404, description = "Identity Not Found")
+    )
+)]
+pub async fn set_streak_timezone_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<StreakTimezoneRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        tracing::warn!("⚠️ Invalid or Expired Challenge Used (Streak)");
+        return Ok((StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Invalid or Expired Challenge." }))));
+    }
+
+    let streak = state.engine.set_streak_timezone(payload).await?;
+    Ok((StatusCode::OK, Json(serde_json::json!(streak))))
+}
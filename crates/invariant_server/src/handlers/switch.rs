@@ -0,0 +1,92 @@
+// crates/invariant_server/src/handlers/switch.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use axum::{Extension, Json, http::StatusCode, extract::Path};
+use uuid::Uuid;
+use invariant_shared::{DeadMansSwitch, SwitchArmRequest, SwitchDisarmRequest};
+use crate::state::SharedState;
+use crate::error_response::AppError;
+use tracing::{info, warn};
+
+// Both signed steps take their nonce from `/heartbeat/challenge`.
+
+fn invalid_challenge() -> (StatusCode, Json<serde_json::Value>) {
+    warn!("⚠️ Invalid or Expired Challenge Used (Switch)");
+    (StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Invalid or Expired Challenge." })))
+}
+
+/// POST /identity/switch/arm
+/// Arms (or re-configures) the dead man's switch. Signed by the identity's current key.
+#[utoipa::path(
+    post,
+    path = "/identity/switch/arm",
+    request_body = SwitchArmRequest,
+    responses(
+        (status = 200, description = "Switch Armed", body = DeadMansSwitch),
+        (status = 400, description = "Invalid Threshold or Beneficiary"),
+        (status = 401, description = "Invalid Signature or Challenge"),
+        (status = 404, description = "Identity or Beneficiary Not Found"),
+        (status = 410, description = "Identity Revoked")
+    )
+)]
+pub async fn arm_switch_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<SwitchArmRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        return Ok(invalid_challenge());
+    }
+
+    let switch = state.engine.arm_switch(payload).await?;
+    info!(event = "switch_armed", identity_id = %switch.identity_id, threshold_days = switch.threshold_days, "🪦 Dead Man's Switch Armed");
+
+    Ok((StatusCode::OK, Json(serde_json::json!(switch))))
+}
+
+/// POST /identity/switch/disarm
+/// Disarms the switch. During the grace period this cancels a pending trigger.
+#[utoipa::path(
+    post,
+    path = "/identity/switch/disarm",
+    request_body = SwitchDisarmRequest,
+    responses(
+        (status = 200, description = "Switch Disarmed", body = DeadMansSwitch),
+        (status = 401, description = "Invalid Signature or Challenge"),
+        (status = 404, description = "Identity Not Found")
+    )
+)]
+pub async fn disarm_switch_handler(
+    Extension(state): Extension<SharedState>,
+    Json(payload): Json<SwitchDisarmRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::consume_challenge(&state, &payload.nonce).await? {
+        return Ok(invalid_challenge());
+    }
+
+    let switch = state.engine.disarm_switch(payload).await?;
+    info!(event = "switch_disarmed", identity_id = %switch.identity_id, "🪦 Dead Man's Switch Disarmed");
+
+    Ok((StatusCode::OK, Json(serde_json::json!(switch))))
+}
+
+/// GET /identity/:id/switch
+#[utoipa::path(
+    get,
+    path = "/identity/{id}/switch",
+    params(("id" = Uuid, Path, description = "Identity ID")),
+    responses(
+        (status = 200, description = "Switch State", body = DeadMansSwitch),
+        (status = 404, description = "Identity Not Found")
+    )
+)]
+pub async fn get_switch_handler(
+    Path(id): Path<Uuid>,
+    Extension(state): Extension<SharedState>,
+) -> Result<Json<DeadMansSwitch>, AppError> {
+    Ok(Json(state.engine.get_switch(id).await?))
+}
@@ -0,0 +1,52 @@
+// crates/invariant_server/src/handlers/sybil.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use axum::{Extension, Json, http::{StatusCode, HeaderMap}, extract::Query};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use invariant_shared::SybilConfidence;
+use crate::state::SharedState;
+use crate::error_response::AppError;
+
+const DEFAULT_CLUSTER_PAGE: u32 = 100;
+const MAX_CLUSTER_PAGE: u32 = 1000;
+
+#[derive(Deserialize, IntoParams)]
+pub struct ClusterQuery {
+    /// Lowest confidence to include (default: low).
+    pub min_confidence: Option<SybilConfidence>,
+    pub limit: Option<u32>,
+}
+
+/// GET /admin/sybil/clusters?min_confidence=&limit=
+/// Suspected Sybil clusters from the latest background scan, strongest first.
+/// Requires `Authorization: Bearer <INVARIANT_ADMIN_TOKEN>`.
+#[utoipa::path(
+    get,
+    path = "/admin/sybil/clusters",
+    params(ClusterQuery),
+    responses(
+        (status = 200, description = "Suspected Clusters", body = [SybilCluster]),
+        (status = 401, description = "Admin Token Required")
+    )
+)]
+pub async fn list_clusters_handler(
+    Query(query): Query<ClusterQuery>,
+    Extension(state): Extension<SharedState>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::is_admin(&state, &headers) {
+        return Ok((StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Admin token required." }))));
+    }
+
+    let min_confidence = query.min_confidence.unwrap_or(SybilConfidence::Low);
+    let limit = query.limit.unwrap_or(DEFAULT_CLUSTER_PAGE).min(MAX_CLUSTER_PAGE);
+    let clusters = state.engine.sybil_clusters(min_confidence, limit).await?;
+
+    Ok((StatusCode::OK, Json(serde_json::json!(clusters))))
+}
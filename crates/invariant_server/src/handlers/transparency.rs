@@ -0,0 +1,116 @@
+// crates/invariant_server/src/handlers/transparency.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1) 
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use axum::{Extension, Json, extract::Query};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use invariant_shared::{LogEntry, SignedTreeHead};
+use crate::state::SharedState;
+use crate::error_response::AppError;
+
+// Auditor API. Everything here is public and read-only.
+// Hashes are hex encoded; tree heads verify against `GET /receipts/key`.
+
+#[derive(Deserialize, IntoParams)]
+pub struct EntriesQuery {
+    pub start: u64,
+    pub end: u64,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct InclusionQuery {
+    pub index: u64,
+    pub tree_size: u64,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct ConsistencyQuery {
+    pub first: u64,
+    pub second: u64,
+}
+
+fn hex_path(proof: Vec<Vec<u8>>) -> Vec<String> {
+    proof.into_iter().map(hex::encode).collect()
+}
+
+/// GET /transparency/sth
+/// Current signed tree head.
+#[utoipa::path(
+    get,
+    path = "/transparency/sth",
+    responses(
+        (status = 200, description = "Signed Tree Head", body = SignedTreeHead)
+    )
+)]
+pub async fn get_sth_handler(
+    Extension(state): Extension<SharedState>,
+) -> Result<Json<SignedTreeHead>, AppError> {
+    let head = state.engine.tree_head().await?;
+    Ok(Json(state.receipts.sign_tree_head(head)))
+}
+
+/// GET /transparency/entries?start=&end=
+#[utoipa::path(
+    get,
+    path = "/transparency/entries",
+    params(EntriesQuery),
+    responses(
+        (status = 200, description = "Log entries in [start, end)", body = [LogEntry]),
+        (status = 400, description = "Invalid Range")
+    )
+)]
+pub async fn get_entries_handler(
+    Extension(state): Extension<SharedState>,
+    Query(query): Query<EntriesQuery>,
+) -> Result<Json<Vec<LogEntry>>, AppError> {
+    Ok(Json(state.engine.log_entries(query.start, query.end).await?))
+}
+
+/// GET /transparency/proof/inclusion?index=&tree_size=
+#[utoipa::path(
+    get,
+    path = "/transparency/proof/inclusion",
+    params(InclusionQuery),
+    responses(
+        (status = 200, description = "Audit path", body = inline(serde_json::Value)),
+        (status = 400, description = "Invalid Range")
+    )
+)]
+pub async fn get_inclusion_proof_handler(
+    Extension(state): Extension<SharedState>,
+    Query(query): Query<InclusionQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let proof = state.engine.inclusion_proof(query.index, query.tree_size).await?;
+    Ok(Json(serde_json::json!({
+        "index": query.index,
+        "tree_size": query.tree_size,
+        "audit_path": hex_path(proof)
+    })))
+}
+
+/// GET /transparency/proof/consistency?first=&second=
+#[utoipa::path(
+    get,
+    path = "/transparency/proof/consistency",
+    params(ConsistencyQuery),
+    responses(
+        (status = 200, description = "Consistency proof", body = inline(serde_json::Value)),
+        (status = 400, description = "Invalid Range")
+    )
+)]
+pub async fn get_consistency_proof_handler(
+    Extension(state): Extension<SharedState>,
+    Query(query): Query<ConsistencyQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let proof = state.engine.consistency_proof(query.first, query.second).await?;
+    Ok(Json(serde_json::json!({
+        "first": query.first,
+        "second": query.second,
+        "consistency": hex_path(proof)
+    })))
+}
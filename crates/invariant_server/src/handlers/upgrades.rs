@@ -0,0 +1,36 @@
+// crates/invariant_server/src/handlers/upgrades.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use axum::{Extension, Json, http::{StatusCode, HeaderMap}};
+use invariant_shared::GenesisUpgradeProgress;
+use crate::state::SharedState;
+use crate::error_response::AppError;
+
+/// GET /admin/genesis/upgrades
+/// Non-revoked identities per genesis version, how many reached it by upgrading, and how
+/// many are still below the policy's minimum.
+/// Requires `Authorization: Bearer <INVARIANT_ADMIN_TOKEN>`.
+#[utoipa::path(
+    get,
+    path = "/admin/genesis/upgrades",
+    responses(
+        (status = 200, description = "Upgrade Progress", body = GenesisUpgradeProgress),
+        (status = 401, description = "Admin Token Required")
+    )
+)]
+pub async fn upgrade_progress_handler(
+    Extension(state): Extension<SharedState>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::is_admin(&state, &headers) {
+        return Ok((StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Admin token required." }))));
+    }
+
+    let progress: GenesisUpgradeProgress = state.engine.genesis_upgrade_progress().await?;
+    Ok((StatusCode::OK, Json(serde_json::json!(progress))))
+}
@@ -0,0 +1,184 @@
+// crates/invariant_server/src/handlers/webhooks.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1)
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use axum::{Extension, Json, http::{StatusCode, HeaderMap}, extract::{Path, Query}};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+use invariant_shared::{WebhookEndpointRequest, WebhookDeliveryStatus};
+use crate::state::SharedState;
+use crate::error_response::AppError;
+use crate::services::webhooks;
+
+const DEFAULT_DELIVERY_PAGE: u32 = 100;
+const MAX_DELIVERY_PAGE: u32 = 1000;
+
+#[derive(Deserialize, IntoParams)]
+pub struct DeliveryQuery {
+    /// Only deliveries in this state (e.g. `dead` for the dead-letter queue).
+    pub status: Option<WebhookDeliveryStatus>,
+    pub limit: Option<u32>,
+}
+
+fn admin_required() -> (StatusCode, Json<serde_json::Value>) {
+    (StatusCode::UNAUTHORIZED, Json(serde_json::json!({ "error": "Admin token required." })))
+}
+
+/// POST /admin/partners/:partner_id/webhooks
+/// Registers an endpoint and its event filter. The signing secret is only returned here.
+/// Requires `Authorization: Bearer <INVARIANT_ADMIN_TOKEN>`.
+#[utoipa::path(
+    post,
+    path = "/admin/partners/{partner_id}/webhooks",
+    params(("partner_id" = String, Path, description = "Partner ID")),
+    request_body = WebhookEndpointRequest,
+    responses(
+        (status = 201, description = "Endpoint Registered (includes the signing secret)", body = WebhookEndpoint),
+        (status = 400, description = "Invalid URL or Event Filter"),
+        (status = 401, description = "Admin Token Required")
+    )
+)]
+pub async fn register_webhook_handler(
+    Path(partner_id): Path<String>,
+    Extension(state): Extension<SharedState>,
+    headers: HeaderMap,
+    Json(payload): Json<WebhookEndpointRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::is_admin(&state, &headers) {
+        return Ok(admin_required());
+    }
+    if let Err(e) = webhooks::validate_endpoint_url(&payload.url) {
+        return Ok((StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e }))));
+    }
+    let mut events = payload.events;
+    events.dedup();
+    if events.is_empty() {
+        return Ok((StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": "At least one event is required." }))));
+    }
+
+    let secret = webhooks::generate_secret();
+    let endpoint = state.engine.get_storage().create_webhook_endpoint(&partner_id, &payload.url, &events, &secret).await?;
+    tracing::info!(event = "webhook_registered", partner_id = %partner_id, endpoint_id = %endpoint.id, "📬 Webhook Endpoint Registered");
+
+    let mut body = serde_json::json!(endpoint);
+    body["secret"] = serde_json::json!(secret);
+    Ok((StatusCode::CREATED, Json(body)))
+}
+
+/// GET /admin/partners/:partner_id/webhooks
+#[utoipa::path(
+    get,
+    path = "/admin/partners/{partner_id}/webhooks",
+    params(("partner_id" = String, Path, description = "Partner ID")),
+    responses(
+        (status = 200, description = "Registered Endpoints", body = [WebhookEndpoint]),
+        (status = 401, description = "Admin Token Required")
+    )
+)]
+pub async fn list_webhooks_handler(
+    Path(partner_id): Path<String>,
+    Extension(state): Extension<SharedState>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::is_admin(&state, &headers) {
+        return Ok(admin_required());
+    }
+
+    let endpoints = state.engine.get_storage().get_webhook_endpoints(&partner_id).await?;
+    Ok((StatusCode::OK, Json(serde_json::json!(endpoints))))
+}
+
+/// DELETE /admin/partners/:partner_id/webhooks/:endpoint_id
+/// Deactivates an endpoint; its pending deliveries are dead-lettered.
+#[utoipa::path(
+    delete,
+    path = "/admin/partners/{partner_id}/webhooks/{endpoint_id}",
+    params(
+        ("partner_id" = String, Path, description = "Partner ID"),
+        ("endpoint_id" = Uuid, Path, description = "Endpoint ID")
+    ),
+    responses(
+        (status = 200, description = "Endpoint Deactivated"),
+        (status = 401, description = "Admin Token Required"),
+        (status = 404, description = "No Active Endpoint")
+    )
+)]
+pub async fn delete_webhook_handler(
+    Path((partner_id, endpoint_id)): Path<(String, Uuid)>,
+    Extension(state): Extension<SharedState>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::is_admin(&state, &headers) {
+        return Ok(admin_required());
+    }
+
+    if !state.engine.get_storage().deactivate_webhook_endpoint(&partner_id, &endpoint_id).await? {
+        return Ok((StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": "No active endpoint." }))));
+    }
+    tracing::info!(event = "webhook_deactivated", partner_id = %partner_id, endpoint_id = %endpoint_id, "📭 Webhook Endpoint Deactivated");
+    Ok((StatusCode::OK, Json(serde_json::json!({ "status": "deactivated" }))))
+}
+
+/// GET /admin/partners/:partner_id/webhooks/deliveries?status=&limit=
+/// Delivery log, newest first. `status=dead` is the dead-letter queue.
+#[utoipa::path(
+    get,
+    path = "/admin/partners/{partner_id}/webhooks/deliveries",
+    params(("partner_id" = String, Path, description = "Partner ID"), DeliveryQuery),
+    responses(
+        (status = 200, description = "Deliveries", body = [WebhookDelivery]),
+        (status = 401, description = "Admin Token Required")
+    )
+)]
+pub async fn list_deliveries_handler(
+    Path(partner_id): Path<String>,
+    Query(query): Query<DeliveryQuery>,
+    Extension(state): Extension<SharedState>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::is_admin(&state, &headers) {
+        return Ok(admin_required());
+    }
+
+    let limit = query.limit.unwrap_or(DEFAULT_DELIVERY_PAGE).min(MAX_DELIVERY_PAGE);
+    let deliveries = state.engine.get_storage().get_webhook_deliveries(&partner_id, query.status, limit).await?;
+    Ok((StatusCode::OK, Json(serde_json::json!(deliveries))))
+}
+
+/// POST /admin/partners/:partner_id/webhooks/deliveries/:delivery_id/replay
+/// Sends a delivered or dead-lettered delivery again (same event ID, fresh retry budget).
+#[utoipa::path(
+    post,
+    path = "/admin/partners/{partner_id}/webhooks/deliveries/{delivery_id}/replay",
+    params(
+        ("partner_id" = String, Path, description = "Partner ID"),
+        ("delivery_id" = Uuid, Path, description = "Delivery ID")
+    ),
+    responses(
+        (status = 200, description = "Delivery Queued Again", body = WebhookDelivery),
+        (status = 401, description = "Admin Token Required"),
+        (status = 404, description = "No Replayable Delivery (unknown, still pending, or endpoint inactive)")
+    )
+)]
+pub async fn replay_delivery_handler(
+    Path((partner_id, delivery_id)): Path<(String, Uuid)>,
+    Extension(state): Extension<SharedState>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if !super::is_admin(&state, &headers) {
+        return Ok(admin_required());
+    }
+
+    match state.engine.get_storage().replay_webhook_delivery(&partner_id, &delivery_id).await? {
+        Some(delivery) => {
+            tracing::info!(event = "webhook_replayed", partner_id = %partner_id, delivery_id = %delivery_id, "🔁 Webhook Delivery Replayed");
+            Ok((StatusCode::OK, Json(serde_json::json!(delivery))))
+        }
+        None => Ok((StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": "No replayable delivery." })))),
+    }
+}
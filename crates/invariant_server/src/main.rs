@@ -12,7 +12,7 @@ mod state;
 mod handlers;
 mod error_response; 
 mod api_docs;      
-mod services { pub mod push; }
+mod services { pub mod push; pub mod receipts; pub mod tokens; pub mod events; pub mod webhooks; }
 
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -20,13 +20,21 @@ use std::time::Duration;
 use sqlx::postgres::PgPoolOptions;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-// 🛡️ Added IdentityStorage to scope for run_reaper / get_late_fcm_tokens
-use invariant_engine::{InvariantEngine, IdentityStorage, core::EngineConfig};
-use invariant_shared::Network;
+// 🛡️ Added IdentityStorage to scope for get_late_fcm_tokens
+use invariant_engine::{InvariantEngine, IdentityStorage, ProtocolParameters, TrustScorer, TrustWeights, DeviceCatalog, EligibilityPolicy, core::EngineConfig};
+use invariant_engine::privacy_pass::PrivacyPassIssuer;
+use invariant_shared::{Network, SybilConfidence};
 use crate::db::PostgresStorage;
 use crate::impls::RedisNonceManager; 
 use crate::state::AppState;
 
+/// The worker ticks every 15 minutes; the Sybil scan runs every 4th tick (hourly).
+const SYBIL_SCAN_EVERY_TICKS: u64 = 4;
+/// Identities whose genesis eligibility is re-evaluated per worker tick.
+const ELIGIBILITY_BATCH: u32 = 5000;
+/// Webhooks run on their own, faster loop: the first retry is due 30 seconds after a failure.
+const WEBHOOK_POLL_SECONDS: u64 = 10;
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenvy::dotenv().ok();
@@ -81,34 +89,112 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .parse::<u16>()
         .expect("Invalid GENESIS_VERSION");
 
+    let params = match std::env::var("INVARIANT_PROTOCOL_POLICY_PATH") {
+        Ok(path) => {
+            let policy = std::fs::read_to_string(&path).expect("Failed to read protocol policy");
+            ProtocolParameters::from_policy(&policy, &network).expect("Invalid protocol policy")
+        }
+        Err(_) => ProtocolParameters::for_network(&network),
+    };
+    if params.min_genesis_version > genesis_version {
+        panic!(
+            "min_genesis_version ({}) is above INVARIANT_GENESIS_VERSION ({}): no identity could upgrade",
+            params.min_genesis_version, genesis_version
+        );
+    }
+
     tracing::info!(
         event = "startup",
         network = ?network,
         version = genesis_version,
+        heartbeat_interval_minutes = params.heartbeat_interval_minutes,
         "🚀 Booting Invariant Node"
     );
 
     // 6. Initialize Engine & State
-    let storage = PostgresStorage::new(pool.clone());
-    let engine_config = EngineConfig { network, genesis_version };
+    let tokens = services::tokens::TokenIssuer::from_env(&network.to_string());
+    let pairwise_secret = match std::env::var("INVARIANT_PAIRWISE_SECRET") {
+        Ok(hex_secret) => services::receipts::parse_seed("INVARIANT_PAIRWISE_SECRET", &hex_secret),
+        Err(_) => {
+            tracing::warn!("⚠️ INVARIANT_PAIRWISE_SECRET not set. New partner links will use an EPHEMERAL secret.");
+            services::receipts::ephemeral_seed()
+        }
+    };
+    let privacy_pass = match std::env::var("INVARIANT_PRIVACY_PASS_KEY_PATH") {
+        Ok(path) => {
+            let pem = std::fs::read_to_string(&path).expect("Failed to read Privacy Pass key");
+            PrivacyPassIssuer::from_pem(&pem).expect("Invalid Privacy Pass key")
+        }
+        Err(_) => {
+            tracing::warn!("⚠️ INVARIANT_PRIVACY_PASS_KEY_PATH not set. Using an EPHEMERAL Privacy Pass key.");
+            PrivacyPassIssuer::generate().expect("Privacy Pass keygen failed")
+        }
+    };
+    let admin_token = std::env::var("INVARIANT_ADMIN_TOKEN").ok().filter(|t| !t.is_empty());
+    if admin_token.is_none() {
+        tracing::warn!("⚠️ INVARIANT_ADMIN_TOKEN not set. Admin endpoints are disabled.");
+    }
+    let trust_weights = match std::env::var("INVARIANT_TRUST_WEIGHTS_PATH") {
+        Ok(path) if !path.is_empty() => {
+            let json = std::fs::read_to_string(&path).expect("Failed to read trust weights");
+            TrustWeights::from_json(&json).expect("Invalid trust weights")
+        }
+        _ => TrustWeights::default(),
+    };
+    let device_catalog = match std::env::var("INVARIANT_DEVICE_CATALOG_PATH") {
+        Ok(path) if !path.is_empty() => {
+            let json = std::fs::read_to_string(&path).expect("Failed to read device catalog");
+            DeviceCatalog::from_json(&json).expect("Invalid device catalog")
+        }
+        _ => DeviceCatalog::default(),
+    };
+    let eligibility_policy = match std::env::var("INVARIANT_ELIGIBILITY_POLICY_PATH") {
+        Ok(path) if !path.is_empty() => {
+            let json = std::fs::read_to_string(&path).expect("Failed to read eligibility policy");
+            EligibilityPolicy::from_json(&json).expect("Invalid eligibility policy")
+        }
+        _ => EligibilityPolicy::default(),
+    };
+    let storage = PostgresStorage::new(pool.clone(), params.clone());
+    let engine_config = EngineConfig { network, genesis_version, params: params.clone() };
     
     // 🛡️ INJECT BOTH STORAGES
-    let engine = InvariantEngine::new(storage, nonce_manager, engine_config);
+    let engine = InvariantEngine::new(storage, nonce_manager, engine_config)
+        .with_trust_scorer(TrustScorer::new(trust_weights, device_catalog))
+        .with_eligibility_policy(eligibility_policy);
+
+    // Domain events (genesis, heartbeats, trust decay) for downstream systems
+    let event_sink = services::events::EventSinkKind::from_env();
+    tracing::info!(event = "event_sink", sink = ?event_sink, "📣 Domain events configured");
+    let engine = match event_sink {
+        services::events::EventSinkKind::Outbox => engine.with_event_sink(services::events::PostgresOutbox { pool: pool.clone() }),
+        services::events::EventSinkKind::RedisStream => engine.with_event_sink(services::events::RedisStreamSink { client: redis_client.clone() }),
+        services::events::EventSinkKind::Disabled => engine,
+    };
     
     let state = Arc::new(AppState { 
         engine,
         redis: redis_client,
+        receipts: services::receipts::ReceiptSigner::from_env(),
+        tokens,
+        pairwise_secret,
+        privacy_pass,
+        admin_token,
     });
 
-    // 7. Background Worker (Reaper + Wake Up Call)
-    let worker_storage = PostgresStorage::new(pool.clone());
+    // 7. Background Worker (Reaper + Wake Up Call + Dead Man's Switch + Sybil Scan + Eligibility)
+    let worker_storage = PostgresStorage::new(pool.clone(), params.clone());
+    let worker_state = state.clone();
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(900)); 
+        let mut ticks: u64 = 0;
+        let mut dropped_events: u64 = 0;
         loop {
             interval.tick().await;
+            ticks += 1;
             
             // A. Wake Up Call
-            match worker_storage.get_late_fcm_tokens(24 * 60).await {
+            match worker_storage.get_late_fcm_tokens(params.wake_up_after_minutes, worker_state.engine.now()).await {
                 Ok(tokens) => {
                     if !tokens.is_empty() {
                         tracing::info!("🔔 Waking up {} late nodes...", tokens.len());
@@ -125,9 +211,74 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
 
             // B. Reaper
-            if let Err(e) = worker_storage.run_reaper().await {
+            if let Err(e) = worker_state.engine.reap_inactive().await {
                 tracing::error!("Reaper failed: {}", e);
             }
+
+            // C. Dead Man's Switch
+            match worker_state.engine.sweep_switches().await {
+                Ok(sweep) => {
+                    if sweep.cancelled > 0 {
+                        tracing::info!("🪦 {} pending switches cancelled by a heartbeat", sweep.cancelled);
+                    }
+                    let alerts = sweep.warned.into_iter().map(|a| (a, false))
+                        .chain(sweep.triggered.into_iter().map(|a| (a, true)));
+                    for (alert, triggered) in alerts {
+                        tracing::warn!(
+                            event = if triggered { "switch_triggered" } else { "switch_pending" },
+                            identity_id = %alert.holder_id,
+                            recipient_id = %alert.recipient_id,
+                            "🪦 Dead Man's Switch"
+                        );
+                        if let Some(token) = alert.push_token {
+                            tokio::spawn(async move {
+                                let _ = services::push::send_switch_alert(&token, &alert.holder_id, triggered).await;
+                            });
+                        }
+                    }
+                }
+                Err(e) => tracing::error!("Switch sweep failed: {}", e),
+            }
+
+            // D. Sybil Scan (hourly; the first tick runs at startup)
+            if ticks % SYBIL_SCAN_EVERY_TICKS == 1 {
+                match worker_state.engine.detect_sybil_clusters().await {
+                    Ok(clusters) => {
+                        let high = clusters.iter().filter(|c| c.confidence == SybilConfidence::High).count();
+                        tracing::info!(event = "sybil_scan", clusters = clusters.len(), high_confidence = high, "🕸️ Sybil scan complete");
+                    }
+                    Err(e) => tracing::error!("Sybil scan failed: {}", e),
+                }
+            }
+
+            // E. Genesis Eligibility (after the reaper and Sybil scan so their changes count)
+            match worker_state.engine.refresh_eligibility(ELIGIBILITY_BATCH).await {
+                Ok(sweep) if sweep.evaluated > 0 => {
+                    tracing::info!(event = "eligibility_refresh", evaluated = sweep.evaluated, eligible = sweep.eligible, "🌱 Genesis eligibility refreshed");
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Eligibility refresh failed: {}", e),
+            }
+
+            // F. Domain events the sink refused since the last tick
+            let dropped = worker_state.engine.dropped_events();
+            if dropped > dropped_events {
+                tracing::warn!(event = "events_dropped", dropped = dropped - dropped_events, total = dropped, "📣 Domain events lost to sink failures");
+                dropped_events = dropped;
+            }
+        }
+    });
+
+    // 7b. Partner Webhooks (fan-out + signed delivery with backoff)
+    let webhook_storage = PostgresStorage::new(pool.clone(), params.clone());
+    tokio::spawn(async move {
+        let dispatcher = services::webhooks::WebhookDispatcher::new();
+        let mut interval = tokio::time::interval(Duration::from_secs(WEBHOOK_POLL_SECONDS));
+        loop {
+            interval.tick().await;
+            if let Err(e) = dispatcher.run_once(&webhook_storage).await {
+                tracing::error!("Webhook dispatch failed: {}", e);
+            }
         }
     });
 
@@ -0,0 +1,106 @@
+// crates/invariant_server/src/services/events.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1) 
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use async_trait::async_trait;
+use invariant_engine::{EngineError, EventSink};
+use invariant_shared::DomainEvent;
+use sqlx::PgPool;
+use tracing::warn;
+
+/// Redis stream the `RedisStreamSink` appends to.
+pub const EVENT_STREAM_KEY: &str = "invariant:events";
+/// Approximate cap on the stream length (`XADD MAXLEN ~`).
+const EVENT_STREAM_MAXLEN: u64 = 100_000;
+
+/// Where engine domain events go, from `INVARIANT_EVENT_SINK`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventSinkKind {
+    /// `event_outbox` table (default).
+    Outbox,
+    /// `XADD` to the `invariant:events` stream.
+    RedisStream,
+    /// Events are dropped.
+    Disabled,
+}
+
+impl EventSinkKind {
+    pub fn from_env() -> Self {
+        match std::env::var("INVARIANT_EVENT_SINK").unwrap_or_default().to_lowercase().as_str() {
+            "" | "outbox" => EventSinkKind::Outbox,
+            "redis" => EventSinkKind::RedisStream,
+            "none" => EventSinkKind::Disabled,
+            other => panic!("Invalid INVARIANT_EVENT_SINK '{}' (expected outbox, redis or none)", other),
+        }
+    }
+}
+
+/// 📮 Postgres outbox. Each event becomes an `event_outbox` row; the insert trigger
+/// NOTIFYs `invariant_events` so relays wake up without polling.
+/// The row is inserted after the change's own transaction has committed, so delivery is
+/// best-effort like every `EventSink`: a failed insert is logged here, counted by the
+/// engine and the event is lost. Webhooks do not depend on it (they are fanned out from
+/// `identity_transitions`).
+pub struct PostgresOutbox {
+    pub pool: PgPool,
+}
+
+#[async_trait]
+impl EventSink for PostgresOutbox {
+    async fn publish(&self, event: &DomainEvent) -> Result<(), EngineError> {
+        let payload = serde_json::to_string(event).map_err(|e| EngineError::Storage(e.to_string()))?;
+        sqlx::query(
+            "INSERT INTO event_outbox (event_id, event_type, identity_id, payload, occurred_at)
+             VALUES ($1, $2, $3, $4::jsonb, $5)
+             ON CONFLICT (event_id) DO NOTHING"
+        )
+        .bind(event.id)
+        .bind(event.kind.tag())
+        .bind(event.identity_id)
+        .bind(payload)
+        .bind(event.occurred_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            warn!(event_id = %event.id, event_type = event.kind.tag(), "⚠️ Outbox insert failed: {}", e);
+            EngineError::Storage(e.to_string())
+        })?;
+        Ok(())
+    }
+}
+
+/// 🌊 Redis Streams. Consumers read `invariant:events` with `XREAD BLOCK` or a consumer group.
+/// Entries carry `type`, `identity_id` and the JSON `event`.
+pub struct RedisStreamSink {
+    pub client: redis::Client,
+}
+
+#[async_trait]
+impl EventSink for RedisStreamSink {
+    async fn publish(&self, event: &DomainEvent) -> Result<(), EngineError> {
+        let result: Result<(), String> = async {
+            let payload = serde_json::to_string(event).map_err(|e| e.to_string())?;
+            let mut conn = self.client.get_multiplexed_async_connection().await.map_err(|e| e.to_string())?;
+            let _: String = redis::cmd("XADD")
+                .arg(EVENT_STREAM_KEY)
+                .arg("MAXLEN").arg("~").arg(EVENT_STREAM_MAXLEN)
+                .arg("*")
+                .arg("type").arg(event.kind.tag())
+                .arg("identity_id").arg(event.identity_id.to_string())
+                .arg("event").arg(payload)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(())
+        }.await;
+
+        result.map_err(|e| {
+            warn!(event_id = %event.id, event_type = event.kind.tag(), "⚠️ XADD failed: {}", e);
+            EngineError::Storage(format!("Redis XADD failed: {}", e))
+        })
+    }
+}
@@ -36,9 +36,37 @@ pub async fn initialize() -> Result<(), String> {
     Ok(())
 }
 
-/// Sends a Data-Only, High-Priority message via FCM HTTP v1 API.
+/// Sends the daily "anchor decay" wake-up to a late device.
 #[instrument(skip(fcm_token))]
 pub async fn send_wake_up_call(fcm_token: &str) -> Result<(), String> {
+    send_data_message(fcm_token, json!({
+        "type": "wake_up_call",
+        "reason": "anchor_decay",
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "action": "VERIFY_NOW"
+    })).await
+}
+
+/// Dead man's switch notices.
+/// `switch_pending` goes to the holder (last chance to cancel),
+/// `switch_triggered` goes to the beneficiary.
+#[instrument(skip(fcm_token))]
+pub async fn send_switch_alert(fcm_token: &str, holder_id: &uuid::Uuid, triggered: bool) -> Result<(), String> {
+    let (kind, action) = if triggered {
+        ("switch_triggered", "OPEN_SWITCH")
+    } else {
+        ("switch_pending", "VERIFY_NOW")
+    };
+    send_data_message(fcm_token, json!({
+        "type": kind,
+        "identity_id": holder_id.to_string(),
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "action": action
+    })).await
+}
+
+/// Sends a Data-Only, High-Priority message via FCM HTTP v1 API.
+async fn send_data_message(fcm_token: &str, data: serde_json::Value) -> Result<(), String> {
     let project_id = env::var("FIREBASE_PROJECT_ID")
         .expect("FIREBASE_PROJECT_ID must be set");
 
@@ -57,12 +85,7 @@ pub async fn send_wake_up_call(fcm_token: &str) -> Result<(), String> {
             "android": {
                 "priority": "HIGH" // Critical for Doze mode breakthrough
             },
-            "data": {
-                "type": "wake_up_call",
-                "reason": "anchor_decay",
-                "timestamp": chrono::Utc::now().to_rfc3339(),
-                "action": "VERIFY_NOW"
-            }
+            "data": data
         }
     });
 
@@ -79,11 +102,11 @@ pub async fn send_wake_up_call(fcm_token: &str) -> Result<(), String> {
         .map_err(|e| e.to_string())?;
 
     if res.status().is_success() {
-        info!("🔔 Wake-Up Signal sent to device");
+        info!("🔔 Push sent to device");
         Ok(())
     } else {
         let err_body = res.text().await.unwrap_or_default();
         error!("❌ FCM Error: {}", err_body);
         Err(format!("FCM Failure: {}", err_body))
     }
-}
\ No newline at end of file
+}
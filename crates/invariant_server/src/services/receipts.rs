@@ -0,0 +1,85 @@
+// crates/invariant_server/src/services/receipts.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1) 
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use invariant_shared::{Receipt, SignedReceipt, TreeHead, SignedTreeHead, ActionVerdict, SignedActionVerdict};
+use invariant_shared::signing;
+use rand::RngCore;
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use sha2::{Sha256, Digest};
+use tracing::{info, warn};
+
+/// The node's Ed25519 receipt key.
+/// Partners verify receipts offline with `invariant_shared::verify_receipt`.
+pub struct ReceiptSigner {
+    key_pair: Ed25519KeyPair,
+    key_id: String,
+}
+
+impl ReceiptSigner {
+    /// Loads the 32-byte seed from `INVARIANT_RECEIPT_SEED` (hex).
+    /// Without it an ephemeral key is generated, which invalidates receipts on every restart.
+    pub fn from_env() -> Self {
+        let seed = match std::env::var("INVARIANT_RECEIPT_SEED") {
+            Ok(hex_seed) => parse_seed("INVARIANT_RECEIPT_SEED", &hex_seed),
+            Err(_) => {
+                warn!("⚠️ INVARIANT_RECEIPT_SEED not set. Using an EPHEMERAL receipt key.");
+                ephemeral_seed()
+            }
+        };
+
+        let signer = Self::from_seed(&seed);
+        info!(key_id = %signer.key_id, "🧾 Receipt Signer Ready");
+        signer
+    }
+
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(seed).expect("Invalid Ed25519 seed");
+        let key_id = key_id(key_pair.public_key().as_ref());
+        Self { key_pair, key_id }
+    }
+
+    pub fn key_id(&self) -> &str { &self.key_id }
+
+    /// Raw 32-byte Ed25519 public key.
+    pub fn public_key(&self) -> &[u8] { self.key_pair.public_key().as_ref() }
+
+    /// Signs the receipt as given. Callers stamp `issued_at` from the engine clock.
+    pub fn issue(&self, receipt: Receipt) -> SignedReceipt {
+        let signature = self.key_pair.sign(&receipt.signing_payload()).as_ref().to_vec();
+        SignedReceipt { receipt, key_id: self.key_id.clone(), signature }
+    }
+
+    /// Transparency log heads are signed with the same node key.
+    pub fn sign_tree_head(&self, tree_head: TreeHead) -> SignedTreeHead {
+        let signature = self.key_pair.sign(&signing::tree_head_payload(&tree_head)).as_ref().to_vec();
+        SignedTreeHead { tree_head, key_id: self.key_id.clone(), signature }
+    }
+
+    /// Action approvals are signed with the same node key.
+    pub fn sign_action_verdict(&self, verdict: ActionVerdict) -> SignedActionVerdict {
+        let signature = self.key_pair.sign(&signing::action_verdict_payload(&verdict)).as_ref().to_vec();
+        SignedActionVerdict { verdict, key_id: self.key_id.clone(), signature }
+    }
+}
+
+/// Decodes a hex Ed25519 seed from config. Panics at boot on bad input (fail fast).
+pub fn parse_seed(var: &str, hex_seed: &str) -> [u8; 32] {
+    let bytes = hex::decode(hex_seed.trim()).unwrap_or_else(|_| panic!("{} must be hex", var));
+    <[u8; 32]>::try_from(bytes.as_slice()).unwrap_or_else(|_| panic!("{} must be 32 bytes", var))
+}
+
+pub fn ephemeral_seed() -> [u8; 32] {
+    let mut seed = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut seed);
+    seed
+}
+
+/// Short, stable key identifier: first 8 bytes of SHA-256(public key), hex.
+pub fn key_id(public_key: &[u8]) -> String {
+    hex::encode(&Sha256::digest(public_key)[..8])
+}
@@ -0,0 +1,98 @@
+// crates/invariant_server/src/services/tokens.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1) 
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use chrono::{DateTime, Utc, Duration};
+use invariant_shared::{Identity, Jwk, JwkSet, ProofTokenClaims};
+use invariant_shared::token::{self, ProofTokenHeader, TOKEN_ALGORITHM};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use tracing::{info, warn};
+use uuid::Uuid;
+use super::receipts::{parse_seed, ephemeral_seed, key_id};
+
+/// Proof tokens are decisions, not sessions: keep them short.
+pub const TOKEN_TTL_SECONDS: i64 = 300;
+
+struct SigningKey {
+    kid: String,
+    key_pair: Ed25519KeyPair,
+}
+
+/// Issues Invariant proof tokens (EdDSA JWTs) and publishes the JWKS.
+///
+/// Rotation: `INVARIANT_TOKEN_SEEDS` is a comma-separated list of hex seeds.
+/// The FIRST seed signs; the rest are retired keys that stay in the JWKS so
+/// tokens issued before the rotation still verify until they expire.
+pub struct TokenIssuer {
+    issuer: String,
+    keys: Vec<SigningKey>,
+}
+
+impl TokenIssuer {
+    pub fn from_env(network: &str) -> Self {
+        let seeds: Vec<[u8; 32]> = match std::env::var("INVARIANT_TOKEN_SEEDS") {
+            Ok(list) => list.split(',')
+                .filter(|s| !s.trim().is_empty())
+                .map(|s| parse_seed("INVARIANT_TOKEN_SEEDS", s))
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        let seeds = if seeds.is_empty() {
+            warn!("⚠️ INVARIANT_TOKEN_SEEDS not set. Using an EPHEMERAL token key.");
+            vec![ephemeral_seed()]
+        } else {
+            seeds
+        };
+
+        let issuer = Self::from_seeds(format!("invariant:{}", network), &seeds);
+        info!(kid = %issuer.keys[0].kid, published = issuer.keys.len(), "🎫 Token Issuer Ready");
+        issuer
+    }
+
+    pub fn from_seeds(issuer: String, seeds: &[[u8; 32]]) -> Self {
+        assert!(!seeds.is_empty(), "TokenIssuer needs at least one key");
+        let keys = seeds.iter().map(|seed| {
+            let key_pair = Ed25519KeyPair::from_seed_unchecked(seed).expect("Invalid Ed25519 seed");
+            SigningKey { kid: key_id(key_pair.public_key().as_ref()), key_pair }
+        }).collect();
+        Self { issuer, keys }
+    }
+
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self.keys.iter().map(|k| Jwk::ed25519(&k.kid, k.key_pair.public_key().as_ref())).collect(),
+        }
+    }
+
+    /// Mints a token for `audience` describing `identity` as of `now`.
+    /// `subject` is the pairwise ID the audience knows the identity by.
+    pub fn issue(&self, identity: &Identity, subject: Uuid, tier: &str, audience: &str, now: DateTime<Utc>) -> (String, ProofTokenClaims) {
+        let active = &self.keys[0];
+        let header = ProofTokenHeader {
+            alg: TOKEN_ALGORITHM.into(),
+            typ: "JWT".into(),
+            kid: active.kid.clone(),
+        };
+        let claims = ProofTokenClaims {
+            iss: self.issuer.clone(),
+            sub: subject,
+            aud: audience.to_string(),
+            iat: now.timestamp(),
+            exp: (now + Duration::seconds(TOKEN_TTL_SECONDS)).timestamp(),
+            jti: Uuid::new_v4(),
+            status: identity.status.clone(),
+            tier: tier.to_string(),
+            streak: identity.streak,
+            last_attestation: identity.last_attestation.timestamp(),
+        };
+
+        let input = token::signing_input(&header, &claims);
+        let signature = active.key_pair.sign(input.as_bytes());
+        (token::assemble(&input, signature.as_ref()), claims)
+    }
+}
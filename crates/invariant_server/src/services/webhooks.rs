@@ -0,0 +1,145 @@
+// crates/invariant_server/src/services/webhooks.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ *
+ * This source code is licensed under the Business Source License (BSL 1.1) 
+ * found in the LICENSE.md file in the root directory of this source tree.
+ */
+
+use std::time::Duration as StdDuration;
+use chrono::{DateTime, Duration, Utc};
+use invariant_engine::EngineError;
+use invariant_shared::webhook::{WEBHOOK_SIGNATURE_HEADER, WEBHOOK_TIMESTAMP_HEADER};
+use invariant_shared::webhook_signature;
+use rand::RngCore;
+use reqwest::Client;
+use tracing::{info, warn};
+use crate::db::{DueWebhookDelivery, PostgresStorage};
+
+/// Attempts before a delivery is dead-lettered (~4h of retries).
+pub const MAX_ATTEMPTS: u32 = 10;
+/// First retry delay; doubles on every failure.
+const BASE_RETRY_SECONDS: i64 = 30;
+const MAX_RETRY_SECONDS: i64 = 6 * 3600;
+/// Per-request timeout. Partners should acknowledge and process asynchronously.
+const DELIVERY_TIMEOUT_SECONDS: u64 = 10;
+/// How long a claimed delivery is hidden from other dispatchers.
+const CLAIM_LEASE_SECONDS: i64 = 60;
+const CLAIM_BATCH: i64 = 100;
+const FANOUT_BATCH: i64 = 500;
+/// Kept in `last_error`.
+const MAX_ERROR_LEN: usize = 500;
+
+/// Delay before the next attempt once `attempts` have failed (30s, 1m, 2m, ... capped at 6h).
+pub fn retry_delay(attempts: u32) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(20);
+    Duration::seconds((BASE_RETRY_SECONDS << exponent).min(MAX_RETRY_SECONDS))
+}
+
+/// Next attempt after a failure, or None when the delivery is out of retries.
+pub fn next_attempt_at(attempts: u32, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    (attempts < MAX_ATTEMPTS).then(|| now + retry_delay(attempts))
+}
+
+/// Fresh endpoint signing secret, shown to the partner once.
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("whsec_{}", hex::encode(bytes))
+}
+
+/// Endpoints must be HTTPS. Plain HTTP is only accepted for loopback (local stand-ins).
+pub fn validate_endpoint_url(url: &str) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+    let loopback = matches!(parsed.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+    match parsed.scheme() {
+        "https" => Ok(()),
+        "http" if loopback => Ok(()),
+        _ => Err("Webhook URL must use https".into()),
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DispatchStats {
+    pub queued: u64,
+    pub delivered: u64,
+    pub failed: u64,
+    pub dead: u64,
+}
+
+/// 📬 Partner webhook dispatcher: turns status transitions into deliveries, then sends
+/// due deliveries with an HMAC signature and exponential backoff.
+pub struct WebhookDispatcher {
+    client: Client,
+}
+
+impl Default for WebhookDispatcher {
+    fn default() -> Self { Self::new() }
+}
+
+impl WebhookDispatcher {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .timeout(StdDuration::from_secs(DELIVERY_TIMEOUT_SECONDS))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to build webhook HTTP client");
+        Self { client }
+    }
+
+    pub async fn run_once(&self, storage: &PostgresStorage) -> Result<DispatchStats, EngineError> {
+        let mut stats = DispatchStats { queued: storage.enqueue_webhook_deliveries(FANOUT_BATCH).await?, ..Default::default() };
+
+        let due = storage.claim_due_webhook_deliveries(CLAIM_BATCH, CLAIM_LEASE_SECONDS).await?;
+        let results = futures::future::join_all(due.iter().map(|d| self.send(d, Utc::now()))).await;
+
+        for (delivery, result) in due.iter().zip(results) {
+            match result {
+                Ok(code) => {
+                    storage.mark_webhook_delivered(&delivery.id, code).await?;
+                    stats.delivered += 1;
+                }
+                Err((code, error)) => {
+                    let attempts = delivery.attempts + 1;
+                    let retry_at = next_attempt_at(attempts, Utc::now());
+                    let error: String = error.chars().take(MAX_ERROR_LEN).collect();
+                    storage.mark_webhook_failed(&delivery.id, code, &error, retry_at).await?;
+                    match retry_at {
+                        Some(_) => stats.failed += 1,
+                        None => {
+                            warn!(event = "webhook_dead", delivery_id = %delivery.id, attempts, "📭 Webhook delivery dead-lettered: {}", error);
+                            stats.dead += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        if stats.queued + stats.delivered + stats.failed + stats.dead > 0 {
+            info!(event = "webhook_dispatch", queued = stats.queued, delivered = stats.delivered, failed = stats.failed, dead = stats.dead, "📬 Webhooks dispatched");
+        }
+        Ok(stats)
+    }
+
+    /// One signed POST. Any 2xx is success; the status code (if any) is kept on failure.
+    async fn send(&self, delivery: &DueWebhookDelivery, now: DateTime<Utc>) -> Result<u16, (Option<u16>, String)> {
+        let timestamp = now.timestamp();
+        let signature = webhook_signature(delivery.secret.as_bytes(), timestamp, delivery.payload.as_bytes());
+
+        let response = self.client.post(&delivery.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(WEBHOOK_TIMESTAMP_HEADER, timestamp.to_string())
+            .header(WEBHOOK_SIGNATURE_HEADER, signature)
+            .body(delivery.payload.clone())
+            .send()
+            .await
+            .map_err(|e| (None, e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(status.as_u16())
+        } else {
+            Err((Some(status.as_u16()), format!("HTTP {}", status)))
+        }
+    }
+}
@@ -8,9 +8,12 @@
 
 use std::sync::Arc;
 use invariant_engine::InvariantEngine;
+use invariant_engine::privacy_pass::PrivacyPassIssuer;
 use crate::db::PostgresStorage;
 use crate::impls::RedisNonceManager; // 👈 NEW
 use redis::Client as RedisClient;
+use crate::services::receipts::ReceiptSigner;
+use crate::services::tokens::TokenIssuer;
 
 pub type SharedState = Arc<AppState>;
 
@@ -18,4 +21,11 @@ pub struct AppState {
     // 🛡️ Update Type Signature: Now accepts TWO generic implementations
     pub engine: InvariantEngine<PostgresStorage, RedisNonceManager>,
     pub redis: RedisClient,
+    pub receipts: ReceiptSigner,
+    pub tokens: TokenIssuer,
+    /// HMAC key for partner-scoped pairwise IDs.
+    pub pairwise_secret: [u8; 32],
+    pub privacy_pass: PrivacyPassIssuer,
+    /// Bearer token for `/admin/*`. None = admin API closed.
+    pub admin_token: Option<String>,
 }
\ No newline at end of file
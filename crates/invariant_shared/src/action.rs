@@ -0,0 +1,70 @@
+// crates/invariant_shared/src/action.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use ring::signature::{UnparsedPublicKey, ED25519};
+use crate::receipt::ReceiptVerdict;
+use crate::signing;
+
+/// Partner asks for a challenge bound to one action of one subject.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ActionChallengeRequest {
+    pub partner_id: String,
+    /// The partner's pairwise subject ID.
+    pub subject_id: Uuid,
+    /// SHA-256 of the partner's canonical description of the action (32 bytes).
+    pub payload_hash: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ActionChallenge {
+    /// Single-use; only valid for the subject and payload hash it was issued for.
+    pub nonce: Vec<u8>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// The device's approval, relayed by the partner.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ActionVerifyRequest {
+    pub partner_id: String,
+    pub subject_id: Uuid,
+    pub nonce: Vec<u8>,
+    pub payload_hash: Vec<u8>,
+    /// Signs: `crate::signing::action_payload(identity_id, nonce, payload_hash)`
+    pub signature: Vec<u8>,
+}
+
+/// Node decision on one action approval.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct ActionVerdict {
+    pub partner_id: String,
+    pub subject_id: Uuid,
+    pub payload_hash: Vec<u8>,
+    pub nonce: Vec<u8>,
+    pub verdict: ReceiptVerdict,
+    pub issued_at: DateTime<Utc>,
+}
+
+/// An action verdict plus the node's detached signature (same key as receipts).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SignedActionVerdict {
+    #[serde(flatten)]
+    pub verdict: ActionVerdict,
+    /// Identifies which node key signed (see `GET /receipts/key`).
+    pub key_id: String,
+    /// Ed25519 signature over `signing::action_verdict_payload(&verdict)`.
+    pub signature: Vec<u8>,
+}
+
+/// Offline verification of an action verdict against the node's Ed25519 public key (32 raw bytes).
+pub fn verify_action_verdict(signed: &SignedActionVerdict, node_public_key: &[u8]) -> bool {
+    UnparsedPublicKey::new(&ED25519, node_public_key)
+        .verify(&signing::action_verdict_payload(&signed.verdict), &signed.signature)
+        .is_ok()
+}
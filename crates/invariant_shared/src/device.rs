@@ -0,0 +1,92 @@
+// crates/invariant_shared/src/device.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use crate::identity::SecurityLevel;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DeviceStatus {
+    Active,
+    Revoked,
+}
+
+impl DeviceStatus {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            DeviceStatus::Active => "active",
+            DeviceStatus::Revoked => "revoked",
+        }
+    }
+}
+
+/// One attested hardware key of an identity (a phone, a tablet).
+/// The primary device is the one whose key is `Identity.public_key`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Device {
+    pub id: Uuid,
+    pub identity_id: Uuid,
+    pub public_key: Vec<u8>,
+    /// Holder-chosen name ("Pixel 8", "Tablet").
+    pub label: Option<String>,
+    pub status: DeviceStatus,
+    #[serde(default)]
+    pub primary: bool,
+
+    pub hardware_brand: Option<String>,
+    /// Hashed device model (raw models are never persisted).
+    pub hardware_device: Option<String>,
+    pub hardware_product: Option<String>,
+    #[serde(default)]
+    pub security_level: Option<SecurityLevel>,
+    #[serde(default)]
+    pub os_patch_level: Option<u32>,
+
+    pub added_at: DateTime<Utc>,
+    /// Trust timer of this key. A heartbeat from a device past the TTL needs a re-attestation.
+    pub last_attestation: DateTime<Utc>,
+    pub last_heartbeat: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Adds a device key to an existing identity.
+/// Authorized by a device that is already active on the identity.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeviceEnrollmentRequest {
+    pub identity_id: Uuid,
+
+    /// The NEW device's P-256 Public Key generated in StrongBox/TEE.
+    pub public_key: Vec<u8>,
+
+    /// Android KeyStore Attestation Certificate Chain for the NEW key.
+    pub attestation_chain: Vec<Vec<u8>>,
+
+    /// The cryptographic nonce (challenge) issued by the server.
+    /// Must be embedded in the new key's attestation AND covered by the authorization.
+    pub nonce: Vec<u8>,
+
+    #[serde(default)]
+    pub label: Option<String>,
+
+    /// Signature by an active device of the identity.
+    /// Signs: `crate::signing::device_enrollment_payload(identity_id, public_key, nonce)`
+    pub authorization_signature: Vec<u8>,
+}
+
+/// Removes a device key from an identity ("I lost my tablet").
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeviceRevocationRequest {
+    pub identity_id: Uuid,
+    pub device_id: Uuid,
+    /// The cryptographic nonce (challenge) issued by the server.
+    pub nonce: Vec<u8>,
+    /// Signature by any active device of the identity, including the one being removed.
+    /// Signs: `crate::signing::device_revocation_payload(identity_id, device_id, nonce)`
+    pub signature: Vec<u8>,
+}
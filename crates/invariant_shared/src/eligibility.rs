@@ -0,0 +1,49 @@
+// crates/invariant_shared/src/eligibility.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+
+/// One condition of genesis eligibility.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EligibilityRule {
+    /// The identity is `active` (not stale, dormant or revoked).
+    Active,
+    MinStreak,
+    MinContinuityScore,
+    /// Key is StrongBox-backed.
+    StrongBox,
+    /// Not in a suspected Sybil cluster.
+    NoSybilFlag,
+    MinAccountAge,
+}
+
+/// Outcome of one rule.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct RuleOutcome {
+    pub rule: EligibilityRule,
+    pub passed: bool,
+    /// Human-readable reason, e.g. "streak 4 of 7 days".
+    pub detail: String,
+}
+
+/// Genesis eligibility with the rule-by-rule explanation.
+/// Only rules enabled in the node's policy are listed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct EligibilityReport {
+    /// True when every listed rule passed.
+    pub eligible: bool,
+    pub rules: Vec<RuleOutcome>,
+    pub evaluated_at: DateTime<Utc>,
+}
+
+impl EligibilityReport {
+    pub fn failed_rules(&self) -> Vec<EligibilityRule> {
+        self.rules.iter().filter(|r| !r.passed).map(|r| r.rule).collect()
+    }
+}
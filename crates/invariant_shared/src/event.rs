@@ -0,0 +1,76 @@
+// crates/invariant_shared/src/event.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::identity::Network;
+
+/// Why a heartbeat was turned away.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HeartbeatRejection {
+    /// The nonce had already been consumed.
+    Replay,
+    /// The device signature did not verify.
+    InvalidSignature,
+    /// The signed timestamp was outside the accepted drift or skew.
+    StaleTimestamp,
+    /// Sent before the heartbeat interval elapsed.
+    RateLimited,
+    /// Hardware attestation has expired (trust decay).
+    AttestationRequired,
+    /// The attestation chain carried by the heartbeat did not verify.
+    InvalidAttestation,
+    /// The identity's genesis version is retired; it must re-attest to upgrade.
+    UpgradeRequired,
+    /// Sent during the cooling-off period after a reactivation.
+    CoolingOff,
+}
+
+/// What happened to an identity.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DomainEventKind {
+    IdentityMinted { network: Network, genesis_version: u16 },
+    HeartbeatAccepted { continuity_score: u64 },
+    HeartbeatRejected { reason: HeartbeatRejection },
+    /// Attestation went stale; the identity is `stale` until it re-attests.
+    TrustDecayed,
+    Reattested,
+    /// Minted by migrating `testnet_id` to this network.
+    IdentityMigrated { testnet_id: Uuid, continuity_score: u64 },
+    /// Re-attested onto the node's current genesis version.
+    GenesisUpgraded { from_version: u16, to_version: u16 },
+    /// Woke from dormancy through a fresh attestation.
+    IdentityReactivated { dormant_days: i64, continuity_score: u64 },
+}
+
+impl DomainEventKind {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            DomainEventKind::IdentityMinted { .. } => "identity_minted",
+            DomainEventKind::HeartbeatAccepted { .. } => "heartbeat_accepted",
+            DomainEventKind::HeartbeatRejected { .. } => "heartbeat_rejected",
+            DomainEventKind::TrustDecayed => "trust_decayed",
+            DomainEventKind::Reattested => "reattested",
+            DomainEventKind::IdentityMigrated { .. } => "identity_migrated",
+            DomainEventKind::GenesisUpgraded { .. } => "genesis_upgraded",
+            DomainEventKind::IdentityReactivated { .. } => "identity_reactivated",
+        }
+    }
+}
+
+/// 📣 Domain event emitted by the engine once the change it describes is stored.
+/// `id` is unique per event so consumers can de-duplicate redeliveries.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DomainEvent {
+    pub id: Uuid,
+    pub identity_id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub kind: DomainEventKind,
+}
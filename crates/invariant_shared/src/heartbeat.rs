@@ -20,7 +20,7 @@ pub struct Heartbeat {
     pub identity_id: Uuid,
 
     /// The cryptographic signature generated by the hardware Secure Enclave.
-    /// Signs: (identity_id || nonce || timestamp)
+    /// Signs: `crate::signing::heartbeat_payload(identity_id, nonce, timestamp)`
     pub device_signature: Vec<u8>,
 
     /// The server-issued challenge (preventing replay attacks).
@@ -28,4 +28,10 @@ pub struct Heartbeat {
 
     /// TrustedTime timestamp (checked for skew).
     pub timestamp: DateTime<Utc>,
+
+    /// Optional fresh attestation chain of the signing key, generated over `nonce`.
+    /// Refreshes an expired trust timer in the same request instead of a separate
+    /// `/identity/reattest` round trip.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation_chain: Option<Vec<Vec<u8>>>,
 }
\ No newline at end of file
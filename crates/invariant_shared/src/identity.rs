@@ -26,17 +26,44 @@ pub enum Network {
     Dev,
 }
 
-impl ToString for Network {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Network::Testnet => "testnet".into(),
-            Network::Mainnet => "mainnet".into(),
-            Network::Dev => "dev".into(),
+            Network::Testnet => f.write_str("testnet"),
+            Network::Mainnet => f.write_str("mainnet"),
+            Network::Dev => f.write_str("dev"),
         }
     }
 }
 
 /// The core invariant representing a persistent entity.
+/// KeyMint security level of an attested key. Software keys never get this far.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SecurityLevel {
+    /// Trusted Execution Environment (TrustZone).
+    Tee,
+    /// Dedicated secure element.
+    StrongBox,
+}
+
+impl SecurityLevel {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            SecurityLevel::Tee => "tee",
+            SecurityLevel::StrongBox => "strongbox",
+        }
+    }
+
+    /// Marketing tier shown to partners and on the leaderboard.
+    pub fn tier(&self) -> &'static str {
+        match self {
+            SecurityLevel::Tee => "STEEL",
+            SecurityLevel::StrongBox => "TITANIUM",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Identity {
     pub id: Uuid,
@@ -68,6 +95,56 @@ pub struct Identity {
     pub hardware_device: Option<String>,
     pub hardware_product: Option<String>,
 
+    /// Where the key lives, from the last attestation.
+    #[serde(default)]
+    pub security_level: Option<SecurityLevel>,
+    /// Android security patch level (YYYYMM) from the last attestation.
+    #[serde(default)]
+    pub os_patch_level: Option<u32>,
+
     pub genesis_version: u16,
     pub network: Network,
-}
\ No newline at end of file
+}
+/// Why an identity changed status. Each trigger is only legal from certain states
+/// (the table lives in `invariant_engine::lifecycle`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionReason {
+    /// A counted heartbeat. Dormant identities no longer wake on one (they must re-attest);
+    /// older history rows may still carry it.
+    Heartbeat,
+    /// Trust decay: the hardware attestation outlived its TTL.
+    AttestationExpired,
+    Reattestation,
+    KeyRotation,
+    Recovery,
+    /// The reaper: no heartbeat within the reaper window.
+    Inactivity,
+    Revocation,
+}
+
+impl TransitionReason {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            TransitionReason::Heartbeat => "heartbeat",
+            TransitionReason::AttestationExpired => "attestation_expired",
+            TransitionReason::Reattestation => "reattestation",
+            TransitionReason::KeyRotation => "key_rotation",
+            TransitionReason::Recovery => "recovery",
+            TransitionReason::Inactivity => "inactivity",
+            TransitionReason::Revocation => "revocation",
+        }
+    }
+}
+
+/// One entry of the identity status audit trail.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IdentityTransition {
+    pub identity_id: Uuid,
+    pub from: IdentityStatus,
+    pub to: IdentityStatus,
+    pub reason: TransitionReason,
+    /// The identity (or guardian/admin) that caused it. None for system triggers.
+    pub actor_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
@@ -8,8 +8,49 @@ pub mod heartbeat;
 pub mod identity;
 pub mod genesis;
 pub mod reattestation; // 👈 NEW
+pub mod signing;
+pub mod rotation;
+pub mod recovery;
+pub mod receipt;
+pub mod token;
+pub mod privacy_pass;
+pub mod transparency;
+pub mod revocation;
+pub mod switch;
+pub mod streak;
+pub mod trust;
+pub mod sybil;
+pub mod action;
+pub mod event;
+pub mod webhook;
+pub mod eligibility;
+pub mod device;
+pub mod migration;
+pub mod upgrade;
+pub mod reactivation;
+pub mod pairwise;
 
 pub use heartbeat::Heartbeat;
-pub use identity::{Identity, IdentityStatus, Network};
+pub use identity::{Identity, IdentityStatus, IdentityTransition, TransitionReason, Network, SecurityLevel};
 pub use genesis::GenesisRequest;
-pub use reattestation::ReAttestationRequest; // 👈 NEW
\ No newline at end of file
+pub use reattestation::ReAttestationRequest; // 👈 NEW
+pub use rotation::KeyRotationRequest;
+pub use recovery::{GuardianSetRequest, RecoveryInitRequest, RecoveryApproval, RecoveryRejection, RecoveryCancelRequest, Recovery, RecoveryStatus, RecoveryEvent};
+pub use receipt::{Receipt, ReceiptKind, ReceiptVerdict, SignedReceipt, verify_receipt};
+pub use token::{ProofTokenClaims, ProofTokenRequest, Jwk, JwkSet, TokenError, verify_proof_token};
+pub use privacy_pass::{TokenIssuanceRequest, PrivacyPassToken, verify_privacy_pass_token};
+pub use transparency::{LogEntry, LogEventKind, TreeHead, SignedTreeHead, verify_tree_head};
+pub use revocation::{Revocation, RevocationReason, RevocationNotice, SelfRevocationRequest};
+pub use switch::{DeadMansSwitch, SwitchStatus, SwitchArmRequest, SwitchDisarmRequest};
+pub use streak::{StreakState, StreakTimezoneRequest};
+pub use trust::{TrustScore, TrustSignal, SignalContribution};
+pub use sybil::{SybilCluster, SybilConfidence, SybilSignal};
+pub use action::{ActionChallengeRequest, ActionChallenge, ActionVerifyRequest, ActionVerdict, SignedActionVerdict, verify_action_verdict};
+pub use event::{DomainEvent, DomainEventKind, HeartbeatRejection};
+pub use webhook::{WebhookEventType, WebhookEndpointRequest, WebhookEndpoint, WebhookPayload, WebhookDelivery, WebhookDeliveryStatus, webhook_signature, verify_webhook_signature};
+pub use eligibility::{EligibilityRule, RuleOutcome, EligibilityReport};
+pub use device::{Device, DeviceStatus, DeviceEnrollmentRequest, DeviceRevocationRequest};
+pub use migration::{MigrationRequest, IdentityMigration};
+pub use upgrade::{GenesisUpgrade, GenesisVersionStats, GenesisUpgradeProgress};
+pub use reactivation::Reactivation;
+pub use pairwise::PairwiseLinkRequest;
@@ -0,0 +1,35 @@
+// crates/invariant_shared/src/migration.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+
+/// Promotes a testnet identity to mainnet, onto a fresh mainnet key.
+/// Requires BOTH a statement signed by the testnet key and an attestation of the new key.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MigrationRequest {
+    pub testnet_id: Uuid,
+    pub new_public_key: Vec<u8>,
+    /// Attestation chain of the new key, generated over `nonce`.
+    pub attestation_chain: Vec<Vec<u8>>,
+    pub nonce: Vec<u8>,
+    /// Signs: `crate::signing::migration_payload(testnet_id, new_public_key, nonce)`
+    /// with the testnet identity's key.
+    pub signature: Vec<u8>,
+}
+
+/// Link between a retired testnet identity and the mainnet identity it became.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct IdentityMigration {
+    pub testnet_id: Uuid,
+    pub mainnet_id: Uuid,
+    /// Continuity score and streak taken over under the mainnet policy (0 = reset).
+    pub carried_score: u64,
+    pub carried_streak: u64,
+    pub migrated_at: DateTime<Utc>,
+}
@@ -0,0 +1,22 @@
+// crates/invariant_shared/src/pairwise.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The holder presents its identity to a partner and asks for the partner's pairwise ID.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PairwiseLinkRequest {
+    /// Partner the identity is being presented to (e.g. "https://bank.example").
+    pub partner_id: String,
+
+    /// Challenge issued by `/heartbeat/challenge`.
+    pub nonce: Vec<u8>,
+
+    /// Signature by the identity's CURRENT key.
+    /// Signs: `crate::signing::pairwise_link_payload(identity_id, partner_id, nonce)`
+    pub signature: Vec<u8>,
+}
@@ -0,0 +1,111 @@
+// crates/invariant_shared/src/privacy_pass.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+//! Anonymous "some hardware-verified human" tokens.
+//!
+//! Publicly verifiable Privacy Pass tokens (RFC 9578, token type `0x0002`,
+//! Blind RSA 2048 / RSABSSA-SHA384-PSS-Deterministic). The node blind-signs
+//! token requests for active identities; the finalized tokens carry no link
+//! back to the identity that obtained them.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::ToSchema;
+use ring::digest::{digest, SHA256};
+use blind_rsa_signatures::{Options, PublicKey, Signature};
+
+/// RFC 9578 token type for Blind RSA (2048-bit).
+pub const TOKEN_TYPE_BLIND_RSA: u16 = 0x0002;
+
+/// Modulus length in bytes (`Nk`) for token type `0x0002`.
+pub const BLIND_RSA_NK: usize = 256;
+
+/// `token_type (2) || truncated_token_key_id (1) || blinded_msg (Nk)`
+pub const TOKEN_REQUEST_LEN: usize = 3 + BLIND_RSA_NK;
+
+/// A batch of blinded token requests, authorized by the identity's hardware key.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TokenIssuanceRequest {
+    pub identity_id: Uuid,
+
+    /// Challenge issued by `/heartbeat/challenge`.
+    pub nonce: Vec<u8>,
+
+    /// Encoded RFC 9578 `TokenRequest` structs (see [`TOKEN_REQUEST_LEN`]).
+    pub token_requests: Vec<Vec<u8>>,
+
+    /// Signs: `crate::signing::token_issuance_payload(identity_id, nonce, &requests_digest(token_requests))`
+    pub signature: Vec<u8>,
+}
+
+/// SHA-256 over the concatenated token requests. Binds the device signature to the exact batch.
+pub fn requests_digest(token_requests: &[Vec<u8>]) -> Vec<u8> {
+    let joined: Vec<u8> = token_requests.concat();
+    digest(&SHA256, &joined).as_ref().to_vec()
+}
+
+/// `token_key_id = SHA-256(issuer public key SPKI)`.
+pub fn token_key_id(issuer_spki: &[u8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out.copy_from_slice(digest(&SHA256, issuer_spki).as_ref());
+    out
+}
+
+/// A finalized RFC 9578 token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrivacyPassToken {
+    pub token_type: u16,
+    pub nonce: [u8; 32],
+    pub challenge_digest: [u8; 32],
+    pub token_key_id: [u8; 32],
+    pub authenticator: Vec<u8>,
+}
+
+impl PrivacyPassToken {
+    /// The message the issuer (blindly) signed.
+    pub fn token_input(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(98);
+        out.extend_from_slice(&self.token_type.to_be_bytes());
+        out.extend_from_slice(&self.nonce);
+        out.extend_from_slice(&self.challenge_digest);
+        out.extend_from_slice(&self.token_key_id);
+        out
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = self.token_input();
+        out.extend_from_slice(&self.authenticator);
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != 98 + BLIND_RSA_NK {
+            return None;
+        }
+        let token_type = u16::from_be_bytes([bytes[0], bytes[1]]);
+        Some(Self {
+            token_type,
+            nonce: bytes[2..34].try_into().ok()?,
+            challenge_digest: bytes[34..66].try_into().ok()?,
+            token_key_id: bytes[66..98].try_into().ok()?,
+            authenticator: bytes[98..].to_vec(),
+        })
+    }
+}
+
+/// Partner-side check: the token was signed by the node key published in the issuer directory.
+/// Double-spend prevention (tracking `nonce`) is the partner's responsibility.
+pub fn verify_privacy_pass_token(token: &PrivacyPassToken, issuer_spki: &[u8]) -> bool {
+    if token.token_type != TOKEN_TYPE_BLIND_RSA || token.token_key_id != token_key_id(issuer_spki) {
+        return false;
+    }
+    let Ok(public_key) = PublicKey::from_spki(issuer_spki, None) else {
+        return false;
+    };
+    Signature(token.authenticator.clone())
+        .verify(&public_key, None, token.token_input(), &Options::default())
+        .is_ok()
+}
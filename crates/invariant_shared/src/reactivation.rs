@@ -0,0 +1,30 @@
+// crates/invariant_shared/src/reactivation.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use crate::identity::TransitionReason;
+
+/// A dormant identity brought back by a fresh attestation, with what its dormancy cost it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct Reactivation {
+    pub identity_id: Uuid,
+    /// The attested operation that reactivated it (re-attestation, key rotation, recovery).
+    pub reason: TransitionReason,
+    /// Last heartbeat before the silence.
+    pub silent_since: DateTime<Utc>,
+    /// When the reaper marked it dormant.
+    pub dormant_since: DateTime<Utc>,
+    pub reactivated_at: DateTime<Utc>,
+    pub score_before: u64,
+    pub score_after: u64,
+    pub streak_before: u64,
+    pub streak_after: u64,
+    /// Heartbeats are not counted before this time. None without a cooling-off period.
+    pub cooling_off_until: Option<DateTime<Utc>>,
+}
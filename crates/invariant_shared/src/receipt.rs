@@ -0,0 +1,71 @@
+// crates/invariant_shared/src/receipt.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use ring::signature::{UnparsedPublicKey, ED25519};
+use crate::signing;
+
+/// Which node decision a receipt attests to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReceiptKind {
+    Genesis,
+    Heartbeat,
+    Verify,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReceiptVerdict {
+    Accepted,
+    Rejected,
+}
+
+/// A node decision, as signed by the node's Ed25519 receipt key.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Receipt {
+    pub kind: ReceiptKind,
+    /// None for stateless `/verify` checks (no identity is minted).
+    pub identity_id: Option<Uuid>,
+    pub score: u64,
+    pub verdict: ReceiptVerdict,
+    /// SHA-256 of the hardware key the decision is about. Empty for heartbeats, which are
+    /// bound to the identity instead.
+    pub public_key_hash: Vec<u8>,
+    /// The server challenge the request answered, so a receipt cannot be replayed for another check.
+    pub nonce: Vec<u8>,
+    /// Taken from the node's clock (the same one its decisions use).
+    pub issued_at: DateTime<Utc>,
+}
+
+impl Receipt {
+    /// The exact bytes covered by the node signature.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        signing::receipt_payload(self)
+    }
+}
+
+/// A receipt plus the node's detached signature.
+/// Partners can store this and verify it offline with [`verify_receipt`].
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SignedReceipt {
+    #[serde(flatten)]
+    pub receipt: Receipt,
+    /// Identifies which node key signed (see `GET /receipts/key`).
+    pub key_id: String,
+    /// Ed25519 signature over `receipt.signing_payload()`.
+    pub signature: Vec<u8>,
+}
+
+/// Offline verification of a node receipt against the node's Ed25519 public key (32 raw bytes).
+pub fn verify_receipt(signed: &SignedReceipt, node_public_key: &[u8]) -> bool {
+    UnparsedPublicKey::new(&ED25519, node_public_key)
+        .verify(&signed.receipt.signing_payload(), &signed.signature)
+        .is_ok()
+}
@@ -0,0 +1,143 @@
+// crates/invariant_shared/src/recovery.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use crate::identity::SecurityLevel;
+
+/// Designates the guardians allowed to recover an identity (M-of-N).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GuardianSetRequest {
+    /// The identity being protected.
+    pub id: Uuid,
+
+    /// Guardian identities (N). Order is significant for the signature.
+    pub guardian_ids: Vec<Uuid>,
+
+    /// Approvals required to recover (M).
+    pub threshold: u8,
+
+    /// The cryptographic nonce (challenge) issued by the server.
+    pub nonce: Vec<u8>,
+
+    /// Signature by the identity's CURRENT key.
+    /// Signs: `crate::signing::guardian_set_payload(id, guardian_ids, threshold, nonce)`
+    pub signature: Vec<u8>,
+}
+
+/// Starts a recovery onto a new, freshly attested device key.
+/// Submitted from the NEW device; nothing here is signed by the lost key.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecoveryInitRequest {
+    /// The identity to recover.
+    pub id: Uuid,
+
+    /// The NEW P-256 Public Key generated in StrongBox/TEE.
+    pub new_public_key: Vec<u8>,
+
+    /// Android KeyStore Attestation Certificate Chain for the NEW key.
+    pub attestation_chain: Vec<Vec<u8>>,
+
+    /// The cryptographic nonce (challenge) issued by the server.
+    pub nonce: Vec<u8>,
+}
+
+/// A guardian's hardware-signed vote for a pending recovery.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecoveryApproval {
+    pub recovery_id: Uuid,
+    pub guardian_id: Uuid,
+
+    /// The cryptographic nonce (challenge) issued by the server.
+    pub nonce: Vec<u8>,
+
+    /// Signature by the GUARDIAN's key.
+    /// Signs: `crate::signing::recovery_approval_payload(recovery_id, identity_id, new_public_key, nonce)`
+    pub signature: Vec<u8>,
+}
+
+/// A guardian refuses a pending recovery (e.g. the holder says the device is not lost).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecoveryRejection {
+    pub recovery_id: Uuid,
+    pub guardian_id: Uuid,
+
+    /// The cryptographic nonce (challenge) issued by the server.
+    pub nonce: Vec<u8>,
+
+    /// Signature by the GUARDIAN's key.
+    /// Signs: `crate::signing::recovery_rejection_payload(recovery_id, identity_id, new_public_key, nonce)`
+    pub signature: Vec<u8>,
+}
+
+/// Aborts a recovery. Only the ORIGINAL key can cancel (i.e. the device was not lost).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecoveryCancelRequest {
+    pub recovery_id: Uuid,
+
+    /// The cryptographic nonce (challenge) issued by the server.
+    pub nonce: Vec<u8>,
+
+    /// Signature by the identity's CURRENT key.
+    /// Signs: `crate::signing::recovery_cancel_payload(recovery_id, nonce)`
+    pub signature: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RecoveryStatus {
+    /// Waiting for guardian approvals.
+    Pending,
+    /// Threshold met; waiting for the cancellation window to elapse.
+    Approved,
+    /// Key swapped.
+    Completed,
+    /// Aborted by the original holder.
+    Cancelled,
+    /// Refused by so many guardians that the threshold can no longer be met.
+    Rejected,
+    /// Still pending when `expires_at` passed.
+    Expired,
+}
+
+/// A recovery attempt and its progress.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Recovery {
+    pub id: Uuid,
+    pub identity_id: Uuid,
+    pub new_public_key: Vec<u8>,
+
+    pub hardware_brand: Option<String>,
+    pub hardware_device: Option<String>,
+    pub hardware_product: Option<String>,
+    #[serde(default)]
+    pub security_level: Option<SecurityLevel>,
+    #[serde(default)]
+    pub os_patch_level: Option<u32>,
+
+    pub status: RecoveryStatus,
+    pub approvals: u32,
+    pub rejections: u32,
+    pub threshold: u8,
+
+    pub initiated_at: DateTime<Utc>,
+    /// A recovery still pending at this time expires (and stops blocking a new one).
+    pub expires_at: DateTime<Utc>,
+    /// Earliest time the recovery can be finalized (set once the threshold is met).
+    pub executable_at: Option<DateTime<Utc>>,
+}
+
+/// One entry of the recovery audit trail.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecoveryEvent {
+    /// `initiated` | `approved` | `rejected` | `threshold_met` | `cancelled` | `completed` | `expired`
+    pub event: String,
+    /// The guardian or holder that caused the event (None for system/new-device steps).
+    pub actor_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
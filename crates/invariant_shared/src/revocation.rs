@@ -0,0 +1,78 @@
+// crates/invariant_shared/src/revocation.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+
+/// Why an identity was revoked. Published to partners, so kept coarse.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RevocationReason {
+    /// The hardware key is (or may be) in someone else's hands.
+    CompromisedKey,
+    /// Operator finding: the identity was used for fraud.
+    Fraud,
+    /// The holder asked to be removed.
+    UserRequest,
+    /// The device's attestation keybox was leaked, so its attestations prove nothing.
+    LeakedKeybox,
+    /// Promoted to mainnet; the identity lives on under its mainnet ID.
+    Migrated,
+}
+
+impl RevocationReason {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            RevocationReason::CompromisedKey => "compromised_key",
+            RevocationReason::Fraud => "fraud",
+            RevocationReason::UserRequest => "user_request",
+            RevocationReason::LeakedKeybox => "leaked_keybox",
+            RevocationReason::Migrated => "migrated",
+        }
+    }
+
+    /// Reasons a holder may give for revoking their own identity.
+    /// The others are operator findings.
+    pub fn is_self_service(&self) -> bool {
+        matches!(self, RevocationReason::CompromisedKey | RevocationReason::UserRequest)
+    }
+}
+
+/// A revocation as recorded by the node.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Revocation {
+    /// Monotonic feed cursor.
+    pub sequence: u64,
+    pub identity_id: Uuid,
+    pub reason: RevocationReason,
+    /// The identity itself for self-revocation, None for operator action.
+    pub revoked_by: Option<Uuid>,
+    pub revoked_at: DateTime<Utc>,
+}
+
+/// One entry of a partner's revocation feed. Carries the partner's pairwise ID only.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RevocationNotice {
+    /// Pass as `after` to fetch the next page.
+    pub sequence: u64,
+    pub subject_id: Uuid,
+    pub reason: RevocationReason,
+    pub revoked_at: DateTime<Utc>,
+}
+
+/// Holder-initiated revocation ("I lost my phone", "delete me").
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SelfRevocationRequest {
+    pub id: Uuid,
+    /// Must be a self-service reason (`compromised_key` or `user_request`).
+    pub reason: RevocationReason,
+    /// The cryptographic nonce (challenge) issued by the server.
+    pub nonce: Vec<u8>,
+    /// Signs: `crate::signing::revocation_payload(id, reason, nonce)`
+    pub signature: Vec<u8>,
+}
@@ -0,0 +1,31 @@
+// crates/invariant_shared/src/rotation.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use utoipa::ToSchema;
+
+/// A request to move an existing identity onto a new hardware key.
+/// Used when a user migrates phones or resets their keystore.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KeyRotationRequest {
+    /// The ID of the identity being migrated.
+    pub id: Uuid,
+
+    /// The NEW P-256 Public Key generated in StrongBox/TEE.
+    pub new_public_key: Vec<u8>,
+
+    /// Android KeyStore Attestation Certificate Chain for the NEW key.
+    pub attestation_chain: Vec<Vec<u8>>,
+
+    /// The cryptographic nonce (challenge) issued by the server.
+    /// Must be embedded in the new key's attestation AND covered by the rotation signature.
+    pub nonce: Vec<u8>,
+
+    /// Signature by the OLD (currently registered) key.
+    /// Signs: `crate::signing::key_rotation_payload(id, new_public_key, nonce)`
+    pub rotation_signature: Vec<u8>,
+}
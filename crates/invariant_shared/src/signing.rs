@@ -0,0 +1,328 @@
+// crates/invariant_shared/src/signing.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+//! Canonical signing payloads.
+//!
+//! Every message a hardware key signs is built here, so the node and the
+//! client SDKs produce byte-identical strings. Each payload is prefixed with
+//! a domain tag, the protocol version and a purpose tag:
+//!
+//! `INVARIANT|v1|heartbeat|<identity_id>|<nonce_hex>|<timestamp_rfc3339>`
+//!
+//! A signature produced for one purpose can therefore never be replayed as
+//! another (e.g. a heartbeat signature presented as an action approval).
+//!
+//! Fields are joined with `|`. Ids, hex and numbers cannot contain it; free-form
+//! text (time zone names, partner ids) is length-prefixed as `<len>:<text>` so
+//! no value can shift the fields after it.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::receipt::{Receipt, ReceiptKind, ReceiptVerdict};
+use crate::transparency::{LogEntry, TreeHead};
+use crate::revocation::RevocationReason;
+use crate::action::ActionVerdict;
+
+/// Domain separator shared by every Invariant payload.
+pub const DOMAIN_TAG: &str = "INVARIANT";
+
+/// Current version of the payload layout. Bump on any format change.
+pub const PROTOCOL_VERSION: u16 = 1;
+
+const FIELD_SEPARATOR: char = '|';
+
+/// What a hardware signature is authorizing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigningPurpose {
+    Heartbeat,
+    Action,
+    Reattestation,
+    KeyRotation,
+    GuardianSet,
+    RecoveryApproval,
+    RecoveryRejection,
+    RecoveryCancel,
+    Receipt,
+    TokenIssuance,
+    LogEntry,
+    TreeHead,
+    Revocation,
+    SwitchArm,
+    SwitchDisarm,
+    StreakTimezone,
+    ActionVerdict,
+    DeviceEnrollment,
+    DeviceRevocation,
+    Migration,
+    ProofToken,
+    PairwiseLink,
+}
+
+impl SigningPurpose {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            SigningPurpose::Heartbeat => "heartbeat",
+            SigningPurpose::Action => "action",
+            SigningPurpose::Reattestation => "reattestation",
+            SigningPurpose::KeyRotation => "key_rotation",
+            SigningPurpose::GuardianSet => "guardian_set",
+            SigningPurpose::RecoveryApproval => "recovery_approval",
+            SigningPurpose::RecoveryRejection => "recovery_rejection",
+            SigningPurpose::RecoveryCancel => "recovery_cancel",
+            SigningPurpose::Receipt => "receipt",
+            SigningPurpose::TokenIssuance => "token_issuance",
+            SigningPurpose::LogEntry => "log_entry",
+            SigningPurpose::TreeHead => "tree_head",
+            SigningPurpose::Revocation => "revocation",
+            SigningPurpose::SwitchArm => "switch_arm",
+            SigningPurpose::SwitchDisarm => "switch_disarm",
+            SigningPurpose::StreakTimezone => "streak_timezone",
+            SigningPurpose::ActionVerdict => "action_verdict",
+            SigningPurpose::DeviceEnrollment => "device_enrollment",
+            SigningPurpose::DeviceRevocation => "device_revocation",
+            SigningPurpose::Migration => "migration",
+            SigningPurpose::ProofToken => "proof_token",
+            SigningPurpose::PairwiseLink => "pairwise_link",
+        }
+    }
+}
+
+/// Daily "Secure Tap": binds the identity, the server challenge and the TrustedTime timestamp.
+pub fn heartbeat_payload(identity_id: &Uuid, nonce: &[u8], timestamp: &DateTime<Utc>) -> Vec<u8> {
+    encode(SigningPurpose::Heartbeat, &[
+        identity_id.to_string(),
+        hex::encode(nonce),
+        timestamp.to_rfc3339(),
+    ])
+}
+
+/// Partner action approval: binds the challenge to the hash of the action being approved.
+pub fn action_payload(identity_id: &Uuid, nonce: &[u8], payload_hash: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::Action, &[
+        identity_id.to_string(),
+        hex::encode(nonce),
+        hex::encode(payload_hash),
+    ])
+}
+
+/// Re-attestation: the attested key refreshes its identity's trust timer against the server challenge.
+pub fn reattestation_payload(identity_id: &Uuid, nonce: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::Reattestation, &[
+        identity_id.to_string(),
+        hex::encode(nonce),
+    ])
+}
+
+/// Key rotation statement: the OLD key authorizes `new_public_key` to take over the identity.
+pub fn key_rotation_payload(identity_id: &Uuid, new_public_key: &[u8], nonce: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::KeyRotation, &[
+        identity_id.to_string(),
+        hex::encode(new_public_key),
+        hex::encode(nonce),
+    ])
+}
+
+/// Guardian designation: the identity commits to its M-of-N recovery set.
+pub fn guardian_set_payload(identity_id: &Uuid, guardian_ids: &[Uuid], threshold: u8, nonce: &[u8]) -> Vec<u8> {
+    let guardians: Vec<String> = guardian_ids.iter().map(|g| g.to_string()).collect();
+    encode(SigningPurpose::GuardianSet, &[
+        identity_id.to_string(),
+        guardians.join(","),
+        threshold.to_string(),
+        hex::encode(nonce),
+    ])
+}
+
+/// Guardian vote: binds the approval to one recovery AND the exact key being installed.
+pub fn recovery_approval_payload(recovery_id: &Uuid, identity_id: &Uuid, new_public_key: &[u8], nonce: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::RecoveryApproval, &[
+        recovery_id.to_string(),
+        identity_id.to_string(),
+        hex::encode(new_public_key),
+        hex::encode(nonce),
+    ])
+}
+
+/// Guardian refusal: same binding as an approval, under its own purpose so one can never pass for the other.
+pub fn recovery_rejection_payload(recovery_id: &Uuid, identity_id: &Uuid, new_public_key: &[u8], nonce: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::RecoveryRejection, &[
+        recovery_id.to_string(),
+        identity_id.to_string(),
+        hex::encode(new_public_key),
+        hex::encode(nonce),
+    ])
+}
+
+/// Recovery veto by the original key holder.
+pub fn recovery_cancel_payload(recovery_id: &Uuid, nonce: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::RecoveryCancel, &[
+        recovery_id.to_string(),
+        hex::encode(nonce),
+    ])
+}
+
+/// Pairwise link: the holder presents itself to one partner (and only that partner learns a subject ID).
+pub fn pairwise_link_payload(identity_id: &Uuid, partner_id: &str, nonce: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::PairwiseLink, &[
+        identity_id.to_string(),
+        free_form(partner_id),
+        hex::encode(nonce),
+    ])
+}
+
+/// Proof token request: the holder lets the node vouch for it to exactly one audience.
+pub fn proof_token_payload(identity_id: &Uuid, audience: &str, nonce: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::ProofToken, &[
+        identity_id.to_string(),
+        free_form(audience),
+        hex::encode(nonce),
+    ])
+}
+
+/// Privacy Pass batch: binds the challenge to the exact set of blinded token requests.
+pub fn token_issuance_payload(identity_id: &Uuid, nonce: &[u8], requests_digest: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::TokenIssuance, &[
+        identity_id.to_string(),
+        hex::encode(nonce),
+        hex::encode(requests_digest),
+    ])
+}
+
+/// Self-revocation: the holder's key retires its own identity, for a stated reason.
+pub fn revocation_payload(identity_id: &Uuid, reason: RevocationReason, nonce: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::Revocation, &[
+        identity_id.to_string(),
+        reason.tag().to_string(),
+        hex::encode(nonce),
+    ])
+}
+
+/// Dead man's switch: the holder names a beneficiary and a silence threshold.
+pub fn switch_arm_payload(identity_id: &Uuid, beneficiary_id: &Uuid, threshold_days: u32, nonce: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::SwitchArm, &[
+        identity_id.to_string(),
+        beneficiary_id.to_string(),
+        threshold_days.to_string(),
+        hex::encode(nonce),
+    ])
+}
+
+/// Dead man's switch off (or cancelled during its grace period).
+pub fn switch_disarm_payload(identity_id: &Uuid, nonce: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::SwitchDisarm, &[
+        identity_id.to_string(),
+        hex::encode(nonce),
+    ])
+}
+
+/// Streak settings: the holder declares the time zone of their streak days.
+pub fn streak_timezone_payload(identity_id: &Uuid, timezone: &str, nonce: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::StreakTimezone, &[
+        identity_id.to_string(),
+        free_form(timezone),
+        hex::encode(nonce),
+    ])
+}
+
+/// Device enrollment: an active device of the identity vouches for `new_public_key`.
+pub fn device_enrollment_payload(identity_id: &Uuid, new_public_key: &[u8], nonce: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::DeviceEnrollment, &[
+        identity_id.to_string(),
+        hex::encode(new_public_key),
+        hex::encode(nonce),
+    ])
+}
+
+/// Device removal: any active device of the identity retires `device_id`.
+pub fn device_revocation_payload(identity_id: &Uuid, device_id: &Uuid, nonce: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::DeviceRevocation, &[
+        identity_id.to_string(),
+        device_id.to_string(),
+        hex::encode(nonce),
+    ])
+}
+
+/// Testnet-to-mainnet migration: the testnet key hands the identity over to `new_public_key`.
+pub fn migration_payload(testnet_id: &Uuid, new_public_key: &[u8], nonce: &[u8]) -> Vec<u8> {
+    encode(SigningPurpose::Migration, &[
+        testnet_id.to_string(),
+        hex::encode(new_public_key),
+        hex::encode(nonce),
+    ])
+}
+
+/// Node receipt (signed by the NODE's Ed25519 key, not a device key).
+/// The timestamp is encoded as unix seconds so any partner language can rebuild it exactly.
+pub fn receipt_payload(receipt: &Receipt) -> Vec<u8> {
+    let kind = match receipt.kind {
+        ReceiptKind::Genesis => "genesis",
+        ReceiptKind::Heartbeat => "heartbeat",
+        ReceiptKind::Verify => "verify",
+    };
+    let verdict = match receipt.verdict {
+        ReceiptVerdict::Accepted => "accepted",
+        ReceiptVerdict::Rejected => "rejected",
+    };
+    encode(SigningPurpose::Receipt, &[
+        kind.to_string(),
+        receipt.identity_id.map(|id| id.to_string()).unwrap_or_default(),
+        receipt.score.to_string(),
+        verdict.to_string(),
+        hex::encode(&receipt.public_key_hash),
+        hex::encode(&receipt.nonce),
+        receipt.issued_at.timestamp().to_string(),
+    ])
+}
+
+/// Transparency log leaf (hashed, not signed). Same canonical layout so auditors can rebuild it.
+pub fn log_entry_payload(entry: &LogEntry) -> Vec<u8> {
+    encode(SigningPurpose::LogEntry, &[
+        entry.index.to_string(),
+        entry.kind.tag().to_string(),
+        entry.identity_id.to_string(),
+        hex::encode(&entry.public_key_hash),
+        entry.logged_at.timestamp().to_string(),
+    ])
+}
+
+/// Signed tree head (signed by the NODE's Ed25519 key).
+pub fn tree_head_payload(head: &TreeHead) -> Vec<u8> {
+    encode(SigningPurpose::TreeHead, &[
+        head.tree_size.to_string(),
+        hex::encode(&head.root_hash),
+        head.timestamp.timestamp().to_string(),
+    ])
+}
+
+/// Node verdict on an action approval (signed by the NODE's Ed25519 key).
+pub fn action_verdict_payload(verdict: &ActionVerdict) -> Vec<u8> {
+    let outcome = match verdict.verdict {
+        ReceiptVerdict::Accepted => "accepted",
+        ReceiptVerdict::Rejected => "rejected",
+    };
+    encode(SigningPurpose::ActionVerdict, &[
+        free_form(&verdict.partner_id),
+        verdict.subject_id.to_string(),
+        hex::encode(&verdict.payload_hash),
+        hex::encode(&verdict.nonce),
+        outcome.to_string(),
+        verdict.issued_at.timestamp().to_string(),
+    ])
+}
+
+/// Length-prefixed free-form text.
+fn free_form(value: &str) -> String {
+    format!("{}:{}", value.len(), value)
+}
+
+fn encode(purpose: SigningPurpose, fields: &[String]) -> Vec<u8> {
+    let mut out = format!("{}{sep}v{}{sep}{}", DOMAIN_TAG, PROTOCOL_VERSION, purpose.tag(), sep = FIELD_SEPARATOR);
+    for field in fields {
+        out.push(FIELD_SEPARATOR);
+        out.push_str(field);
+    }
+    out.into_bytes()
+}
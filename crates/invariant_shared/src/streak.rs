@@ -0,0 +1,45 @@
+// crates/invariant_shared/src/streak.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::NaiveDate;
+use utoipa::ToSchema;
+
+pub const DEFAULT_STREAK_TIMEZONE: &str = "UTC";
+
+/// Streak bookkeeping of one identity. A streak counts consecutive local days
+/// (in the holder's declared time zone) with a counted heartbeat.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct StreakState {
+    pub streak: u64,
+    /// Last local day credited to the streak.
+    pub last_day: Option<NaiveDate>,
+    /// Earned freezes. Each one covers a single missed day.
+    pub freezes: u32,
+    /// IANA time zone name (e.g. "Europe/Paris").
+    pub timezone: String,
+}
+
+impl Default for StreakState {
+    fn default() -> Self {
+        Self { streak: 0, last_day: None, freezes: 0, timezone: DEFAULT_STREAK_TIMEZONE.to_string() }
+    }
+}
+
+/// Declares the time zone that defines the holder's streak days.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StreakTimezoneRequest {
+    pub id: Uuid,
+    /// IANA time zone name.
+    pub timezone: String,
+
+    /// The cryptographic nonce (challenge) issued by the server.
+    pub nonce: Vec<u8>,
+
+    /// Signs: `crate::signing::streak_timezone_payload(id, timezone, nonce)`
+    pub signature: Vec<u8>,
+}
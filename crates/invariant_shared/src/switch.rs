@@ -0,0 +1,75 @@
+// crates/invariant_shared/src/switch.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SwitchStatus {
+    /// Not configured, or disarmed by the holder.
+    Inactive,
+    /// Watching the holder's heartbeats.
+    Armed,
+    /// Threshold missed. The holder has been warned and the grace period is running;
+    /// a heartbeat or a disarm cancels it.
+    Pending,
+    /// Grace period expired. The beneficiary has been notified.
+    Triggered,
+}
+
+impl SwitchStatus {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            SwitchStatus::Inactive => "inactive",
+            SwitchStatus::Armed => "armed",
+            SwitchStatus::Pending => "pending",
+            SwitchStatus::Triggered => "triggered",
+        }
+    }
+}
+
+/// Dead man's switch of one identity.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct DeadMansSwitch {
+    pub identity_id: Uuid,
+    pub status: SwitchStatus,
+    /// Days without a heartbeat before the switch fires.
+    pub threshold_days: u32,
+    /// Identity notified when the switch triggers.
+    pub beneficiary_id: Option<Uuid>,
+    /// When the threshold was found missed (start of the grace period).
+    pub pending_since: Option<DateTime<Utc>>,
+    pub triggered_at: Option<DateTime<Utc>>,
+}
+
+/// Arms (or re-configures) the switch. Signed by the identity's current key.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SwitchArmRequest {
+    pub id: Uuid,
+    pub beneficiary_id: Uuid,
+    pub threshold_days: u32,
+
+    /// The cryptographic nonce (challenge) issued by the server.
+    pub nonce: Vec<u8>,
+
+    /// Signs: `crate::signing::switch_arm_payload(id, beneficiary_id, threshold_days, nonce)`
+    pub signature: Vec<u8>,
+}
+
+/// Disarms the switch. Also cancels a pending switch during its grace period.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SwitchDisarmRequest {
+    pub id: Uuid,
+
+    /// The cryptographic nonce (challenge) issued by the server.
+    pub nonce: Vec<u8>,
+
+    /// Signs: `crate::signing::switch_disarm_payload(id, nonce)`
+    pub signature: Vec<u8>,
+}
@@ -0,0 +1,78 @@
+// crates/invariant_shared/src/sybil.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+
+/// Value two identities can share that hints they are controlled by the same operator.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SybilSignal {
+    /// Same attested device ID (stored hashed).
+    DeviceHash,
+    /// Same verified boot key in the attestation root of trust.
+    BootKey,
+    /// Same certificate directly above the attested key (attestation batch key).
+    AttestationIntermediate,
+    /// Genesis from the same /24 (IPv4) or /48 (IPv6).
+    GenesisIpPrefix,
+    /// Identical time-of-day pattern over the last week of heartbeats.
+    HeartbeatTiming,
+}
+
+impl SybilSignal {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            SybilSignal::DeviceHash => "device_hash",
+            SybilSignal::BootKey => "boot_key",
+            SybilSignal::AttestationIntermediate => "attestation_intermediate",
+            SybilSignal::GenesisIpPrefix => "genesis_ip_prefix",
+            SybilSignal::HeartbeatTiming => "heartbeat_timing",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        [
+            SybilSignal::DeviceHash, SybilSignal::BootKey, SybilSignal::AttestationIntermediate,
+            SybilSignal::GenesisIpPrefix, SybilSignal::HeartbeatTiming,
+        ].into_iter().find(|s| s.tag() == tag)
+    }
+}
+
+/// How many independent signals tie the closest pair of a cluster together.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SybilConfidence {
+    /// One shared signal.
+    Low,
+    /// Two shared signals.
+    Medium,
+    /// Three or more.
+    High,
+}
+
+impl SybilConfidence {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            SybilConfidence::Low => "low",
+            SybilConfidence::Medium => "medium",
+            SybilConfidence::High => "high",
+        }
+    }
+}
+
+/// Group of identities suspected to be run by one operator. Advisory: nothing is revoked automatically.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct SybilCluster {
+    pub id: Uuid,
+    pub confidence: SybilConfidence,
+    /// Every signal that links at least two members.
+    pub signals: Vec<SybilSignal>,
+    pub members: Vec<Uuid>,
+    pub detected_at: DateTime<Utc>,
+}
@@ -0,0 +1,183 @@
+// crates/invariant_shared/src/token.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+//! Invariant proof tokens.
+//!
+//! Short-lived EdDSA (Ed25519) JWTs issued by a node, asserting the current
+//! state of one identity to ONE partner audience. Partners fetch the node's
+//! `/.well-known/jwks.json` once, then verify tokens offline with
+//! [`verify_proof_token`].
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ring::signature::{UnparsedPublicKey, ED25519};
+use crate::identity::IdentityStatus;
+
+/// JOSE algorithm name for Ed25519 signatures.
+pub const TOKEN_ALGORITHM: &str = "EdDSA";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProofTokenHeader {
+    pub alg: String,
+    pub typ: String,
+    pub kid: String,
+}
+
+/// What the node asserts about an identity at `iat`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct ProofTokenClaims {
+    /// Issuing node, e.g. `invariant:testnet`.
+    pub iss: String,
+    /// The identity the token speaks for.
+    pub sub: Uuid,
+    /// The partner the token was minted for. Tokens are useless to anyone else.
+    pub aud: String,
+    pub iat: i64,
+    pub exp: i64,
+    pub jti: Uuid,
+
+    pub status: IdentityStatus,
+    pub tier: String,
+    pub streak: u64,
+    /// Unix seconds of the last hardware chain verification.
+    pub last_attestation: i64,
+}
+
+/// The holder's authorization to mint one proof token for one partner.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProofTokenRequest {
+    /// Challenge issued by `/heartbeat/challenge`.
+    pub nonce: Vec<u8>,
+
+    /// Signature by the identity's CURRENT key.
+    /// Signs: `crate::signing::proof_token_payload(identity_id, audience, nonce)`
+    pub signature: Vec<u8>,
+}
+
+/// A single public key in JWK form (RFC 8037, `OKP` / `Ed25519`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct Jwk {
+    pub kty: String,
+    pub crv: String,
+    pub alg: String,
+    #[serde(rename = "use")]
+    pub key_use: String,
+    pub kid: String,
+    /// base64url (no padding) of the raw 32-byte public key.
+    pub x: String,
+}
+
+impl Jwk {
+    pub fn ed25519(kid: &str, public_key: &[u8]) -> Self {
+        Self {
+            kty: "OKP".into(),
+            crv: "Ed25519".into(),
+            alg: TOKEN_ALGORITHM.into(),
+            key_use: "sig".into(),
+            kid: kid.into(),
+            x: URL_SAFE_NO_PAD.encode(public_key),
+        }
+    }
+}
+
+/// The node's published key set. Retired keys stay listed until their tokens expire.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct JwkSet {
+    pub keys: Vec<Jwk>,
+}
+
+impl JwkSet {
+    pub fn find(&self, kid: &str) -> Option<&Jwk> {
+        self.keys.iter().find(|k| k.kid == kid)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenError {
+    Malformed,
+    UnsupportedAlgorithm,
+    UnknownKey(String),
+    InvalidSignature,
+    WrongIssuer,
+    WrongAudience,
+    Expired,
+}
+
+impl std::fmt::Display for TokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenError::Malformed => f.write_str("malformed token"),
+            TokenError::UnsupportedAlgorithm => f.write_str("unsupported algorithm"),
+            TokenError::UnknownKey(kid) => write!(f, "unknown key id: {}", kid),
+            TokenError::InvalidSignature => f.write_str("invalid signature"),
+            TokenError::WrongIssuer => f.write_str("token was issued by another node"),
+            TokenError::WrongAudience => f.write_str("token was issued for another audience"),
+            TokenError::Expired => f.write_str("token expired"),
+        }
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+/// Builds the `header.claims` part of a token. The issuer signs these bytes.
+pub fn signing_input(header: &ProofTokenHeader, claims: &ProofTokenClaims) -> String {
+    let header_json = serde_json::to_vec(header).expect("header serializes");
+    let claims_json = serde_json::to_vec(claims).expect("claims serialize");
+    format!("{}.{}", URL_SAFE_NO_PAD.encode(header_json), URL_SAFE_NO_PAD.encode(claims_json))
+}
+
+/// Appends the detached signature to a signing input, producing the compact JWT.
+pub fn assemble(signing_input: &str, signature: &[u8]) -> String {
+    format!("{}.{}", signing_input, URL_SAFE_NO_PAD.encode(signature))
+}
+
+/// Offline verification: signature against the JWKS, then issuer, audience and expiry.
+/// `issuer` is the node the JWKS was fetched from (e.g. `invariant:mainnet`), so a token
+/// minted by another node (say, a testnet one) is refused even if its key is listed.
+pub fn verify_proof_token(token: &str, jwks: &JwkSet, issuer: &str, audience: &str, now: DateTime<Utc>) -> Result<ProofTokenClaims, TokenError> {
+    let mut parts = token.split('.');
+    let (Some(header_b64), Some(claims_b64), Some(sig_b64), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+        return Err(TokenError::Malformed);
+    };
+
+    let header: ProofTokenHeader = decode_json(header_b64)?;
+    if header.alg != TOKEN_ALGORITHM {
+        return Err(TokenError::UnsupportedAlgorithm);
+    }
+
+    let jwk = jwks.find(&header.kid).ok_or_else(|| TokenError::UnknownKey(header.kid.clone()))?;
+    if jwk.crv != "Ed25519" {
+        return Err(TokenError::UnsupportedAlgorithm);
+    }
+    let public_key = URL_SAFE_NO_PAD.decode(&jwk.x).map_err(|_| TokenError::Malformed)?;
+    let signature = URL_SAFE_NO_PAD.decode(sig_b64).map_err(|_| TokenError::Malformed)?;
+
+    let message = format!("{}.{}", header_b64, claims_b64);
+    UnparsedPublicKey::new(&ED25519, &public_key)
+        .verify(message.as_bytes(), &signature)
+        .map_err(|_| TokenError::InvalidSignature)?;
+
+    let claims: ProofTokenClaims = decode_json(claims_b64)?;
+    if claims.iss != issuer {
+        return Err(TokenError::WrongIssuer);
+    }
+    if claims.aud != audience {
+        return Err(TokenError::WrongAudience);
+    }
+    if now.timestamp() >= claims.exp {
+        return Err(TokenError::Expired);
+    }
+
+    Ok(claims)
+}
+
+fn decode_json<T: serde::de::DeserializeOwned>(segment: &str) -> Result<T, TokenError> {
+    let bytes = URL_SAFE_NO_PAD.decode(segment).map_err(|_| TokenError::Malformed)?;
+    serde_json::from_slice(&bytes).map_err(|_| TokenError::Malformed)
+}
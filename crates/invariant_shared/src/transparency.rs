@@ -0,0 +1,359 @@
+// crates/invariant_shared/src/transparency.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+//! Transparency log primitives (RFC 6962 / RFC 9162 Merkle trees).
+//!
+//! Every identity lifecycle event is a leaf. The node publishes signed tree
+//! heads; auditors use the proof verifiers below to check that the log only
+//! ever grows and that a given event is included.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use ring::digest::{Context, SHA256};
+use ring::signature::{UnparsedPublicKey, ED25519};
+use crate::signing;
+
+pub type Hash = [u8; 32];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LogEventKind {
+    Genesis,
+    KeyRotation,
+    Recovery,
+    Reattestation,
+    Revocation,
+    DeviceEnrolled,
+    DeviceRevoked,
+    Migration,
+}
+
+impl LogEventKind {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            LogEventKind::Genesis => "genesis",
+            LogEventKind::KeyRotation => "key_rotation",
+            LogEventKind::Recovery => "recovery",
+            LogEventKind::Reattestation => "reattestation",
+            LogEventKind::Revocation => "revocation",
+            LogEventKind::DeviceEnrolled => "device_enrolled",
+            LogEventKind::DeviceRevoked => "device_revoked",
+            LogEventKind::Migration => "migration",
+        }
+    }
+}
+
+/// One leaf of the log.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct LogEntry {
+    pub index: u64,
+    pub kind: LogEventKind,
+    pub identity_id: Uuid,
+    /// SHA-256 of the hardware public key in force AFTER the event
+    /// (for device events: the key that was enrolled or revoked).
+    pub public_key_hash: Vec<u8>,
+    pub logged_at: DateTime<Utc>,
+}
+
+impl LogEntry {
+    /// RFC 6962 leaf hash of the canonical entry encoding.
+    pub fn leaf_hash(&self) -> Hash {
+        leaf_hash(&signing::log_entry_payload(self))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct TreeHead {
+    pub tree_size: u64,
+    pub root_hash: Vec<u8>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// A tree head signed with the node's Ed25519 key (the receipt key, see `GET /receipts/key`).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SignedTreeHead {
+    #[serde(flatten)]
+    pub tree_head: TreeHead,
+    pub key_id: String,
+    pub signature: Vec<u8>,
+}
+
+pub fn verify_tree_head(sth: &SignedTreeHead, node_public_key: &[u8]) -> bool {
+    UnparsedPublicKey::new(&ED25519, node_public_key)
+        .verify(&signing::tree_head_payload(&sth.tree_head), &sth.signature)
+        .is_ok()
+}
+
+// --- MERKLE TREE (RFC 6962 §2.1) ---
+
+pub fn leaf_hash(data: &[u8]) -> Hash {
+    hash_parts(&[&[0x00], data])
+}
+
+fn node_hash(left: &Hash, right: &Hash) -> Hash {
+    hash_parts(&[&[0x01], left, right])
+}
+
+fn hash_parts(parts: &[&[u8]]) -> Hash {
+    let mut ctx = Context::new(&SHA256);
+    for part in parts {
+        ctx.update(part);
+    }
+    let mut out = [0u8; 32];
+    out.copy_from_slice(ctx.finish().as_ref());
+    out
+}
+
+/// Largest power of two strictly smaller than `n` (n > 1).
+fn split_point(n: usize) -> usize {
+    let mut k = 1;
+    while k << 1 < n {
+        k <<= 1;
+    }
+    k
+}
+
+/// MTH(D[n]) over already-hashed leaves.
+pub fn merkle_root(leaves: &[Hash]) -> Hash {
+    match leaves.len() {
+        0 => hash_parts(&[]),
+        1 => leaves[0],
+        n => {
+            let k = split_point(n);
+            node_hash(&merkle_root(&leaves[..k]), &merkle_root(&leaves[k..]))
+        }
+    }
+}
+
+/// PATH(m, D[n]): audit path for leaf `index`.
+pub fn inclusion_proof(leaves: &[Hash], index: usize) -> Vec<Hash> {
+    let n = leaves.len();
+    if n <= 1 || index >= n {
+        return Vec::new();
+    }
+    let k = split_point(n);
+    if index < k {
+        let mut path = inclusion_proof(&leaves[..k], index);
+        path.push(merkle_root(&leaves[k..]));
+        path
+    } else {
+        let mut path = inclusion_proof(&leaves[k..], index - k);
+        path.push(merkle_root(&leaves[..k]));
+        path
+    }
+}
+
+/// PROOF(m, D[n]): proves the first `old_size` leaves are a prefix of `leaves`.
+pub fn consistency_proof(leaves: &[Hash], old_size: usize) -> Vec<Hash> {
+    if old_size == 0 || old_size >= leaves.len() {
+        return Vec::new();
+    }
+    subproof(old_size, leaves, true)
+}
+
+fn subproof(m: usize, leaves: &[Hash], complete: bool) -> Vec<Hash> {
+    let n = leaves.len();
+    if m == n {
+        return if complete { Vec::new() } else { vec![merkle_root(leaves)] };
+    }
+    let k = split_point(n);
+    if m <= k {
+        let mut proof = subproof(m, &leaves[..k], complete);
+        proof.push(merkle_root(&leaves[k..]));
+        proof
+    } else {
+        let mut proof = subproof(m - k, &leaves[k..], false);
+        proof.push(merkle_root(&leaves[..k]));
+        proof
+    }
+}
+
+/// RFC 9162 §2.1.3.2
+pub fn verify_inclusion(leaf: &Hash, index: u64, tree_size: u64, proof: &[Hash], root: &Hash) -> bool {
+    if index >= tree_size {
+        return false;
+    }
+    let (mut fn_, mut sn) = (index, tree_size - 1);
+    let mut r = *leaf;
+    for p in proof {
+        if sn == 0 {
+            return false;
+        }
+        if fn_ & 1 == 1 || fn_ == sn {
+            r = node_hash(p, &r);
+            while fn_ & 1 == 0 && fn_ != 0 {
+                fn_ >>= 1;
+                sn >>= 1;
+            }
+        } else {
+            r = node_hash(&r, p);
+        }
+        fn_ >>= 1;
+        sn >>= 1;
+    }
+    sn == 0 && &r == root
+}
+
+/// RFC 9162 §2.1.4.2
+pub fn verify_consistency(old_size: u64, new_size: u64, old_root: &Hash, new_root: &Hash, proof: &[Hash]) -> bool {
+    if old_size > new_size {
+        return false;
+    }
+    if old_size == new_size {
+        return proof.is_empty() && old_root == new_root;
+    }
+    if old_size == 0 {
+        // The empty tree is a prefix of every tree.
+        return proof.is_empty();
+    }
+    if proof.is_empty() {
+        return false;
+    }
+
+    let mut path: Vec<Hash> = Vec::with_capacity(proof.len() + 1);
+    if old_size.is_power_of_two() {
+        path.push(*old_root);
+    }
+    path.extend_from_slice(proof);
+
+    let (mut fn_, mut sn) = (old_size - 1, new_size - 1);
+    while fn_ & 1 == 1 {
+        fn_ >>= 1;
+        sn >>= 1;
+    }
+
+    let (mut fr, mut sr) = (path[0], path[0]);
+    for c in &path[1..] {
+        if sn == 0 {
+            return false;
+        }
+        if fn_ & 1 == 1 || fn_ == sn {
+            fr = node_hash(c, &fr);
+            sr = node_hash(c, &sr);
+            while fn_ & 1 == 0 && fn_ != 0 {
+                fn_ >>= 1;
+                sn >>= 1;
+            }
+        } else {
+            sr = node_hash(&sr, c);
+        }
+        fn_ >>= 1;
+        sn >>= 1;
+    }
+    sn == 0 && &fr == old_root && &sr == new_root
+}
+
+/// Incremental Merkle tree that keeps every complete subtree hash, so roots and
+/// proofs cost O(log n) node lookups instead of rehashing all leaves.
+///
+/// `levels[l][i]` is MTH of the aligned block of `2^l` leaves starting at `i * 2^l`.
+#[derive(Debug, Clone, Default)]
+pub struct MerkleTree {
+    levels: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels.first().map_or(0, Vec::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends one leaf hash and folds every subtree it completes.
+    pub fn push(&mut self, leaf: Hash) {
+        let mut node = leaf;
+        let mut level = 0;
+        loop {
+            if self.levels.len() == level {
+                self.levels.push(Vec::new());
+            }
+            self.levels[level].push(node);
+            let row = &self.levels[level];
+            if !row.len().is_multiple_of(2) {
+                break;
+            }
+            node = node_hash(&row[row.len() - 2], &row[row.len() - 1]);
+            level += 1;
+        }
+    }
+
+    /// MTH of the first `size` leaves (`size <= len()`).
+    pub fn root(&self, size: usize) -> Hash {
+        if size == 0 {
+            return hash_parts(&[]);
+        }
+        self.subtree(0, size)
+    }
+
+    /// Same as [`inclusion_proof`] over the first `size` leaves.
+    pub fn inclusion_proof(&self, index: usize, size: usize) -> Vec<Hash> {
+        if size <= 1 || index >= size {
+            return Vec::new();
+        }
+        let mut path = Vec::new();
+        self.path(index, 0, size, &mut path);
+        path
+    }
+
+    /// Same as [`consistency_proof`] between the first `old_size` and `new_size` leaves.
+    pub fn consistency_proof(&self, old_size: usize, new_size: usize) -> Vec<Hash> {
+        if old_size == 0 || old_size >= new_size {
+            return Vec::new();
+        }
+        let mut proof = Vec::new();
+        self.subproof(old_size, 0, new_size, true, &mut proof);
+        proof
+    }
+
+    /// MTH of leaves `[start, start + n)`. The RFC 6962 split always yields an aligned
+    /// power-of-two left half, so only the right spine is recomputed.
+    fn subtree(&self, start: usize, n: usize) -> Hash {
+        if n.is_power_of_two() && start.is_multiple_of(n) {
+            return self.levels[n.trailing_zeros() as usize][start / n];
+        }
+        let k = split_point(n);
+        node_hash(&self.subtree(start, k), &self.subtree(start + k, n - k))
+    }
+
+    fn path(&self, index: usize, start: usize, n: usize, out: &mut Vec<Hash>) {
+        if n <= 1 {
+            return;
+        }
+        let k = split_point(n);
+        if index < k {
+            self.path(index, start, k, out);
+            out.push(self.subtree(start + k, n - k));
+        } else {
+            self.path(index - k, start + k, n - k, out);
+            out.push(self.subtree(start, k));
+        }
+    }
+
+    fn subproof(&self, m: usize, start: usize, n: usize, complete: bool, out: &mut Vec<Hash>) {
+        if m == n {
+            if !complete {
+                out.push(self.subtree(start, n));
+            }
+            return;
+        }
+        let k = split_point(n);
+        if m <= k {
+            self.subproof(m, start, k, complete, out);
+            out.push(self.subtree(start + k, n - k));
+        } else {
+            self.subproof(m - k, start + k, n - k, false, out);
+            out.push(self.subtree(start, k));
+        }
+    }
+}
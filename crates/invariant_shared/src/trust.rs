@@ -0,0 +1,48 @@
+// crates/invariant_shared/src/trust.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// One input of the composite trust score.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustSignal {
+    /// StrongBox over TEE.
+    SecurityLevel,
+    /// Age of the Android security patch.
+    PatchAge,
+    /// Time since the last hardware attestation.
+    AttestationAge,
+    Streak,
+    ContinuityScore,
+    /// Attested brand/product agree with the node's device catalog.
+    DeviceCatalog,
+    /// Recent key rotations, recoveries and reactivations.
+    Velocity,
+}
+
+/// How one signal moved the score.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct SignalContribution {
+    pub signal: TrustSignal,
+    /// Configured weight of the signal.
+    pub weight: f64,
+    /// Normalized signal value in 0..=1. None if the signal was unavailable
+    /// (its weight is then spread over the other signals).
+    pub value: Option<f64>,
+    /// Points added to the 0..=100 score.
+    pub points: f64,
+    /// Human-readable reason, e.g. "patch 14 months old".
+    pub detail: String,
+}
+
+/// Composite 0..=100 trust score with its explanation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct TrustScore {
+    pub score: f64,
+    pub contributions: Vec<SignalContribution>,
+}
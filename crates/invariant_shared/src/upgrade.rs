@@ -0,0 +1,42 @@
+// crates/invariant_shared/src/upgrade.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+
+/// An identity moved from an older genesis version onto the node's current one
+/// by re-attesting under the current policy.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct GenesisUpgrade {
+    pub identity_id: Uuid,
+    pub from_version: u16,
+    pub to_version: u16,
+    pub upgraded_at: DateTime<Utc>,
+}
+
+/// Non-revoked identities stamped with one genesis version.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct GenesisVersionStats {
+    pub genesis_version: u16,
+    pub identities: u64,
+    /// How many of them reached this version by upgrading from an older one.
+    pub upgraded: u64,
+}
+
+/// Where the network stands in moving identities off retired genesis versions.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct GenesisUpgradeProgress {
+    /// Version stamped on new and upgraded identities.
+    pub current_version: u16,
+    /// Identities below this version must re-attest before their heartbeats count.
+    pub min_version: u16,
+    /// Identities still below `min_version`.
+    pub pending: u64,
+    /// Lowest version first.
+    pub versions: Vec<GenesisVersionStats>,
+}
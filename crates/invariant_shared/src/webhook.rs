@@ -0,0 +1,149 @@
+// crates/invariant_shared/src/webhook.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use ring::hmac;
+use utoipa::ToSchema;
+
+/// Unix seconds at which the node signed the delivery.
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "Invariant-Timestamp";
+/// `v1=<hex HMAC-SHA256(secret, "<timestamp>.<body>")>`
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "Invariant-Signature";
+/// Oldest timestamp [`verify_webhook_signature`] callers should accept, against replays.
+pub const WEBHOOK_TOLERANCE_SECONDS: i64 = 300;
+
+/// Status changes a partner can subscribe to. Only ever about that partner's own subjects.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventType {
+    IdentityRevoked,
+    /// Hardware attestation expired; the subject must re-attest.
+    IdentityStale,
+    /// Silent past the reaper window.
+    IdentityDormant,
+}
+
+impl WebhookEventType {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            WebhookEventType::IdentityRevoked => "identity_revoked",
+            WebhookEventType::IdentityStale => "identity_stale",
+            WebhookEventType::IdentityDormant => "identity_dormant",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        [WebhookEventType::IdentityRevoked, WebhookEventType::IdentityStale, WebhookEventType::IdentityDormant]
+            .into_iter().find(|e| e.tag() == tag)
+    }
+}
+
+/// Partner endpoint registration.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookEndpointRequest {
+    /// `https://` URL receiving `POST`s.
+    pub url: String,
+    /// Event filter. Must not be empty.
+    pub events: Vec<WebhookEventType>,
+}
+
+/// A registered endpoint. The signing secret is only returned once, at registration.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookEndpoint {
+    pub id: Uuid,
+    pub partner_id: String,
+    pub url: String,
+    pub events: Vec<WebhookEventType>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body of a webhook `POST`. Carries the partner's pairwise ID only.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct WebhookPayload {
+    /// Stable across retries and replays: de-duplicate on it.
+    pub id: Uuid,
+    pub event: WebhookEventType,
+    pub partner_id: String,
+    pub subject_id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    /// Waiting for its first or next attempt.
+    Pending,
+    Delivered,
+    /// Out of retries. Only a replay sends it again.
+    Dead,
+}
+
+impl WebhookDeliveryStatus {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            WebhookDeliveryStatus::Pending => "pending",
+            WebhookDeliveryStatus::Delivered => "delivered",
+            WebhookDeliveryStatus::Dead => "dead",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        [WebhookDeliveryStatus::Pending, WebhookDeliveryStatus::Delivered, WebhookDeliveryStatus::Dead]
+            .into_iter().find(|s| s.tag() == tag)
+    }
+}
+
+/// One payload on its way to one endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub endpoint_id: Uuid,
+    pub payload: WebhookPayload,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    /// None once delivered or dead.
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub last_status_code: Option<u16>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// Value of the `Invariant-Signature` header for `body` sent at `timestamp` (Unix seconds).
+/// `secret` is the endpoint secret exactly as returned at registration (`whsec_...`).
+pub fn webhook_signature(secret: &[u8], timestamp: i64, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+    format!("v1={}", hex::encode(hmac::sign(&key, &signed_content(timestamp, body)).as_ref()))
+}
+
+/// Partner-side check of a webhook delivery: the signature must match the exact body
+/// and the timestamp must be within `tolerance_seconds` of `now`.
+pub fn verify_webhook_signature(
+    secret: &[u8],
+    timestamp_header: &str,
+    signature_header: &str,
+    body: &[u8],
+    now: DateTime<Utc>,
+    tolerance_seconds: i64,
+) -> bool {
+    let Ok(timestamp) = timestamp_header.trim().parse::<i64>() else { return false };
+    if (now.timestamp() - timestamp).abs() > tolerance_seconds {
+        return false;
+    }
+    let Some(tag) = signature_header.trim().strip_prefix("v1=").and_then(|h| hex::decode(h).ok()) else { return false };
+
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+    hmac::verify(&key, &signed_content(timestamp, body), &tag).is_ok()
+}
+
+fn signed_content(timestamp: i64, body: &[u8]) -> Vec<u8> {
+    let mut content = format!("{}.", timestamp).into_bytes();
+    content.extend_from_slice(body);
+    content
+}
@@ -0,0 +1,127 @@
+// crates/invariant_shared/tests/receipt_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use invariant_shared::{verify_receipt, Receipt, ReceiptKind, ReceiptVerdict, SignedReceipt};
+    use invariant_shared::{verify_action_verdict, ActionVerdict, SignedActionVerdict};
+    use invariant_shared::signing;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+    use uuid::Uuid;
+
+    fn node_key() -> Ed25519KeyPair {
+        Ed25519KeyPair::from_seed_unchecked(&[7u8; 32]).unwrap()
+    }
+
+    fn sign(key: &Ed25519KeyPair, receipt: Receipt) -> SignedReceipt {
+        let signature = key.sign(&receipt.signing_payload()).as_ref().to_vec();
+        SignedReceipt { receipt, key_id: "test".into(), signature }
+    }
+
+    #[test]
+    fn test_receipt_round_trip_verifies() {
+        let key = node_key();
+        let signed = sign(&key, Receipt {
+            kind: ReceiptKind::Heartbeat,
+            identity_id: Some(Uuid::new_v4()),
+            score: 42,
+            verdict: ReceiptVerdict::Accepted,
+            public_key_hash: vec![],
+            nonce: vec![1, 2, 3],
+            issued_at: Utc::now(),
+        });
+
+        // Partners receive JSON, so verify what survives the wire.
+        let wire = serde_json::to_string(&signed).unwrap();
+        let decoded: SignedReceipt = serde_json::from_str(&wire).unwrap();
+        assert!(verify_receipt(&decoded, key.public_key().as_ref()));
+    }
+
+    #[test]
+    fn test_tampered_receipt_is_rejected() {
+        let key = node_key();
+        let signed = sign(&key, Receipt {
+            kind: ReceiptKind::Verify,
+            identity_id: None,
+            score: 0,
+            verdict: ReceiptVerdict::Rejected,
+            public_key_hash: vec![0xAB; 32],
+            nonce: vec![4, 5, 6],
+            issued_at: Utc::now(),
+        });
+
+        let mut flipped = signed.clone();
+        flipped.receipt.verdict = ReceiptVerdict::Accepted;
+        assert!(!verify_receipt(&flipped, key.public_key().as_ref()));
+
+        let mut inflated = signed.clone();
+        inflated.receipt.score = 1000;
+        assert!(!verify_receipt(&inflated, key.public_key().as_ref()));
+
+        // A receipt for one key and challenge cannot be passed off for another
+        let mut other_key = signed.clone();
+        other_key.receipt.public_key_hash = vec![0xCD; 32];
+        assert!(!verify_receipt(&other_key, key.public_key().as_ref()));
+
+        let mut other_challenge = signed.clone();
+        other_challenge.receipt.nonce = vec![7, 8, 9];
+        assert!(!verify_receipt(&other_challenge, key.public_key().as_ref()));
+
+        let other = Ed25519KeyPair::from_seed_unchecked(&[9u8; 32]).unwrap();
+        assert!(!verify_receipt(&signed, other.public_key().as_ref()));
+    }
+
+    #[test]
+    fn test_action_verdict_binds_subject_and_payload() {
+        let key = node_key();
+        let verdict = ActionVerdict {
+            partner_id: "https://bank.example".into(),
+            subject_id: Uuid::new_v4(),
+            payload_hash: vec![0x42; 32],
+            nonce: vec![1, 2, 3],
+            verdict: ReceiptVerdict::Accepted,
+            issued_at: Utc::now(),
+        };
+        let signature = key.sign(&signing::action_verdict_payload(&verdict)).as_ref().to_vec();
+        let signed = SignedActionVerdict { verdict, key_id: "test".into(), signature };
+
+        let wire = serde_json::to_string(&signed).unwrap();
+        let decoded: SignedActionVerdict = serde_json::from_str(&wire).unwrap();
+        assert!(verify_action_verdict(&decoded, key.public_key().as_ref()));
+
+        // A verdict for one withdrawal cannot be presented for another.
+        let mut other_action = signed.clone();
+        other_action.verdict.payload_hash = vec![0x43; 32];
+        assert!(!verify_action_verdict(&other_action, key.public_key().as_ref()));
+
+        let mut other_partner = signed.clone();
+        other_partner.verdict.partner_id = "https://evil.example".into();
+        assert!(!verify_action_verdict(&other_partner, key.public_key().as_ref()));
+    }
+
+    #[test]
+    fn test_free_form_fields_cannot_shift_the_payload() {
+        let subject_id = Uuid::new_v4();
+        let verdict = |partner_id: &str, subject_id: Uuid| ActionVerdict {
+            partner_id: partner_id.into(),
+            subject_id,
+            payload_hash: vec![0x42; 32],
+            nonce: vec![1, 2, 3],
+            verdict: ReceiptVerdict::Accepted,
+            issued_at: Utc::now(),
+        };
+
+        // Unescaped, "acme|<subject>" would line up with partner "acme" and that subject.
+        let smuggled = verdict(&format!("acme|{}", subject_id), Uuid::nil());
+        let genuine = verdict("acme", subject_id);
+        let smuggled = String::from_utf8(signing::action_verdict_payload(&smuggled)).unwrap();
+        let genuine = String::from_utf8(signing::action_verdict_payload(&genuine)).unwrap();
+        assert!(genuine.contains("|4:acme|"));
+        assert!(smuggled.contains(&format!("|41:acme|{}|", subject_id)));
+        assert_ne!(smuggled, genuine);
+    }
+}
@@ -0,0 +1,46 @@
+// crates/invariant_shared/tests/signing_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use invariant_shared::signing;
+    use serde_json::Value;
+    use uuid::Uuid;
+
+    /// Shared with the mobile client (`test/signing_payload_test.dart`).
+    const VECTORS: &str = include_str!("vectors/signing_payloads.json");
+
+    fn field<'a>(vector: &'a Value, name: &str) -> &'a str {
+        vector[name].as_str().unwrap_or_else(|| panic!("vector is missing '{}'", name))
+    }
+
+    fn bytes(vector: &Value, name: &str) -> Vec<u8> {
+        hex::decode(field(vector, name)).unwrap()
+    }
+
+    #[test]
+    fn test_payloads_match_cross_language_vectors() {
+        let vectors: Vec<Value> = serde_json::from_str(VECTORS).unwrap();
+        assert_eq!(vectors.len(), 4);
+
+        for vector in &vectors {
+            let id: Uuid = field(vector, "identity_id").parse().unwrap();
+            let nonce = bytes(vector, "nonce");
+            let payload = match field(vector, "purpose") {
+                "heartbeat" => {
+                    let timestamp: DateTime<Utc> = DateTime::parse_from_rfc3339(field(vector, "timestamp")).unwrap().into();
+                    signing::heartbeat_payload(&id, &nonce, &timestamp)
+                }
+                "action" => signing::action_payload(&id, &nonce, &bytes(vector, "payload_hash")),
+                "reattestation" => signing::reattestation_payload(&id, &nonce),
+                "key_rotation" => signing::key_rotation_payload(&id, &bytes(vector, "new_public_key"), &nonce),
+                other => panic!("unknown purpose '{}'", other),
+            };
+            assert_eq!(String::from_utf8(payload).unwrap(), field(vector, "payload"), "{}", field(vector, "purpose"));
+        }
+    }
+}
@@ -0,0 +1,85 @@
+// crates/invariant_shared/tests/token_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use invariant_shared::token::{self, ProofTokenHeader, TOKEN_ALGORITHM};
+    use invariant_shared::{verify_proof_token, IdentityStatus, Jwk, JwkSet, ProofTokenClaims, TokenError};
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+    use uuid::Uuid;
+
+    const ISSUER: &str = "invariant:testnet";
+    const AUDIENCE: &str = "https://partner.example";
+
+    fn mint(key: &Ed25519KeyPair, kid: &str, exp_in: Duration) -> String {
+        mint_by(key, kid, ISSUER, exp_in)
+    }
+
+    fn mint_by(key: &Ed25519KeyPair, kid: &str, issuer: &str, exp_in: Duration) -> String {
+        let now = Utc::now();
+        let header = ProofTokenHeader { alg: TOKEN_ALGORITHM.into(), typ: "JWT".into(), kid: kid.into() };
+        let claims = ProofTokenClaims {
+            iss: issuer.into(),
+            sub: Uuid::new_v4(),
+            aud: AUDIENCE.into(),
+            iat: now.timestamp(),
+            exp: (now + exp_in).timestamp(),
+            jti: Uuid::new_v4(),
+            status: IdentityStatus::Active,
+            tier: "STEEL".into(),
+            streak: 12,
+            last_attestation: now.timestamp(),
+        };
+        let input = token::signing_input(&header, &claims);
+        token::assemble(&input, key.sign(input.as_bytes()).as_ref())
+    }
+
+    #[test]
+    fn test_token_verifies_against_current_and_retired_keys() {
+        let current = Ed25519KeyPair::from_seed_unchecked(&[1u8; 32]).unwrap();
+        let retired = Ed25519KeyPair::from_seed_unchecked(&[2u8; 32]).unwrap();
+        let jwks = JwkSet { keys: vec![
+            Jwk::ed25519("current", current.public_key().as_ref()),
+            Jwk::ed25519("retired", retired.public_key().as_ref()),
+        ]};
+
+        let claims = verify_proof_token(&mint(&current, "current", Duration::minutes(5)), &jwks, ISSUER, AUDIENCE, Utc::now()).unwrap();
+        assert_eq!(claims.streak, 12);
+        assert_eq!(claims.status, IdentityStatus::Active);
+
+        // Tokens minted before a rotation keep verifying while the old key stays published.
+        assert!(verify_proof_token(&mint(&retired, "retired", Duration::minutes(5)), &jwks, ISSUER, AUDIENCE, Utc::now()).is_ok());
+    }
+
+    #[test]
+    fn test_token_rejections() {
+        let key = Ed25519KeyPair::from_seed_unchecked(&[1u8; 32]).unwrap();
+        let jwks = JwkSet { keys: vec![Jwk::ed25519("k1", key.public_key().as_ref())] };
+        let token = mint(&key, "k1", Duration::minutes(5));
+
+        assert_eq!(verify_proof_token(&token, &jwks, ISSUER, "https://other.example", Utc::now()), Err(TokenError::WrongAudience));
+        assert_eq!(verify_proof_token(&token, &jwks, ISSUER, AUDIENCE, Utc::now() + Duration::minutes(6)), Err(TokenError::Expired));
+
+        // Minted by another node that shares the key (e.g. a testnet token shown to a mainnet verifier).
+        let foreign = mint_by(&key, "k1", "invariant:mainnet", Duration::minutes(5));
+        assert_eq!(verify_proof_token(&foreign, &jwks, ISSUER, AUDIENCE, Utc::now()), Err(TokenError::WrongIssuer));
+        assert_eq!(verify_proof_token("not.a-token", &jwks, ISSUER, AUDIENCE, Utc::now()), Err(TokenError::Malformed));
+
+        // Signed by a key the node never published.
+        let rogue = Ed25519KeyPair::from_seed_unchecked(&[9u8; 32]).unwrap();
+        assert_eq!(verify_proof_token(&mint(&rogue, "k1", Duration::minutes(5)), &jwks, ISSUER, AUDIENCE, Utc::now()), Err(TokenError::InvalidSignature));
+        assert_eq!(
+            verify_proof_token(&mint(&key, "unknown", Duration::minutes(5)), &jwks, ISSUER, AUDIENCE, Utc::now()),
+            Err(TokenError::UnknownKey("unknown".into()))
+        );
+
+        // Tampered claims invalidate the signature.
+        let parts: Vec<&str> = token.split('.').collect();
+        let forged = format!("{}.{}.{}", parts[0], parts[1].replacen('e', "f", 1), parts[2]);
+        assert!(verify_proof_token(&forged, &jwks, ISSUER, AUDIENCE, Utc::now()).is_err());
+    }
+}
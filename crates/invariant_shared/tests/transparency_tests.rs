@@ -0,0 +1,85 @@
+// crates/invariant_shared/tests/transparency_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use invariant_shared::transparency::{
+        leaf_hash, merkle_root, inclusion_proof, consistency_proof, verify_inclusion, verify_consistency, Hash,
+        MerkleTree,
+    };
+
+    fn leaves(n: usize) -> Vec<Hash> {
+        (0..n).map(|i| leaf_hash(format!("entry-{}", i).as_bytes())).collect()
+    }
+
+    #[test]
+    fn test_inclusion_proofs_for_every_leaf() {
+        for n in 1..=33 {
+            let tree = leaves(n);
+            let root = merkle_root(&tree);
+            for (i, leaf) in tree.iter().enumerate() {
+                let proof = inclusion_proof(&tree, i);
+                assert!(verify_inclusion(leaf, i as u64, n as u64, &proof, &root), "n={} i={}", n, i);
+
+                // Wrong position or wrong leaf must fail.
+                if n > 1 {
+                    assert!(!verify_inclusion(leaf, ((i + 1) % n) as u64, n as u64, &proof, &root));
+                }
+                assert!(!verify_inclusion(&leaf_hash(b"forged"), i as u64, n as u64, &proof, &root));
+            }
+        }
+    }
+
+    #[test]
+    fn test_consistency_proofs_between_all_sizes() {
+        let full = leaves(33);
+        for new_size in 1..=full.len() {
+            let new_root = merkle_root(&full[..new_size]);
+            for old_size in 1..=new_size {
+                let old_root = merkle_root(&full[..old_size]);
+                let proof = consistency_proof(&full[..new_size], old_size);
+                assert!(verify_consistency(old_size as u64, new_size as u64, &old_root, &new_root, &proof),
+                    "old={} new={}", old_size, new_size);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rewritten_history_is_detected() {
+        let honest = leaves(12);
+        let old_root = merkle_root(&honest[..7]);
+
+        // The node "deletes" entry 3 and appends a new one.
+        let mut rewritten = honest.clone();
+        rewritten[3] = leaf_hash(b"quietly replaced");
+        let new_root = merkle_root(&rewritten);
+        let proof = consistency_proof(&rewritten, 7);
+
+        assert!(!verify_consistency(7, 12, &old_root, &new_root, &proof));
+    }
+
+    #[test]
+    fn test_cached_tree_matches_full_recomputation() {
+        let full = leaves(40);
+        let mut tree = MerkleTree::new();
+        assert_eq!(tree.root(0), merkle_root(&[]));
+        for leaf in &full {
+            tree.push(*leaf);
+        }
+        assert_eq!(tree.len(), full.len());
+
+        for size in 1..=full.len() {
+            let prefix = &full[..size];
+            assert_eq!(tree.root(size), merkle_root(prefix), "size={}", size);
+            for index in 0..size {
+                assert_eq!(tree.inclusion_proof(index, size), inclusion_proof(prefix, index), "size={} index={}", size, index);
+            }
+            for old_size in 1..=size {
+                assert_eq!(tree.consistency_proof(old_size, size), consistency_proof(prefix, old_size), "old={} new={}", old_size, size);
+            }
+        }
+    }
+}
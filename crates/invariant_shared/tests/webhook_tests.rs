@@ -0,0 +1,70 @@
+// crates/invariant_shared/tests/webhook_tests.rs
+/*
+ * Copyright (c) 2026 Invariant Protocol.
+ * Use of this software is governed by the MIT License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use invariant_shared::{webhook_signature, verify_webhook_signature, WebhookEventType, WebhookPayload};
+    use invariant_shared::webhook::WEBHOOK_TOLERANCE_SECONDS;
+    use uuid::Uuid;
+
+    const SECRET: &[u8] = b"whsec-test-secret";
+
+    fn body() -> Vec<u8> {
+        serde_json::to_vec(&WebhookPayload {
+            id: Uuid::new_v4(),
+            event: WebhookEventType::IdentityRevoked,
+            partner_id: "https://bank.example".into(),
+            subject_id: Uuid::new_v4(),
+            occurred_at: Utc.with_ymd_and_hms(2026, 5, 1, 9, 0, 0).unwrap(),
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_signature_round_trip() {
+        let now = Utc.with_ymd_and_hms(2026, 5, 1, 9, 0, 5).unwrap();
+        let body = body();
+        let signature = webhook_signature(SECRET, now.timestamp(), &body);
+        assert!(signature.starts_with("v1="));
+
+        let ts = now.timestamp().to_string();
+        assert!(verify_webhook_signature(SECRET, &ts, &signature, &body, now, WEBHOOK_TOLERANCE_SECONDS));
+
+        // Other secret, other body, other timestamp: all rejected.
+        assert!(!verify_webhook_signature(b"other", &ts, &signature, &body, now, WEBHOOK_TOLERANCE_SECONDS));
+        let mut tampered = body.clone();
+        tampered.push(b' ');
+        assert!(!verify_webhook_signature(SECRET, &ts, &signature, &tampered, now, WEBHOOK_TOLERANCE_SECONDS));
+        let shifted = (now.timestamp() + 1).to_string();
+        assert!(!verify_webhook_signature(SECRET, &shifted, &signature, &body, now, WEBHOOK_TOLERANCE_SECONDS));
+
+        // Malformed headers never verify.
+        assert!(!verify_webhook_signature(SECRET, "yesterday", &signature, &body, now, WEBHOOK_TOLERANCE_SECONDS));
+        assert!(!verify_webhook_signature(SECRET, &ts, signature.trim_start_matches("v1="), &body, now, WEBHOOK_TOLERANCE_SECONDS));
+    }
+
+    #[test]
+    fn test_old_signatures_are_rejected() {
+        let signed_at = Utc.with_ymd_and_hms(2026, 5, 1, 9, 0, 0).unwrap();
+        let body = body();
+        let signature = webhook_signature(SECRET, signed_at.timestamp(), &body);
+        let ts = signed_at.timestamp().to_string();
+
+        let within = signed_at + Duration::seconds(WEBHOOK_TOLERANCE_SECONDS);
+        assert!(verify_webhook_signature(SECRET, &ts, &signature, &body, within, WEBHOOK_TOLERANCE_SECONDS));
+        let replayed = signed_at + Duration::seconds(WEBHOOK_TOLERANCE_SECONDS + 1);
+        assert!(!verify_webhook_signature(SECRET, &ts, &signature, &body, replayed, WEBHOOK_TOLERANCE_SECONDS));
+    }
+
+    #[test]
+    fn test_event_tags_round_trip() {
+        for event in [WebhookEventType::IdentityRevoked, WebhookEventType::IdentityStale, WebhookEventType::IdentityDormant] {
+            assert_eq!(WebhookEventType::from_tag(event.tag()), Some(event));
+            assert_eq!(serde_json::to_value(event).unwrap(), event.tag());
+        }
+        assert_eq!(WebhookEventType::from_tag("identity_active"), None);
+    }
+}